@@ -58,56 +58,51 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
                 }
                 if changed {
                     body.update_charge_from_electrons();
-                    println!("Particle {} new charge: {}", id, body.charge);
-                    println!(
+                    crate::sim_log!(Physics, Debug, "Particle {} new charge: {}", id, body.charge);
+                    crate::sim_log!(
+                        Physics,
+                        Debug,
                         "Particle {} new electron count: {}",
                         id,
                         body.electrons.len()
                     );
-                    println!("Particle {} new species: {:?}", id, body.species);
+                    crate::sim_log!(Physics, Debug, "Particle {} new species: {:?}", id, body.species);
                     let was_metal = body.species == crate::body::Species::LithiumMetal;
                     let was_ion = body.species == crate::body::Species::LithiumIon;
                     body.update_species();
                     if was_metal && body.species == crate::body::Species::LithiumIon {
-                        println!();
-                        println!("Should become ion below...");
-                        println!("Particle {} new species: {:?}", id, body.species);
+                        crate::sim_log!(Physics, Debug, "");
+                        crate::sim_log!(Physics, Debug, "Should become ion below...");
+                        crate::sim_log!(Physics, Debug, "Particle {} new species: {:?}", id, body.species);
                     }
                     if was_ion && body.species == crate::body::Species::LithiumMetal {
-                        println!();
-                        println!("Should become metal below...");
-                        println!("Particle {} new species: {:?}", id, body.species);
+                        crate::sim_log!(Physics, Debug, "");
+                        crate::sim_log!(Physics, Debug, "Should become metal below...");
+                        crate::sim_log!(Physics, Debug, "Particle {} new species: {:?}", id, body.species);
                     }
-                    println!("Particle {} new charge: {}", id, body.charge);
+                    crate::sim_log!(Physics, Debug, "Particle {} new charge: {}", id, body.charge);
                 }
             }
             if changed {
                 mark_dirty(simulation);
             }
         }
-        SimCommand::AddBody { mut body } => {
-            body.electrons.clear();
-            if matches!(
-                body.species,
-                crate::body::Species::LithiumMetal
-                    | crate::body::Species::ElectrolyteAnion
-                    | crate::body::Species::EC
-                    | crate::body::Species::DMC
-            ) {
-                body.electrons.push(crate::body::Electron {
-                    rel_pos: ultraviolet::Vec2::zero(),
-                    vel: ultraviolet::Vec2::zero(),
-                });
-            }
+        SimCommand::AddBody {
+            mut body,
+            electron_count_override,
+            charge_override,
+        } => {
+            spawn::seed_spawn_electrons(&mut body, electron_count_override, charge_override);
             let temp = crate::config::LJ_CONFIG.lock().temperature;
             body.vel = super::spawn::sample_velocity(body.mass, temp);
-            body.update_charge_from_electrons();
             body.update_species();
             simulation.bodies.push(body);
             mark_dirty(simulation);
         }
         SimCommand::DeleteAll => {
-            eprintln!(
+            crate::sim_log!(
+                Io,
+                Debug,
                 "[bodies-debug] DeleteAll command clearing {} bodies",
                 simulation.bodies.len()
             );
@@ -118,7 +113,7 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
         }
         SimCommand::ResetFoilIds => {
             crate::body::foil::Foil::reset_id_counter();
-            eprintln!("[foil-debug] Foil ID counter reset to 1");
+            crate::sim_log!(Io, Debug, "[foil-debug] Foil ID counter reset to 1");
         }
         SimCommand::DeleteSpecies { species } => {
             simulation.bodies.retain(|body| body.species != species);
@@ -140,8 +135,31 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
             }
             mark_dirty(simulation);
         }
-        SimCommand::AddCircle { body, x, y, radius } => {
-            spawn::add_circle(simulation, body, x, y, radius);
+        SimCommand::FreezeSpecies { species, frozen } => {
+            if frozen {
+                simulation.frozen_species.insert(species);
+            } else {
+                simulation.frozen_species.remove(&species);
+            }
+            mark_dirty(simulation);
+        }
+        SimCommand::AddCircle {
+            body,
+            x,
+            y,
+            radius,
+            electron_count_override,
+            charge_override,
+        } => {
+            spawn::add_circle(
+                simulation,
+                body,
+                x,
+                y,
+                radius,
+                electron_count_override,
+                charge_override,
+            );
             mark_dirty(simulation);
         }
         SimCommand::StepOnce => {
@@ -156,18 +174,49 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
             state_changed = true;
             PAUSED.store(true, Ordering::Relaxed);
         }
+        SimCommand::DedupeIds => {
+            if simulation.dedupe_ids() > 0 {
+                mark_dirty(simulation);
+            } else {
+                crate::sim_log!(Physics, Info, "Body IDs are unique");
+            }
+        }
+        SimCommand::SetStepReplay { armed } => {
+            simulation.set_step_replay(armed);
+        }
+        SimCommand::ReplayStep => {
+            if simulation.replay_step() {
+                super::simulation_loop::render(simulation);
+                state_changed = true;
+                PAUSED.store(true, Ordering::Relaxed);
+            } else {
+                crate::sim_log!(Physics, Warn, "Replay step: no pre-step snapshot (arm hold-at-frame and step once)");
+            }
+        }
+        SimCommand::RunReversibilityTest { steps } => {
+            let rms = simulation.run_reversibility_test(steps);
+            crate::sim_log!(
+                Physics,
+                Info,
+                "Reversibility test: {} steps forward and back, RMS return error {:.6} Å",
+                steps,
+                rms
+            );
+            *crate::renderer::state::REVERSIBILITY_RESULT.lock() = Some((steps, rms));
+        }
         SimCommand::SaveState { path } => {
             if let Err(e) = save_state(path, simulation) {
-                eprintln!("Failed to save state: {}", e);
+                crate::sim_log!(Io, Error, "Failed to save state: {}", e);
             }
         }
         SimCommand::LoadState { path } => match load_state(path) {
             Ok(scenario) => {
                 simulation.load_state(scenario);
+                simulation.dedupe_ids();
                 PAUSED.store(true, Ordering::Relaxed);
                 state_changed = true;
             }
-            Err(e) => eprintln!("Failed to load state: {}", e),
+            Err(e) => crate::sim_log!(Io, Error, "Failed to load state: {}", e),
         },
         SimCommand::AddRing { body, x, y, radius } => {
             spawn::add_ring(simulation, body, x, y, radius);
@@ -192,6 +241,25 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
             spawn::add_random(simulation, body, count, domain_width, domain_height);
             mark_dirty(simulation);
         }
+        SimCommand::AddElectrolyte {
+            total,
+            molarity,
+            ec_parts,
+            dmc_parts,
+            domain_width,
+            domain_height,
+        } => {
+            spawn::add_electrolyte(
+                simulation,
+                total,
+                molarity,
+                ec_parts,
+                dmc_parts,
+                domain_width,
+                domain_height,
+            );
+            mark_dirty(simulation);
+        }
         SimCommand::AddFoil {
             width,
             height,
@@ -201,6 +269,9 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
             current,
         } => {
             spawn::add_foil(simulation, width, height, x, y, particle_radius, current);
+            if let Some(id) = simulation.foils.last().map(|f| f.id) {
+                simulation.auto_link_new_foils(&[id]);
+            }
             mark_dirty(simulation);
         }
         SimCommand::SetFoilCurrent { foil_id, current } => {
@@ -269,6 +340,44 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
                 }
             }
         }
+        SimCommand::SetFoilACWaveform { foil_id, waveform } => {
+            let link_info = simulation
+                .foils
+                .iter()
+                .find(|f| f.id == foil_id)
+                .and_then(|foil| foil.link_id);
+            if let Some(foil) = simulation.foils.iter_mut().find(|f| f.id == foil_id) {
+                foil.ac_waveform = waveform;
+                mark_dirty(simulation);
+            }
+            if let Some(link_id) = link_info {
+                if let Some(linked_foil) = simulation.foils.iter_mut().find(|f| f.id == link_id) {
+                    linked_foil.ac_waveform = waveform;
+                    mark_dirty(simulation);
+                }
+            }
+        }
+        SimCommand::SetFoilMaxElectrons {
+            foil_id,
+            max_electrons,
+        } => {
+            if let Some(foil) = simulation.foils.iter_mut().find(|f| f.id == foil_id) {
+                foil.max_electrons_override = max_electrons;
+                mark_dirty(simulation);
+            }
+        }
+        SimCommand::SetFoilSchedule { foil_id, schedule } => {
+            if let Some(foil) = simulation.foils.iter_mut().find(|f| f.id == foil_id) {
+                foil.schedule = schedule;
+                mark_dirty(simulation);
+            }
+        }
+        SimCommand::SetFoilHandoff { foil_id, rule } => {
+            if let Some(foil) = simulation.foils.iter_mut().find(|f| f.id == foil_id) {
+                foil.handoff = rule;
+                mark_dirty(simulation);
+            }
+        }
         SimCommand::SetFoilChargingMode { foil_id, mode } => {
             if let Some(foil) = simulation.foils.iter_mut().find(|f| f.id == foil_id) {
                 foil.charging_mode = mode;
@@ -491,6 +600,28 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
                 mark_dirty(simulation);
             }
         }
+        SimCommand::MergeFoils { ids } => {
+            if simulation.merge_foils(&ids).is_some() {
+                // Keep the GUI's published group state in sync
+                let mut shared = crate::simulation::eis::EIS_RESULTS.lock();
+                let mut a_sorted: Vec<u64> = simulation.group_a.iter().copied().collect();
+                let mut b_sorted: Vec<u64> = simulation.group_b.iter().copied().collect();
+                a_sorted.sort_unstable();
+                b_sorted.sort_unstable();
+                shared.group_a_ids = a_sorted;
+                shared.group_b_ids = b_sorted;
+                drop(shared);
+                mark_dirty(simulation);
+            }
+        }
+        SimCommand::DetectFoils { connection_radius } => {
+            let created = simulation.detect_foils(connection_radius);
+            simulation.auto_link_new_foils(&created);
+            crate::sim_log!(Io, Info, "✓ Detected {} new foil(s) from FoilMetal particles", created.len());
+            if !created.is_empty() {
+                mark_dirty(simulation);
+            }
+        }
         SimCommand::SetTemperature { temperature } => {
             crate::config::LJ_CONFIG.lock().temperature = temperature;
             mark_dirty(simulation);
@@ -614,12 +745,12 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
         }
         SimCommand::SyncActiveMaterialRegions { regions } => {
             simulation.active_regions = regions;
-            println!("📍 Synced {} active material regions to simulation", simulation.active_regions.len());
+            crate::sim_log!(Io, Info, "📍 Synced {} active material regions to simulation", simulation.active_regions.len());
             mark_dirty(simulation);
         }
         SimCommand::ClearActiveMaterialRegions => {
             simulation.active_regions.clear();
-            println!("🗑️ Cleared all active material regions");
+            crate::sim_log!(Io, Info, "🗑️ Cleared all active material regions");
             mark_dirty(simulation);
         }
         SimCommand::StartEIS {
@@ -719,7 +850,7 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
                 saved_target_ratios,
                 simulation.time,
             ));
-            println!("EIS sweep started ({:?})", mode);
+            crate::sim_log!(Io, Info, "EIS sweep started ({:?})", mode);
             mark_dirty(simulation);
         }
         SimCommand::StopEIS => {
@@ -741,7 +872,7 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
             simulation.eis_state = None;
             let mut shared = crate::simulation::eis::EIS_RESULTS.lock();
             shared.is_running = false;
-            println!("EIS sweep stopped");
+            crate::sim_log!(Io, Info, "EIS sweep stopped");
             mark_dirty(simulation);
         }
         SimCommand::StartMorphologyLog { path, log_every_frames } => {
@@ -750,19 +881,169 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
                 log_every_frames,
             ) {
                 Ok(logger) => {
-                    println!(
+                    crate::sim_log!(
+                        Io,
+                        Info,
                         "morphology log opened: {} (every {} frames)",
                         path.display(),
                         log_every_frames
                     );
                     simulation.morphology_logger = Some(logger);
                 }
-                Err(e) => eprintln!("morphology log open failed: {e}"),
+                Err(e) => crate::sim_log!(Io, Error, "morphology log open failed: {e}"),
             }
         }
         SimCommand::StopMorphologyLog => {
             if simulation.morphology_logger.take().is_some() {
-                println!("morphology log closed");
+                crate::sim_log!(Io, Info, "morphology log closed");
+            }
+        }
+        SimCommand::ValidateField { samples } => {
+            // Fresh tree on a copy so the live body order is left untouched
+            let theta = simulation.quadtree.t_sq.sqrt();
+            let mut bodies = simulation.bodies.clone();
+            let mut quadtree = crate::quadtree::Quadtree::new(
+                theta,
+                simulation.quadtree.e_sq.sqrt(),
+                simulation.quadtree.leaf_capacity,
+                simulation.quadtree.thread_capacity,
+            );
+            quadtree.build(&mut bodies);
+            let results =
+                quadtree.validate_field(&bodies, samples, simulation.config.coulomb_constant);
+            let theta_str = format!("{:.2}", theta).replace('.', "p");
+            let path = std::path::Path::new("doe_results")
+                .join(format!("field_validation_theta{}.csv", theta_str));
+            match crate::quadtree::validation::write_field_validation_csv(&path, &results) {
+                Ok(()) => {
+                    let n = results.len().max(1) as f32;
+                    let mean = results.iter().map(|s| s.rel_error).sum::<f32>() / n;
+                    let max = results.iter().fold(0.0f32, |m, s| m.max(s.rel_error));
+                    crate::sim_log!(
+                        Io,
+                        Error,
+                        "✓ Field validation (theta {:.2}, {} samples): mean rel error {:.3e}, max {:.3e} -> {}",
+                        theta,
+                        results.len(),
+                        mean,
+                        max,
+                        path.display()
+                    );
+                }
+                Err(e) => crate::sim_log!(Io, Error, "✗ Failed to write field validation CSV: {}", e),
+            }
+        }
+        SimCommand::ExportFoilConfig { path } => match simulation.export_foil_config(&path) {
+            Ok(n) => crate::sim_log!(Io, Info, "✓ Foil config ({} foils) -> {}", n, path.display()),
+            Err(e) => crate::sim_log!(Io, Error, "✗ Failed to write foil config: {}", e),
+        },
+        SimCommand::ExportLightweightHistory { path } => {
+            match simulation.export_lightweight_history(&path) {
+                Ok(n) => crate::sim_log!(Io, Info, "✓ Lightweight history ({} snapshots) -> {}", n, path.display()),
+                Err(e) => crate::sim_log!(Io, Error, "✗ Failed to write lightweight history: {}", e),
+            }
+        }
+        SimCommand::LoadLightweightPlayback { path } => {
+            if let Some(path) = path {
+                match simulation.load_lightweight_history(&path) {
+                    Ok(n) => crate::sim_log!(Io, Info, "✓ Lightweight history ({} snapshots) <- {}", n, path.display()),
+                    Err(e) => {
+                        crate::sim_log!(Io, Error, "✗ Failed to read lightweight history: {}", e);
+                        return;
+                    }
+                }
+            }
+            simulation.publish_lightweight_playback();
+        }
+        SimCommand::SaveCompressedHistory { path } => {
+            match simulation.save_compressed_history(&path) {
+                Ok((frames, keys)) => crate::sim_log!(
+                    Io,
+                    Info,
+                    "✓ Compressed history ({} frames, {} keyframes) -> {}",
+                    frames,
+                    keys,
+                    path.display()
+                ),
+                Err(e) => crate::sim_log!(Io, Error, "✗ Failed to write compressed history: {}", e),
+            }
+        }
+        SimCommand::LoadCompressedHistory { path } => {
+            match simulation.load_compressed_history(&path) {
+                Ok(frames) => {
+                    let shown = path.display();
+                    crate::sim_log!(Io, Info, "✓ Loaded {} history frames from {}", frames, shown);
+                    PAUSED.store(true, Ordering::Relaxed);
+                    state_changed = true;
+                }
+                Err(e) => crate::sim_log!(Io, Error, "✗ Failed to load compressed history: {}", e),
+            }
+        }
+        SimCommand::ExportCurrentResidual { path } => {
+            match simulation.export_current_residual(&path) {
+                Ok(rows) => crate::sim_log!(Io, Info, "✓ Current residual ({} samples) -> {}", rows, path.display()),
+                Err(e) => crate::sim_log!(Io, Error, "✗ Failed to write current residual CSV: {}", e),
+            }
+        }
+        SimCommand::ClearCurrentResidual => simulation.clear_current_residual(),
+        SimCommand::ImportFoilConfig { path } => match simulation.import_foil_config(&path) {
+            Ok((applied, skipped)) => {
+                mark_dirty(simulation);
+                crate::sim_log!(
+                    Io,
+                    Info,
+                    "✓ Foil config from {}: {} applied, {} skipped",
+                    path.display(),
+                    applied,
+                    skipped
+                );
+            }
+            Err(e) => crate::sim_log!(Io, Error, "✗ Failed to read foil config: {}", e),
+        },
+        SimCommand::SaveConfigPreset { path } => {
+            match crate::io::save_config_preset(&path, &simulation.config) {
+                Ok(()) => crate::sim_log!(Io, Info, "✓ Config preset saved to {}", path.display()),
+                Err(e) => crate::sim_log!(Io, Error, "✗ Failed to save config preset: {}", e),
+            }
+        }
+        SimCommand::LoadConfigPreset { path } => match crate::io::load_config_preset(&path) {
+            Ok(preset) => {
+                preset.apply_species();
+                let version = simulation.config.config_version;
+                simulation.config = preset.config;
+                simulation.config.config_version = version;
+                *crate::config::LJ_CONFIG.lock() = simulation.config.clone();
+                *crate::renderer::state::PERSIST_UI_SIM_CONFIG.lock() = Some(simulation.config.clone());
+                *crate::renderer::state::PERSIST_UI_DIRTY.lock() = true;
+                mark_dirty(simulation);
+                crate::sim_log!(Io, Info, "✓ Config preset loaded from {}", path.display());
+            }
+            Err(e) => crate::sim_log!(Io, Error, "✗ Failed to load config preset: {}", e),
+        },
+        SimCommand::ExportXyz { path, stride } => {
+            match crate::io::export_xyz(&path, simulation, stride) {
+                Ok(frames) => {
+                    crate::sim_log!(Io, Info, "✓ XYZ trajectory ({} frames) -> {}", frames, path.display())
+                }
+                Err(e) => crate::sim_log!(Io, Error, "✗ Failed to write XYZ trajectory: {}", e),
+            }
+        }
+        SimCommand::ExportSpeciesCounts { path } => match simulation.export_species_counts(&path) {
+            Ok(rows) => crate::sim_log!(Io, Info, "✓ Species counts ({} frames) -> {}", rows, path.display()),
+            Err(e) => crate::sim_log!(Io, Error, "✗ Failed to write species count CSV: {}", e),
+        },
+        SimCommand::ExportElectronEvents { path } => {
+            match simulation.electron_events.write_csv(&path) {
+                Ok(rows) => crate::sim_log!(
+                    Io,
+                    Info,
+                    "✓ Electron events ({} buffered, {} flushed, {} dropped) -> {}",
+                    rows,
+                    simulation.electron_events.flushed,
+                    simulation.electron_events.dropped,
+                    path.display()
+                ),
+                Err(e) => crate::sim_log!(Io, Error, "✗ Failed to write electron event CSV: {}", e),
             }
         }
         SimCommand::LoadInitConfigToml { path } => {
@@ -797,7 +1078,7 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
                                     rect.height,
                                 );
                             }
-                            Err(e) => eprintln!("LoadInitConfigToml: skip metal_rectangle: {e}"),
+                            Err(e) => crate::sim_log!(Io, Warn, "LoadInitConfigToml: skip metal_rectangle: {e}"),
                         }
                     }
                     for foil in &cfg.particles.foil_rectangles {
@@ -813,10 +1094,11 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
                         );
                         // Apply initial electron-count offset if specified.
                         // Positive = surplus, negative = deficit; distributed
-                        // across foil bodies round-robin, respecting FOIL_MAX_ELECTRONS.
+                        // across foil bodies round-robin, respecting the foil's max_electrons().
                         if foil.initial_excess_electrons != 0 {
                             if let Some(new_foil) = simulation.foils.last() {
                                 let body_ids = new_foil.body_ids.clone();
+                                let max_electrons = new_foil.max_electrons();
                                 let target = foil.initial_excess_electrons;
                                 let abs_target = target.unsigned_abs() as usize;
                                 let mut applied = 0usize;
@@ -832,9 +1114,7 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
                                         {
                                             let body = &mut simulation.bodies[idx];
                                             if target > 0 {
-                                                if body.electrons.len()
-                                                    < crate::config::FOIL_MAX_ELECTRONS
-                                                {
+                                                if body.electrons.len() < max_electrons {
                                                     body.electrons.push(crate::body::Electron {
                                                         rel_pos: ultraviolet::Vec2::zero(),
                                                         vel: ultraviolet::Vec2::zero(),
@@ -861,7 +1141,9 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
                                         simulation.bodies[idx].update_charge_from_electrons();
                                     }
                                 }
-                                println!(
+                                crate::sim_log!(
+                                    Io,
+                                    Info,
                                     "  applied initial_excess_electrons={} to foil ({}/{} done)",
                                     target, applied, abs_target
                                 );
@@ -874,10 +1156,12 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
                                 let body = template_body_for_species(species);
                                 spawn::add_random(simulation, body, entry.count, full_w, full_h);
                             }
-                            Err(e) => eprintln!("LoadInitConfigToml: skip random entry: {e}"),
+                            Err(e) => crate::sim_log!(Io, Warn, "LoadInitConfigToml: skip random entry: {e}"),
                         }
                     }
-                    println!(
+                    crate::sim_log!(
+                        Io,
+                        Info,
                         "loaded init_config TOML: {} ({} bodies, {} foils)",
                         path.display(),
                         simulation.bodies.len(),
@@ -885,7 +1169,7 @@ pub fn handle_command(cmd: SimCommand, simulation: &mut Simulation) {
                     );
                     mark_dirty(simulation);
                 }
-                Err(e) => eprintln!("LoadInitConfigToml({}) failed: {e}", path.display()),
+                Err(e) => crate::sim_log!(Io, Error, "LoadInitConfigToml({}) failed: {e}", path.display()),
             }
         }
     }
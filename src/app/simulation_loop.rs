@@ -67,13 +67,17 @@ pub fn run_simulation_loop(
         // Handle commands
         while let Ok(cmd) = rx.try_recv() {
             #[cfg(feature = "command_debug")]
-            eprintln!(
+            crate::sim_log!(
+                Physics,
+                Debug,
                 "[command-recv] Processing command: {:?}",
                 std::mem::discriminant(&cmd)
             );
             command_loop::handle_command(cmd, &mut simulation);
             #[cfg(feature = "command_debug")]
-            eprintln!(
+            crate::sim_log!(
+                Physics,
+                Debug,
                 "[command-done] Bodies count now: {}",
                 simulation.bodies.len()
             );
@@ -95,6 +99,9 @@ pub fn run_simulation_loop(
 
         if (is_paused && !do_single_step) || is_viewing_history {
             // debug log removed
+            if is_paused && !is_viewing_history {
+                simulation.poll_measurement_auto_resume();
+            }
             if let PlaybackProgress::ReachedLive { should_resume_live } =
                 simulation.advance_playback(Instant::now())
             {
@@ -120,7 +127,9 @@ pub fn run_simulation_loop(
                 .count();
 
             if invalid_count > 0 {
-                eprintln!(
+                crate::sim_log!(
+                    Physics,
+                    Error,
                     "[ERROR] Found {} particles with invalid positions/velocities! Resetting...",
                     invalid_count
                 );
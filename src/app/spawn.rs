@@ -24,10 +24,46 @@ pub fn sample_velocity(mass: f32, temperature: f32) -> Vec2 {
     Vec2::new(z0 * sigma, z1 * sigma)
 }
 
-pub fn overlaps_any(existing: &[crate::body::Body], pos: Vec2, radius: f32) -> Option<usize> {
+/// Index of the first body whose centre lies closer than `min_sep` times the
+/// summed radii to a body of `radius` at `pos`.
+pub fn overlaps_any(
+    existing: &[crate::body::Body],
+    pos: Vec2,
+    radius: f32,
+    min_sep: f32,
+) -> Option<usize> {
     existing
         .iter()
-        .position(|b| (b.pos - pos).mag() < (b.radius + radius) * 0.65)
+        .position(|b| (b.pos - pos).mag() < (b.radius + radius) * min_sep)
+}
+
+/// Whether a body of `radius` fits at `pos` without coming closer than
+/// `min_sep` times the summed radii to any existing body. Shared by every
+/// spawn path so they all pack to the same `config.spawn_min_separation`.
+pub fn is_position_valid(
+    pos: Vec2,
+    radius: f32,
+    existing: &[crate::body::Body],
+    min_sep: f32,
+) -> bool {
+    overlaps_any(existing, pos, radius, min_sep).is_none()
+}
+
+/// Remove every body among the first `*existing` entries of
+/// `simulation.bodies` that crowds a new body of `radius` at `pos`, shrinking
+/// `*existing` to match. Bodies the current batch already pushed sit past that
+/// prefix, so a `min_sep` above the lattice spacing never eats its own sites.
+pub fn clear_spawn_site(
+    simulation: &mut Simulation,
+    existing: &mut usize,
+    pos: Vec2,
+    radius: f32,
+    min_sep: f32,
+) {
+    while let Some(idx) = overlaps_any(&simulation.bodies[..*existing], pos, radius, min_sep) {
+        remove_body_with_foils(simulation, idx);
+        *existing -= 1;
+    }
 }
 
 /// Check if a position is within exclusion distance of any metal particle.
@@ -39,10 +75,94 @@ pub fn is_near_metal(existing: &[crate::body::Body], pos: Vec2, exclusion_radius
     })
 }
 
+/// Whether a body of `radius` at `pos` stays at least
+/// `config.spawn_wall_margin` away from every domain wall.
+pub fn clear_of_walls(simulation: &Simulation, pos: Vec2, radius: f32) -> bool {
+    let inset = simulation.config.spawn_wall_margin.max(0.0) + radius;
+    pos.x.abs() <= simulation.domain_width - inset && pos.y.abs() <= simulation.domain_height - inset
+}
+
+fn report_wall_skips(skipped: usize) {
+    if skipped > 0 {
+        crate::sim_log!(Physics, Info, "Skipped {} spawn positions within the wall margin", skipped);
+    }
+}
+
 /// Metal exclusion radius for Li+ ion placement (in simulation units / Angstroms)
 /// Should be large enough to prevent immediate conversion upon simulation start
 const LITHIUM_ION_METAL_EXCLUSION: f32 = 5.0;
 
+/// Electrons a freshly spawned body carries when no override is given.
+fn default_spawn_electrons(species: Species) -> usize {
+    match species {
+        Species::LithiumMetal | Species::ElectrolyteAnion | Species::EC | Species::DMC => 1,
+        _ => 0,
+    }
+}
+
+/// Upper bound on the electrons a spawned body of `species` may start with.
+/// Metals and electrode materials use their hopping capacity; Li⁺ and SEI
+/// cannot hold any; electrolyte species may carry one electron beyond neutral.
+pub fn max_spawn_electrons(species: Species) -> usize {
+    match species {
+        Species::FoilMetal => crate::config::FOIL_MAX_ELECTRONS,
+        Species::LithiumMetal => crate::config::LITHIUM_METAL_MAX_ELECTRONS,
+        Species::Graphite | Species::HardCarbon | Species::SiliconOxide | Species::LTO => {
+            crate::config::ELECTRODE_ANODE_MAX_ELECTRONS
+        }
+        Species::LFP | Species::LMFP | Species::NMC | Species::NCA => {
+            crate::config::ELECTRODE_CATHODE_MAX_ELECTRONS
+        }
+        // Any electron on an ion would immediately reduce it to metal
+        Species::LithiumIon | Species::SEI => 0,
+        Species::ElectrolyteAnion | Species::LLZO | Species::LLZT | Species::S40B => 1,
+        Species::EC | Species::DMC | Species::VC | Species::FEC | Species::EMC => 2,
+    }
+}
+
+/// Electron count for a spawned body: the override clamped to
+/// [`max_spawn_electrons`], or the species default when `None`.
+pub fn spawn_electron_count(species: Species, electron_count_override: Option<usize>) -> usize {
+    match electron_count_override {
+        Some(count) => count.min(max_spawn_electrons(species)),
+        None => default_spawn_electrons(species),
+    }
+}
+
+/// Electron count that gives `body` a net charge of `charge` (rounded to
+/// whole electrons), clamped to [`max_spawn_electrons`].
+pub fn electrons_for_charge(body: &crate::body::Body, charge: f32) -> usize {
+    let electrons = (body.neutral_electron_count() as f32 - charge.round()).max(0.0) as usize;
+    electrons.min(max_spawn_electrons(body.species))
+}
+
+/// Seed a freshly spawned `body` and update its charge. An electron-count
+/// override wins over a charge override; with neither the species default
+/// is used.
+pub fn seed_spawn_electrons(
+    body: &mut crate::body::Body,
+    electron_count_override: Option<usize>,
+    charge_override: Option<f32>,
+) {
+    let count = match (electron_count_override, charge_override) {
+        (None, Some(charge)) => electrons_for_charge(body, charge),
+        (count, _) => spawn_electron_count(body.species, count),
+    };
+    seed_electrons(body, count);
+    body.update_charge_from_electrons();
+}
+
+/// Replace `body`'s electrons with `count` electrons at the host centre.
+pub fn seed_electrons(body: &mut crate::body::Body, count: usize) {
+    body.electrons.clear();
+    for _ in 0..count {
+        body.electrons.push(Electron {
+            rel_pos: Vec2::zero(),
+            vel: Vec2::zero(),
+        });
+    }
+}
+
 pub fn remove_body_with_foils(simulation: &mut Simulation, idx: usize) {
     let body = simulation.bodies.remove(idx);
     if let Some(foil_id) = simulation.body_to_foil.remove(&body.id) {
@@ -58,12 +178,17 @@ pub fn add_circle(
     x: f32,
     y: f32,
     radius: f32,
+    electron_count_override: Option<usize>,
+    charge_override: Option<f32>,
 ) {
     profile_scope!("particle_spawn");
     let temp = crate::config::LJ_CONFIG.lock().temperature;
     let center = Vec2::new(x, y);
+    let min_sep = simulation.config.spawn_min_separation;
+    let mut existing = simulation.bodies.len();
     let particle_radius = body.radius;
     let particle_diameter = 2.0 * particle_radius;
+    let mut skipped = 0;
     let mut r = particle_radius;
     while r <= radius {
         let circumference = 2.0 * std::f32::consts::PI * r;
@@ -76,9 +201,11 @@ pub fn add_circle(
             let angle = (i as f32) * std::f32::consts::TAU / (count as f32);
             let offset = Vec2::new(angle.cos(), angle.sin()) * r;
             let pos = center + offset;
-            while let Some(idx) = overlaps_any(&simulation.bodies, pos, particle_radius) {
-                remove_body_with_foils(simulation, idx);
+            if !clear_of_walls(simulation, pos, particle_radius) {
+                skipped += 1;
+                continue;
             }
+            clear_spawn_site(simulation, &mut existing, pos, particle_radius, min_sep);
             let mut new_body = crate::body::Body::new(
                 pos,
                 Vec2::zero(),
@@ -88,54 +215,41 @@ pub fn add_circle(
                 body.species,
             );
             new_body.vel = sample_velocity(new_body.mass, temp);
-            new_body.electrons.clear();
-            if matches!(
-                new_body.species,
-                Species::LithiumMetal | Species::ElectrolyteAnion | Species::EC | Species::DMC
-            ) {
-                new_body.electrons.push(Electron {
-                    rel_pos: Vec2::zero(),
-                    vel: Vec2::zero(),
-                });
-            }
-            new_body.update_charge_from_electrons();
+            seed_spawn_electrons(&mut new_body, electron_count_override, charge_override);
             new_body.update_species();
             simulation.bodies.push(new_body);
         }
         r += particle_diameter;
     }
+    report_wall_skips(skipped);
 }
 
 pub fn add_ring(simulation: &mut Simulation, body: crate::body::Body, x: f32, y: f32, radius: f32) {
     let temp = crate::config::LJ_CONFIG.lock().temperature;
     let center = Vec2::new(x, y);
+    let min_sep = simulation.config.spawn_min_separation;
+    let mut existing = simulation.bodies.len();
     let particle_radius = body.radius;
     let particle_diameter = 2.0 * particle_radius;
     let circumference = 2.0 * std::f32::consts::PI * radius;
     let count = (circumference / particle_diameter).floor() as usize;
+    let mut skipped = 0;
     for i in 0..count {
         let angle = (i as f32) * std::f32::consts::TAU / (count as f32);
         let pos = center + Vec2::new(angle.cos(), angle.sin()) * radius;
-        while let Some(idx) = overlaps_any(&simulation.bodies, pos, particle_radius) {
-            remove_body_with_foils(simulation, idx);
+        if !clear_of_walls(simulation, pos, particle_radius) {
+            skipped += 1;
+            continue;
         }
+        clear_spawn_site(simulation, &mut existing, pos, particle_radius, min_sep);
         let mut new_body =
             crate::body::Body::new(pos, Vec2::zero(), body.mass, body.radius, 0.0, body.species);
         new_body.vel = sample_velocity(new_body.mass, temp);
-        new_body.electrons.clear();
-        if matches!(
-            new_body.species,
-            Species::LithiumMetal | Species::ElectrolyteAnion | Species::EC | Species::DMC
-        ) {
-            new_body.electrons.push(Electron {
-                rel_pos: Vec2::zero(),
-                vel: Vec2::zero(),
-            });
-        }
-        new_body.update_charge_from_electrons();
+        seed_spawn_electrons(&mut new_body, None, None);
         new_body.update_species();
         simulation.bodies.push(new_body);
     }
+    report_wall_skips(skipped);
 }
 
 pub fn add_rectangle(
@@ -148,10 +262,13 @@ pub fn add_rectangle(
 ) {
     let _temp = crate::config::LJ_CONFIG.lock().temperature;
     let origin = Vec2::new(x, y);
+    let min_sep = simulation.config.spawn_min_separation;
+    let mut existing = simulation.bodies.len();
     let particle_radius = body.radius;
     let particle_diameter = 2.0 * particle_radius;
     let cols = (width / particle_diameter).floor() as usize;
     let rows = (height / particle_diameter).floor() as usize;
+    let mut skipped = 0;
     for row in 0..rows {
         for col in 0..cols {
             let pos = origin
@@ -159,9 +276,11 @@ pub fn add_rectangle(
                     (col as f32 + 0.5) * particle_diameter,
                     (row as f32 + 0.5) * particle_diameter,
                 );
-            while let Some(idx) = overlaps_any(&simulation.bodies, pos, particle_radius) {
-                remove_body_with_foils(simulation, idx);
+            if !clear_of_walls(simulation, pos, particle_radius) {
+                skipped += 1;
+                continue;
             }
+            clear_spawn_site(simulation, &mut existing, pos, particle_radius, min_sep);
             let mut new_body = crate::body::Body::new(
                 pos,
                 Vec2::zero(),
@@ -192,6 +311,7 @@ pub fn add_rectangle(
             simulation.bodies.push(new_body);
         }
     }
+    report_wall_skips(skipped);
 }
 
 pub fn add_random(
@@ -204,14 +324,23 @@ pub fn add_random(
     // Attempt to place 'count' random bodies, tracking failures
     let mut failures = 0;
     let temp = crate::config::LJ_CONFIG.lock().temperature;
+    let min_sep = simulation.config.spawn_min_separation;
+    // Sample only inside the wall margin
+    let inset = simulation.config.spawn_wall_margin.max(0.0) + body.radius;
+    let half_w = (domain_width / 2.0).min(simulation.domain_width) - inset;
+    let half_h = (domain_height / 2.0).min(simulation.domain_height) - inset;
     for _ in 0..count {
         let mut placed = false;
+        if half_w < 0.0 || half_h < 0.0 {
+            failures += 1;
+            continue;
+        }
         for _ in 0..RANDOM_ATTEMPTS {
             let pos = Vec2::new(
-                fastrand::f32() * domain_width - domain_width / 2.0,
-                fastrand::f32() * domain_height - domain_height / 2.0,
+                (fastrand::f32() * 2.0 - 1.0) * half_w,
+                (fastrand::f32() * 2.0 - 1.0) * half_h,
             );
-            if overlaps_any(&simulation.bodies, pos, body.radius).is_none() {
+            if is_position_valid(pos, body.radius, &simulation.bodies, min_sep) {
                 // For LithiumIon, also check that we're not near any metal
                 // to prevent immediate conversion to LithiumMetal
                 if body.species == Species::LithiumIon
@@ -229,17 +358,7 @@ pub fn add_random(
                     body.species,
                 );
                 new_body.vel = sample_velocity(new_body.mass, temp);
-                new_body.electrons.clear();
-                if matches!(
-                    new_body.species,
-                    Species::LithiumMetal | Species::ElectrolyteAnion | Species::EC | Species::DMC
-                ) {
-                    new_body.electrons.push(Electron {
-                        rel_pos: Vec2::zero(),
-                        vel: Vec2::zero(),
-                    });
-                }
-                new_body.update_charge_from_electrons();
+                seed_spawn_electrons(&mut new_body, None, None);
                 new_body.update_species();
                 simulation.bodies.push(new_body);
                 placed = true;
@@ -252,18 +371,61 @@ pub fn add_random(
     }
     // Report summary of placement results
     if failures > 0 {
-        eprintln!(
+        crate::sim_log!(
+            Physics,
+            Error,
             "Failed to place {} random bodies out of {} after {} attempts each",
             failures, count, RANDOM_ATTEMPTS
         );
     } else {
-        eprintln!(
+        crate::sim_log!(
+            Physics,
+            Info,
             "Successfully placed {} random bodies",
             count
         );
     }
 }
 
+/// Randomly place a LiPF6 in EC:DMC electrolyte with the given salt molarity
+/// and EC:DMC volume parts (see `species::electrolyte_counts`).
+pub fn add_electrolyte(
+    simulation: &mut Simulation,
+    total: usize,
+    molarity: f32,
+    ec_parts: f32,
+    dmc_parts: f32,
+    domain_width: f32,
+    domain_height: f32,
+) {
+    let counts = crate::species::electrolyte_counts(total, molarity, ec_parts, dmc_parts);
+    for (species, count) in [
+        (Species::LithiumIon, counts.lithium),
+        (Species::ElectrolyteAnion, counts.anion),
+        (Species::EC, counts.ec),
+        (Species::DMC, counts.dmc),
+    ] {
+        if count == 0 {
+            continue;
+        }
+        let template = crate::body::Body::new(
+            Vec2::zero(),
+            Vec2::zero(),
+            species.mass(),
+            species.radius(),
+            0.0,
+            species,
+        );
+        add_random(simulation, template, count, domain_width, domain_height);
+    }
+    crate::sim_log!(
+        Physics,
+        Info,
+        "[Electrolyte] {:.2}M, EC:DMC {}:{}: {} Li+, {} PF6-, {} EC, {} DMC",
+        molarity, ec_parts, dmc_parts, counts.lithium, counts.anion, counts.ec, counts.dmc
+    );
+}
+
 pub fn add_foil(
     simulation: &mut Simulation,
     width: f32,
@@ -275,6 +437,8 @@ pub fn add_foil(
 ) {
     let temp = crate::config::LJ_CONFIG.lock().temperature;
     let origin = Vec2::new(x, y);
+    let min_sep = simulation.config.spawn_min_separation;
+    let mut existing = simulation.bodies.len();
     let particle_diameter = 2.0 * particle_radius;
     let cols = (width / particle_diameter).floor() as usize;
     let rows = (height / particle_diameter).floor() as usize;
@@ -286,9 +450,7 @@ pub fn add_foil(
                     (col as f32 + 0.5) * particle_diameter,
                     (row as f32 + 0.5) * particle_diameter,
                 );
-            while let Some(idx) = overlaps_any(&simulation.bodies, pos, particle_radius) {
-                remove_body_with_foils(simulation, idx);
-            }
+            clear_spawn_site(simulation, &mut existing, pos, particle_radius, min_sep);
             let mut new_body = crate::body::Body::new(
                 pos,
                 Vec2::zero(),
@@ -310,3 +472,128 @@ pub fn add_foil(
     }
     simulation.foils.push(foil);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::Body;
+
+    #[test]
+    fn lithium_metal_electron_override_sets_count_and_charge() {
+        let mut sim = Simulation::new();
+        sim.bodies.clear();
+        let template = Body::new(
+            Vec2::zero(),
+            Vec2::zero(),
+            Species::LithiumMetal.mass(),
+            Species::LithiumMetal.radius(),
+            0.0,
+            Species::LithiumMetal,
+        );
+        // A radius equal to the particle radius spawns a single ring of bodies
+        let radius = template.radius;
+        add_circle(&mut sim, template.clone(), 0.0, 0.0, radius, Some(2), None);
+        assert!(!sim.bodies.is_empty());
+        for body in &sim.bodies {
+            assert_eq!(body.species, Species::LithiumMetal);
+            assert_eq!(body.electrons.len(), 2);
+            assert_eq!(body.charge, -1.0);
+        }
+
+        // A charge override of -1 picks the same electron count
+        sim.bodies.clear();
+        add_circle(&mut sim, template, 0.0, 0.0, radius, None, Some(-1.0));
+        assert!(!sim.bodies.is_empty());
+        for body in &sim.bodies {
+            assert_eq!(body.electrons.len(), 2);
+            assert_eq!(body.charge, -1.0);
+        }
+    }
+
+    #[test]
+    fn spawned_bodies_respect_wall_margin() {
+        let mut sim = Simulation::new();
+        sim.bodies.clear();
+        sim.domain_width = 40.0;
+        sim.domain_height = 30.0;
+        sim.config.spawn_wall_margin = 3.0;
+        let template = |species: Species| {
+            Body::new(Vec2::zero(), Vec2::zero(), species.mass(), species.radius(), 0.0, species)
+        };
+        add_random(&mut sim, template(Species::EC), 60, 80.0, 60.0);
+        // A disk and a lattice that both run past the right and bottom walls
+        add_circle(&mut sim, template(Species::LithiumMetal), 35.0, 0.0, 12.0, None, None);
+        add_rectangle(&mut sim, template(Species::LLZO), 20.0, -30.0, 30.0, 15.0);
+        assert!(sim.bodies.len() > 60);
+        for body in &sim.bodies {
+            let inset = sim.config.spawn_wall_margin + body.radius;
+            assert!(body.pos.x.abs() <= sim.domain_width - inset + 1e-4, "{:?}", body.pos);
+            assert!(body.pos.y.abs() <= sim.domain_height - inset + 1e-4, "{:?}", body.pos);
+        }
+    }
+
+    #[test]
+    fn every_generator_respects_min_separation() {
+        fn template(species: Species) -> Body {
+            Body::new(Vec2::zero(), Vec2::zero(), species.mass(), species.radius(), 0.0, species)
+        }
+        let generators: [(&str, fn(&mut Simulation)); 6] = [
+            ("circle", |sim| {
+                add_circle(sim, template(Species::LithiumMetal), 0.0, 0.0, 8.0, None, None)
+            }),
+            ("ring", |sim| add_ring(sim, template(Species::LithiumMetal), 0.0, 0.0, 6.0)),
+            ("rectangle", |sim| add_rectangle(sim, template(Species::LLZO), -6.0, -6.0, 12.0, 12.0)),
+            ("random", |sim| add_random(sim, template(Species::EC), 20, 40.0, 40.0)),
+            ("electrolyte", |sim| add_electrolyte(sim, 20, 1.0, 1.0, 1.0, 40.0, 40.0)),
+            ("foil", |sim| add_foil(sim, 12.0, 12.0, -6.0, -6.0, 1.0, 0.0)),
+        ];
+        for (name, generate) in generators {
+            let mut sim = Simulation::new();
+            sim.bodies.clear();
+            sim.foils.clear();
+            sim.config.spawn_min_separation = 0.8;
+            // A body sitting where most generators want to place one
+            sim.bodies.push(template(Species::DMC));
+            generate(&mut sim);
+            assert!(sim.bodies.len() > 1, "{name} spawned nothing");
+            for (i, a) in sim.bodies.iter().enumerate() {
+                let others = &sim.bodies[i + 1..];
+                assert!(
+                    is_position_valid(a.pos, a.radius, others, 0.8 - 1e-4),
+                    "{name} left a pair closer than min_sep near {:?}",
+                    a.pos
+                );
+            }
+        }
+        assert!(!is_position_valid(Vec2::new(1.0, 0.0), 1.0, &[template(Species::EC)], 0.65));
+    }
+
+    #[test]
+    fn wide_min_separation_keeps_the_whole_batch() {
+        let mut sim = Simulation::new();
+        sim.bodies.clear();
+        sim.foils.clear();
+        sim.config.spawn_min_separation = 1.5;
+        let species = Species::LLZO;
+        let template =
+            Body::new(Vec2::zero(), Vec2::zero(), species.mass(), species.radius(), 0.0, species);
+        let diameter = 2.0 * species.radius();
+        add_rectangle(&mut sim, template, -6.0, -6.0, 12.0, 12.0);
+        let per_side = (12.0 / diameter).floor() as usize;
+        assert_eq!(sim.bodies.len(), per_side * per_side);
+        add_foil(&mut sim, 12.0, 12.0, -6.0, -6.0, 1.0, 0.0);
+        // The foil replaces the lattice but keeps every one of its own sites
+        assert_eq!(sim.bodies.len(), 36);
+        assert_eq!(sim.foils[0].body_ids.len(), 36);
+    }
+
+    #[test]
+    fn electron_override_is_clamped_per_species() {
+        assert_eq!(
+            spawn_electron_count(Species::LithiumMetal, Some(10)),
+            crate::config::LITHIUM_METAL_MAX_ELECTRONS
+        );
+        assert_eq!(spawn_electron_count(Species::LithiumIon, Some(1)), 0);
+        assert_eq!(spawn_electron_count(Species::EC, None), 1);
+    }
+}
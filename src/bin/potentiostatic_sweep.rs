@@ -1,10 +1,19 @@
 //! Potentiostatic DCR: impose voltage steps via overpotential mode,
 //! measure resulting steady-state current at each step.
 //! R_DC = eta / I_ss in the linear regime.
+//!
+//! Usage: potentiostatic_sweep [--curves <csv>] [--steady-tol <spread>]
+//!   --curves      also write foil A's electron-ratio approach curve for every
+//!                 target to one CSV (one column per target)
+//!   --steady-tol  end a target's dwell early once its ratio spread over the
+//!                 last 20 ps is within this value (default 0 = full dwell)
 
 use particle_sim::app::command_loop::handle_command;
 use particle_sim::body::{Body, Species};
 use particle_sim::renderer::state::{SimCommand, SIM_COMMAND_SENDER};
+use particle_sim::simulation::ratio_convergence::{
+    write_ratio_curves_csv, RatioCurveRecorder, RatioSweepConfig,
+};
 use particle_sim::simulation::Simulation;
 use std::sync::mpsc::channel;
 use ultraviolet::Vec2;
@@ -81,6 +90,31 @@ fn build_cell(esize: f32, foil_w: f32, domain_w: f32, domain_h: f32,
 }
 
 fn main() {
+    let mut curves_path: Option<String> = None;
+    let mut steady_tolerance = 0.0f32;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--curves" => {
+                i += 1;
+                curves_path = args.get(i).cloned();
+            }
+            "--steady-tol" => {
+                i += 1;
+                steady_tolerance = args.get(i).and_then(|s| s.parse().ok()).unwrap_or_else(|| {
+                    eprintln!("--steady-tol expects a number");
+                    std::process::exit(2);
+                });
+            }
+            other => {
+                eprintln!("unknown argument: {other}");
+                std::process::exit(2);
+            }
+        }
+        i += 1;
+    }
+
     let esize = 80.0f32;
     let foil_w = 20.0f32;
     let domain_w = esize * 5.0;
@@ -102,6 +136,13 @@ fn main() {
     let equilibrate_steps = 1000;  // 5k fs
     let measure_steps = 30000;     // 150k fs = 150 ps
     let log_stride = 20;           // every 100 fs
+    let curve_config = RatioSweepConfig {
+        dwell_steps: measure_steps,
+        sample_stride: log_stride,
+        steady_window_fs: 20_000.0,
+        steady_tolerance,
+    };
+    let mut curves = Vec::with_capacity(ratios.len());
 
     println!("target_ratio,sim_time,eta_a,eta_b,eta_avg,i_controller_a,i_controller_b,e_delta_a,e_delta_b");
 
@@ -131,9 +172,11 @@ fn main() {
         let mut last_delta_a: i32 = 0;
         let mut last_delta_b: i32 = 0;
         let mut last_t = t0;
+        let mut recorder = RatioCurveRecorder::new(ratio, curve_config);
 
         for step in 1..=measure_steps {
             sim.step();
+            let done = recorder.push(sim.time, sim.calculate_foil_electron_ratio(&sim.foils[0]));
 
             if step % log_stride == 0 {
                 let t = sim.time - t0;
@@ -163,6 +206,21 @@ fn main() {
                 println!("{:.3},{:.1},{:.6},{:.6},{:.6},{:.6},{:.6},{},{}",
                     ratio, t, eta_a, eta_b, eta_avg, i_a, i_b, d_a, d_b);
             }
+
+            if done {
+                if step < measure_steps {
+                    eprintln!("  ratio={:.3} steady after {} steps", ratio, step);
+                }
+                break;
+            }
+        }
+        curves.push(recorder.finish());
+    }
+
+    if let Some(path) = curves_path {
+        match write_ratio_curves_csv(&curves, &path) {
+            Ok(()) => eprintln!("Wrote ratio convergence curves to {}", path),
+            Err(e) => eprintln!("Failed to write {}: {}", path, e),
         }
     }
 }
@@ -23,6 +23,8 @@ impl Body {
         background_field: Vec2,
         dt: f32,
         coulomb_constant: f32,
+        stiffness: f32,
+        max_displacement: f32,
     ) {
         profile_scope!("electron_updates");
         let k = config::electron_spring_k(self.species);
@@ -30,7 +32,8 @@ impl Body {
             let electron_pos = self.pos + e.rel_pos;
             let local_field =
                 quadtree.field_at_point(bodies, electron_pos, coulomb_constant) + background_field;
-            let acc = -local_field * k;
+            // Field drive plus restoring spring toward the host centre
+            let acc = -local_field * k - e.rel_pos * stiffness;
             e.vel += acc * dt;
             let speed = e.vel.mag();
             let max_speed = config::ELECTRON_MAX_SPEED_FACTOR * self.radius / dt;
@@ -38,7 +41,10 @@ impl Body {
                 e.vel = e.vel / speed * max_speed;
             }
             e.rel_pos += e.vel * dt;
-            let max_dist = self.species.polar_offset() * self.radius;
+            let mut max_dist = self.species.polar_offset() * self.radius;
+            if max_displacement > 0.0 {
+                max_dist = max_dist.min(max_displacement);
+            }
             if e.rel_pos.mag() > max_dist {
                 e.rel_pos = e.rel_pos.normalized() * max_dist;
             }
@@ -43,6 +43,44 @@ pub enum ChargingMode {
     Overpotential,
 }
 
+/// Shape of the AC current component.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AcWaveform {
+    /// +amplitude for the first half period, -amplitude for the second.
+    #[default]
+    Square,
+    /// `amplitude * sin(2π f t)`.
+    Sine,
+    /// Linear ramp in phase with the sine: 0 → +amp at T/4 → -amp at 3T/4 → 0.
+    Triangle,
+}
+
+impl AcWaveform {
+    /// Unit-amplitude waveform value at `phase` (fraction of a period, wrapped to [0, 1)).
+    pub fn value(self, phase: f32) -> f32 {
+        let p = phase.rem_euclid(1.0);
+        match self {
+            AcWaveform::Square => {
+                if p < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            AcWaveform::Sine => (std::f32::consts::TAU * p).sin(),
+            AcWaveform::Triangle => {
+                if p < 0.25 {
+                    4.0 * p
+                } else if p < 0.75 {
+                    2.0 - 4.0 * p
+                } else {
+                    4.0 * p - 4.0
+                }
+            }
+        }
+    }
+}
+
 /// Overpotential controller parameters for voltage-controlled charging.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OverpotentialController {
@@ -62,6 +100,12 @@ pub struct OverpotentialController {
     pub max_current: f32,
     /// Last computed output current for display
     pub last_output_current: f32,
+    /// Derivative of the error from the last update (per fs)
+    #[serde(default)]
+    pub last_derivative_error: f32,
+    /// Electron ratio the controller last reacted to
+    #[serde(default)]
+    pub last_actual_ratio: f32,
     /// History of PID controller performance for plotting
     pub history: VecDeque<PidHistoryPoint>,
     /// Maximum number of history points to keep
@@ -70,6 +114,34 @@ pub struct OverpotentialController {
     pub master_foil_id: Option<u64>,
 }
 
+/// One window of a foil's activation schedule.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleSegment {
+    pub start_fs: f32,
+    pub end_fs: f32,
+    /// DC current driven while `start_fs <= t < end_fs`
+    pub setpoint: f32,
+}
+
+/// What a [`HandoffRule`] watches and the level that triggers it.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum HandoffCondition {
+    /// Li metal attached to the foil reaches this many bodies
+    MetalCountAtLeast(usize),
+    /// Electron ratio of the foil network rises to this value
+    RatioAtLeast(f32),
+    /// Electron ratio of the foil network falls to this value
+    RatioAtMost(f32),
+}
+
+/// One-shot switch from current control to an overpotential hold at
+/// `target_ratio` once `condition` is met.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HandoffRule {
+    pub condition: HandoffCondition,
+    pub target_ratio: f32,
+}
+
 /// Collection of fixed lithium metal particles representing a foil.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Foil {
@@ -85,6 +157,9 @@ pub struct Foil {
     pub accum: f32,
     /// Frequency in Hz for toggling the current on/off. `0.0` disables switching.
     pub switch_hz: f32,
+    /// Shape of the AC component driven at `switch_hz`.
+    #[serde(default)]
+    pub ac_waveform: AcWaveform,
     /// Identifier of a linked foil, if any.
     pub link_id: Option<u64>,
     /// Link mode describing how the currents are related.
@@ -95,6 +170,17 @@ pub struct Foil {
     pub overpotential_controller: Option<OverpotentialController>,
     /// Slave overpotential current for linked foils
     pub slave_overpotential_current: f32,
+    /// Per-body electron capacity for this foil; `None` uses `FOIL_MAX_ELECTRONS`.
+    #[serde(default)]
+    pub max_electrons_override: Option<usize>,
+    /// Activation windows applied each step, independent of switch charging;
+    /// empty leaves the DC current alone.
+    #[serde(default)]
+    pub schedule: Vec<ScheduleSegment>,
+    /// Current-to-overpotential handoff, checked every `HANDOFF_CHECK_INTERVAL`
+    /// frames while in current mode
+    #[serde(default)]
+    pub handoff: Option<HandoffRule>,
     /// Signed count of electrons added (positive) or removed (negative) since last measurement
     #[serde(skip)]
     pub electron_delta_since_measure: i32,
@@ -122,15 +208,39 @@ impl Foil {
             ac_current: 0.0,     // No AC component by default
             accum: 0.0,
             switch_hz: 0.0,
+            ac_waveform: AcWaveform::Square,
             link_id: None,
             mode: LinkMode::Parallel,
             charging_mode: ChargingMode::Current, // Default to current control
             overpotential_controller: None,       // No overpotential controller by default
             slave_overpotential_current: 0.0,     // Initialize slave current to zero
+            max_electrons_override: None,
+            schedule: Vec::new(),
+            handoff: None,
             electron_delta_since_measure: 0,
         }
     }
 
+    /// Electrons each body of this foil may hold.
+    pub fn max_electrons(&self) -> usize {
+        self.max_electrons_override
+            .unwrap_or(crate::config::FOIL_MAX_ELECTRONS)
+    }
+
+    /// DC current the schedule asks for at `time`: the first segment covering
+    /// it, zero between segments, `None` when the foil has no schedule.
+    pub fn scheduled_current(&self, time: f32) -> Option<f32> {
+        if self.schedule.is_empty() {
+            return None;
+        }
+        Some(
+            self.schedule
+                .iter()
+                .find(|seg| time >= seg.start_fs && time < seg.end_fs)
+                .map_or(0.0, |seg| seg.setpoint),
+        )
+    }
+
     /// Enable overpotential control mode with specified target electron ratio
     pub fn enable_overpotential_mode(&mut self, target_ratio: f32) {
         self.charging_mode = ChargingMode::Overpotential;
@@ -144,6 +254,8 @@ impl Foil {
             previous_error: 0.0,
             max_current: 500.0, // Maximum current limit - tunable
             last_output_current: 0.0,
+            last_derivative_error: 0.0,
+            last_actual_ratio: target_ratio,
             history: VecDeque::new(),
             max_history_size: 1000,
             master_foil_id: None,
@@ -170,6 +282,8 @@ impl Foil {
                 + controller.kd * derivative_error;
 
             controller.previous_error = error;
+            controller.last_derivative_error = derivative_error;
+            controller.last_actual_ratio = actual_ratio;
 
             // Clamp to maximum current
             let output = pid_output.clamp(-controller.max_current, controller.max_current);
@@ -180,6 +294,31 @@ impl Foil {
         }
     }
 
+    /// AC contribution at `time` for the selected waveform (zero when
+    /// switching is disabled).
+    pub fn ac_component(&self, time: f32) -> f32 {
+        if self.switch_hz > 0.0 {
+            self.ac_current * self.ac_waveform.value(time * self.switch_hz)
+        } else {
+            0.0
+        }
+    }
+
+    /// Current this foil is commanded to drive at `time`, without stepping the
+    /// PID controller. Overpotential masters report their last controller
+    /// output and slaves their stored slave current; the AC waveform is
+    /// added on top in every mode.
+    pub fn commanded_current(&self, time: f32) -> f32 {
+        let base = match self.charging_mode {
+            ChargingMode::Current => self.dc_current,
+            ChargingMode::Overpotential => match &self.overpotential_controller {
+                Some(controller) => controller.last_output_current,
+                None => self.slave_overpotential_current,
+            },
+        };
+        base + self.ac_component(time)
+    }
+
     /// Enable slave mode for overpotential control (linked to master foil)
     #[allow(dead_code)]
     pub fn enable_overpotential_slave_mode(&mut self, _master_id: u64) {
@@ -188,3 +327,36 @@ impl Foil {
         self.slave_overpotential_current = 0.0;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waveforms_at_quarter_and_eighth_period() {
+        let mut foil = Foil::new(Vec::new(), Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+        foil.ac_current = 2.0;
+        foil.switch_hz = 0.01; // period 100 fs
+        let (quarter, eighth) = (25.0, 12.5);
+
+        let expect = [
+            (AcWaveform::Square, 1.0, 1.0),
+            (AcWaveform::Sine, 1.0, std::f32::consts::FRAC_1_SQRT_2),
+            (AcWaveform::Triangle, 1.0, 0.5),
+        ];
+        for (waveform, at_quarter, at_eighth) in expect {
+            foil.ac_waveform = waveform;
+            let q = foil.ac_component(quarter) / foil.ac_current;
+            let e = foil.ac_component(eighth) / foil.ac_current;
+            assert!((q - at_quarter).abs() < 1e-4, "{waveform:?} at T/4: {q}");
+            assert!((e - at_eighth).abs() < 1e-4, "{waveform:?} at T/8: {e}");
+            // Second half of the period mirrors the first
+            assert!((foil.ac_component(75.0) / foil.ac_current + at_quarter).abs() < 1e-4);
+        }
+
+        // Square stays the default so existing runs are unchanged
+        assert_eq!(AcWaveform::default(), AcWaveform::Square);
+        foil.switch_hz = 0.0;
+        assert_eq!(foil.ac_component(quarter), 0.0);
+    }
+}
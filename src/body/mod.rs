@@ -29,3 +29,7 @@ mod ion_vs_anion;
 #[cfg(test)]
 #[path = "tests/anion.rs"]
 mod anion;
+
+#[cfg(test)]
+#[path = "tests/electron_stiffness.rs"]
+mod electron_stiffness;
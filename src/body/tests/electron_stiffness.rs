@@ -0,0 +1,67 @@
+#![cfg(any(test, feature = "unit_tests"))]
+#[cfg(test)]
+mod electron_stiffness {
+    use crate::body::{Body, Electron, Species};
+    use crate::quadtree::Quadtree;
+    use ultraviolet::Vec2;
+
+    /// Time-averaged electron offset along the applied field direction.
+    fn mean_displacement(stiffness: f32) -> f32 {
+        let mut metal = Body::new(
+            Vec2::zero(),
+            Vec2::zero(),
+            1.0,
+            1.0,
+            0.0,
+            Species::LithiumMetal,
+        );
+        metal.electrons.push(Electron {
+            rel_pos: Vec2::zero(),
+            vel: Vec2::zero(),
+        });
+        // Empty tree: the applied field is the only field
+        let qt = Quadtree::new(0.5, 0.01, 1, 1);
+        let field = Vec2::new(0.01, 0.0);
+        let steps = 4000;
+        let mut sum = 0.0;
+        for _ in 0..steps {
+            metal.update_electrons(&[], &qt, field, 0.05, 1.0, stiffness, 0.0);
+            sum += -metal.electrons[0].rel_pos.x;
+        }
+        sum / steps as f32
+    }
+
+    #[test]
+    fn softer_cloud_polarizes_further() {
+        let stiff = mean_displacement(1.0);
+        let soft = mean_displacement(0.25);
+        assert!(stiff > 0.0, "electron should shift against the field");
+        assert!(soft > 2.0 * stiff, "soft {} vs stiff {}", soft, stiff);
+    }
+
+    #[test]
+    fn huge_field_saturates_at_displacement_clamp() {
+        let mut metal = Body::new(
+            Vec2::zero(),
+            Vec2::zero(),
+            1.0,
+            1.0,
+            0.0,
+            Species::LithiumMetal,
+        );
+        metal.electrons.push(Electron {
+            rel_pos: Vec2::zero(),
+            vel: Vec2::zero(),
+        });
+        let qt = Quadtree::new(0.5, 0.01, 1, 1);
+        let field = Vec2::new(1.0e6, 0.0);
+        let clamp = 0.05;
+        for _ in 0..200 {
+            metal.update_electrons(&[], &qt, field, 0.05, 1.0, 0.0, clamp);
+            let offset = metal.electrons[0].rel_pos.mag();
+            assert!(offset.is_finite() && offset <= clamp + 1e-6, "offset {}", offset);
+        }
+        assert!((metal.electrons[0].rel_pos.mag() - clamp).abs() < 1e-4);
+        assert!(metal.electrons[0].rel_pos.x < 0.0, "pushed against the field");
+    }
+}
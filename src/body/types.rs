@@ -65,6 +65,13 @@ use std::sync::atomic::{AtomicU64, Ordering};
 static NEXT_ID: AtomicU64 = AtomicU64::new(1);
 
 impl Body {
+    /// Claim an unused ID greater than both the global counter and `floor`,
+    /// so IDs restored from disk cannot be handed out again.
+    pub fn fresh_id_above(floor: u64) -> u64 {
+        NEXT_ID.fetch_max(floor.saturating_add(1), Ordering::Relaxed);
+        NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    }
+
     pub fn new(
         pos: Vec2,
         vel: Vec2,
@@ -302,6 +309,14 @@ impl Body {
             self.vz = 0.0;
         }
     }
+
+    /// Contact radius for collisions and touching checks: the species
+    /// `collision_radius` override if set, else the body's own radius.
+    pub fn collision_radius(&self) -> f32 {
+        crate::species::get_species_props(self.species)
+            .collision_radius
+            .unwrap_or(self.radius)
+    }
 }
 
 impl Species {
@@ -317,10 +332,23 @@ impl Species {
         self.props().radius
     }
 
+    /// Radius used for collision contact; defaults to `radius()`.
+    pub fn collision_radius(&self) -> f32 {
+        let props = self.props();
+        props.collision_radius.unwrap_or(props.radius)
+    }
+
     pub fn damping(&self) -> f32 {
         self.props().damping
     }
 
+    /// Out-of-plane participation, per-species override or the default.
+    pub fn z_mobility(&self) -> crate::species::ZMobility {
+        self.props()
+            .z_mobility
+            .unwrap_or_else(|| crate::species::ZMobility::default_for(*self))
+    }
+
     pub fn color(&self) -> [u8; 4] {
         self.props().color
     }
@@ -1,5 +1,6 @@
 #![allow(dead_code)] // Public API functions that may be used by other systems
 
+use crate::app::spawn::clear_spawn_site;
 use crate::body::{foil::LinkMode, Species};
 use crate::simulation::Simulation;
 use ultraviolet::Vec2;
@@ -16,6 +17,8 @@ pub fn handle_add_foil(
     current: f32,
 ) {
     let origin = Vec2::new(x, y);
+    let min_sep = simulation.config.spawn_min_separation;
+    let mut existing = simulation.bodies.len();
     let particle_diameter = 2.0 * particle_radius;
     let cols = (width / particle_diameter).floor() as usize;
     let rows = (height / particle_diameter).floor() as usize;
@@ -27,11 +30,7 @@ pub fn handle_add_foil(
                     (col as f32 + 0.5) * particle_diameter,
                     (row as f32 + 0.5) * particle_diameter,
                 );
-            while let Some(idx) =
-                super::particle::overlaps_any(&simulation.bodies, pos, particle_radius)
-            {
-                super::particle::remove_body_with_foils(simulation, idx);
-            }
+            clear_spawn_site(simulation, &mut existing, pos, particle_radius, min_sep);
             let mut new_body = crate::body::Body::new(
                 pos,
                 Vec2::zero(),
@@ -1,5 +1,6 @@
 #![allow(dead_code)] // Public API functions that may be used by other systems
 
+use crate::app::spawn::{clear_spawn_site, is_position_valid};
 use crate::body::{Electron, Species};
 use crate::simulation::Simulation;
 use ultraviolet::Vec2;
@@ -29,7 +30,9 @@ pub fn handle_change_charge(simulation: &mut Simulation, id: u64, delta: f32) {
             let actual_additions = requested_additions.min(available_capacity);
 
             if actual_additions < requested_additions {
-                println!(
+                crate::sim_log!(
+                    Physics,
+                    Warn,
                     "Warning: Particle {} can only accept {} more electrons (max: {}, current: {})",
                     id, actual_additions, max_electrons, current_count
                 );
@@ -46,30 +49,32 @@ pub fn handle_change_charge(simulation: &mut Simulation, id: u64, delta: f32) {
             }
         }
         body.update_charge_from_electrons();
-        println!("Particle {} new charge: {}", id, body.charge);
-        println!(
+        crate::sim_log!(Physics, Debug, "Particle {} new charge: {}", id, body.charge);
+        crate::sim_log!(
+            Physics,
+            Debug,
             "Particle {} new electron count: {}",
             id,
             body.electrons.len()
         );
-        println!("Particle {} new species: {:?}", id, body.species);
+        crate::sim_log!(Physics, Debug, "Particle {} new species: {:?}", id, body.species);
 
         let was_metal = body.species == Species::LithiumMetal;
         let was_ion = body.species == Species::LithiumIon;
         body.update_species();
 
         if was_metal && body.species == Species::LithiumIon {
-            println!();
-            println!("Should become ion below...");
-            println!("Particle {} new species: {:?}", id, body.species);
+            crate::sim_log!(Physics, Debug, "");
+            crate::sim_log!(Physics, Debug, "Should become ion below...");
+            crate::sim_log!(Physics, Debug, "Particle {} new species: {:?}", id, body.species);
         }
         if was_ion && body.species == Species::LithiumMetal {
-            println!();
-            println!("Should become metal below...");
-            println!("Particle {} new species: {:?}", id, body.species);
+            crate::sim_log!(Physics, Debug, "");
+            crate::sim_log!(Physics, Debug, "Should become metal below...");
+            crate::sim_log!(Physics, Debug, "Particle {} new species: {:?}", id, body.species);
         }
 
-        println!("Particle {} new charge: {}", id, body.charge);
+        crate::sim_log!(Physics, Debug, "Particle {} new charge: {}", id, body.charge);
     }
 }
 
@@ -122,6 +127,8 @@ pub fn handle_add_circle(
     radius: f32,
 ) {
     let center = Vec2::new(x, y);
+    let min_sep = simulation.config.spawn_min_separation;
+    let mut existing = simulation.bodies.len();
     let particle_radius = body.radius;
     let particle_diameter = 2.0 * particle_radius;
     let mut r = particle_radius;
@@ -136,9 +143,7 @@ pub fn handle_add_circle(
             let angle = (i as f32) * std::f32::consts::TAU / (count as f32);
             let offset = Vec2::new(angle.cos(), angle.sin()) * r;
             let pos = center + offset;
-            while let Some(idx) = overlaps_any(&simulation.bodies, pos, particle_radius) {
-                remove_body_with_foils(simulation, idx);
-            }
+            clear_spawn_site(simulation, &mut existing, pos, particle_radius, min_sep);
             let mut new_body = crate::body::Body::new(
                 pos,
                 Vec2::zero(),
@@ -173,6 +178,8 @@ pub fn handle_add_ring(
     radius: f32,
 ) {
     let center = Vec2::new(x, y);
+    let min_sep = simulation.config.spawn_min_separation;
+    let mut existing = simulation.bodies.len();
     let particle_radius = body.radius;
     let particle_diameter = 2.0 * particle_radius;
     let circumference = 2.0 * std::f32::consts::PI * radius;
@@ -180,9 +187,7 @@ pub fn handle_add_ring(
     for i in 0..count {
         let angle = (i as f32) * std::f32::consts::TAU / (count as f32);
         let pos = center + Vec2::new(angle.cos(), angle.sin()) * radius;
-        while let Some(idx) = overlaps_any(&simulation.bodies, pos, particle_radius) {
-            remove_body_with_foils(simulation, idx);
-        }
+        clear_spawn_site(simulation, &mut existing, pos, particle_radius, min_sep);
         let mut new_body =
             crate::body::Body::new(pos, Vec2::zero(), body.mass, body.radius, 0.0, body.species);
         new_body.electrons.clear();
@@ -210,6 +215,8 @@ pub fn handle_add_rectangle(
     height: f32,
 ) {
     let origin = Vec2::new(x, y);
+    let min_sep = simulation.config.spawn_min_separation;
+    let mut existing = simulation.bodies.len();
     let particle_radius = body.radius;
     let particle_diameter = 2.0 * particle_radius;
     let cols = (width / particle_diameter).floor() as usize;
@@ -221,9 +228,7 @@ pub fn handle_add_rectangle(
                     (col as f32 + 0.5) * particle_diameter,
                     (row as f32 + 0.5) * particle_diameter,
                 );
-            while let Some(idx) = overlaps_any(&simulation.bodies, pos, particle_radius) {
-                remove_body_with_foils(simulation, idx);
-            }
+            clear_spawn_site(simulation, &mut existing, pos, particle_radius, min_sep);
             let mut new_body = crate::body::Body::new(
                 pos,
                 Vec2::zero(),
@@ -256,6 +261,7 @@ pub fn handle_add_random(
     domain_width: f32,
     domain_height: f32,
 ) {
+    let min_sep = simulation.config.spawn_min_separation;
     for _ in 0..count {
         let mut placed = false;
         for _ in 0..RANDOM_ATTEMPTS {
@@ -263,7 +269,7 @@ pub fn handle_add_random(
                 fastrand::f32() * domain_width - domain_width / 2.0,
                 fastrand::f32() * domain_height - domain_height / 2.0,
             );
-            if overlaps_any(&simulation.bodies, pos, body.radius).is_none() {
+            if is_position_valid(pos, body.radius, &simulation.bodies, min_sep) {
                 let mut new_body = crate::body::Body::new(
                     pos,
                     Vec2::zero(),
@@ -290,9 +296,9 @@ pub fn handle_add_random(
             }
         }
         if !placed {
-            eprintln!("Failed to place random body after {} attempts", RANDOM_ATTEMPTS);
+            crate::sim_log!(Physics, Error, "Failed to place random body after {} attempts", RANDOM_ATTEMPTS);
         } else {
-            eprintln!("Successfully placed random body");
+            crate::sim_log!(Physics, Info, "Successfully placed random body");
         }
     }
 }
@@ -326,30 +332,20 @@ pub fn handle_set_temperature(simulation: &mut Simulation, temperature: f32) {
             body.vel *= scale;
         }
     } else {
+        let species = &simulation.config.thermostat_species;
+        let frozen = &simulation.frozen_species;
         crate::simulation::utils::initialize_liquid_velocities_to_temperature(
             &mut simulation.bodies,
             temperature,
+            |s| crate::simulation::thermal::thermostatted(s, species, frozen),
         );
         // If we only initialize liquid species, optionally scale metal species tiny random? Keep them zero for now.
-        eprintln!(
+        crate::sim_log!(
+            Physics,
+            Info,
             "[set_temperature-bootstrap] initialized velocities to {:.2}K (prev ~0)",
             temperature
         );
     }
     crate::config::LJ_CONFIG.lock().temperature = temperature;
 }
-
-pub fn overlaps_any(existing: &[crate::body::Body], pos: Vec2, radius: f32) -> Option<usize> {
-    existing
-        .iter()
-        .position(|b| (b.pos - pos).mag() < (b.radius + radius))
-}
-
-pub fn remove_body_with_foils(simulation: &mut Simulation, idx: usize) {
-    let body = simulation.bodies.remove(idx);
-    if let Some(foil_id) = simulation.body_to_foil.remove(&body.id) {
-        if let Some(foil) = simulation.foils.iter_mut().find(|f| f.id == foil_id) {
-            foil.body_ids.retain(|&id| id != body.id);
-        }
-    }
-}
@@ -22,7 +22,7 @@ pub fn handle_step_once(simulation: &mut Simulation) {
 /// Save the simulation state to disk.
 pub fn handle_save_state(simulation: &Simulation, path: String) {
     if let Err(e) = save_state(path, simulation) {
-        eprintln!("Failed to save state: {}", e);
+        crate::sim_log!(Io, Error, "Failed to save state: {}", e);
     }
 }
 
@@ -33,7 +33,7 @@ pub fn handle_load_state(simulation: &mut Simulation, path: String) {
             simulation.load_state(scenario);
             PAUSED.store(true, std::sync::atomic::Ordering::Relaxed);
         }
-        Err(e) => eprintln!("Failed to load state: {}", e),
+        Err(e) => crate::sim_log!(Io, Error, "Failed to load state: {}", e),
     }
 }
 
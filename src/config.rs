@@ -23,6 +23,7 @@ pub const POLAR_CHARGE_DEFAULT: f32 = 1.0;
 
 use crate::body::Species;
 use crate::units;
+use ultraviolet::Vec2;
 
 pub fn electron_spring_k(species: Species) -> f32 {
     use Species::*;
@@ -288,6 +289,65 @@ pub enum IsolineFieldMode {
     BodyOnly,
 }
 
+/// Which metal species the foil connectivity BFS may traverse.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConnectivityFilter {
+    /// Hop through LithiumMetal and FoilMetal alike (total connected metal)
+    #[default]
+    AllMetal,
+    /// Hop only through LithiumMetal, so counts cover the contiguous deposit
+    /// grown from the foil and not metal reached via other foil bodies
+    LithiumOnly,
+}
+
+impl ConnectivityFilter {
+    pub fn traversable(self) -> &'static [Species] {
+        match self {
+            ConnectivityFilter::AllMetal => &[Species::LithiumMetal, Species::FoilMetal],
+            ConnectivityFilter::LithiumOnly => &[Species::LithiumMetal],
+        }
+    }
+}
+
+/// What the playback history records each capture.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryMode {
+    /// Every body, foil and setting (scrubbable playback)
+    #[default]
+    Full,
+    /// Foil states, species counts, net charge and switch step only
+    Lightweight,
+}
+
+/// Quantity the convergence monitor watches.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ConvergenceObservable {
+    /// Kinetic temperature of all bodies (K)
+    #[default]
+    Temperature,
+    /// Number of bodies of one species
+    SpeciesCount(Species),
+    /// Magnitude of the net dipole Σ q·r (e·Å)
+    NetDipole,
+}
+
+/// Data-driven pair reaction: an `a` body within `cutoff` of a `b` body
+/// becomes `product_a`, and `b` becomes `product_b` or is consumed. Each
+/// eligible `a` body fires with `probability` per step. Electrons of both
+/// reactants are pooled into the products; outcomes that would change the
+/// pair's net charge are rejected.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReactionRule {
+    pub a: Species,
+    pub b: Species,
+    pub product_a: Species,
+    #[serde(default)]
+    pub product_b: Option<Species>,
+    /// Centre-to-centre reaction distance (Å)
+    pub cutoff: f32,
+    pub probability: f32,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SimConfig {
     pub hop_rate_k0: f32,
@@ -347,6 +407,9 @@ pub struct SimConfig {
     /// Interval between thermostat applications (fs)
     #[serde(alias = "thermostat_frequency")]
     pub thermostat_interval_fs: f32,
+    /// Species whose velocities the thermostat measures and rescales
+    #[serde(default = "default_thermostat_species")]
+    pub thermostat_species: Vec<Species>,
     pub enable_out_of_plane: bool,
     pub z_stiffness: f32,
     pub z_damping: f32,
@@ -416,6 +479,204 @@ pub struct SimConfig {
     #[serde(default = "default_stack_pressure_decay")]
     pub stack_pressure_decay: f32,
 
+    /// Number of initial frames during which LJ/repulsive pair forces are capped
+    /// to `soft_start_force_cap` so overlapping hand-built scenarios can relax (0 = off)
+    #[serde(default)]
+    pub soft_start_frames: usize,
+
+    /// Maximum pairwise LJ/repulsive force magnitude during the soft-start window
+    #[serde(default = "default_soft_start_force_cap")]
+    pub soft_start_force_cap: f32,
+
+    /// Replace the induced-field heuristic with a parallel-plate capacitor model
+    /// between foil groups A (positive plate) and B (negative plate)
+    #[serde(default)]
+    pub capacitive_coupling: bool,
+
+    /// Uniform gravity-like body acceleration (Å/fs²) applied to every particle.
+    /// Applied as a force `species mass × g`, so heavy foils stay put.
+    #[serde(default = "Vec2::zero")]
+    pub body_force: Vec2,
+
+    /// Out-of-plane component of the body acceleration (only with `enable_out_of_plane`)
+    #[serde(default)]
+    pub body_force_z: f32,
+
+    /// Scale the body force by each species' mass excess over the mean particle
+    /// mass (buoyancy), so heavier species settle and lighter ones rise
+    #[serde(default)]
+    pub body_force_buoyant: bool,
+
+    /// Restoring spring pulling electrons back toward their host (1/fs²).
+    /// Lower values let electron clouds polarize further under a given field.
+    /// 0 = no restoring force (offset limited only by the species drift radius).
+    #[serde(default)]
+    pub electron_stiffness: f32,
+
+    /// Stiffness override for metals (Li metal, foil); None = `electron_stiffness`
+    #[serde(default)]
+    pub electron_stiffness_metal: Option<f32>,
+
+    /// Stiffness override for solvent molecules (EC, DMC, VC, FEC, EMC); None = `electron_stiffness`
+    #[serde(default)]
+    pub electron_stiffness_solvent: Option<f32>,
+
+    /// Largest electron offset from its host (Å), on top of the species drift
+    /// radius; 0 = unclamped.
+    #[serde(default)]
+    pub max_electron_displacement: f32,
+
+    /// Pick foil electron hosts deterministically (fewest electrons when adding,
+    /// most when removing) and place new electrons at the cloud centroid,
+    /// instead of a random host and a centred electron
+    #[serde(default)]
+    pub deterministic_foil_electrons: bool,
+
+    /// Most electrons an overpotential foil may add or remove in one step,
+    /// whatever its controller asks for
+    #[serde(default = "default_max_electrons_per_step_per_foil")]
+    pub max_electrons_per_step_per_foil: u32,
+
+    /// Gather all overlapping pairs and resolve them sorted by body id, so
+    /// collision outcomes are reproducible across runs and thread counts
+    #[serde(default)]
+    pub deterministic_collisions: bool,
+
+    /// Master switch for chemistry: electron hopping (and the redox it drives),
+    /// SEI formation and intercalation. Forces and integration always run.
+    #[serde(default = "default_reactions_enabled")]
+    pub reactions_enabled: bool,
+
+    /// With reactions off, also stop foil currents from adding/removing electrons
+    #[serde(default = "default_reactions_enabled")]
+    pub reactions_off_skips_foils: bool,
+
+    /// Debug: record per-foil intended vs. achieved electron transfers each step
+    #[serde(default)]
+    pub foil_charge_audit: bool,
+
+    /// Max bodies per quadtree leaf; takes effect at the next tree rebuild
+    #[serde(default = "default_quadtree_leaf_capacity")]
+    pub quadtree_leaf_capacity: usize,
+    /// Node size above which tree construction is split across threads
+    #[serde(default = "default_quadtree_thread_capacity")]
+    pub quadtree_thread_capacity: usize,
+
+    /// Debug: periodically compare cell-list and quadtree neighbor sets
+    #[serde(default)]
+    pub neighbor_self_check: bool,
+    /// Frames between neighbor self-checks
+    #[serde(default = "default_neighbor_self_check_interval")]
+    pub neighbor_self_check_interval: usize,
+    /// Randomly chosen bodies compared per self-check
+    #[serde(default = "default_neighbor_self_check_samples")]
+    pub neighbor_self_check_samples: usize,
+
+    /// Strength of the orientational torque nearby ions exert on EC/DMC
+    /// dipoles in `apply_polar_forces` (0 = dipoles orient only via electron drift)
+    #[serde(default)]
+    pub solvent_dipole_strength: f32,
+
+    /// Pause the simulation when a body develops a non-finite position,
+    /// velocity or charge
+    #[serde(default = "default_pause_on_nan")]
+    pub pause_on_nan: bool,
+
+    /// Record every foil electron add/remove as an event for current validation
+    #[serde(default)]
+    pub electron_event_log: bool,
+    /// Maximum buffered electron events before flushing or dropping the oldest
+    #[serde(default = "default_electron_event_buffer")]
+    pub electron_event_buffer: usize,
+    /// Append full buffers to doe_results/electron_events_stream.csv instead of dropping
+    #[serde(default)]
+    pub electron_event_flush: bool,
+
+    /// Console verbosity threshold for `sim_log!` messages
+    #[serde(default)]
+    pub log_level: crate::logging::LogLevel,
+    /// Console message categories to show
+    #[serde(default)]
+    pub log_categories: crate::logging::LogCategories,
+
+    /// Target body counts held by spawning/culling in the bulk (empty = no balancing)
+    #[serde(default)]
+    pub species_targets: std::collections::HashMap<Species, usize>,
+    /// Simulation time between species balancing passes (fs)
+    #[serde(default = "default_species_balance_interval_fs")]
+    pub species_balance_interval_fs: f32,
+    /// Minimum gap (Å) between spawned bodies and the domain walls
+    #[serde(default = "default_spawn_wall_margin")]
+    pub spawn_wall_margin: f32,
+    /// Minimum centre distance between spawned bodies, as a fraction of their summed radii
+    #[serde(default = "default_spawn_min_separation")]
+    pub spawn_min_separation: f32,
+    /// Record the active switch step and setpoint over time for the timeline plot
+    #[serde(default)]
+    pub switch_timeline: bool,
+    /// Record per-foil commanded vs. measured current for residual analysis
+    #[serde(default)]
+    pub current_residual_tracking: bool,
+    /// Integration window for one commanded/measured current sample (fs)
+    #[serde(default = "default_current_residual_window_fs")]
+    pub current_residual_window_fs: f32,
+    /// Stream each foil's per-step resolved current and control role to CSV
+    #[serde(default)]
+    pub current_ledger: bool,
+    /// Opposite-link each new foil to the nearest free foil of opposite polarity
+    #[serde(default)]
+    pub auto_link_opposite_foils: bool,
+    /// Species the foil connectivity BFS traverses when counting attached Li metal
+    #[serde(default)]
+    pub connectivity_species_filter: ConnectivityFilter,
+    /// Full-body or reduced (foils and scalars only) history capture
+    #[serde(default)]
+    pub history_mode: HistoryMode,
+    /// Frames between reduced snapshots in `HistoryMode::Lightweight`
+    #[serde(default = "default_lightweight_history_stride")]
+    pub lightweight_history_stride: usize,
+    /// Maximum frames between full keyframes in a saved compressed history
+    #[serde(default = "default_compressed_history_keyframe_interval")]
+    pub compressed_history_keyframe_interval: usize,
+    /// Position change (Å) a body needs before a compressed delta stores it
+    #[serde(default = "default_compressed_history_position_tolerance")]
+    pub compressed_history_position_tolerance: f32,
+    /// Velocity change (Å/fs) a body needs before a compressed delta stores it
+    #[serde(default = "default_compressed_history_velocity_tolerance")]
+    pub compressed_history_velocity_tolerance: f32,
+    /// Widen the domain instead of reflecting when bodies near the walls
+    #[serde(default)]
+    pub auto_grow_domain: bool,
+    /// Distance from a wall (Å) that triggers growth
+    #[serde(default = "default_auto_grow_margin")]
+    pub auto_grow_margin: f32,
+    /// Amount (Å) added to each side of the grown axis
+    #[serde(default = "default_auto_grow_increment")]
+    pub auto_grow_increment: f32,
+    /// Largest full width/height (Å) automatic growth may reach
+    #[serde(default = "default_auto_grow_max_size")]
+    pub auto_grow_max_size: f32,
+    /// Frames between re-centering the cloud on its center of mass (0 = off)
+    #[serde(default)]
+    pub recenter_interval_frames: usize,
+    /// Move frozen species with the cloud when re-centering instead of leaving them out
+    #[serde(default)]
+    pub recenter_include_frozen: bool,
+    /// Generic pair reactions applied after collisions (empty = none)
+    #[serde(default)]
+    pub reactions: Vec<ReactionRule>,
+    /// Pause once `convergence_observable` settles within the tolerance
+    #[serde(default)]
+    pub convergence_monitor: bool,
+    #[serde(default)]
+    pub convergence_observable: ConvergenceObservable,
+    /// Largest allowed max - min of the observable over the window
+    #[serde(default = "default_convergence_tolerance")]
+    pub convergence_tolerance: f32,
+    /// Window (fs) the observable must stay within tolerance
+    #[serde(default = "default_convergence_window_fs")]
+    pub convergence_window_fs: f32,
+
     /// Version number incremented whenever config changes (for clone detection)
     #[serde(skip)]
     pub config_version: u64,
@@ -433,6 +694,106 @@ fn default_stack_pressure_decay() -> f32 {
     10.0  // Simulation units
 }
 
+fn default_soft_start_force_cap() -> f32 {
+    10.0
+}
+
+fn default_reactions_enabled() -> bool {
+    true
+}
+
+fn default_pause_on_nan() -> bool {
+    true
+}
+
+fn default_electron_event_buffer() -> usize {
+    100_000
+}
+
+fn default_species_balance_interval_fs() -> f32 {
+    100.0
+}
+
+fn default_spawn_wall_margin() -> f32 {
+    1.0
+}
+
+fn default_max_electrons_per_step_per_foil() -> u32 {
+    // High enough that controller output is never clipped unless asked for
+    1_000_000
+}
+
+fn default_spawn_min_separation() -> f32 {
+    0.65
+}
+
+fn default_current_residual_window_fs() -> f32 {
+    50.0
+}
+
+fn default_lightweight_history_stride() -> usize {
+    10
+}
+
+fn default_compressed_history_keyframe_interval() -> usize {
+    100
+}
+
+fn default_compressed_history_position_tolerance() -> f32 {
+    0.01
+}
+
+fn default_compressed_history_velocity_tolerance() -> f32 {
+    0.001
+}
+
+fn default_auto_grow_margin() -> f32 {
+    5.0
+}
+
+fn default_auto_grow_increment() -> f32 {
+    20.0
+}
+
+fn default_auto_grow_max_size() -> f32 {
+    5000.0
+}
+
+fn default_quadtree_leaf_capacity() -> usize {
+    QUADTREE_LEAF_CAPACITY
+}
+
+fn default_quadtree_thread_capacity() -> usize {
+    QUADTREE_THREAD_CAPACITY
+}
+
+fn default_thermostat_species() -> Vec<Species> {
+    vec![
+        Species::ElectrolyteAnion,
+        Species::EC,
+        Species::DMC,
+        Species::VC,
+        Species::FEC,
+        Species::EMC,
+    ]
+}
+
+fn default_neighbor_self_check_interval() -> usize {
+    100
+}
+
+fn default_neighbor_self_check_samples() -> usize {
+    64
+}
+
+fn default_convergence_tolerance() -> f32 {
+    1.0
+}
+
+fn default_convergence_window_fs() -> f32 {
+    500.0
+}
+
 impl Default for SimConfig {
     fn default() -> Self {
         Self {
@@ -473,6 +834,8 @@ impl Default for SimConfig {
             coulomb_constant: units::COULOMB_CONSTANT,
             temperature: DEFAULT_TEMPERATURE,
             thermostat_interval_fs: 1.0, // Apply thermostat every 1 fs by default
+            // Liquid only: Li+ is Coulomb-driven and metals stay cold
+            thermostat_species: default_thermostat_species(),
             enable_out_of_plane: OUT_OF_PLANE_ENABLED,
             z_stiffness: Z_STIFFNESS,
             z_damping: Z_DAMPING,
@@ -512,10 +875,100 @@ impl Default for SimConfig {
             stack_pressure_enabled: false,
             stack_pressure: default_stack_pressure(),
             stack_pressure_decay: default_stack_pressure_decay(),
+            // Soft start disabled by default
+            soft_start_frames: 0,
+            soft_start_force_cap: default_soft_start_force_cap(),
+            // Induced-field heuristic stays the default
+            capacitive_coupling: false,
+            // No body force by default
+            body_force: Vec2::zero(),
+            body_force_z: 0.0,
+            body_force_buoyant: false,
+            // No electron restoring spring by default
+            electron_stiffness: 0.0,
+            electron_stiffness_metal: None,
+            electron_stiffness_solvent: None,
+            max_electron_displacement: 0.0,
+            // Random foil electron placement by default
+            deterministic_foil_electrons: false,
+            max_electrons_per_step_per_foil: default_max_electrons_per_step_per_foil(),
+            // Parallel collision resolution unless reproducibility is requested
+            deterministic_collisions: false,
+            // Chemistry on; turning it off also freezes foil electron counts
+            reactions_enabled: true,
+            reactions_off_skips_foils: true,
+            // Charge-conservation audit is a debug aid, off by default
+            foil_charge_audit: false,
+            quadtree_leaf_capacity: QUADTREE_LEAF_CAPACITY,
+            quadtree_thread_capacity: QUADTREE_THREAD_CAPACITY,
+            // Backend cross-check is a debug aid, off by default
+            neighbor_self_check: false,
+            neighbor_self_check_interval: default_neighbor_self_check_interval(),
+            neighbor_self_check_samples: default_neighbor_self_check_samples(),
+            // No extra ion-dipole alignment beyond the existing polar forces
+            solvent_dipole_strength: 0.0,
+            // Stop on NaN/Inf instead of integrating a corrupted state
+            pause_on_nan: true,
+            // Electron transfer event stream is a validation aid, off by default
+            electron_event_log: false,
+            electron_event_buffer: default_electron_event_buffer(),
+            electron_event_flush: false,
+            // Info and above in every category; per-step debug output stays quiet
+            log_level: crate::logging::LogLevel::Info,
+            log_categories: crate::logging::LogCategories::default(),
+            // No species held at a fixed count unless targets are set
+            species_targets: std::collections::HashMap::new(),
+            species_balance_interval_fs: default_species_balance_interval_fs(),
+            // Keep fresh bodies slightly off the reflecting walls
+            spawn_wall_margin: default_spawn_wall_margin(),
+            spawn_min_separation: default_spawn_min_separation(),
+            switch_timeline: false,
+            // Residual tracking is a tuning aid, off by default
+            current_residual_tracking: false,
+            current_residual_window_fs: default_current_residual_window_fs(),
+            current_ledger: false,
+            // Links stay a manual choice unless auto-pairing is requested
+            auto_link_opposite_foils: false,
+            // Count Li reached through any metal, as before the filter existed
+            connectivity_species_filter: ConnectivityFilter::AllMetal,
+            // Scrubbable full history unless memory is a concern
+            history_mode: HistoryMode::Full,
+            lightweight_history_stride: default_lightweight_history_stride(),
+            compressed_history_keyframe_interval: default_compressed_history_keyframe_interval(),
+            compressed_history_position_tolerance: default_compressed_history_position_tolerance(),
+            compressed_history_velocity_tolerance: default_compressed_history_velocity_tolerance(),
+            // Fixed walls unless open-ended growth is requested
+            auto_grow_domain: false,
+            auto_grow_margin: default_auto_grow_margin(),
+            auto_grow_increment: default_auto_grow_increment(),
+            auto_grow_max_size: default_auto_grow_max_size(),
+            // The cloud may drift freely unless re-centering is requested
+            recenter_interval_frames: 0,
+            recenter_include_frozen: false,
+            // No generic chemistry unless a table is configured
+            reactions: Vec::new(),
+            // Run length is left to the user unless convergence is requested
+            convergence_monitor: false,
+            convergence_observable: ConvergenceObservable::Temperature,
+            convergence_tolerance: default_convergence_tolerance(),
+            convergence_window_fs: default_convergence_window_fs(),
         }
     }
 }
 
+impl SimConfig {
+    /// Electron restoring stiffness for `species`, honouring per-species overrides.
+    pub fn electron_stiffness_for(&self, species: Species) -> f32 {
+        use Species::*;
+        let over = match species {
+            LithiumMetal | FoilMetal => self.electron_stiffness_metal,
+            EC | DMC | VC | FEC | EMC => self.electron_stiffness_solvent,
+            _ => None,
+        };
+        over.unwrap_or(self.electron_stiffness)
+    }
+}
+
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 
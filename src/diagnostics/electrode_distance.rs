@@ -0,0 +1,150 @@
+// diagnostics/electrode_distance.rs
+// Distance from each cation to the nearest foil surface, for double-layer structure
+
+use std::io::Write;
+use std::path::Path;
+
+use crate::body::{Body, Species};
+use crate::profile_scope;
+use crate::quadtree::Quadtree;
+use ultraviolet::Vec2;
+
+/// Surface distance (centre distance minus foil radius, floored at 0) from
+/// each Li+ to the nearest foil body, as `(cation_id, distance)`. Uses
+/// expanding neighbor queries on `quadtree`, which must be built over
+/// `bodies`. Empty when there are no foil bodies.
+pub fn cation_electrode_distances(bodies: &[Body], quadtree: &Quadtree) -> Vec<(u64, f32)> {
+    let max_foil_radius = bodies
+        .iter()
+        .filter(|b| b.species == Species::FoilMetal)
+        .map(|b| b.radius)
+        .reduce(f32::max);
+    let Some(max_foil_radius) = max_foil_radius else {
+        return Vec::new();
+    };
+    // No foil body can be farther away than the extent of the whole system
+    let (lo, hi) = bodies.iter().fold(
+        (Vec2::broadcast(f32::INFINITY), Vec2::broadcast(f32::NEG_INFINITY)),
+        |(lo, hi), b| (lo.min_by_component(b.pos), hi.max_by_component(b.pos)),
+    );
+    let max_search = (hi - lo).mag() + max_foil_radius + 1.0;
+
+    bodies
+        .iter()
+        .enumerate()
+        .filter(|(_, b)| b.species == Species::LithiumIon)
+        .map(|(i, ion)| {
+            let mut search = ion.radius * 3.0 + max_foil_radius;
+            loop {
+                let best = quadtree
+                    .find_neighbors_within(bodies, i, search)
+                    .into_iter()
+                    .filter(|&j| bodies[j].species == Species::FoilMetal)
+                    .map(|j| ((ion.pos - bodies[j].pos).mag() - bodies[j].radius).max(0.0))
+                    .fold(f32::INFINITY, f32::min);
+                // Foils outside the query are at least `search - max_foil_radius` away
+                if best <= search - max_foil_radius || search >= max_search {
+                    break (ion.id, best);
+                }
+                search *= 2.0;
+            }
+        })
+        .collect()
+}
+
+/// Snapshot of cation-to-electrode distances with a histogram for plotting.
+pub struct ElectrodeDistanceDiagnostic {
+    /// Histogram bin width (Å)
+    pub bin_width: f32,
+    pub distances: Vec<(u64, f32)>,
+    /// Cation count per bin, bin `i` covering `[i, i + 1) * bin_width`
+    pub histogram: Vec<usize>,
+}
+
+impl Default for ElectrodeDistanceDiagnostic {
+    fn default() -> Self {
+        Self {
+            bin_width: 2.0,
+            distances: Vec::new(),
+            histogram: Vec::new(),
+        }
+    }
+}
+
+impl ElectrodeDistanceDiagnostic {
+    pub fn calculate(&mut self, bodies: &[Body], quadtree: &Quadtree) {
+        profile_scope!("electrode_distance_calculation");
+        self.distances = cation_electrode_distances(bodies, quadtree);
+        self.histogram.clear();
+        let width = self.bin_width.max(1e-3);
+        for &(_, d) in &self.distances {
+            let bin = (d / width) as usize;
+            if bin >= self.histogram.len() {
+                self.histogram.resize(bin + 1, 0);
+            }
+            self.histogram[bin] += 1;
+        }
+    }
+
+    /// Write `<stem>_raw.csv` (one row per cation) and `<stem>_hist.csv`
+    /// into `dir`, returning the two paths.
+    pub fn write_csv(&self, dir: &Path, stem: &str) -> std::io::Result<(std::path::PathBuf, std::path::PathBuf)> {
+        std::fs::create_dir_all(dir)?;
+        let raw_path = dir.join(format!("{}_raw.csv", stem));
+        let mut raw = std::io::BufWriter::new(std::fs::File::create(&raw_path)?);
+        writeln!(raw, "cation_id,distance_a")?;
+        for (id, d) in &self.distances {
+            writeln!(raw, "{},{:.4}", id, d)?;
+        }
+        raw.flush()?;
+
+        let hist_path = dir.join(format!("{}_hist.csv", stem));
+        let mut hist = std::io::BufWriter::new(std::fs::File::create(&hist_path)?);
+        writeln!(hist, "bin_start_a,bin_end_a,count")?;
+        for (i, count) in self.histogram.iter().enumerate() {
+            let start = i as f32 * self.bin_width;
+            writeln!(hist, "{:.3},{:.3},{}", start, start + self.bin_width, count)?;
+        }
+        hist.flush()?;
+        Ok((raw_path, hist_path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn cation_distance_to_nearest_foil_surface() {
+        let body = |x: f32, y: f32, radius: f32, species| Body::new(Vec2::new(x, y), Vec2::zero(), 1.0, radius, 0.0, species);
+        // Foil column at x = 0, radius 1
+        let mut bodies: Vec<Body> = (-5..=5).map(|i| body(0.0, 2.0 * i as f32, 1.0, Species::FoilMetal)).collect();
+        let near = body(4.0, 0.0, 0.7, Species::LithiumIon);
+        let far = body(-51.0, 4.0, 0.7, Species::LithiumIon);
+        let touching = body(0.5, 0.0, 0.7, Species::LithiumIon);
+        let (near_id, far_id, touching_id) = (near.id, far.id, touching.id);
+        bodies.extend([near, far, touching, body(6.0, 0.0, 1.0, Species::EC)]);
+        let mut quadtree = Quadtree::new(1.0, 2.0, 1, 1024);
+        quadtree.build(&mut bodies);
+
+        let distances: HashMap<u64, f32> =
+            cation_electrode_distances(&bodies, &quadtree).into_iter().collect();
+        assert_eq!(distances.len(), 3, "only cations are measured");
+        assert!((distances[&near_id] - 3.0).abs() < 1e-5);
+        assert!((distances[&far_id] - 50.0).abs() < 1e-4);
+        assert_eq!(distances[&touching_id], 0.0);
+
+        let mut diag = ElectrodeDistanceDiagnostic::default();
+        diag.calculate(&bodies, &quadtree);
+        assert_eq!(diag.histogram.iter().sum::<usize>(), 3);
+        assert_eq!(diag.histogram[0], 1);
+        assert_eq!(diag.histogram[1], 1);
+        assert_eq!(diag.histogram[25], 1);
+
+        // No foil: nothing to measure
+        bodies.retain(|b| b.species != Species::FoilMetal);
+        quadtree.build(&mut bodies);
+        assert!(cation_electrode_distances(&bodies, &quadtree).is_empty());
+    }
+}
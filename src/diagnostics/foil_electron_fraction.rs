@@ -4,6 +4,76 @@ use crate::body::{foil::Foil, Body, Species};
 use crate::profile_scope;
 use crate::quadtree::Quadtree;
 
+/// Indices of every metal body connected to `foil`, including the foil's own
+/// bodies, found by BFS over touching LithiumMetal/FoilMetal neighbors.
+pub fn connected_metal_indices(
+    bodies: &[Body],
+    foil: &Foil,
+    quadtree: &Quadtree,
+    id_to_index: &HashMap<u64, usize>,
+) -> Vec<usize> {
+    connected_indices_through(
+        bodies,
+        foil,
+        quadtree,
+        id_to_index,
+        &[Species::LithiumMetal, Species::FoilMetal],
+    )
+}
+
+/// Like [`connected_metal_indices`], but the BFS only steps onto neighbors
+/// whose species is in `traversable`. The foil's own bodies are always seeds.
+pub fn connected_indices_through(
+    bodies: &[Body],
+    foil: &Foil,
+    quadtree: &Quadtree,
+    id_to_index: &HashMap<u64, usize>,
+    traversable: &[Species],
+) -> Vec<usize> {
+    let mut queue = VecDeque::new();
+    let mut visited: HashSet<usize> = HashSet::new();
+    let mut connected = Vec::new();
+
+    // Start BFS from all foil bodies
+    for id in &foil.body_ids {
+        if let Some(&idx) = id_to_index.get(id) {
+            if visited.insert(idx) {
+                queue.push_back(idx);
+            }
+        }
+    }
+
+    // BFS to find all connected metal bodies using quadtree for neighbor search
+    while let Some(idx) = queue.pop_front() {
+        connected.push(idx);
+        let body = &bodies[idx];
+
+        // Use quadtree to efficiently find nearby neighbors
+        let search_radius = body.radius * 2.2; // Slightly larger than connection threshold
+        let nearby_indices = quadtree.find_neighbors_within(bodies, idx, search_radius);
+
+        for &neighbor_idx in &nearby_indices {
+            if visited.contains(&neighbor_idx) {
+                continue;
+            }
+
+            let neighbor = &bodies[neighbor_idx];
+            if !traversable.contains(&neighbor.species) {
+                continue;
+            }
+
+            // Check actual connection threshold
+            let threshold = (body.radius + neighbor.radius) * 1.1;
+            if (body.pos - neighbor.pos).mag() <= threshold {
+                visited.insert(neighbor_idx);
+                queue.push_back(neighbor_idx);
+            }
+        }
+    }
+
+    connected
+}
+
 /// Diagnostic calculating the ratio of actual electrons to neutral electrons
 /// for each foil and connected metal cluster.
 #[derive(Default)]
@@ -28,46 +98,12 @@ impl FoilElectronFractionDiagnostic {
             bodies.iter().enumerate().map(|(i, b)| (b.id, i)).collect();
 
         for foil in foils {
-            let mut queue = VecDeque::new();
-            let mut visited: HashSet<usize> = HashSet::new();
             let mut total_electrons = 0usize;
             let mut total_neutral = 0usize;
-
-            // Start BFS from all foil bodies
-            for id in &foil.body_ids {
-                if let Some(&idx) = id_to_index.get(id) {
-                    queue.push_back(idx);
-                    visited.insert(idx);
-                }
-            }
-
-            // BFS to find all connected metal bodies using quadtree for neighbor search
-            while let Some(idx) = queue.pop_front() {
+            for idx in connected_metal_indices(bodies, foil, quadtree, &id_to_index) {
                 let body = &bodies[idx];
                 total_electrons += body.electrons.len();
                 total_neutral += body.neutral_electron_count();
-
-                // Use quadtree to efficiently find nearby neighbors
-                let search_radius = body.radius * 2.2; // Slightly larger than connection threshold
-                let nearby_indices = quadtree.find_neighbors_within(bodies, idx, search_radius);
-
-                for &neighbor_idx in &nearby_indices {
-                    if visited.contains(&neighbor_idx) {
-                        continue;
-                    }
-
-                    let neighbor = &bodies[neighbor_idx];
-                    if !matches!(neighbor.species, Species::LithiumMetal | Species::FoilMetal) {
-                        continue;
-                    }
-
-                    // Check actual connection threshold
-                    let threshold = (body.radius + neighbor.radius) * 1.1;
-                    if (body.pos - neighbor.pos).mag() <= threshold {
-                        visited.insert(neighbor_idx);
-                        queue.push_back(neighbor_idx);
-                    }
-                }
             }
 
             if total_neutral > 0 {
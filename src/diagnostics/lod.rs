@@ -0,0 +1,42 @@
+// diagnostics/lod.rs
+// Particle-count-based level of detail for GUI diagnostics
+
+/// Body count at which the interval grows by one `scaling` step.
+pub const LOD_REFERENCE_BODIES: f32 = 1000.0;
+
+/// Multiplier applied to a diagnostic's base update interval.
+///
+/// Grows linearly with the body count: `1 + scaling * n / LOD_REFERENCE_BODIES`.
+/// A `scaling` of zero (or a negative value) disables adaptive throttling.
+pub fn lod_factor(body_count: usize, scaling: f32) -> f32 {
+    1.0 + scaling.max(0.0) * body_count as f32 / LOD_REFERENCE_BODIES
+}
+
+/// Effective update interval in frames for a frame-throttled diagnostic.
+/// Never returns less than one frame.
+pub fn lod_interval_frames(base_frames: usize, body_count: usize, scaling: f32) -> usize {
+    ((base_frames.max(1) as f32 * lod_factor(body_count, scaling)).round() as usize).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_scales_with_body_count() {
+        // No scaling keeps the base interval regardless of size
+        assert_eq!(lod_interval_frames(10, 0, 0.0), 10);
+        assert_eq!(lod_interval_frames(10, 50_000, 0.0), 10);
+
+        // Linear growth relative to the reference count
+        assert_eq!(lod_interval_frames(10, 0, 0.5), 10);
+        assert_eq!(lod_interval_frames(10, 1_000, 0.5), 15);
+        assert_eq!(lod_interval_frames(10, 10_000, 0.5), 60);
+        assert_eq!(lod_interval_frames(10, 100_000, 0.5), 510);
+
+        // Degenerate inputs still yield at least one frame
+        assert_eq!(lod_interval_frames(0, 0, 1.0), 1);
+        assert_eq!(lod_interval_frames(10, 10_000, -1.0), 10);
+        assert!((lod_factor(2_000, 0.25) - 1.5).abs() < 1e-6);
+    }
+}
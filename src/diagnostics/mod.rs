@@ -1,10 +1,24 @@
 // diagnostics/mod.rs
 // Module for diagnostics-related calculations and GUI integration
 
+pub mod electrode_distance;
 pub mod foil_electron_fraction;
+pub mod lod;
+pub mod molarity;
+pub mod pair_lifetime;
+pub mod resistance;
 pub mod solvation;
+pub mod solvation_transference;
+pub mod surface_roughness;
 pub mod transference_number;
 
+pub use electrode_distance::*;
 pub use foil_electron_fraction::*;
+pub use lod::*;
+pub use molarity::*;
+pub use pair_lifetime::*;
+pub use resistance::*;
 pub use solvation::*;
+pub use solvation_transference::*;
+pub use surface_roughness::*;
 pub use transference_number::*;
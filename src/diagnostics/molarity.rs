@@ -0,0 +1,148 @@
+// diagnostics/molarity.rs
+// Bulk electrolyte molarity from the cation count away from the electrodes
+
+use std::collections::HashSet;
+
+use crate::body::{Body, Species};
+use crate::profile_scope;
+
+const AVOGADRO: f32 = 6.022_140_8e23;
+/// Litres per cubic Ångström.
+const LITRES_PER_A3: f32 = 1e-27;
+
+/// Molarity (mol/L) of `count` ions spread over `area` Å² of a slab
+/// `thickness` Å deep. Returns `None` for an empty region.
+pub fn molarity_from_count(count: usize, area: f32, thickness: f32) -> Option<f32> {
+    let volume_l = area * thickness * LITRES_PER_A3;
+    (volume_l > 0.0).then(|| count as f32 / (AVOGADRO * volume_l))
+}
+
+/// Live bulk Li+ molarity for the electrolyte setup HUD.
+///
+/// The bulk region is the domain minus bands of width `electrode_band`
+/// around every metal body, rasterised on a `cell_size` grid.
+pub struct MolarityMeter {
+    /// Effective out-of-plane depth of the 2D slab (Å); about one particle diameter.
+    pub thickness: f32,
+    /// Distance from metal (Å) excluded from the bulk region.
+    pub electrode_band: f32,
+    /// Grid resolution (Å) for the bulk region.
+    pub cell_size: f32,
+    /// Relative deviation from the target still shown as on-target.
+    pub tolerance: f32,
+    pub bulk_area: f32,
+    pub cation_count: usize,
+    pub molarity: Option<f32>,
+}
+
+impl Default for MolarityMeter {
+    fn default() -> Self {
+        Self {
+            thickness: 4.0,
+            electrode_band: 15.0,
+            cell_size: 5.0,
+            tolerance: 0.1,
+            bulk_area: 0.0,
+            cation_count: 0,
+            molarity: None,
+        }
+    }
+}
+
+impl MolarityMeter {
+    /// Recompute from `bodies` in a domain of full size `width` x `height`
+    /// centred on the origin.
+    pub fn calculate(&mut self, bodies: &[Body], width: f32, height: f32) {
+        profile_scope!("molarity_calculation_internal");
+        let cell = self.cell_size.max(0.5);
+        let nx = (width / cell).ceil().max(1.0) as i32;
+        let ny = (height / cell).ceil().max(1.0) as i32;
+        let (x0, y0) = (-width / 2.0, -height / 2.0);
+        let cell_of = |x: f32, y: f32| {
+            (
+                ((x - x0) / cell).floor() as i32,
+                ((y - y0) / cell).floor() as i32,
+            )
+        };
+
+        // Cells whose centre lies within the band of any metal body
+        let reach = (self.electrode_band / cell).ceil() as i32 + 1;
+        let mut excluded: HashSet<(i32, i32)> = HashSet::new();
+        for body in bodies
+            .iter()
+            .filter(|b| matches!(b.species, Species::FoilMetal | Species::LithiumMetal))
+        {
+            let (cx, cy) = cell_of(body.pos.x, body.pos.y);
+            let band = self.electrode_band + body.radius;
+            for gx in (cx - reach).max(0)..=(cx + reach).min(nx - 1) {
+                for gy in (cy - reach).max(0)..=(cy + reach).min(ny - 1) {
+                    let centre_x = x0 + (gx as f32 + 0.5) * cell;
+                    let centre_y = y0 + (gy as f32 + 0.5) * cell;
+                    let (dx, dy) = (centre_x - body.pos.x, centre_y - body.pos.y);
+                    if dx * dx + dy * dy <= band * band {
+                        excluded.insert((gx, gy));
+                    }
+                }
+            }
+        }
+
+        let bulk_cells = (nx as usize * ny as usize).saturating_sub(excluded.len());
+        self.bulk_area = bulk_cells as f32 * cell * cell;
+        self.cation_count = bodies
+            .iter()
+            .filter(|b| b.species == Species::LithiumIon)
+            .filter(|b| {
+                let (gx, gy) = cell_of(b.pos.x, b.pos.y);
+                (0..nx).contains(&gx) && (0..ny).contains(&gy) && !excluded.contains(&(gx, gy))
+            })
+            .count();
+        self.molarity = molarity_from_count(self.cation_count, self.bulk_area, self.thickness);
+    }
+
+    /// Whether the measured molarity is within `tolerance` of `target`.
+    pub fn on_target(&self, target: f32) -> Option<bool> {
+        let measured = self.molarity?;
+        Some(if target > 0.0 {
+            ((measured - target) / target).abs() <= self.tolerance
+        } else {
+            measured == 0.0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn molarity_follows_count_area_and_band() {
+        // 602 ions in 1000 x 1000 Å at 1 Å depth: 602 / (6.022e23 * 1e-21 L) = 1 M
+        let m = molarity_from_count(602, 1.0e6, 1.0).unwrap();
+        assert!((m - 0.99964).abs() < 1e-3);
+        assert_eq!(molarity_from_count(5, 0.0, 4.0), None);
+
+        // 100 x 100 Å domain, metal on the left edge removes a band of cells
+        let body = |x: f32, y: f32, species| {
+            Body::new(Vec2::new(x, y), Vec2::zero(), 1.0, 1.0, 0.0, species)
+        };
+        let bodies = vec![
+            body(-48.0, 0.0, Species::FoilMetal),
+            body(-45.0, 0.0, Species::LithiumIon), // inside the band
+            body(20.0, 20.0, Species::LithiumIon),
+            body(30.0, -10.0, Species::LithiumIon),
+        ];
+        let mut meter = MolarityMeter {
+            electrode_band: 10.0,
+            cell_size: 5.0,
+            ..Default::default()
+        };
+        meter.calculate(&bodies, 100.0, 100.0);
+        assert_eq!(meter.cation_count, 2);
+        assert!(meter.bulk_area < 100.0 * 100.0 && meter.bulk_area > 9000.0);
+        let expected = molarity_from_count(2, meter.bulk_area, meter.thickness).unwrap();
+        assert_eq!(meter.molarity, Some(expected));
+        assert_eq!(meter.on_target(expected * 1.05), Some(true));
+        assert_eq!(meter.on_target(expected * 2.0), Some(false));
+    }
+}
@@ -0,0 +1,122 @@
+// diagnostics/pair_lifetime.rs
+// Lifetime distribution of contact ion pairs (CIP) across solvation updates
+//
+// Pairs are matched by (cation_id, anion_id) between consecutive solvation
+// calculations. Because the solvation diagnostic runs on a throttled cadence,
+// a pair's lifetime is the number of updates it was seen in times the update
+// interval (simulation time between the last two updates).
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Debug, Default)]
+pub struct PairLifetimeTracker {
+    /// Updates each live pair has been observed in
+    active: HashMap<(u64, u64), u32>,
+    last_time_fs: Option<f32>,
+    /// Completed pair lifetimes (fs)
+    pub lifetimes: Vec<f32>,
+}
+
+impl PairLifetimeTracker {
+    /// Record the pairs present at `time_fs`. Pairs that were present at the
+    /// previous update but not now are closed and their lifetime recorded.
+    /// Time moving backwards (rewind/playback) resets the tracker.
+    pub fn update(&mut self, pairs: impl IntoIterator<Item = (u64, u64)>, time_fs: f32) {
+        let interval = match self.last_time_fs {
+            Some(last) if time_fs < last => {
+                self.reset();
+                0.0
+            }
+            Some(last) => time_fs - last,
+            None => 0.0,
+        };
+        self.last_time_fs = Some(time_fs);
+
+        let mut current: HashMap<(u64, u64), u32> = HashMap::new();
+        for pair in pairs {
+            let seen = self.active.get(&pair).copied().unwrap_or(0);
+            current.insert(pair, seen + 1);
+        }
+        for (pair, samples) in &self.active {
+            if !current.contains_key(pair) {
+                self.lifetimes.push(*samples as f32 * interval);
+            }
+        }
+        self.active = current;
+    }
+
+    pub fn active_pairs(&self) -> usize {
+        self.active.len()
+    }
+
+    pub fn mean_lifetime(&self) -> Option<f32> {
+        (!self.lifetimes.is_empty())
+            .then(|| self.lifetimes.iter().sum::<f32>() / self.lifetimes.len() as f32)
+    }
+
+    /// Counts of completed lifetimes in bins of `bin_fs`, from 0 up to the
+    /// longest lifetime. Each entry is `(bin_start_fs, count)`.
+    pub fn histogram(&self, bin_fs: f32) -> Vec<(f32, usize)> {
+        let bin_fs = bin_fs.max(1e-6);
+        let max = self.lifetimes.iter().copied().fold(0.0f32, f32::max);
+        let mut counts = vec![0usize; (max / bin_fs).floor() as usize + 1];
+        for &t in &self.lifetimes {
+            counts[(t / bin_fs).floor() as usize] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, n)| (i as f32 * bin_fs, n))
+            .collect()
+    }
+
+    /// Write `histogram(bin_fs)` as `bin_start_fs,bin_end_fs,count` CSV.
+    pub fn write_histogram_csv(&self, path: &Path, bin_fs: f32) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        writeln!(file, "bin_start_fs,bin_end_fs,count")?;
+        for (start, count) in self.histogram(bin_fs) {
+            writeln!(file, "{:.3},{:.3},{}", start, start + bin_fs, count)?;
+        }
+        file.flush()
+    }
+
+    pub fn reset(&mut self) {
+        self.active.clear();
+        self.last_time_fs = None;
+        self.lifetimes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lifetimes_follow_pair_identity_at_update_cadence() {
+        let mut tracker = PairLifetimeTracker::default();
+        // Updates every 10 fs; pair (1, 10) lives for three updates,
+        // (2, 20) for one, and (3, 30) swaps partner to (3, 31) at 20 fs.
+        tracker.update([(1, 10), (3, 30)], 0.0);
+        tracker.update([(1, 10), (2, 20), (3, 30)], 10.0);
+        tracker.update([(1, 10), (3, 31)], 20.0);
+        tracker.update([(3, 31)], 30.0);
+        assert_eq!(tracker.active_pairs(), 1);
+
+        let mut lifetimes = tracker.lifetimes.clone();
+        lifetimes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        // (2,20): 1 × 10, (3,30): 2 × 10, (1,10): 3 × 10
+        assert_eq!(lifetimes, vec![10.0, 20.0, 30.0]);
+        assert_eq!(tracker.mean_lifetime(), Some(20.0));
+        assert_eq!(tracker.histogram(15.0), vec![(0.0, 1), (15.0, 1), (30.0, 1)]);
+
+        // Rewinding starts over
+        tracker.update([(1, 10)], 5.0);
+        assert!(tracker.lifetimes.is_empty());
+        assert_eq!(tracker.active_pairs(), 1);
+    }
+}
@@ -0,0 +1,212 @@
+// diagnostics/resistance.rs
+// Effective electrolyte resistance between foil group A and group B
+
+use std::collections::{HashSet, VecDeque};
+use std::io::Write;
+use std::path::Path;
+
+use crate::body::{foil::Foil, Body, Species};
+use crate::profile_scope;
+use ultraviolet::Vec2;
+
+/// Softcore minimum distance (Å) for the group potential sum, matching the
+/// EIS potential measurement.
+const POTENTIAL_R_MIN: f32 = 5.0;
+
+/// Number of (time, resistance) samples kept for the time series.
+const RESISTANCE_HISTORY_LEN: usize = 1000;
+
+/// Smoothed voltage / ionic current ratio.
+///
+/// Returns `None` when the window is empty or the mean current is below
+/// `min_current` in magnitude, so a resting cell does not report a spurious
+/// huge resistance.
+pub fn smoothed_resistance(samples: &[(f32, f32)], min_current: f32) -> Option<f32> {
+    if samples.is_empty() {
+        return None;
+    }
+    let n = samples.len() as f32;
+    let mean_v = samples.iter().map(|s| s.0).sum::<f32>() / n;
+    let mean_i = samples.iter().map(|s| s.1).sum::<f32>() / n;
+    if !mean_i.is_finite() || mean_i.abs() < min_current.max(f32::MIN_POSITIVE) {
+        return None;
+    }
+    Some(mean_v / mean_i)
+}
+
+/// Centroid of every body belonging to the given foils.
+fn group_centroid(bodies: &[Body], foils: &[Foil], foil_ids: &[u64]) -> Option<Vec2> {
+    let ids: HashSet<u64> = foils
+        .iter()
+        .filter(|f| foil_ids.contains(&f.id))
+        .flat_map(|f| f.body_ids.iter().copied())
+        .collect();
+    let mut sum = Vec2::zero();
+    let mut n = 0usize;
+    for body in bodies.iter().filter(|b| ids.contains(&b.id)) {
+        sum += body.pos;
+        n += 1;
+    }
+    (n > 0).then(|| sum / n as f32)
+}
+
+/// Coulomb potential at `pos`, softened at `POTENTIAL_R_MIN`.
+fn potential_at(pos: Vec2, bodies: &[Body], coulomb_constant: f32) -> f32 {
+    bodies
+        .iter()
+        .map(|b| coulomb_constant * b.charge / (pos - b.pos).mag().max(POTENTIAL_R_MIN))
+        .sum()
+}
+
+/// Ionic current from group A towards group B in e/fs.
+///
+/// Uses the drift form `I = Σ q_i (v_i · d̂) / L`, where `d̂` and `L` are the
+/// direction and distance from the A centroid to the B centroid. Only mobile
+/// ions (Li+ and anions) contribute.
+pub fn ionic_current(bodies: &[Body], a_centroid: Vec2, b_centroid: Vec2) -> f32 {
+    let axis = b_centroid - a_centroid;
+    let length = axis.mag();
+    if length < 1e-6 {
+        return 0.0;
+    }
+    let dir = axis / length;
+    bodies
+        .iter()
+        .filter(|b| matches!(b.species, Species::LithiumIon | Species::ElectrolyteAnion))
+        .map(|b| b.charge * b.vel.dot(dir))
+        .sum::<f32>()
+        / length
+}
+
+/// Resistance estimate between the foil groups used by charging and EIS.
+pub struct ResistanceDiagnostic {
+    /// Number of samples averaged for the ratio.
+    pub window: usize,
+    /// Mean currents below this magnitude (e/fs) yield no estimate.
+    pub min_current: f32,
+    /// Latest instantaneous group voltage V_A - V_B.
+    pub voltage: f32,
+    /// Latest instantaneous ionic current A → B in e/fs.
+    pub current: f32,
+    /// Latest smoothed estimate in Ω-equivalent (potential units · fs / e).
+    pub resistance: Option<f32>,
+    /// `(time_fs, resistance)` for the most recent steps that produced an
+    /// estimate, capped at `RESISTANCE_HISTORY_LEN`.
+    pub history: VecDeque<(f32, f32)>,
+    samples: VecDeque<(f32, f32)>,
+}
+
+impl Default for ResistanceDiagnostic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResistanceDiagnostic {
+    pub fn new() -> Self {
+        Self {
+            window: 200,
+            min_current: 1e-4,
+            voltage: 0.0,
+            current: 0.0,
+            resistance: None,
+            history: VecDeque::new(),
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Add one `(voltage, current)` sample and refresh the smoothed estimate.
+    pub fn push_sample(&mut self, time_fs: f32, voltage: f32, current: f32) {
+        self.voltage = voltage;
+        self.current = current;
+        self.samples.push_back((voltage, current));
+        while self.samples.len() > self.window.max(1) {
+            self.samples.pop_front();
+        }
+        let window: Vec<(f32, f32)> = self.samples.iter().copied().collect();
+        self.resistance = smoothed_resistance(&window, self.min_current);
+        if let Some(r) = self.resistance {
+            self.history.push_back((time_fs, r));
+            while self.history.len() > RESISTANCE_HISTORY_LEN {
+                self.history.pop_front();
+            }
+        }
+    }
+
+    /// Measure group voltage and ionic current for the current frame.
+    /// Does nothing unless both groups have at least one live foil body.
+    pub fn calculate(
+        &mut self,
+        bodies: &[Body],
+        foils: &[Foil],
+        group_a: &[u64],
+        group_b: &[u64],
+        coulomb_constant: f32,
+        time_fs: f32,
+    ) {
+        profile_scope!("resistance_calculation_internal");
+        let (Some(a), Some(b)) = (
+            group_centroid(bodies, foils, group_a),
+            group_centroid(bodies, foils, group_b),
+        ) else {
+            return;
+        };
+        let voltage =
+            potential_at(a, bodies, coulomb_constant) - potential_at(b, bodies, coulomb_constant);
+        let current = ionic_current(bodies, a, b);
+        self.push_sample(time_fs, voltage, current);
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.history.clear();
+        self.resistance = None;
+    }
+
+    /// Write the resistance time series as `time_fs,resistance`.
+    pub fn write_csv(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        writeln!(file, "time_fs,resistance")?;
+        for (t, r) in &self.history {
+            writeln!(file, "{:.3},{:.6e}", t, r)?;
+        }
+        file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ratio_is_smoothed_and_guards_zero_current() {
+        // Noisy samples average to V = 2, I = 0.5
+        let samples = [(1.5, 0.4), (2.5, 0.6), (2.0, 0.5)];
+        let r = smoothed_resistance(&samples, 1e-3).unwrap();
+        assert!((r - 4.0).abs() < 1e-5);
+
+        // Zero or vanishing mean current gives no estimate
+        assert_eq!(smoothed_resistance(&[(1.0, 0.1), (1.0, -0.1)], 1e-3), None);
+        assert_eq!(smoothed_resistance(&[(1.0, 1e-6)], 1e-3), None);
+        assert_eq!(smoothed_resistance(&[], 1e-3), None);
+
+        // The diagnostic only averages over the most recent `window` samples
+        let mut diag = ResistanceDiagnostic::new();
+        diag.window = 2;
+        diag.push_sample(0.0, 100.0, 1.0);
+        diag.push_sample(1.0, 3.0, 1.0);
+        diag.push_sample(2.0, 5.0, 1.0);
+        assert!((diag.resistance.unwrap() - 4.0).abs() < 1e-5);
+        assert_eq!(diag.history.len(), 3);
+
+        // The time series keeps only the most recent samples
+        for step in 0..RESISTANCE_HISTORY_LEN + 10 {
+            diag.push_sample(step as f32, 4.0, 1.0);
+        }
+        assert_eq!(diag.history.len(), RESISTANCE_HISTORY_LEN);
+        assert_eq!(diag.history.front().unwrap().0, 10.0);
+    }
+}
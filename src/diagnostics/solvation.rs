@@ -4,6 +4,8 @@
 use crate::body::{Body, Species};
 use crate::profile_scope;
 use crate::quadtree::Quadtree;
+use serde::Serialize;
+use std::path::Path;
 
 /// Calculate 3D distance between two bodies, accounting for z-coordinates
 fn distance_3d(body1: &Body, body2: &Body) -> f32 {
@@ -13,6 +15,18 @@ fn distance_3d(body1: &Body, body2: &Body) -> f32 {
     (dx * dx + dy * dy + dz * dz).sqrt()
 }
 
+/// Solvation shell of one cation, as written by `export_shells_json`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CationShell {
+    pub cation_id: u64,
+    /// "CIP", "SIP", "S2IP", "FD", or "none" for metal-surrounded ions that
+    /// were not classified
+    pub classification: &'static str,
+    pub solvent_ids: Vec<u64>,
+    pub anion_id: Option<u64>,
+    pub anion_solvent_ids: Vec<u64>,
+}
+
 pub struct SolvationDiagnostic {
     pub cip_fraction: f32,
     pub sip_fraction: f32,
@@ -29,6 +43,8 @@ pub struct SolvationDiagnostic {
     pub sip_pairs: Vec<(u64, u64, Vec<u64>, Vec<u64>)>,
     pub s2ip_pairs: Vec<(u64, u64, Vec<u64>, Vec<u64>)>,
     pub fd_cations: Vec<(u64, Vec<u64>)>, // (cation_id, cation_solvent_ids)
+    // Cations skipped because they are surrounded by metal
+    pub unclassified_cation_ids: Vec<u64>,
 }
 
 impl SolvationDiagnostic {
@@ -48,6 +64,7 @@ impl SolvationDiagnostic {
             sip_pairs: Vec::new(),
             s2ip_pairs: Vec::new(),
             fd_cations: Vec::new(),
+            unclassified_cation_ids: Vec::new(),
         }
     }
 
@@ -69,6 +86,7 @@ impl SolvationDiagnostic {
         self.sip_pairs.clear();
         self.s2ip_pairs.clear();
         self.fd_cations.clear();
+        self.unclassified_cation_ids.clear();
 
         // Get typical solvent radius (average of all solvents)
         let ec_radius = crate::body::Species::EC.radius();
@@ -117,6 +135,7 @@ impl SolvationDiagnostic {
                 Species::LithiumIon => {
                     // Skip ions that are surrounded by metal (not truly ionic)
                     if body.surrounded_by_metal {
+                        self.unclassified_cation_ids.push(body.id);
                         continue;
                     }
 
@@ -259,6 +278,52 @@ impl SolvationDiagnostic {
         }
     }
 
+    /// Per-cation shell membership from the last `calculate`, ordered by
+    /// cation ID. Cations with no shell appear with empty ID lists.
+    pub fn shells(&self) -> Vec<CationShell> {
+        let paired = |label: &'static str, pairs: &[(u64, u64, Vec<u64>, Vec<u64>)]| {
+            pairs
+                .iter()
+                .map(move |(cation, anion, solvents, anion_solvents)| CationShell {
+                    cation_id: *cation,
+                    classification: label,
+                    solvent_ids: solvents.clone(),
+                    anion_id: Some(*anion),
+                    anion_solvent_ids: anion_solvents.clone(),
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut shells = paired("CIP", &self.cip_pairs);
+        shells.extend(paired("SIP", &self.sip_pairs));
+        shells.extend(paired("S2IP", &self.s2ip_pairs));
+        shells.extend(self.fd_cations.iter().map(|(cation, solvents)| CationShell {
+            cation_id: *cation,
+            classification: "FD",
+            solvent_ids: solvents.clone(),
+            anion_id: None,
+            anion_solvent_ids: Vec::new(),
+        }));
+        shells.extend(self.unclassified_cation_ids.iter().map(|&cation| CationShell {
+            cation_id: cation,
+            classification: "none",
+            solvent_ids: Vec::new(),
+            anion_id: None,
+            anion_solvent_ids: Vec::new(),
+        }));
+        shells.sort_by_key(|s| s.cation_id);
+        shells
+    }
+
+    /// Write `shells()` as a JSON array to `path`.
+    pub fn export_shells_json(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.shells())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        std::fs::write(path, json)
+    }
+
     /// Helper method to find nearest anion using quadtree with expanding search radius
     fn find_nearest_anion_with_quadtree(
         &self,
@@ -297,3 +362,30 @@ impl SolvationDiagnostic {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shells_list_every_cation_including_empty_ones() {
+        let mut diag = SolvationDiagnostic::new();
+        diag.cip_pairs.push((3, 10, vec![20, 21], vec![22]));
+        diag.fd_cations.push((1, Vec::new()));
+        diag.unclassified_cation_ids.push(2);
+
+        let shells = diag.shells();
+        let ids: Vec<u64> = shells.iter().map(|s| s.cation_id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(shells[0].classification, "FD");
+        assert!(shells[0].solvent_ids.is_empty() && shells[0].anion_id.is_none());
+        assert_eq!(shells[1].classification, "none");
+        assert_eq!(shells[2].classification, "CIP");
+        assert_eq!(shells[2].anion_id, Some(10));
+        assert_eq!(shells[2].solvent_ids, vec![20, 21]);
+
+        let json = serde_json::to_value(&shells).unwrap();
+        assert_eq!(json[1]["solvent_ids"], serde_json::json!([]));
+        assert_eq!(json[2]["anion_solvent_ids"], serde_json::json!([22]));
+    }
+}
@@ -0,0 +1,178 @@
+// diagnostics/solvation_transference.rs
+// Cation transference number resolved by solvation state
+//
+// Each classified Li+ carries current `v · d` along the drift direction `d`
+// used by the transference diagnostic; anions carry `-v · d`. A class's
+// contribution is its summed cation current over the total current of all
+// classified cations plus anions, so the four contributions add up to the
+// cation transference number of the classified population. Cations skipped by
+// the solvation diagnostic (surrounded by metal) are left out.
+
+use super::SolvationDiagnostic;
+use crate::body::{Body, Species};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use ultraviolet::Vec2;
+
+pub const SOLVATION_TRANSFERENCE_HEADER: &str =
+    "time_fs,n_cip,n_sip,n_s2ip,n_fd,t_cip,t_sip,t_s2ip,t_fd,t_total";
+
+/// Per-class contributions to the transference number at one solvation update.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SolvationTransferenceSample {
+    pub time_fs: f32,
+    /// Cations in each class, ordered CIP, SIP, S2IP, FD
+    pub counts: [usize; 4],
+    /// Share of the total current carried by each class (0 when empty)
+    pub contributions: [f32; 4],
+}
+
+impl SolvationTransferenceSample {
+    /// Split the current of `bodies` along `drift_direction` by the cation
+    /// classes of `solvation`. Empty classes and a vanishing total current
+    /// give zero contributions.
+    pub fn compute(
+        bodies: &[Body],
+        solvation: &SolvationDiagnostic,
+        drift_direction: Vec2,
+        time_fs: f32,
+    ) -> Self {
+        let classes = [
+            &solvation.cip_ion_ids,
+            &solvation.sip_ion_ids,
+            &solvation.s2ip_ion_ids,
+            &solvation.fd_ion_ids,
+        ];
+        let class_of: HashMap<u64, usize> = classes
+            .iter()
+            .enumerate()
+            .flat_map(|(class, ids)| ids.iter().map(move |&id| (id, class)))
+            .collect();
+
+        let mut counts = [0usize; 4];
+        let mut currents = [0.0f32; 4];
+        let mut anion_current = 0.0f32;
+        for body in bodies {
+            let projection = body.vel.dot(drift_direction);
+            match body.species {
+                Species::LithiumIon => {
+                    if let Some(&class) = class_of.get(&body.id) {
+                        counts[class] += 1;
+                        currents[class] += projection;
+                    }
+                }
+                Species::ElectrolyteAnion => anion_current -= projection,
+                _ => {}
+            }
+        }
+
+        let total = currents.iter().sum::<f32>() + anion_current;
+        let contributions = if total.abs() > 1e-6 {
+            currents.map(|c| c / total)
+        } else {
+            [0.0; 4]
+        };
+        Self {
+            time_fs,
+            counts,
+            contributions,
+        }
+    }
+
+    pub fn total(&self) -> f32 {
+        self.contributions.iter().sum()
+    }
+
+    pub fn csv_row(&self) -> String {
+        let [cip, sip, s2ip, fd] = self.contributions;
+        format!(
+            "{:.3},{},{},{},{},{:.6},{:.6},{:.6},{:.6},{:.6}",
+            self.time_fs,
+            self.counts[0],
+            self.counts[1],
+            self.counts[2],
+            self.counts[3],
+            cip,
+            sip,
+            s2ip,
+            fd,
+            self.total()
+        )
+    }
+}
+
+/// Time series of solvation-resolved transference samples.
+#[derive(Debug, Default)]
+pub struct SolvationTransferenceLog {
+    pub samples: Vec<SolvationTransferenceSample>,
+}
+
+impl SolvationTransferenceLog {
+    pub fn record(&mut self, sample: SolvationTransferenceSample) {
+        // Time moving backwards (rewind/playback) starts a fresh series
+        if self.samples.last().map_or(false, |last| sample.time_fs < last.time_fs) {
+            self.samples.clear();
+        }
+        self.samples.push(sample);
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn latest(&self) -> Option<&SolvationTransferenceSample> {
+        self.samples.last()
+    }
+
+    pub fn write_csv(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        writeln!(file, "{}", SOLVATION_TRANSFERENCE_HEADER)?;
+        for sample in &self.samples {
+            writeln!(file, "{}", sample.csv_row())?;
+        }
+        file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ion(species: Species, vx: f32) -> Body {
+        Body::new(Vec2::zero(), Vec2::new(vx, 0.0), 1.0, 1.0, 0.0, species)
+    }
+
+    #[test]
+    fn classes_split_current_by_drift_and_empty_classes_are_zero() {
+        // Two free cations at 2.0, one contact pair cation at 0.5 and one
+        // anion drifting backwards at -1.0: total current 4.5 + 1.0 = 5.5
+        let bodies = vec![
+            ion(Species::LithiumIon, 2.0),
+            ion(Species::LithiumIon, 2.0),
+            ion(Species::LithiumIon, 0.5),
+            ion(Species::ElectrolyteAnion, -1.0),
+        ];
+        let mut solvation = SolvationDiagnostic::new();
+        solvation.fd_ion_ids = vec![bodies[0].id, bodies[1].id];
+        solvation.cip_ion_ids = vec![bodies[2].id];
+
+        let sample =
+            SolvationTransferenceSample::compute(&bodies, &solvation, Vec2::new(1.0, 0.0), 10.0);
+        assert_eq!(sample.counts, [1, 0, 0, 2]);
+        assert!((sample.contributions[0] - 0.5 / 5.5).abs() < 1e-6);
+        assert!((sample.contributions[3] - 4.0 / 5.5).abs() < 1e-6);
+        assert_eq!((sample.contributions[1], sample.contributions[2]), (0.0, 0.0));
+        assert!((sample.total() - 4.5 / 5.5).abs() < 1e-6);
+        let row = sample.csv_row();
+        assert_eq!(row.split(',').count(), SOLVATION_TRANSFERENCE_HEADER.split(',').count());
+
+        // Nothing moving along the drift direction
+        let still =
+            SolvationTransferenceSample::compute(&bodies, &solvation, Vec2::new(0.0, 1.0), 20.0);
+        assert_eq!(still.contributions, [0.0; 4]);
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::{HashMap, VecDeque};
+
+use ultraviolet::Vec2;
+
+use super::foil_electron_fraction::connected_metal_indices;
+use crate::body::{foil::Foil, Body, Species};
+use crate::profile_scope;
+use crate::quadtree::Quadtree;
+
+/// Number of (time, roughness) samples kept per foil for the time series.
+const ROUGHNESS_HISTORY_LEN: usize = 1000;
+
+/// Roughness of the deposited metal front grown on `foil`.
+///
+/// `connected` are the indices of the metal cluster attached to the foil
+/// (see [`connected_metal_indices`]). The front is measured along the foil's
+/// facing direction (from the foil centroid toward the domain centre) and
+/// binned laterally into `bin_width` strips spanning the foil. The result is
+/// the standard deviation of the outermost deposit height across those bins;
+/// bins without deposit count as zero height. Returns 0.0 with no deposit.
+pub fn surface_roughness(bodies: &[Body], foil: &Foil, connected: &[usize], bin_width: f32) -> f32 {
+    let foil_positions: Vec<Vec2> = connected
+        .iter()
+        .map(|&i| &bodies[i])
+        .filter(|b| foil.body_ids.contains(&b.id))
+        .map(|b| b.pos)
+        .collect();
+    let deposit: Vec<Vec2> = connected
+        .iter()
+        .map(|&i| &bodies[i])
+        .filter(|b| b.species == Species::LithiumMetal && !foil.body_ids.contains(&b.id))
+        .map(|b| b.pos)
+        .collect();
+    if foil_positions.is_empty() || deposit.is_empty() || bin_width <= 0.0 {
+        return 0.0;
+    }
+
+    let centroid = foil_positions.iter().fold(Vec2::zero(), |acc, &p| acc + p)
+        / foil_positions.len() as f32;
+    let normal = if centroid.mag() > 1e-6 {
+        -centroid.normalized()
+    } else {
+        // Foil sits at the domain centre; face toward the deposit instead
+        let deposit_centroid =
+            deposit.iter().fold(Vec2::zero(), |acc, &p| acc + p) / deposit.len() as f32;
+        let d = deposit_centroid - centroid;
+        if d.mag() > 1e-6 {
+            d.normalized()
+        } else {
+            Vec2::unit_x()
+        }
+    };
+    let lateral = Vec2::new(-normal.y, normal.x);
+
+    // Foil surface plane and lateral extent
+    let mut base = f32::NEG_INFINITY;
+    let mut s_min = f32::INFINITY;
+    let mut s_max = f32::NEG_INFINITY;
+    for &p in &foil_positions {
+        let rel = p - centroid;
+        base = base.max(rel.dot(normal));
+        let s = rel.dot(lateral);
+        s_min = s_min.min(s);
+        s_max = s_max.max(s);
+    }
+
+    let bins = (((s_max - s_min) / bin_width).floor() as usize + 1).max(1);
+    let mut front = vec![0.0f32; bins];
+    for &p in &deposit {
+        let rel = p - centroid;
+        let height = (rel.dot(normal) - base).max(0.0);
+        let bin = (((rel.dot(lateral) - s_min) / bin_width).floor().max(0.0) as usize).min(bins - 1);
+        front[bin] = front[bin].max(height);
+    }
+
+    let mean = front.iter().sum::<f32>() / bins as f32;
+    let variance = front.iter().map(|h| (h - mean).powi(2)).sum::<f32>() / bins as f32;
+    variance.sqrt()
+}
+
+/// Diagnostic tracking the roughness of the metal deposit on each foil.
+#[derive(Default)]
+pub struct SurfaceRoughnessDiagnostic {
+    /// Latest roughness (Å) per foil id.
+    pub roughness: HashMap<u64, f32>,
+    /// Per-foil (sim time fs, roughness Å) time series.
+    pub history: HashMap<u64, VecDeque<(f32, f32)>>,
+    pub last_calc_time: f32,
+}
+
+impl SurfaceRoughnessDiagnostic {
+    pub fn new() -> Self {
+        Self {
+            roughness: HashMap::new(),
+            history: HashMap::new(),
+            last_calc_time: f32::NEG_INFINITY,
+        }
+    }
+
+    /// Recompute roughness for all foils and append to the time series.
+    pub fn calculate(&mut self, bodies: &[Body], foils: &[Foil], quadtree: &Quadtree, time: f32) {
+        profile_scope!("surface_roughness_calculation");
+        self.roughness.clear();
+        let id_to_index: HashMap<u64, usize> =
+            bodies.iter().enumerate().map(|(i, b)| (b.id, i)).collect();
+        let bin_width = 2.0 * Species::LithiumMetal.radius();
+
+        for foil in foils {
+            let connected = connected_metal_indices(bodies, foil, quadtree, &id_to_index);
+            let value = surface_roughness(bodies, foil, &connected, bin_width);
+            self.roughness.insert(foil.id, value);
+            let series = self.history.entry(foil.id).or_default();
+            series.push_back((time, value));
+            while series.len() > ROUGHNESS_HISTORY_LEN {
+                series.pop_front();
+            }
+        }
+        self.history.retain(|id, _| foils.iter().any(|f| f.id == *id));
+    }
+
+    /// Recalculate only if `min_interval` fs have elapsed since the last run.
+    pub fn calculate_if_needed(
+        &mut self,
+        bodies: &[Body],
+        foils: &[Foil],
+        quadtree: &Quadtree,
+        current_time: f32,
+        min_interval: f32,
+    ) -> bool {
+        let elapsed = current_time - self.last_calc_time;
+        // Time going backwards means a reset or rewind; drop the stale series
+        if elapsed < 0.0 {
+            self.history.clear();
+        }
+        if elapsed >= min_interval || elapsed < 0.0 {
+            self.calculate(bodies, foils, quadtree, current_time);
+            self.last_calc_time = current_time;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metal(x: f32, y: f32, species: Species) -> Body {
+        Body::new(Vec2::new(x, y), Vec2::zero(), 1.0, 1.0, 0.0, species)
+    }
+
+    /// Vertical foil at x = -20 spanning y in [-10, 10] with deposit columns
+    /// of the given heights (in particle layers) grown toward +x.
+    fn foil_with_front(heights: &[usize]) -> (Vec<Body>, Foil) {
+        let mut bodies = Vec::new();
+        for (i, _) in heights.iter().enumerate() {
+            bodies.push(metal(-20.0, -10.0 + 2.0 * i as f32, Species::FoilMetal));
+        }
+        let foil_ids = bodies.iter().map(|b| b.id).collect();
+        for (i, &h) in heights.iter().enumerate() {
+            for layer in 1..=h {
+                let x = -20.0 + 2.0 * layer as f32;
+                bodies.push(metal(x, -10.0 + 2.0 * i as f32, Species::LithiumMetal));
+            }
+        }
+        let foil = Foil::new(foil_ids, Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+        (bodies, foil)
+    }
+
+    #[test]
+    fn flat_front_has_zero_roughness() {
+        let (bodies, foil) = foil_with_front(&[2; 11]);
+        let all: Vec<usize> = (0..bodies.len()).collect();
+        assert!(surface_roughness(&bodies, &foil, &all, 2.0) < 1e-4);
+    }
+
+    #[test]
+    fn jagged_front_is_rougher_than_flat() {
+        let (bodies, foil) = foil_with_front(&[1, 4, 1, 4, 1, 4, 1, 4, 1, 4, 1]);
+        let all: Vec<usize> = (0..bodies.len()).collect();
+        let r = surface_roughness(&bodies, &foil, &all, 2.0);
+        // Heights alternate between 2 Å and 8 Å
+        assert!(r > 2.5 && r < 3.5, "roughness {}", r);
+    }
+
+    #[test]
+    fn no_deposit_returns_zero() {
+        let (bodies, foil) = foil_with_front(&[0; 11]);
+        let all: Vec<usize> = (0..bodies.len()).collect();
+        assert_eq!(surface_roughness(&bodies, &foil, &all, 2.0), 0.0);
+    }
+}
@@ -20,6 +20,46 @@ pub struct DoeConfig {
 
     /// List of test cases to execute
     pub test_cases: Vec<TestCase>,
+
+    /// Optional heatmap matrix of one metric over two swept parameters
+    #[serde(default)]
+    pub phase_diagram: Option<PhaseDiagramSpec>,
+}
+
+/// Which two parameters span the phase diagram and which outcome fills it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PhaseDiagramSpec {
+    /// Parameter along the matrix columns
+    pub param_x: DoeParameter,
+    /// Parameter along the matrix rows
+    pub param_y: DoeParameter,
+    /// Outcome written into each cell
+    pub metric: DoeMetric,
+}
+
+/// Swept test-case parameter
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DoeParameter {
+    Overpotential,
+    /// Only defined for switch-charging cases
+    SwitchingFrequency,
+}
+
+impl DoeParameter {
+    pub fn value(&self, case: &TestCase) -> Option<f64> {
+        match self {
+            DoeParameter::Overpotential => Some(case.overpotential as f64),
+            DoeParameter::SwitchingFrequency => case.switching_frequency_steps.map(|f| f as f64),
+        }
+    }
+}
+
+/// Per-case outcome, as in the DOE summary columns
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DoeMetric {
+    FinalLiMetalCount,
+    AvgEdgePosition,
+    MaxEdgePosition,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -158,6 +198,7 @@ impl DoeConfig {
             measurement_interval_fs,
             measurements,
             test_cases,
+            phase_diagram: None,
         }
     }
 
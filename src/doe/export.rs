@@ -1,4 +1,4 @@
-use super::config::TestCase;
+use super::config::{DoeMetric, DoeParameter, TestCase};
 use super::measurement::MeasurementSample;
 use std::collections::HashMap;
 /// Export DOE results to CSV format for Excel analysis
@@ -126,14 +126,47 @@ pub fn export_doe_summary(
 
     // Write summary row for each case
     for (case, samples) in cases.iter().zip(all_samples.iter()) {
-        if samples.is_empty() {
+        let Some(summary) = CaseSummary::from_samples(samples) else {
             continue;
-        }
+        };
+
+        let freq_str = case
+            .switching_frequency_steps
+            .map(|f| f.to_string())
+            .unwrap_or_else(|| "N/A".to_string());
+
+        writeln!(
+            file,
+            "{},{:?},{},{},{},{},{}",
+            case.case_id,
+            case.mode,
+            case.overpotential,
+            freq_str,
+            summary.final_li_metal_count,
+            summary.avg_edge_position,
+            summary.max_edge_position
+        )?;
+    }
+
+    println!("✓ Exported DOE summary to {}", filename);
+    Ok(())
+}
+
+/// Summary statistics of one case, as written to `DOE_Summary.csv`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaseSummary {
+    pub final_li_metal_count: usize,
+    pub avg_edge_position: f32,
+    pub max_edge_position: f32,
+}
 
-        // Calculate summary statistics
+impl CaseSummary {
+    /// Averages over the last 1000 fs of samples; `None` without samples.
+    pub fn from_samples(samples: &[MeasurementSample]) -> Option<Self> {
+        let last_time = samples.last()?.time_fs;
         let final_samples: Vec<_> = samples
             .iter()
-            .filter(|s| s.time_fs >= samples.last().map(|l| l.time_fs - 1000.0).unwrap_or(0.0))
+            .filter(|s| s.time_fs >= last_time - 1000.0)
             .collect();
 
         let final_li_metal_count: usize = final_samples
@@ -154,24 +187,221 @@ pub fn export_doe_summary(
             .max_by(|a, b| a.partial_cmp(b).unwrap())
             .unwrap_or(0.0);
 
-        let freq_str = case
-            .switching_frequency_steps
-            .map(|f| f.to_string())
-            .unwrap_or_else(|| "N/A".to_string());
-
-        writeln!(
-            file,
-            "{},{:?},{},{},{},{},{}",
-            case.case_id,
-            case.mode,
-            case.overpotential,
-            freq_str,
+        Some(Self {
             final_li_metal_count,
             avg_edge_position,
-            max_edge_position
-        )?;
+            max_edge_position,
+        })
     }
 
-    println!("✓ Exported DOE summary to {}", filename);
-    Ok(())
+    pub fn metric(&self, metric: DoeMetric) -> f64 {
+        match metric {
+            DoeMetric::FinalLiMetalCount => self.final_li_metal_count as f64,
+            DoeMetric::AvgEdgePosition => self.avg_edge_position as f64,
+            DoeMetric::MaxEdgePosition => self.max_edge_position as f64,
+        }
+    }
+}
+
+/// Outcome of one test case; `summary` is `None` when the case failed or
+/// produced no samples.
+#[derive(Debug, Clone)]
+pub struct CaseResult {
+    pub case: TestCase,
+    pub summary: Option<CaseSummary>,
+}
+
+/// One cell of a phase diagram
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhaseCell {
+    /// No case was configured at this parameter combination
+    NotRun,
+    /// Every case at this combination failed
+    Failed,
+    /// Metric, averaged over replicate cases at the same combination
+    Value(f64),
+}
+
+impl PhaseCell {
+    fn csv(&self) -> String {
+        match self {
+            PhaseCell::NotRun => String::new(),
+            PhaseCell::Failed => "FAILED".to_string(),
+            PhaseCell::Value(v) => v.to_string(),
+        }
+    }
+}
+
+/// Metric matrix over two swept parameters: `cells[row][col]` is at
+/// `(x_values[col], y_values[row])`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseDiagram {
+    pub x_values: Vec<f64>,
+    pub y_values: Vec<f64>,
+    pub cells: Vec<Vec<PhaseCell>>,
+}
+
+fn sorted_unique(mut values: Vec<f64>) -> Vec<f64> {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    values.dedup();
+    values
+}
+
+/// Pivot case results into a grid. Cases without a value for either
+/// parameter (e.g. switching frequency of a conventional case) are left out.
+pub fn pivot_phase_diagram(
+    results: &[CaseResult],
+    param_x: DoeParameter,
+    param_y: DoeParameter,
+    metric: DoeMetric,
+) -> PhaseDiagram {
+    let placed: Vec<(f64, f64, Option<f64>)> = results
+        .iter()
+        .filter_map(|r| {
+            let x = param_x.value(&r.case)?;
+            let y = param_y.value(&r.case)?;
+            Some((x, y, r.summary.map(|s| s.metric(metric))))
+        })
+        .collect();
+    let x_values = sorted_unique(placed.iter().map(|p| p.0).collect());
+    let y_values = sorted_unique(placed.iter().map(|p| p.1).collect());
+
+    let cells = y_values
+        .iter()
+        .map(|&y| {
+            x_values
+                .iter()
+                .map(|&x| {
+                    let at_cell: Vec<Option<f64>> = placed
+                        .iter()
+                        .filter(|p| p.0 == x && p.1 == y)
+                        .map(|p| p.2)
+                        .collect();
+                    let values: Vec<f64> = at_cell.iter().flatten().copied().collect();
+                    if at_cell.is_empty() {
+                        PhaseCell::NotRun
+                    } else if values.is_empty() {
+                        PhaseCell::Failed
+                    } else {
+                        PhaseCell::Value(values.iter().sum::<f64>() / values.len() as f64)
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    PhaseDiagram {
+        x_values,
+        y_values,
+        cells,
+    }
+}
+
+impl PhaseDiagram {
+    /// Matrix CSV: the corner names both axes, the first row holds the X
+    /// values and each following row a Y value and its cells. Failed cells
+    /// read `FAILED`, combinations that were never run are left empty.
+    pub fn to_csv(&self, param_x: DoeParameter, param_y: DoeParameter) -> String {
+        let mut content = format!("{:?}\\{:?}", param_y, param_x);
+        for x in &self.x_values {
+            content.push_str(&format!(",{}", x));
+        }
+        content.push('\n');
+        for (y, row) in self.y_values.iter().zip(&self.cells) {
+            content.push_str(&y.to_string());
+            for cell in row {
+                content.push(',');
+                content.push_str(&cell.csv());
+            }
+            content.push('\n');
+        }
+        content
+    }
+}
+
+/// Write `PhaseDiagram_<metric>.csv` into `output_dir`, ready for a heatmap.
+pub fn write_phase_diagram(
+    results: &[CaseResult],
+    param_x: DoeParameter,
+    param_y: DoeParameter,
+    metric: DoeMetric,
+    output_dir: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let diagram = pivot_phase_diagram(results, param_x, param_y, metric);
+    std::fs::create_dir_all(output_dir)?;
+    let filename = format!("{}/PhaseDiagram_{:?}.csv", output_dir, metric);
+    std::fs::write(&filename, diagram.to_csv(param_x, param_y))?;
+
+    let failed = diagram
+        .cells
+        .iter()
+        .flatten()
+        .filter(|c| **c == PhaseCell::Failed)
+        .count();
+    println!(
+        "✓ Exported {}x{} phase diagram of {:?} to {} ({} failed cells)",
+        diagram.y_values.len(),
+        diagram.x_values.len(),
+        metric,
+        filename,
+        failed
+    );
+    Ok(filename)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doe::config::ChargingMode;
+
+    fn result(op: f32, freq: Option<u64>, max_edge: Option<f32>) -> CaseResult {
+        CaseResult {
+            case: TestCase {
+                case_id: format!("OP{op}_F{freq:?}"),
+                mode: ChargingMode::SwitchCharging,
+                overpotential: op,
+                switching_frequency_steps: freq,
+                group_a_foils: vec![1],
+                group_b_foils: vec![2],
+            },
+            summary: max_edge.map(|m| CaseSummary {
+                final_li_metal_count: 0,
+                avg_edge_position: 0.0,
+                max_edge_position: m,
+            }),
+        }
+    }
+
+    #[test]
+    fn pivot_places_values_and_marks_missing_cells() {
+        let results = vec![
+            result(1.0, Some(100), Some(5.0)),
+            result(1.0, Some(200), Some(7.0)),
+            result(2.0, Some(100), None),
+            // Replicate at (200, 1.0) is averaged in
+            result(1.0, Some(200), Some(9.0)),
+            // Conventional case has no switching frequency and is left out
+            result(3.0, None, Some(1.0)),
+        ];
+        let diagram = pivot_phase_diagram(
+            &results,
+            DoeParameter::SwitchingFrequency,
+            DoeParameter::Overpotential,
+            DoeMetric::MaxEdgePosition,
+        );
+        assert_eq!(diagram.x_values, vec![100.0, 200.0]);
+        assert_eq!(diagram.y_values, vec![1.0, 2.0]);
+        assert_eq!(
+            diagram.cells,
+            vec![
+                vec![PhaseCell::Value(5.0), PhaseCell::Value(8.0)],
+                vec![PhaseCell::Failed, PhaseCell::NotRun],
+            ]
+        );
+        let csv = diagram.to_csv(DoeParameter::SwitchingFrequency, DoeParameter::Overpotential);
+        assert_eq!(
+            csv,
+            "Overpotential\\SwitchingFrequency,100,200\n1,5,8\n2,FAILED,\n"
+        );
+    }
 }
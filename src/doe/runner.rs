@@ -1,5 +1,7 @@
 use super::config::{ChargingMode, DoeConfig, TestCase};
-use super::export::{export_doe_summary, export_results_to_csv};
+use super::export::{
+    export_doe_summary, export_results_to_csv, write_phase_diagram, CaseResult, CaseSummary,
+};
 use super::measurement::AutoMeasurement;
 /// DOE runner for executing test cases headlessly
 use crate::simulation::Simulation;
@@ -35,18 +37,51 @@ impl DoeRunner {
     /// Run all test cases sequentially
     pub fn run_all(&self) -> Result<(), Box<dyn std::error::Error>> {
         let mut all_samples = Vec::new();
+        let mut results = Vec::new();
+        let mut failed = 0;
 
         for case in &self.config.test_cases {
             println!("\n╔══════════════════════════════════════════╗");
             println!("║  Running DOE Case: {}  ", case.case_id);
             println!("╚══════════════════════════════════════════╝\n");
 
-            let samples = self.execute_case(case)?;
+            // A failed case leaves a hole in the summary instead of ending the study
+            let samples = match self.execute_case(case) {
+                Ok(samples) => samples,
+                Err(e) => {
+                    eprintln!("✗ Case {} failed: {}", case.case_id, e);
+                    failed += 1;
+                    Vec::new()
+                }
+            };
+            results.push(CaseResult {
+                case: case.clone(),
+                summary: CaseSummary::from_samples(&samples),
+            });
             all_samples.push(samples);
         }
 
         // Export summary
         export_doe_summary(&self.config.test_cases, &all_samples, &self.output_dir)?;
+        if let Some(spec) = &self.config.phase_diagram {
+            write_phase_diagram(
+                &results,
+                spec.param_x,
+                spec.param_y,
+                spec.metric,
+                &self.output_dir,
+            )?;
+        }
+
+        if failed > 0 {
+            println!("📊 Partial results saved to: {}", self.output_dir);
+            return Err(format!(
+                "{} of {} DOE cases failed",
+                failed,
+                self.config.test_cases.len()
+            )
+            .into());
+        }
 
         println!(
             "\n✅ DOE study '{}' completed successfully!",
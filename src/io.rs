@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use std::io::{BufWriter, Cursor, Read, Write};
 use std::path::Path;
 
-use crate::body::{foil::Foil, Body};
+use crate::body::{foil::Foil, Body, Species};
 use crate::config::SimConfig;
 use crate::simulation::Simulation;
 
@@ -359,3 +359,200 @@ fn maybe_decompress_gzip(data: &[u8]) -> std::io::Result<Option<Vec<u8>>> {
     decoder.read_to_end(&mut decoded)?;
     Ok(Some(decoded))
 }
+
+/// Reusable physics settings: the full `SimConfig` plus the effective
+/// per-species parameters, without any bodies or foils.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ConfigPreset {
+    pub config: SimConfig,
+    /// Species parameters (defaults with GUI overrides applied), in enum order
+    #[serde(default)]
+    pub species: Vec<(Species, crate::species::SpeciesProps)>,
+}
+
+impl ConfigPreset {
+    pub fn capture(config: &SimConfig) -> Self {
+        let mut species: Vec<Species> = crate::species::SPECIES_PROPERTIES.keys().copied().collect();
+        species.sort_by_key(|s| *s as u8);
+        Self {
+            config: config.clone(),
+            species: species
+                .into_iter()
+                .map(|s| (s, crate::species::get_species_props(s)))
+                .collect(),
+        }
+    }
+
+    /// Install the preset's species parameters as GUI overrides.
+    pub fn apply_species(&self) {
+        for (species, props) in &self.species {
+            crate::species::update_species_props(*species, *props);
+        }
+    }
+}
+
+pub fn save_config_preset<P: AsRef<Path>>(path: P, config: &SimConfig) -> std::io::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let text = serde_json::to_string_pretty(&ConfigPreset::capture(config))?;
+    std::fs::write(path, text)
+}
+
+pub fn load_config_preset<P: AsRef<Path>>(path: P) -> std::io::Result<ConfigPreset> {
+    let text = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// Element-like symbol written for each species in XYZ trajectories.
+///
+/// Real elements are used where a species is dominated by one (Li, Cu, metal
+/// oxides by their transition metal); solvents keep their abbreviations so
+/// VMD/Ovito can still select them by name.
+pub fn xyz_symbol(species: Species) -> &'static str {
+    match species {
+        Species::LithiumIon => "Li",
+        Species::LithiumMetal => "LiM",
+        Species::FoilMetal => "Cu",
+        Species::ElectrolyteAnion => "P",
+        Species::EC => "EC",
+        Species::DMC => "DMC",
+        Species::VC => "VC",
+        Species::FEC => "FEC",
+        Species::EMC => "EMC",
+        Species::LLZO => "Zr",
+        Species::LLZT => "Ta",
+        Species::S40B => "B",
+        Species::SEI => "F",
+        Species::Graphite => "C",
+        Species::HardCarbon => "Ch",
+        Species::SiliconOxide => "Si",
+        Species::LTO => "Ti",
+        Species::LFP => "Fe",
+        Species::LMFP => "Mn",
+        Species::NMC => "Ni",
+        Species::NCA => "Al",
+    }
+}
+
+/// Write one XYZ block per `stride`-th frame: atom count, a comment line with
+/// the frame and time, then `SYMBOL x y z` per body (Å). `z` is 0 for frames
+/// without out-of-plane motion.
+pub fn write_xyz(
+    out: &mut impl Write,
+    frames: &[&SimulationState],
+    stride: usize,
+) -> std::io::Result<usize> {
+    let mut written = 0;
+    for state in frames.iter().step_by(stride.max(1)) {
+        let with_z = state.config.enable_out_of_plane;
+        writeln!(out, "{}", state.bodies.len())?;
+        writeln!(out, "frame={} time_fs={:.3}", state.frame, state.sim_time)?;
+        for body in &state.bodies {
+            let z = if with_z { body.z } else { 0.0 };
+            writeln!(
+                out,
+                "{} {:.4} {:.4} {:.4}",
+                xyz_symbol(body.species),
+                body.pos.x,
+                body.pos.y,
+                z
+            )?;
+        }
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Export the playback history (or the current state when there is none) as
+/// an XYZ trajectory. Returns the number of frames written.
+pub fn export_xyz<P: AsRef<Path>>(path: P, sim: &Simulation, stride: usize) -> std::io::Result<usize> {
+    profile_scope!("export_xyz");
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let current;
+    let frames: Vec<&SimulationState> = if sim.simple_history.is_empty() {
+        current = SimulationState::from_simulation(sim);
+        vec![&current]
+    } else {
+        sim.simple_history.iter().collect()
+    };
+    let mut file = BufWriter::new(std::fs::File::create(path)?);
+    let written = write_xyz(&mut file, &frames, stride)?;
+    file.flush()?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn config_preset_round_trips_every_field() {
+        let mut config = SimConfig::default();
+        config.temperature = 313.0;
+        config.deterministic_collisions = true;
+        config.species_targets.insert(Species::EC, 42);
+        config.log_level = crate::logging::LogLevel::Debug;
+
+        let path = std::env::temp_dir().join(format!("config_preset_{}.json", std::process::id()));
+        save_config_preset(&path, &config).unwrap();
+        let loaded = load_config_preset(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            serde_json::to_value(&loaded.config).unwrap(),
+            serde_json::to_value(&config).unwrap()
+        );
+        let expected = ConfigPreset::capture(&config);
+        assert_eq!(loaded.species.len(), expected.species.len());
+        assert_eq!(
+            serde_json::to_value(&loaded.species).unwrap(),
+            serde_json::to_value(&expected.species).unwrap()
+        );
+    }
+
+    #[test]
+    fn xyz_blocks_match_body_count() {
+        let mut sim = Simulation::new();
+        sim.config.enable_out_of_plane = false;
+        for (i, species) in [Species::LithiumIon, Species::EC, Species::FoilMetal]
+            .into_iter()
+            .enumerate()
+        {
+            let mut body = Body::new(Vec2::new(i as f32, 2.0), Vec2::zero(), 1.0, 1.0, 0.0, species);
+            body.z = 3.0;
+            sim.bodies.push(body);
+        }
+        let first = SimulationState::from_simulation(&sim);
+        sim.bodies.pop();
+        sim.frame = 1;
+        let second = SimulationState::from_simulation(&sim);
+
+        let mut out = Vec::new();
+        let written = write_xyz(&mut out, &[&first, &second], 1).unwrap();
+        assert_eq!(written, 2);
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        let mut i = 0;
+        for state in [&first, &second] {
+            let n: usize = lines[i].parse().unwrap();
+            assert_eq!(n, state.bodies.len());
+            assert!(lines[i + 1].starts_with(&format!("frame={}", state.frame)));
+            i += 2 + n;
+        }
+        assert_eq!(i, lines.len());
+
+        // 2D runs flatten z
+        assert_eq!(lines[2], "Li 0.0000 2.0000 0.0000");
+        assert_eq!(lines[4].split_whitespace().next(), Some("Cu"));
+
+        let mut strided = Vec::new();
+        assert_eq!(write_xyz(&mut strided, &[&first, &second], 2).unwrap(), 1);
+    }
+}
@@ -7,6 +7,7 @@ pub mod doe;
 pub mod electrode;
 pub mod init_config;
 pub mod io;
+pub mod logging;
 pub mod manual_measurement;
 pub mod measurement_csv;
 pub mod partition;
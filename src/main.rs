@@ -8,6 +8,7 @@ mod doe;
 mod electrode;
 mod init_config;
 mod io;
+mod logging;
 mod manual_measurement;
 mod measurement_csv;
 mod partition;
@@ -15,6 +15,15 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use ultraviolet::Vec2;
+
+/// Species counted around each fixed probe point, in CSV column order.
+/// Solvent molecules are summed into the last column.
+const PROBE_COUNT_COLUMNS: [&str; 4] = ["n_li_ion", "n_anion", "n_li_metal", "n_solvent"];
+
+fn default_probe_radius() -> f32 {
+    10.0
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ManualMeasurementPoint {
@@ -63,6 +72,17 @@ pub struct ManualMeasurementConfig {
     /// Optionally auto-pause simulation when this time (in femtoseconds) is reached (None = no auto-pause)
     #[serde(default)]
     pub auto_pause_time_fs: Option<f32>,
+    /// After an auto-pause has recorded, resume and pause again this many femtoseconds
+    /// later (None = stay paused). Turns the auto-pause into stroboscopic sampling.
+    #[serde(default)]
+    pub auto_resume_after_fs: Option<f32>,
+    /// Fixed probe points logged to a separate wide CSV (`<output>_probes.csv`)
+    /// with one column group per point
+    #[serde(default)]
+    pub probe_points: Vec<Vec2>,
+    /// Sampling radius around each probe point (Angstroms)
+    #[serde(default = "default_probe_radius")]
+    pub probe_radius: f32,
 }
 
 impl Default for ManualMeasurementConfig {
@@ -73,10 +93,74 @@ impl Default for ManualMeasurementConfig {
             interval_fs: 1000.0,
             output_file: "manual_measurements.csv".to_string(),
             auto_pause_time_fs: None,
+            auto_resume_after_fs: None,
+            probe_points: Vec::new(),
+            probe_radius: default_probe_radius(),
         }
     }
 }
 
+/// Header for the fixed probe CSV: charge density, field and species counts
+/// for every point, in point order.
+pub fn probe_points_header(points: &[Vec2]) -> String {
+    let mut header = String::from("frame,time_fs");
+    for i in 0..points.len() {
+        header.push_str(&format!(",p{i}_rho,p{i}_ex,p{i}_ey"));
+        for col in PROBE_COUNT_COLUMNS {
+            header.push_str(&format!(",p{i}_{col}"));
+        }
+    }
+    header
+}
+
+/// One wide probe CSV row. Charge density is the net charge within `radius`
+/// divided by the disc area (e/Å²); the field is the Coulomb field at the
+/// point from every body.
+pub fn probe_points_row(
+    frame: usize,
+    simulation_time_fs: f32,
+    bodies: &[Body],
+    points: &[Vec2],
+    radius: f32,
+) -> String {
+    let k = crate::units::COULOMB_CONSTANT;
+    let r_sq = radius * radius;
+    let area = std::f32::consts::PI * r_sq.max(f32::MIN_POSITIVE);
+    let mut line = format!("{},{}", frame, simulation_time_fs);
+    for &point in points {
+        let mut charge = 0.0f32;
+        let mut field = Vec2::zero();
+        let mut counts = [0usize; PROBE_COUNT_COLUMNS.len()];
+        for body in bodies {
+            let d = point - body.pos;
+            let d_sq = d.mag_sq();
+            if d_sq > 1e-6 {
+                field += d * (k * body.charge / (d_sq * d_sq.sqrt()));
+            }
+            if d_sq <= r_sq {
+                charge += body.charge;
+                let col = match body.species {
+                    Species::LithiumIon => Some(0),
+                    Species::ElectrolyteAnion => Some(1),
+                    Species::LithiumMetal => Some(2),
+                    Species::EC | Species::DMC | Species::VC | Species::FEC | Species::EMC => {
+                        Some(3)
+                    }
+                    _ => None,
+                };
+                if let Some(col) = col {
+                    counts[col] += 1;
+                }
+            }
+        }
+        line.push_str(&format!(",{:.6e},{:.6e},{:.6e}", charge / area, field.x, field.y));
+        for n in counts {
+            line.push_str(&format!(",{}", n));
+        }
+    }
+    line
+}
+
 #[derive(Debug, Clone)]
 pub struct MeasurementResult {
     pub label: String,
@@ -88,18 +172,30 @@ pub struct ManualMeasurementRecorder {
     is_recording: bool,
     last_measurement_time: f32,
     csv_file: Option<File>,
+    /// Wide CSV for `probe_points`; only open while recording with probes set
+    probe_file: Option<File>,
     measurement_count: usize,
     /// Resolved unique path for this run's point-based CSV. Set once per run.
     resolved_path: Option<PathBuf>,
+    /// Next simulation time (fs) at which to auto-pause
+    next_auto_pause_fs: Option<f32>,
+    /// Set when an auto-pause should be lifted by the simulation loop
+    auto_resume_pending: bool,
+    /// Pause target whose measurement sample has already been forced
+    sampled_pause_fs: Option<f32>,
 }
 
 impl ManualMeasurementRecorder {
     pub fn new(config: ManualMeasurementConfig) -> Self {
         Self {
+            next_auto_pause_fs: config.auto_pause_time_fs,
+            auto_resume_pending: false,
+            sampled_pause_fs: None,
             config,
             is_recording: false,
             last_measurement_time: -999999.0,
             csv_file: None,
+            probe_file: None,
             measurement_count: 0,
             resolved_path: None,
         }
@@ -109,6 +205,46 @@ impl ManualMeasurementRecorder {
         &self.config
     }
 
+    /// Returns true if the simulation should auto-pause at `simulation_time_fs`.
+    ///
+    /// Without `auto_resume_after_fs` the pause target stays put, so every later
+    /// step pauses again. With it, the next target moves one offset past the
+    /// current one and a resume is queued for [`Self::take_auto_resume`].
+    /// Either way the first trigger for each target makes the next `update`
+    /// record a measurement at the pause.
+    pub fn check_auto_pause(&mut self, simulation_time_fs: f32) -> bool {
+        let Some(target) = self.next_auto_pause_fs else {
+            return false;
+        };
+        if simulation_time_fs < target {
+            return false;
+        }
+        if self.sampled_pause_fs != Some(target) {
+            // Force a sample on the paused frame
+            self.sampled_pause_fs = Some(target);
+            self.last_measurement_time = f32::NEG_INFINITY;
+        }
+        if let Some(offset) = self.config.auto_resume_after_fs.filter(|o| *o > 0.0) {
+            let mut next = target + offset;
+            while next <= simulation_time_fs {
+                next += offset;
+            }
+            self.next_auto_pause_fs = Some(next);
+            self.auto_resume_pending = true;
+        }
+        true
+    }
+
+    /// Take a queued auto-resume request, if any.
+    pub fn take_auto_resume(&mut self) -> bool {
+        std::mem::take(&mut self.auto_resume_pending)
+    }
+
+    /// Simulation time (fs) of the next scheduled auto-pause.
+    pub fn next_auto_pause_fs(&self) -> Option<f32> {
+        self.next_auto_pause_fs
+    }
+
     /// Start recording measurements to CSV
     pub fn start_recording(
         &mut self,
@@ -138,11 +274,28 @@ impl ManualMeasurementRecorder {
         writeln!(file)?;
 
         self.csv_file = Some(file);
+        self.probe_file = if self.config.probe_points.is_empty() {
+            None
+        } else {
+            let probe_path = probe_csv_path(&file_path);
+            let mut probe_file = File::create(&probe_path)?;
+            writeln!(probe_file, "{}", probe_points_header(&self.config.probe_points))?;
+            crate::sim_log!(
+                Diagnostics,
+                Info,
+                "✓ Recording {} probe points to: {}",
+                self.config.probe_points.len(),
+                probe_path.display()
+            );
+            Some(probe_file)
+        };
         self.is_recording = true;
         self.last_measurement_time = simulation_time_fs - self.config.interval_fs; // Ensure first measurement happens
         self.measurement_count = 0;
 
-        println!(
+        crate::sim_log!(
+            Diagnostics,
+            Info,
             "✓ Started recording measurements to: {}",
             file_path.display()
         );
@@ -156,8 +309,11 @@ impl ManualMeasurementRecorder {
         }
 
         self.csv_file = None;
+        self.probe_file = None;
         self.is_recording = false;
-        println!(
+        crate::sim_log!(
+            Diagnostics,
+            Info,
             "✓ Stopped recording. Total measurements: {}",
             self.measurement_count
         );
@@ -237,6 +393,17 @@ impl ManualMeasurementRecorder {
                     let _ = file.flush();
                     self.measurement_count += 1;
                 }
+                if let Some(file) = &mut self.probe_file {
+                    let row = probe_points_row(
+                        frame,
+                        simulation_time_fs,
+                        bodies,
+                        &self.config.probe_points,
+                        self.config.probe_radius,
+                    );
+                    let _ = writeln!(file, "{}", row);
+                    let _ = file.flush();
+                }
             }
         }
 
@@ -397,6 +564,15 @@ impl ManualMeasurementRecorder {
     }
 }
 
+/// `<stem>_probes.csv` next to the point-based CSV.
+fn probe_csv_path(points_path: &Path) -> PathBuf {
+    let stem = points_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("manual_measurements");
+    points_path.with_file_name(format!("{}_probes.csv", stem))
+}
+
 /// Compute set of indices of metal bodies connected to the specified foil via contact connections.
 fn bfs_connected_metals_for_foil(
     foil_id: u64,
@@ -542,4 +718,52 @@ mod tests {
 
         cleanup(prefix);
     }
+
+    #[test]
+    fn auto_pause_forces_one_sample_per_target() {
+        let config = ManualMeasurementConfig {
+            auto_pause_time_fs: Some(100.0),
+            ..Default::default()
+        };
+        let mut recorder = ManualMeasurementRecorder::new(config);
+
+        assert!(!recorder.check_auto_pause(50.0));
+        assert!(recorder.check_auto_pause(100.0));
+        assert_eq!(recorder.last_measurement_time, f32::NEG_INFINITY);
+
+        // Still paused on later steps, but the regular interval applies again
+        recorder.last_measurement_time = 100.0;
+        assert!(recorder.check_auto_pause(105.0));
+        assert_eq!(recorder.last_measurement_time, 100.0);
+    }
+
+    #[test]
+    fn probe_header_enumerates_points_and_row_width_matches() {
+        let points = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(20.0, 0.0),
+            Vec2::new(-20.0, 5.0),
+        ];
+        let header = probe_points_header(&points);
+        let columns: Vec<&str> = header.split(',').collect();
+        assert_eq!(columns.len(), 2 + points.len() * (3 + PROBE_COUNT_COLUMNS.len()));
+        for i in 0..points.len() {
+            assert!(columns.contains(&format!("p{i}_rho").as_str()));
+            assert!(columns.contains(&format!("p{i}_n_solvent").as_str()));
+        }
+
+        let bodies = vec![
+            Body::new(Vec2::new(1.0, 0.0), Vec2::zero(), 1.0, 1.0, 1.0, Species::LithiumIon),
+            Body::new(Vec2::new(21.0, 0.0), Vec2::zero(), 1.0, 1.0, -1.0, Species::ElectrolyteAnion),
+        ];
+        let row = probe_points_row(7, 12.5, &bodies, &points, 5.0);
+        let cells: Vec<&str> = row.split(',').collect();
+        assert_eq!(cells.len(), columns.len());
+        // Point 0 sees the Li+ only, point 1 the anion only
+        let col = |name: &str| columns.iter().position(|c| *c == name).unwrap();
+        assert_eq!(cells[col("p0_n_li_ion")], "1");
+        assert_eq!(cells[col("p0_n_anion")], "0");
+        assert_eq!(cells[col("p1_n_anion")], "1");
+        assert_eq!(cells[col("p2_n_li_ion")], "0");
+    }
 }
@@ -1,6 +1,7 @@
+use crate::renderer::MeasurementRecord;
 use std::collections::{HashMap, HashSet};
-use std::io;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 #[derive(Default)]
 pub struct MeasurementCsv {
@@ -38,3 +39,110 @@ impl MeasurementCsv {
         self.header_written.contains(key)
     }
 }
+
+/// Column header for manual measurement history exports.
+pub const MEASUREMENT_HISTORY_HEADER: &str =
+    "step,time_fs,distance,view_scale,switch_step,switch_mode,switch_value,pos_role,neg_role";
+
+/// Context written as `#` comment lines above an exported measurement history.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MeasurementMetadata {
+    /// Full domain width (Å)
+    pub domain_width: f32,
+    /// Full domain height (Å)
+    pub domain_height: f32,
+}
+
+/// One CSV row for a manual measurement.
+pub fn measurement_history_row(record: &MeasurementRecord) -> String {
+    format!(
+        "{},{:.6},{:.6},{:.6},{},{},{},{},{}",
+        record.step,
+        record.time_fs,
+        record.distance,
+        record.view_scale,
+        record.switch_step.map(|v| v.to_string()).unwrap_or_default(),
+        record.switch_mode.clone().unwrap_or_default(),
+        record.switch_value.map(|v| format!("{:.6}", v)).unwrap_or_default(),
+        record.pos_role.clone().unwrap_or_default(),
+        record.neg_role.clone().unwrap_or_default(),
+    )
+}
+
+/// Header plus one row per record, without metadata (clipboard format).
+pub fn measurement_history_csv(records: &[MeasurementRecord]) -> String {
+    let mut csv = format!("{}\n", MEASUREMENT_HISTORY_HEADER);
+    for record in records {
+        csv.push_str(&measurement_history_row(record));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Write the measurement history with metadata comment lines. An empty
+/// history still produces the metadata and column header.
+pub fn write_measurement_history(
+    path: &Path,
+    records: &[MeasurementRecord],
+    metadata: MeasurementMetadata,
+) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = io::BufWriter::new(std::fs::File::create(path)?);
+    writeln!(file, "# domain_width_A={:.3}", metadata.domain_width)?;
+    writeln!(file, "# domain_height_A={:.3}", metadata.domain_height)?;
+    writeln!(file, "# records={}", records.len())?;
+    write!(file, "{}", measurement_history_csv(records))?;
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(step: usize, time_fs: f32, distance: f32) -> MeasurementRecord {
+        MeasurementRecord {
+            step,
+            time_fs,
+            distance,
+            view_scale: 150.0,
+            switch_step: None,
+            switch_mode: None,
+            switch_value: None,
+            pos_role: None,
+            neg_role: None,
+        }
+    }
+
+    #[test]
+    fn exported_rows_match_records() {
+        let records = vec![record(10, 50.0, 12.5), record(250, 1250.0, 13.75)];
+        let metadata = MeasurementMetadata {
+            domain_width: 300.0,
+            domain_height: 200.0,
+        };
+        let path = std::env::temp_dir()
+            .join(format!("measurement_history_{}.csv", std::process::id()));
+        write_measurement_history(&path, &records, metadata).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(text.contains("# domain_width_A=300.000"));
+        let rows: Vec<&str> = text.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(rows[0], MEASUREMENT_HISTORY_HEADER);
+        assert_eq!(rows.len(), records.len() + 1);
+        for (row, rec) in rows[1..].iter().zip(&records) {
+            let fields: Vec<&str> = row.split(',').collect();
+            assert_eq!(fields[0].parse::<usize>().unwrap(), rec.step);
+            assert_eq!(fields[1].parse::<f32>().unwrap(), rec.time_fs);
+            assert_eq!(fields[2].parse::<f32>().unwrap(), rec.distance);
+        }
+
+        // An empty history still yields metadata and the column header
+        write_measurement_history(&path, &[], metadata).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(text.lines().last(), Some(MEASUREMENT_HISTORY_HEADER));
+    }
+}
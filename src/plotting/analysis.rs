@@ -3,10 +3,256 @@
 
 use crate::body::foil::Foil;
 use crate::body::{Body, Species};
+use crate::cell_list::CellList;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use ultraviolet::Vec2;
 
-/// Calculate concentration map of particles in a 2D grid
+/// Grid dimensions `(nx, ny)` for a domain of half extents
+/// `domain_width` x `domain_height` and a target square cell of `cell_size`.
+/// Each axis is binned separately so elongated domains keep near-square cells.
+pub fn concentration_grid_dims(domain_width: f32, domain_height: f32, cell_size: f32) -> (usize, usize) {
+    let cell = cell_size.max(1e-3);
+    let nx = ((2.0 * domain_width) / cell).round().max(1.0) as usize;
+    let ny = ((2.0 * domain_height) / cell).round().max(1.0) as usize;
+    (nx, ny)
+}
+
+/// Number density (bodies per Å²) of one species on a 2D grid spanning the
+/// domain. `values` is row-major with `nx` columns.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConcentrationMap {
+    pub nx: usize,
+    pub ny: usize,
+    /// Actual cell extents (Å), close to the requested cell size
+    pub cell_width: f32,
+    pub cell_height: f32,
+    pub values: Vec<f32>,
+}
+
+impl ConcentrationMap {
+    pub fn value(&self, ix: usize, iy: usize) -> f32 {
+        self.values[iy * self.nx + ix]
+    }
+
+    /// World-space centre of cell `(ix, iy)`; the grid is centred on the origin.
+    pub fn cell_center(&self, ix: usize, iy: usize) -> Vec2 {
+        Vec2::new(
+            (ix as f32 + 0.5 - 0.5 * self.nx as f32) * self.cell_width,
+            (iy as f32 + 0.5 - 0.5 * self.ny as f32) * self.cell_height,
+        )
+    }
+
+    /// Grid description for `PlotData::metadata`, written into export headers.
+    pub fn metadata(&self) -> HashMap<String, String> {
+        HashMap::from([
+            ("Grid NX".to_string(), self.nx.to_string()),
+            ("Grid NY".to_string(), self.ny.to_string()),
+            ("Cell Width (A)".to_string(), format!("{:.3}", self.cell_width)),
+            ("Cell Height (A)".to_string(), format!("{:.3}", self.cell_height)),
+        ])
+    }
+}
+
+/// Calculate concentration map of `species` in a 2D grid whose bin counts
+/// follow the domain aspect ratio (see `concentration_grid_dims`)
+pub fn calculate_concentration_map(
+    bodies: &[Body],
+    species: Species,
+    domain_width: f32,
+    domain_height: f32,
+    cell_size: f32,
+) -> ConcentrationMap {
+    let (nx, ny) = concentration_grid_dims(domain_width, domain_height, cell_size);
+    let cell_width = 2.0 * domain_width / nx as f32;
+    let cell_height = 2.0 * domain_height / ny as f32;
+    let mut values = vec![0.0; nx * ny];
+
+    for body in bodies.iter().filter(|b| b.species == species) {
+        let fx = (body.pos.x + domain_width) / cell_width;
+        let fy = (body.pos.y + domain_height) / cell_height;
+        if fx >= 0.0 && fy >= 0.0 && fx < nx as f32 && fy < ny as f32 {
+            values[fy as usize * nx + fx as usize] += 1.0;
+        }
+    }
+    let cell_area = cell_width * cell_height;
+    if cell_area > 0.0 {
+        for v in &mut values {
+            *v /= cell_area;
+        }
+    }
+
+    ConcentrationMap {
+        nx,
+        ny,
+        cell_width,
+        cell_height,
+        values,
+    }
+}
+
+/// Running per-bin sums of a spatial profile over successive frames, for a
+/// time-averaged profile with per-bin spread. Accumulation stops once
+/// `window_frames` frames are in (0 = no limit). A frame with a different bin
+/// layout restarts the average.
+#[derive(Clone, Debug, Default)]
+pub struct ProfileAccumulator {
+    pub window_frames: usize,
+    pub positions: Vec<f64>,
+    pub frames: usize,
+    sum: Vec<f64>,
+    sum_sq: Vec<f64>,
+}
+
+impl ProfileAccumulator {
+    pub fn new(window_frames: usize) -> Self {
+        Self {
+            window_frames,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.window_frames > 0 && self.frames >= self.window_frames
+    }
+
+    /// Add one frame's profile (`positions` are the bin centres).
+    pub fn add_frame(&mut self, positions: &[f64], values: &[f64]) {
+        if self.is_complete() || positions.len() != values.len() {
+            return;
+        }
+        if positions != self.positions.as_slice() {
+            self.positions = positions.to_vec();
+            self.sum = vec![0.0; values.len()];
+            self.sum_sq = vec![0.0; values.len()];
+            self.frames = 0;
+        }
+        for (i, &v) in values.iter().enumerate() {
+            self.sum[i] += v;
+            self.sum_sq[i] += v * v;
+        }
+        self.frames += 1;
+    }
+
+    pub fn mean(&self) -> Vec<f64> {
+        let n = self.frames.max(1) as f64;
+        self.sum.iter().map(|s| s / n).collect()
+    }
+
+    /// Per-bin population standard deviation across the accumulated frames.
+    pub fn std_dev(&self) -> Vec<f64> {
+        let n = self.frames.max(1) as f64;
+        self.sum
+            .iter()
+            .zip(&self.sum_sq)
+            .map(|(s, sq)| {
+                let mean = s / n;
+                (sq / n - mean * mean).max(0.0).sqrt()
+            })
+            .collect()
+    }
+
+    /// Per-bin standard error of the averaged value, `std_dev / sqrt(frames)`.
+    pub fn std_error(&self) -> Vec<f64> {
+        let root_n = (self.frames.max(1) as f64).sqrt();
+        self.std_dev().into_iter().map(|s| s / root_n).collect()
+    }
+}
+
+/// Mean squared displacement per species from a fixed time origin. The first
+/// `record` call samples up to `max_per_species` bodies of each species and
+/// stores their positions; later calls average |r(t) - r(0)|² over the
+/// sampled bodies that still exist (deleted or converted bodies drop out).
+#[derive(Clone, Debug)]
+pub struct MsdCollector {
+    pub max_per_species: usize,
+    origin_time: Option<f32>,
+    origins: HashMap<u64, (Species, Vec2)>,
+    /// `(elapsed, msd)` series per species
+    pub series: HashMap<Species, Vec<(f32, f32)>>,
+}
+
+impl Default for MsdCollector {
+    fn default() -> Self {
+        Self::new(200)
+    }
+}
+
+impl MsdCollector {
+    pub fn new(max_per_species: usize) -> Self {
+        Self {
+            max_per_species,
+            origin_time: None,
+            origins: HashMap::new(),
+            series: HashMap::new(),
+        }
+    }
+
+    /// Forget the origin; the next `record` starts a new measurement.
+    pub fn reset(&mut self) {
+        self.origin_time = None;
+        self.origins.clear();
+        self.series.clear();
+    }
+
+    pub fn record(&mut self, bodies: &[Body], time: f32) {
+        let Some(origin_time) = self.origin_time else {
+            let mut per_species: HashMap<Species, usize> = HashMap::new();
+            for body in bodies {
+                let n = per_species.entry(body.species).or_insert(0);
+                if *n < self.max_per_species {
+                    *n += 1;
+                    self.origins.insert(body.id, (body.species, body.pos));
+                }
+            }
+            self.origin_time = Some(time);
+            return;
+        };
+        let mut sums: HashMap<Species, (f32, usize)> = HashMap::new();
+        for body in bodies {
+            if let Some(&(species, start)) = self.origins.get(&body.id) {
+                if species == body.species {
+                    let entry = sums.entry(species).or_insert((0.0, 0));
+                    entry.0 += (body.pos - start).mag_sq();
+                    entry.1 += 1;
+                }
+            }
+        }
+        let elapsed = time - origin_time;
+        for (species, (sum, n)) in sums {
+            self.series
+                .entry(species)
+                .or_default()
+                .push((elapsed, sum / n as f32));
+        }
+    }
+
+    /// Most recent MSD (Å²) of `species`.
+    pub fn latest(&self, species: Species) -> Option<f32> {
+        self.series.get(&species)?.last().map(|&(_, msd)| msd)
+    }
+
+    /// 2D diffusion coefficient `slope / 4` from a least-squares line through
+    /// the later half of the series, skipping the early ballistic regime.
+    pub fn diffusion_coefficient(&self, species: Species) -> Option<f32> {
+        let series = self.series.get(&species)?;
+        let tail = &series[series.len() / 2..];
+        if tail.len() < 2 {
+            return None;
+        }
+        let n = tail.len() as f64;
+        let mean_t = tail.iter().map(|&(t, _)| t as f64).sum::<f64>() / n;
+        let mean_m = tail.iter().map(|&(_, m)| m as f64).sum::<f64>() / n;
+        let (mut cov, mut var) = (0.0, 0.0);
+        for &(t, m) in tail {
+            let dt = t as f64 - mean_t;
+            cov += dt * (m as f64 - mean_m);
+            var += dt * dt;
+        }
+        (var > 0.0).then(|| (cov / var / 4.0) as f32)
+    }
+}
+
 /// Calculate species population counts
 pub fn calculate_species_populations(bodies: &[Body]) -> HashMap<Species, usize> {
     let mut populations = HashMap::new();
@@ -18,6 +264,116 @@ pub fn calculate_species_populations(bodies: &[Body]) -> HashMap<Species, usize>
     populations
 }
 
+/// Split the `species` count into `(interface, bulk)`: a body is interfacial
+/// when its center lies within `band_width` of any body of any foil, so the
+/// bands of several electrodes are unioned. With no foils everything is bulk.
+pub fn interface_bulk_counts(
+    bodies: &[Body],
+    foils: &[Foil],
+    band_width: f32,
+    species: Species,
+) -> (usize, usize) {
+    let foil_ids: std::collections::HashSet<u64> =
+        foils.iter().flat_map(|f| f.body_ids.iter().copied()).collect();
+    let electrode: Vec<Vec2> = bodies
+        .iter()
+        .filter(|b| foil_ids.contains(&b.id))
+        .map(|b| b.pos)
+        .collect();
+    let band_sq = band_width.max(0.0).powi(2);
+    bodies
+        .iter()
+        .filter(|b| b.species == species && !foil_ids.contains(&b.id))
+        .fold((0, 0), |(interface, bulk), b| {
+            if electrode.iter().any(|p| (b.pos - *p).mag_sq() <= band_sq) {
+                (interface + 1, bulk)
+            } else {
+                (interface, bulk + 1)
+            }
+        })
+}
+
+/// Nearest-neighbour distance statistics `(min, mean, max)` over the bodies
+/// matching `species_filter` (all bodies when `None`). Only neighbours of the
+/// same selection are considered. Uses a cell list sized to the mean spacing
+/// and widens the search radius until a neighbour is found. Returns NaN for
+/// all three values when fewer than two bodies are selected.
+pub fn nearest_neighbor_stats(bodies: &[Body], species_filter: Option<Species>) -> (f32, f32, f32) {
+    let selected: Vec<usize> = bodies
+        .iter()
+        .enumerate()
+        .filter(|(_, b)| species_filter.map_or(true, |s| b.species == s))
+        .map(|(i, _)| i)
+        .collect();
+    if selected.len() < 2 {
+        return (f32::NAN, f32::NAN, f32::NAN);
+    }
+
+    let mut half_w = 1.0f32;
+    let mut half_h = 1.0f32;
+    for &i in &selected {
+        half_w = half_w.max(bodies[i].pos.x.abs());
+        half_h = half_h.max(bodies[i].pos.y.abs());
+    }
+    let mean_spacing = (4.0 * half_w * half_h / selected.len() as f32).sqrt().max(1e-3);
+    let mut cells = CellList::new(half_w, half_h, mean_spacing);
+    cells.rebuild(bodies);
+    let max_search = 2.0 * (half_w * half_w + half_h * half_h).sqrt() + mean_spacing;
+
+    let mut min = f32::INFINITY;
+    let mut max = 0.0f32;
+    let mut sum = 0.0f32;
+    for &i in &selected {
+        let mut cutoff = mean_spacing;
+        let nearest = loop {
+            let best = cells
+                .find_neighbors_within(bodies, i, cutoff)
+                .into_iter()
+                .filter(|&j| species_filter.map_or(true, |s| bodies[j].species == s))
+                .map(|j| (bodies[j].pos - bodies[i].pos).mag())
+                .fold(f32::INFINITY, f32::min);
+            if best.is_finite() || cutoff >= max_search {
+                break best;
+            }
+            cutoff *= 2.0;
+        };
+        min = min.min(nearest);
+        max = max.max(nearest);
+        sum += nearest;
+    }
+    (min, sum / selected.len() as f32, max)
+}
+
+/// Bodies within `radius` of the body with id `probe_id`, as
+/// `(id, species, distance)` sorted by increasing distance. Returns `None`
+/// when no body has that id (e.g. the probe was deleted).
+pub fn probe_neighbors(
+    bodies: &[Body],
+    probe_id: u64,
+    radius: f32,
+) -> Option<Vec<(u64, Species, f32)>> {
+    let probe = bodies.iter().position(|b| b.id == probe_id)?;
+    let radius = radius.max(1e-3);
+    let mut half_w = 1.0f32;
+    let mut half_h = 1.0f32;
+    for b in bodies {
+        half_w = half_w.max(b.pos.x.abs());
+        half_h = half_h.max(b.pos.y.abs());
+    }
+    let mut cells = CellList::new(half_w, half_h, radius);
+    cells.rebuild(bodies);
+    let mut neighbors: Vec<(u64, Species, f32)> = cells
+        .find_neighbors_within(bodies, probe, radius)
+        .into_iter()
+        .map(|j| {
+            let b = &bodies[j];
+            (b.id, b.species, (b.pos - bodies[probe].pos).mag())
+        })
+        .collect();
+    neighbors.sort_by(|a, b| a.2.total_cmp(&b.2));
+    Some(neighbors)
+}
+
 /// Calculate charge distribution along an axis
 pub fn calculate_charge_distribution(
     bodies: &[Body],
@@ -247,6 +603,101 @@ pub fn calculate_cell_voltage(bodies: &[Body], foils: &[Foil], coulomb_constant:
     v_pos - v_neg
 }
 
+/// Square-cell grid for `coarse_grain`, laid out like the density overlay
+/// (`domain_grid`): lower-left origin at `(-domain_width, -domain_height)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContinuumGrid {
+    /// Half extents of the domain (Å)
+    pub domain_width: f32,
+    pub domain_height: f32,
+    /// Cell edge (Å)
+    pub spacing: f32,
+}
+
+/// Coarse-grained continuum fields on a `ContinuumGrid`, row-major with `nx`
+/// columns. Empty cells hold zeros.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContinuumFields {
+    pub nx: usize,
+    pub ny: usize,
+    pub origin: Vec2,
+    pub spacing: f32,
+    /// Bodies per Å²
+    pub number_density: Vec<f32>,
+    /// Charge (e) per Å²
+    pub charge_density: Vec<f32>,
+    /// Mean velocity of the bodies in each cell (Å/fs)
+    pub mean_velocity: Vec<Vec2>,
+}
+
+impl ContinuumFields {
+    pub fn cell_center(&self, ix: usize, iy: usize) -> Vec2 {
+        self.origin + Vec2::new(ix as f32 + 0.5, iy as f32 + 0.5) * self.spacing
+    }
+
+    /// Write one row per cell (`x,y,ix,iy,number_density,charge_density,vx,vy`)
+    /// below `#` lines describing the grid and snapshot time.
+    pub fn write_csv(&self, path: &std::path::Path, time_fs: f32) -> std::io::Result<()> {
+        use std::io::Write;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        writeln!(file, "# time_fs={:.3}", time_fs)?;
+        writeln!(file, "# nx={} ny={} spacing_A={:.4}", self.nx, self.ny, self.spacing)?;
+        writeln!(file, "# origin_A={:.4},{:.4}", self.origin.x, self.origin.y)?;
+        writeln!(file, "x,y,ix,iy,number_density,charge_density,vx,vy")?;
+        for iy in 0..self.ny {
+            for ix in 0..self.nx {
+                let i = iy * self.nx + ix;
+                let c = self.cell_center(ix, iy);
+                let v = self.mean_velocity[i];
+                writeln!(
+                    file,
+                    "{:.4},{:.4},{},{},{:.6e},{:.6e},{:.6e},{:.6e}",
+                    c.x, c.y, ix, iy, self.number_density[i], self.charge_density[i], v.x, v.y
+                )?;
+            }
+        }
+        file.flush()
+    }
+}
+
+/// Bin `bodies` into number density, charge density and mean velocity on
+/// `grid`. Bodies outside the grid are skipped.
+pub fn coarse_grain(bodies: &[Body], grid: &ContinuumGrid) -> ContinuumFields {
+    let spacing = grid.spacing.max(1e-3);
+    let (origin, nx, ny) =
+        crate::renderer::draw::density::domain_grid(grid.domain_width, grid.domain_height, spacing);
+    let mut counts = vec![0u32; nx * ny];
+    let mut charge = vec![0.0f32; nx * ny];
+    let mut momentum = vec![Vec2::zero(); nx * ny];
+    for body in bodies {
+        let f = (body.pos - origin) / spacing;
+        if !(f.x >= 0.0 && f.y >= 0.0 && f.x < nx as f32 && f.y < ny as f32) {
+            continue;
+        }
+        let i = f.y as usize * nx + f.x as usize;
+        counts[i] += 1;
+        charge[i] += body.charge;
+        momentum[i] += body.vel;
+    }
+    let area = spacing * spacing;
+    ContinuumFields {
+        nx,
+        ny,
+        origin,
+        spacing,
+        number_density: counts.iter().map(|&n| n as f32 / area).collect(),
+        charge_density: charge.iter().map(|&q| q / area).collect(),
+        mean_velocity: counts
+            .iter()
+            .zip(&momentum)
+            .map(|(&n, &p)| if n > 0 { p / n as f32 } else { Vec2::zero() })
+            .collect(),
+    }
+}
+
 /// Calculate electric potential distribution along an axis (spatial profile).
 /// Samples potential at bin centers, averaging across perpendicular axis.
 pub fn calculate_potential_distribution(
@@ -295,3 +746,199 @@ pub fn calculate_potential_distribution(
 
     potentials
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lattice(n: usize, spacing: f32, species: Species) -> Vec<Body> {
+        let mut bodies = Vec::new();
+        for i in 0..n {
+            for j in 0..n {
+                let pos = Vec2::new(i as f32 * spacing, j as f32 * spacing)
+                    - Vec2::broadcast(0.5 * (n - 1) as f32 * spacing);
+                bodies.push(Body::new(pos, Vec2::zero(), 1.0, 1.0, 0.0, species));
+            }
+        }
+        bodies
+    }
+
+    #[test]
+    fn square_lattice_has_uniform_spacing() {
+        let bodies = lattice(6, 3.0, Species::LithiumMetal);
+        let (min, mean, max) = nearest_neighbor_stats(&bodies, None);
+        for v in [min, mean, max] {
+            assert!((v - 3.0).abs() < 1e-4, "got {}", v);
+        }
+    }
+
+    #[test]
+    fn species_filter_and_single_particle() {
+        let mut bodies = lattice(4, 2.0, Species::EC);
+        bodies.push(Body::new(Vec2::zero(), Vec2::zero(), 1.0, 1.0, 0.0, Species::LithiumIon));
+        let (min, mean, max) = nearest_neighbor_stats(&bodies, Some(Species::EC));
+        assert!((min - 2.0).abs() < 1e-4 && (mean - 2.0).abs() < 1e-4 && (max - 2.0).abs() < 1e-4);
+
+        let (min, mean, max) = nearest_neighbor_stats(&bodies, Some(Species::LithiumIon));
+        assert!(min.is_nan() && mean.is_nan() && max.is_nan());
+    }
+
+    #[test]
+    fn probe_neighbors_sorted_and_missing_probe() {
+        let bodies = lattice(5, 2.0, Species::EC);
+        let center = bodies[12].id;
+        let found = probe_neighbors(&bodies, center, 3.0).unwrap();
+        // Four edge neighbours at 2.0 and four diagonal ones at 2.83
+        assert_eq!(found.len(), 8);
+        assert!(found.iter().all(|&(id, _, _)| id != center));
+        assert!(found.windows(2).all(|w| w[0].2 <= w[1].2));
+        assert!((found[0].2 - 2.0).abs() < 1e-4);
+        assert!(probe_neighbors(&bodies, u64::MAX, 3.0).is_none());
+    }
+
+    #[test]
+    fn concentration_grid_follows_aspect_ratio() {
+        // 2:1 domain (half extents 100 x 50) with 10 Å cells
+        assert_eq!(concentration_grid_dims(100.0, 50.0, 10.0), (20, 10));
+
+        let bodies = vec![
+            Body::new(Vec2::new(-95.0, -45.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::LithiumIon),
+            Body::new(Vec2::new(95.0, 45.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::LithiumIon),
+            Body::new(Vec2::new(95.0, 45.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::EC),
+        ];
+        let map = calculate_concentration_map(&bodies, Species::LithiumIon, 100.0, 50.0, 10.0);
+        assert_eq!(map.nx, 2 * map.ny);
+        assert!((map.cell_width - map.cell_height).abs() < 1e-4);
+        assert!((map.value(0, 0) - 0.01).abs() < 1e-6);
+        assert!((map.value(19, 9) - 0.01).abs() < 1e-6);
+        assert_eq!(map.values.iter().filter(|&&v| v > 0.0).count(), 2);
+        assert_eq!(map.metadata()["Grid NX"], "20");
+        assert!((map.cell_center(0, 0) - Vec2::new(-95.0, -45.0)).mag() < 1e-4);
+        assert!((map.cell_center(19, 9) - Vec2::new(95.0, 45.0)).mag() < 1e-4);
+    }
+
+    #[test]
+    fn averaging_stationary_profile_shrinks_error() {
+        let positions = [-1.0, 0.0, 1.0];
+        let truth = [2.0, 5.0, 3.0];
+        // Deterministic +-1 noise around a fixed profile
+        let frame = |k: usize| -> Vec<f64> {
+            truth
+                .iter()
+                .enumerate()
+                .map(|(i, t)| t + if (k + i) % 2 == 0 { 1.0 } else { -1.0 })
+                .collect()
+        };
+        let mut short = ProfileAccumulator::new(4);
+        let mut long = ProfileAccumulator::new(400);
+        for k in 0..1000 {
+            short.add_frame(&positions, &frame(k));
+            long.add_frame(&positions, &frame(k));
+        }
+        assert_eq!(short.frames, 4);
+        assert!(long.is_complete() && long.frames == 400);
+        for i in 0..3 {
+            assert!((long.mean()[i] - truth[i]).abs() < 1e-9);
+            assert!((long.std_dev()[i] - 1.0).abs() < 1e-9);
+            assert!(long.std_error()[i] < short.std_error()[i] / 5.0);
+            assert!(long.std_error()[i] < 0.06);
+        }
+
+        // A noise-free profile has zero spread; a new bin layout restarts
+        let mut still = ProfileAccumulator::new(0);
+        for _ in 0..10 {
+            still.add_frame(&positions, &truth);
+        }
+        assert!(still.std_dev().iter().all(|&s| s < 1e-9));
+        still.add_frame(&[0.0, 1.0], &[1.0, 1.0]);
+        assert_eq!(still.frames, 1);
+    }
+
+    #[test]
+    fn msd_is_quadratic_when_ballistic_and_linear_for_random_walk() {
+        let mut ballistic = MsdCollector::new(10);
+        let mut body = Body::new(Vec2::zero(), Vec2::new(2.0, 0.0), 1.0, 1.0, 0.0, Species::LithiumIon);
+        for k in 0..=20 {
+            body.pos = body.vel * k as f32;
+            ballistic.record(std::slice::from_ref(&body), k as f32);
+        }
+        let series = &ballistic.series[&Species::LithiumIon];
+        let at = |t: f32| series.iter().find(|&&(e, _)| e == t).unwrap().1;
+        assert!((at(10.0) - 400.0).abs() < 1e-3);
+        assert!((at(20.0) / at(10.0) - 4.0).abs() < 1e-4, "MSD ∝ t²");
+
+        // Unit lattice steps in x and y: MSD = 2t, D = 0.5
+        let mut rng = fastrand::Rng::with_seed(7);
+        let mut walkers: Vec<Body> = (0..1000)
+            .map(|_| Body::new(Vec2::zero(), Vec2::zero(), 1.0, 1.0, 0.0, Species::EC))
+            .collect();
+        let mut walk = MsdCollector::new(1000);
+        walk.record(&walkers, 0.0);
+        for k in 1..=200 {
+            for b in &mut walkers {
+                b.pos.x += if rng.bool() { 1.0 } else { -1.0 };
+                b.pos.y += if rng.bool() { 1.0 } else { -1.0 };
+            }
+            // Deleted walkers drop out of the average
+            if k == 100 {
+                walkers.truncate(800);
+            }
+            walk.record(&walkers, k as f32);
+        }
+        let series = &walk.series[&Species::EC];
+        let at = |t: f32| series.iter().find(|&&(e, _)| e == t).unwrap().1;
+        assert!((at(200.0) / at(100.0) - 2.0).abs() < 0.3, "MSD ∝ t");
+        let d = walk.diffusion_coefficient(Species::EC).unwrap();
+        assert!((d - 0.5).abs() < 0.1, "D = {d}");
+        assert!(walk.latest(Species::LithiumIon).is_none());
+    }
+
+    #[test]
+    fn interface_band_is_the_union_of_all_foil_bands() {
+        let mut bodies = Vec::new();
+        let mut foils = Vec::new();
+        for x in [-50.0, 50.0] {
+            let metal =
+                Body::new(Vec2::new(x, 0.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::FoilMetal);
+            foils.push(Foil::new(vec![metal.id], Vec2::zero(), 1.0, 1.0, 0.0, 0.0));
+            bodies.push(metal);
+        }
+        // Within 5 Å of the left or right electrode, then two in the middle
+        let ion = |x: f32, species: Species| {
+            Body::new(Vec2::new(x, 0.0), Vec2::zero(), 1.0, 1.0, 0.0, species)
+        };
+        for x in [-46.0, 47.0, 54.0, 0.0, 20.0] {
+            bodies.push(ion(x, Species::LithiumIon));
+        }
+        bodies.push(ion(-49.0, Species::ElectrolyteAnion));
+
+        let split = |species| interface_bulk_counts(&bodies, &foils, 5.0, species);
+        assert_eq!(split(Species::LithiumIon), (3, 2));
+        assert_eq!(split(Species::ElectrolyteAnion), (1, 0));
+        // Foil bodies are the electrodes, not counted in their own bands
+        assert_eq!(split(Species::FoilMetal), (0, 0));
+        assert_eq!(interface_bulk_counts(&bodies, &[], 5.0, Species::LithiumIon), (0, 5));
+    }
+
+    #[test]
+    fn coarse_grained_density_integrates_to_body_count() {
+        let mut bodies = lattice(9, 7.0, Species::EC);
+        bodies[0].vel = Vec2::new(2.0, 0.0);
+        bodies[0].charge = 1.0;
+        let grid = ContinuumGrid {
+            domain_width: 40.0,
+            domain_height: 30.0,
+            spacing: 6.0,
+        };
+        let fields = coarse_grain(&bodies, &grid);
+        let area = grid.spacing * grid.spacing;
+        let total: f32 = fields.number_density.iter().sum::<f32>() * area;
+        assert!((total - bodies.len() as f32).abs() < 1e-3, "integrated {}", total);
+        let charge: f32 = fields.charge_density.iter().sum::<f32>() * area;
+        assert!((charge - 1.0).abs() < 1e-4);
+        // Empty cells are zero in every field
+        let empty = fields.number_density.iter().position(|&d| d == 0.0).unwrap();
+        assert_eq!(fields.mean_velocity[empty], Vec2::zero());
+        assert_eq!(fields.charge_density[empty], 0.0);
+    }
+}
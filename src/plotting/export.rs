@@ -1,7 +1,8 @@
 // plotting/export.rs
 // Data export functionality for plots
 
-use super::{ExportFormat, PlotData};
+use super::analysis::ProfileAccumulator;
+use super::{ExportFormat, PlotConfig, PlotData};
 use std::fs::File;
 use std::io::Write;
 
@@ -74,11 +75,30 @@ fn export_csv(data: &PlotData) -> Result<String, String> {
         super::PlotType::SpatialProfileX => content.push_str("X_Position,Value\n"),
         super::PlotType::SpatialProfileY => content.push_str("Y_Position,Value\n"),
         super::PlotType::TimeSeries => content.push_str("Time,Value\n"),
+        super::PlotType::SwitchTimeline => content.push_str("Time_fs,Step,Setpoint\n"),
+        super::PlotType::ConcentrationMap => content.push_str("X_Center,Y_Center,Density\n"),
+    }
+
+    // A map writes one row per grid cell instead of the 1D series
+    if let Some(map) = &data.concentration_map {
+        for iy in 0..map.ny {
+            for ix in 0..map.nx {
+                let center = map.cell_center(ix, iy);
+                content.push_str(&format!("{},{},{}\n", center.x, center.y, map.value(ix, iy)));
+            }
+        }
+        return Ok(content);
     }
 
     // Data rows
     for i in 0..data.x_data.len().min(data.y_data.len()) {
-        content.push_str(&format!("{},{}\n", data.x_data[i], data.y_data[i]));
+        match data.overlay.get(i) {
+            Some(overlay) => content.push_str(&format!(
+                "{},{},{}\n",
+                data.x_data[i], data.y_data[i], overlay
+            )),
+            None => content.push_str(&format!("{},{}\n", data.x_data[i], data.y_data[i])),
+        }
     }
 
     Ok(content)
@@ -92,3 +112,42 @@ fn export_tsv(data: &PlotData) -> Result<String, String> {
     let csv_content = export_csv(data)?;
     Ok(csv_content.replace(",", "\t"))
 }
+
+/// Write a time-averaged spatial profile as CSV with per-bin spread.
+pub fn export_averaged_profile(
+    config: &PlotConfig,
+    average: &ProfileAccumulator,
+) -> Result<String, String> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let filename = format!(
+        "plot_{}_avg_{}.csv",
+        config.title.replace(" ", "_"),
+        timestamp
+    );
+    let path = std::path::Path::new("plots").join(&filename);
+    std::fs::create_dir_all("plots").map_err(|e| format!("Failed to create directory: {}", e))?;
+    std::fs::write(&path, averaged_profile_csv(config, average))
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+fn averaged_profile_csv(config: &PlotConfig, average: &ProfileAccumulator) -> String {
+    let mut content = String::new();
+    content.push_str("# Time-Averaged Profile Export\n");
+    content.push_str(&format!("# Title: {}\n", config.title));
+    content.push_str(&format!("# Plot Type: {:?}\n", config.plot_type));
+    content.push_str(&format!("# Quantity: {:?}\n", config.quantity));
+    content.push_str(&format!("# Frames: {}\n", average.frames));
+    content.push('\n');
+    let axis = match config.plot_type {
+        super::PlotType::SpatialProfileY => "Y_Position",
+        _ => "X_Position",
+    };
+    content.push_str(&format!("{},Mean,StdDev,StdError\n", axis));
+    let (mean, std_dev, std_error) = (average.mean(), average.std_dev(), average.std_error());
+    for (i, x) in average.positions.iter().enumerate() {
+        content.push_str(&format!("{},{},{},{}\n", x, mean[i], std_dev[i], std_error[i]));
+    }
+    content
+}
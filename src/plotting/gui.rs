@@ -2,7 +2,9 @@
 // GUI controls for the plotting system
 
 use crate::body::Species;
-use crate::plotting::{ExportFormat, PlotConfig, PlotType, PlottingSystem, Quantity, SamplingMode};
+use crate::plotting::{
+    ExportFormat, LineStyle, PlotConfig, PlotType, PlottingSystem, Quantity, SamplingMode,
+};
 use quarkstrom::egui;
 
 pub fn show_plotting_controls(
@@ -38,6 +40,8 @@ pub fn show_plotting_controls(
                         spatial_bins: 50,
                         time_window: 20.0,
                         update_frequency: 2.0,
+                        y_log: false,
+                        line_style: LineStyle::Solid,
                     };
                     plotting_system.create_plot_window(config);
                 }
@@ -51,6 +55,8 @@ pub fn show_plotting_controls(
                         spatial_bins: 100,
                         time_window: 20.0,
                         update_frequency: 2.0,
+                        y_log: false,
+                        line_style: LineStyle::Solid,
                     };
                     plotting_system.create_plot_window(config);
                 }
@@ -64,6 +70,8 @@ pub fn show_plotting_controls(
                         spatial_bins: 100,
                         time_window: 10.0,
                         update_frequency: 1.0,
+                        y_log: false,
+                        line_style: LineStyle::Solid,
                     };
                     plotting_system.create_plot_window(config);
                 }
@@ -79,6 +87,8 @@ pub fn show_plotting_controls(
                         spatial_bins: 100,
                         time_window: 10.0,
                         update_frequency: 1.0,
+                        y_log: false,
+                        line_style: LineStyle::Solid,
                     };
                     plotting_system.create_plot_window(config);
                 }
@@ -104,6 +114,8 @@ pub fn show_plotting_controls(
                             spatial_bins: 50,
                             time_window: 30.0,
                             update_frequency: 2.0,
+                            y_log: false,
+                            line_style: LineStyle::Solid,
                         };
                         plotting_system.create_plot_window(config);
                     }
@@ -120,6 +132,8 @@ pub fn show_plotting_controls(
                         spatial_bins: 50,
                         time_window: 15.0,
                         update_frequency: 5.0,
+                        y_log: false,
+                        line_style: LineStyle::Solid,
                     };
                     plotting_system.create_plot_window(config);
                 }
@@ -133,6 +147,8 @@ pub fn show_plotting_controls(
                         spatial_bins: 80,
                         time_window: 10.0,
                         update_frequency: 1.0,
+                        y_log: false,
+                        line_style: LineStyle::Solid,
                     };
                     plotting_system.create_plot_window(config);
                 }
@@ -148,6 +164,8 @@ pub fn show_plotting_controls(
                         spatial_bins: 50,
                         time_window: 20.0,
                         update_frequency: 2.0,
+                        y_log: false,
+                        line_style: LineStyle::Solid,
                     };
                     plotting_system.create_plot_window(config);
                 }
@@ -161,6 +179,27 @@ pub fn show_plotting_controls(
                         spatial_bins: 100,
                         time_window: 10.0,
                         update_frequency: 1.0,
+                        y_log: false,
+                        line_style: LineStyle::Solid,
+                    };
+                    plotting_system.create_plot_window(config);
+                }
+
+                if ui
+                    .button("Switch Step Timeline")
+                    .on_hover_text("Needs 'Record switch timeline' in the Charging tab")
+                    .clicked()
+                {
+                    let config = PlotConfig {
+                        plot_type: PlotType::SwitchTimeline,
+                        quantity: Quantity::Charge,
+                        title: "Switch Step Timeline".to_string(),
+                        sampling_mode: SamplingMode::Continuous,
+                        spatial_bins: 50,
+                        time_window: 20.0,
+                        update_frequency: 2.0,
+                        y_log: false,
+                        line_style: LineStyle::Solid,
                     };
                     plotting_system.create_plot_window(config);
                 }
@@ -229,6 +268,16 @@ pub fn show_plotting_window(
                             PlotType::SpatialProfileY,
                             "Spatial Profile (Y)",
                         );
+                        ui.selectable_value(
+                            new_plot_type,
+                            PlotType::SwitchTimeline,
+                            "Switch Step Timeline",
+                        );
+                        ui.selectable_value(
+                            new_plot_type,
+                            PlotType::ConcentrationMap,
+                            "Concentration Map",
+                        );
                     });
             });
 
@@ -288,6 +337,8 @@ pub fn show_plotting_window(
                     spatial_bins: *new_plot_spatial_bins,
                     time_window: *new_plot_time_window,
                     update_frequency: *new_plot_update_frequency,
+                    y_log: false,
+                    line_style: LineStyle::Solid,
                 };
                 plotting_system.create_plot_window(config);
             }
@@ -304,7 +355,9 @@ fn show_quantity_selector(ui: &mut egui::Ui, quantity: &mut Quantity, plot_type:
     if !is_quantity_compatible_with_plot_type(quantity, plot_type) {
         // Reset to a safe default
         *quantity = match plot_type {
-            PlotType::TimeSeries => Quantity::TotalSpeciesCount(Species::LithiumIon),
+            PlotType::TimeSeries | PlotType::ConcentrationMap => {
+                Quantity::TotalSpeciesCount(Species::LithiumIon)
+            }
             _ => Quantity::Charge,
         };
     }
@@ -312,10 +365,12 @@ fn show_quantity_selector(ui: &mut egui::Ui, quantity: &mut Quantity, plot_type:
     egui::ComboBox::from_id_source("quantity")
         .selected_text(format!("{:?}", quantity))
         .show_ui(ui, |ui| {
-            // Always available quantities
-            ui.selectable_value(quantity, Quantity::Charge, "Charge");
-            ui.selectable_value(quantity, Quantity::ElectronCount, "Electron Count");
-            ui.selectable_value(quantity, Quantity::Velocity, "Velocity");
+            // Always available quantities (a concentration map only counts species)
+            if !matches!(plot_type, PlotType::ConcentrationMap) {
+                ui.selectable_value(quantity, Quantity::Charge, "Charge");
+                ui.selectable_value(quantity, Quantity::ElectronCount, "Electron Count");
+                ui.selectable_value(quantity, Quantity::Velocity, "Velocity");
+            }
 
             // Species-related quantities
             ui.selectable_value(
@@ -339,6 +394,35 @@ fn show_quantity_selector(ui: &mut egui::Ui, quantity: &mut Quantity, plot_type:
                 ui.selectable_value(quantity, Quantity::FoilCurrent(1), "Foil Current (ID 1)");
                 ui.selectable_value(quantity, Quantity::ElectronHopRate, "Electron Hop Rate");
                 ui.selectable_value(quantity, Quantity::CellVoltage, "Cell Voltage");
+                ui.selectable_value(
+                    quantity,
+                    Quantity::NearestNeighborDistance,
+                    "Mean Nearest-Neighbor Distance",
+                );
+                ui.selectable_value(
+                    quantity,
+                    Quantity::MeanSquaredDisplacement(Species::LithiumIon),
+                    "Li+ MSD",
+                );
+                ui.selectable_value(
+                    quantity,
+                    Quantity::MeanSquaredDisplacement(Species::ElectrolyteAnion),
+                    "Anion MSD",
+                );
+                for (species, name) in
+                    [(Species::LithiumIon, "Li+"), (Species::ElectrolyteAnion, "Anion")]
+                {
+                    ui.selectable_value(
+                        quantity,
+                        Quantity::InterfaceSpeciesCount(species),
+                        format!("{} at Interface", name),
+                    );
+                    ui.selectable_value(
+                        quantity,
+                        Quantity::BulkSpeciesCount(species),
+                        format!("{} in Bulk", name),
+                    );
+                }
             }
 
             // Spatial quantities only
@@ -361,9 +445,18 @@ fn show_quantity_selector(ui: &mut egui::Ui, quantity: &mut Quantity, plot_type:
 }
 
 fn is_quantity_compatible_with_plot_type(quantity: &Quantity, plot_type: &PlotType) -> bool {
+    if matches!(plot_type, PlotType::ConcentrationMap) {
+        return matches!(quantity, Quantity::TotalSpeciesCount(_));
+    }
     match quantity {
         // These quantities only make sense for time series
-        Quantity::FoilCurrent(_) | Quantity::ElectronHopRate | Quantity::CellVoltage => {
+        Quantity::FoilCurrent(_)
+        | Quantity::ElectronHopRate
+        | Quantity::CellVoltage
+        | Quantity::NearestNeighborDistance
+        | Quantity::MeanSquaredDisplacement(_)
+        | Quantity::InterfaceSpeciesCount(_)
+        | Quantity::BulkSpeciesCount(_) => {
             matches!(plot_type, PlotType::TimeSeries)
         }
         // These are only meaningful for spatial plots
@@ -397,19 +490,19 @@ fn show_active_plots(ui: &mut egui::Ui, plotting_system: &mut PlottingSystem) {
 
                 if ui.button("Export CSV").clicked() {
                     if let Ok(path) = plotting_system.export_data(&window_id, ExportFormat::CSV) {
-                        println!("Exported plot data to: {}", path);
+                        crate::sim_log!(Io, Info, "Exported plot data to: {}", path);
                     }
                 }
 
                 if ui.button("Export JSON").clicked() {
                     if let Ok(path) = plotting_system.export_data(&window_id, ExportFormat::JSON) {
-                        println!("Exported plot data to: {}", path);
+                        crate::sim_log!(Io, Info, "Exported plot data to: {}", path);
                     }
                 }
 
                 if ui.button("Export TSV").clicked() {
                     if let Ok(path) = plotting_system.export_data(&window_id, ExportFormat::TSV) {
-                        println!("Exported plot data to: {}", path);
+                        crate::sim_log!(Io, Info, "Exported plot data to: {}", path);
                     }
                 }
 
@@ -418,6 +511,93 @@ fn show_active_plots(ui: &mut egui::Ui, plotting_system: &mut PlottingSystem) {
                 }
             }
         });
+
+        // Diffusion estimate from the linear part of the MSD curve
+        let msd_species = match plotting_system.windows.get(&window_id).map(|w| &w.config.quantity) {
+            Some(Quantity::MeanSquaredDisplacement(species)) => Some(*species),
+            _ => None,
+        };
+        if let Some(species) = msd_species {
+            ui.horizontal(|ui| {
+                match plotting_system.msd.diffusion_coefficient(species) {
+                    Some(d) => ui.label(format!("    D ≈ {:.4e} Å²/fs", d)),
+                    None => ui.label("    D: collecting samples…"),
+                };
+                if ui
+                    .button("Reset Origin")
+                    .on_hover_text("Restart the MSD from the current positions")
+                    .clicked()
+                {
+                    plotting_system.reset_msd();
+                }
+            });
+        }
+
+        // Band width shared by every interface/bulk split window
+        let splits_interface = plotting_system.windows.get(&window_id).map_or(false, |w| {
+            matches!(
+                w.config.quantity,
+                Quantity::InterfaceSpeciesCount(_) | Quantity::BulkSpeciesCount(_)
+            )
+        });
+        if splits_interface {
+            ui.horizontal(|ui| {
+                ui.label("    Interface band");
+                ui.add(
+                    egui::DragValue::new(&mut plotting_system.interface_band_width)
+                        .speed(0.5)
+                        .clamp_range(0.0..=1000.0)
+                        .suffix(" Å"),
+                )
+                .on_hover_text("Bodies this close to any foil body count as interfacial");
+            });
+        }
+
+        // Time-averaged export for spatial profiles, separate from the live plot
+        let averaging = match plotting_system.windows.get(&window_id) {
+            Some(w)
+                if matches!(
+                    w.config.plot_type,
+                    PlotType::SpatialProfileX | PlotType::SpatialProfileY
+                ) =>
+            {
+                w.profile_average.as_ref().map(|a| (a.frames, a.window_frames))
+            }
+            _ => continue,
+        };
+        ui.horizontal(|ui| {
+            ui.label("    Average over");
+            ui.add(
+                egui::DragValue::new(&mut plotting_system.average_frames)
+                    .speed(10.0)
+                    .clamp_range(1..=100_000),
+            );
+            ui.label("frames");
+            match averaging {
+                None => {
+                    if ui.button("Start Averaging").clicked() {
+                        plotting_system.start_profile_average(&window_id);
+                    }
+                }
+                Some((frames, window_frames)) => {
+                    ui.label(format!("{}/{}", frames, window_frames));
+                    if ui.button("Export Averaged CSV").clicked() {
+                        match plotting_system.export_profile_average(&window_id) {
+                            Ok(path) => {
+                                crate::sim_log!(Io, Info, "Exported averaged profile to: {}", path)
+                            }
+                            Err(e) => crate::sim_log!(Io, Error, "Averaged export failed: {}", e),
+                        }
+                    }
+                    if ui.button("Restart").clicked() {
+                        plotting_system.start_profile_average(&window_id);
+                    }
+                    if ui.button("Stop").clicked() {
+                        plotting_system.stop_profile_average(&window_id);
+                    }
+                }
+            }
+        });
     }
 }
 
@@ -460,9 +640,23 @@ fn show_plot_content(ui: &mut egui::Ui, window: &mut crate::plotting::PlotWindow
         }
     });
 
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut window.config.y_log, "Log Y");
+        ui.label("Line:");
+        egui::ComboBox::from_id_source(format!("line_style_{}", window.id))
+            .selected_text(format!("{:?}", window.config.line_style))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut window.config.line_style, LineStyle::Solid, "Solid");
+                ui.selectable_value(&mut window.config.line_style, LineStyle::Dashed, "Dashed");
+                ui.selectable_value(&mut window.config.line_style, LineStyle::Dotted, "Dotted");
+            });
+    });
+
     ui.separator();
 
-    if window.data.x_data.is_empty() {
+    if let Some(map) = &window.data.concentration_map {
+        show_concentration_map(ui, map);
+    } else if window.data.x_data.is_empty() {
         ui.label("No data available yet...");
         match window.config.sampling_mode {
             crate::plotting::SamplingMode::SingleTimestep => {
@@ -492,15 +686,23 @@ fn show_plot_content(ui: &mut egui::Ui, window: &mut crate::plotting::PlotWindow
             ui.painter()
                 .rect_stroke(rect, 2.0, egui::Stroke::new(1.0, egui::Color32::BLACK));
 
+            // Under log scale, plot log10(y) and drop samples that have no logarithm
+            let (x_values, y_values) = if window.config.y_log {
+                let (x_log, y_log, _) = log10_points(&window.data.x_data, &window.data.y_data);
+                (x_log, y_log)
+            } else {
+                (window.data.x_data.clone(), window.data.y_data.clone())
+            };
+
             // Calculate plot ranges - use domain bounds for spatial axes
             let (plot_x_min, plot_x_max, plot_y_min, plot_y_max) =
-                calculate_plot_ranges(&window.config, &window.data);
+                calculate_plot_ranges(&window.config, &x_values, &y_values);
 
             // Convert data points to screen coordinates
             let mut screen_points = Vec::new();
-            for i in 0..window.data.x_data.len() {
-                let x_norm = (window.data.x_data[i] - plot_x_min) / (plot_x_max - plot_x_min);
-                let y_norm = 1.0 - (window.data.y_data[i] - plot_y_min) / (plot_y_max - plot_y_min); // Flip Y
+            for i in 0..x_values.len() {
+                let x_norm = (x_values[i] - plot_x_min) / (plot_x_max - plot_x_min);
+                let y_norm = 1.0 - (y_values[i] - plot_y_min) / (plot_y_max - plot_y_min); // Flip Y
 
                 let screen_x = rect.min.x + (x_norm as f32) * rect.width();
                 let screen_y = rect.min.y + (y_norm as f32) * rect.height();
@@ -508,20 +710,32 @@ fn show_plot_content(ui: &mut egui::Ui, window: &mut crate::plotting::PlotWindow
                 screen_points.push(egui::Pos2::new(screen_x, screen_y));
             }
 
-            // Draw data line
+            let is_timeline = window.config.plot_type == PlotType::SwitchTimeline;
+            if is_timeline {
+                draw_timeline_gaps_and_overlay(ui, rect, &window.data, plot_x_min, plot_x_max);
+            }
+
+            // Draw data line; NaN samples (timeline pauses) break it
             if screen_points.len() > 1 {
+                let stroke = egui::Stroke::new(2.0, egui::Color32::from_rgb(0, 100, 255));
                 for i in 0..screen_points.len() - 1 {
-                    ui.painter().line_segment(
-                        [screen_points[i], screen_points[i + 1]],
-                        egui::Stroke::new(2.0, egui::Color32::from_rgb(0, 100, 255)),
-                    );
+                    if !screen_points[i].is_finite() || !screen_points[i + 1].is_finite() {
+                        continue;
+                    }
+                    for segment in
+                        styled_segments(screen_points[i], screen_points[i + 1], window.config.line_style)
+                    {
+                        ui.painter().line_segment(segment, stroke);
+                    }
                 }
             }
 
             // Draw data points
-            for point in &screen_points {
-                ui.painter()
-                    .circle_filled(*point, 2.0, egui::Color32::from_rgb(0, 100, 255));
+            if !is_timeline {
+                for point in screen_points.iter().filter(|p| p.is_finite()) {
+                    ui.painter()
+                        .circle_filled(*point, 2.0, egui::Color32::from_rgb(0, 100, 255));
+                }
             }
 
             // Draw axes labels
@@ -567,17 +781,32 @@ fn show_plot_content(ui: &mut egui::Ui, window: &mut crate::plotting::PlotWindow
                 let y_norm = i as f32 / 4.0;
                 let y_val = plot_y_min + (plot_y_max - plot_y_min) * (1.0 - y_norm as f64);
                 let screen_y = rect.min.y + y_norm * rect.height();
+                let tick_text = if window.config.y_log {
+                    format!("{:.2e}", 10f64.powf(y_val))
+                } else {
+                    format!("{:.2}", y_val)
+                };
 
                 ui.painter().text(
                     egui::Pos2::new(rect.min.x - 5.0, screen_y),
                     egui::Align2::RIGHT_CENTER,
-                    format!("{:.2}", y_val),
+                    tick_text,
                     egui::FontId::proportional(10.0),
                     label_color,
                 );
             }
         }
 
+        if window.config.y_log {
+            let (_, _, skipped) = log10_points(&window.data.x_data, &window.data.y_data);
+            if skipped > 0 {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!("⚠ {} non-positive point(s) hidden on log scale", skipped),
+                );
+            }
+        }
+
         // Show data statistics
         ui.separator();
         ui.horizontal(|ui| {
@@ -619,6 +848,7 @@ fn show_plot_content(ui: &mut egui::Ui, window: &mut crate::plotting::PlotWindow
             window.data.x_data.clear();
             window.data.y_data.clear();
             window.data.timestamps.clear();
+            window.data.concentration_map = None;
         }
 
         if ui.button("Manual Update").clicked() {
@@ -655,6 +885,95 @@ fn show_plot_content(ui: &mut egui::Ui, window: &mut crate::plotting::PlotWindow
     });
 }
 
+/// Paint a concentration map as a heat map scaled to its densest cell.
+fn show_concentration_map(ui: &mut egui::Ui, map: &crate::plotting::analysis::ConcentrationMap) {
+    let peak = map.values.iter().fold(0.0f32, |a, &b| a.max(b));
+    let available = ui.available_size();
+    let width = (available.x - 20.0).max(1.0);
+    let aspect = (map.ny as f32 * map.cell_height) / (map.nx as f32 * map.cell_width).max(1e-6);
+    let size = egui::Vec2::new(width, (width * aspect).min(available.y - 100.0).max(1.0));
+    let (rect, _response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    if ui.is_rect_visible(rect) {
+        let (cw, ch) = (rect.width() / map.nx as f32, rect.height() / map.ny as f32);
+        for iy in 0..map.ny {
+            for ix in 0..map.nx {
+                let t = if peak > 0.0 { map.value(ix, iy) / peak } else { 0.0 };
+                let shade = (255.0 * (1.0 - t)) as u8;
+                // Row 0 is the bottom of the domain
+                let min = egui::Pos2::new(
+                    rect.min.x + ix as f32 * cw,
+                    rect.max.y - (iy + 1) as f32 * ch,
+                );
+                let cell = egui::Rect::from_min_size(min, egui::Vec2::new(cw, ch));
+                ui.painter().rect_filled(cell, 0.0, egui::Color32::from_rgb(255, shade, shade));
+            }
+        }
+        ui.painter()
+            .rect_stroke(rect, 0.0, egui::Stroke::new(1.0, egui::Color32::BLACK));
+    }
+    ui.label(format!(
+        "{} x {} cells of {:.1} x {:.1} Å, peak {:.4} /Å²",
+        map.nx, map.ny, map.cell_width, map.cell_height, peak
+    ));
+}
+
+/// Shade paused stretches of a switch timeline and draw the setpoint
+/// overlay in orange on its own vertical scale.
+fn draw_timeline_gaps_and_overlay(
+    ui: &egui::Ui,
+    rect: egui::Rect,
+    data: &crate::plotting::PlotData,
+    x_min: f64,
+    x_max: f64,
+) {
+    let to_x = |x: f64| rect.min.x + ((x - x_min) / (x_max - x_min)) as f32 * rect.width();
+    let pairs: Vec<(f64, f64)> =
+        data.x_data.iter().copied().zip(data.y_data.iter().copied()).collect();
+    for pair in pairs.windows(2) {
+        let ((x0, y0), (x1, _)) = (pair[0], pair[1]);
+        if y0.is_nan() && x1 > x0 {
+            let gap = egui::Rect::from_min_max(
+                egui::Pos2::new(to_x(x0), rect.min.y),
+                egui::Pos2::new(to_x(x1), rect.max.y),
+            );
+            ui.painter().rect_filled(gap, 0.0, egui::Color32::from_gray(200));
+        }
+    }
+
+    let finite = data.overlay.iter().copied().filter(|v| v.is_finite());
+    let (lo, hi) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    if lo > hi {
+        return;
+    }
+    let pad = ((hi - lo) * 0.1).max(1e-3);
+    let to_y =
+        |v: f64| rect.max.y - ((v - lo + pad) / (hi - lo + 2.0 * pad)) as f32 * rect.height();
+    let orange = egui::Color32::from_rgb(255, 140, 0);
+    let stroke = egui::Stroke::new(1.5, orange);
+    for i in 1..data.overlay.len().min(data.x_data.len()) {
+        let (a, b) = (data.overlay[i - 1], data.overlay[i]);
+        if a.is_finite() && b.is_finite() {
+            ui.painter().line_segment(
+                [
+                    egui::Pos2::new(to_x(data.x_data[i - 1]), to_y(a)),
+                    egui::Pos2::new(to_x(data.x_data[i]), to_y(b)),
+                ],
+                stroke,
+            );
+        }
+    }
+    let font = egui::FontId::proportional(10.0);
+    for (pos, align, value) in [
+        (rect.right_top(), egui::Align2::RIGHT_TOP, hi),
+        (rect.right_bottom(), egui::Align2::RIGHT_BOTTOM, lo),
+    ] {
+        ui.painter()
+            .text(pos, align, format!("setpoint {:.3}", value), font.clone(), orange);
+    }
+}
+
 fn get_axis_labels(config: &crate::plotting::PlotConfig) -> (&'static str, &'static str) {
     use crate::plotting::{PlotType, Quantity};
 
@@ -662,6 +981,8 @@ fn get_axis_labels(config: &crate::plotting::PlotConfig) -> (&'static str, &'sta
         PlotType::SpatialProfileX => "X Position",
         PlotType::SpatialProfileY => "Y Position",
         PlotType::TimeSeries => "Time (s)",
+        PlotType::SwitchTimeline => return ("Time (fs)", "Active Step"),
+        PlotType::ConcentrationMap => return ("X Position (Å)", "Y Position (Å)"),
     };
 
     let y_label = match config.quantity {
@@ -674,14 +995,59 @@ fn get_axis_labels(config: &crate::plotting::PlotConfig) -> (&'static str, &'sta
         Quantity::LocalFieldStrength => "Field Strength",
         Quantity::CellVoltage => "Cell Voltage (sim units)",
         Quantity::ElectricPotential => "Electric Potential (sim units)",
+        Quantity::NearestNeighborDistance => "Nearest-Neighbor Distance (Å)",
+        Quantity::MeanSquaredDisplacement(_) => "MSD (Å²)",
+        Quantity::InterfaceSpeciesCount(_) => "Interface Count",
+        Quantity::BulkSpeciesCount(_) => "Bulk Count",
     };
 
     (x_label, y_label)
 }
 
+/// Map samples onto a log10 Y axis, dropping points whose Y is non-positive
+/// or non-finite. Returns the kept X values, log10(Y) values and the number
+/// of skipped points so the caller can tell the user.
+pub fn log10_points(x_data: &[f64], y_data: &[f64]) -> (Vec<f64>, Vec<f64>, usize) {
+    let mut xs = Vec::with_capacity(x_data.len());
+    let mut ys = Vec::with_capacity(y_data.len());
+    let mut skipped = 0;
+    for (&x, &y) in x_data.iter().zip(y_data.iter()) {
+        if y > 0.0 && y.is_finite() {
+            xs.push(x);
+            ys.push(y.log10());
+        } else {
+            skipped += 1;
+        }
+    }
+    (xs, ys, skipped)
+}
+
+/// Split the segment `a`→`b` into the visible pieces for `style`.
+fn styled_segments(a: egui::Pos2, b: egui::Pos2, style: LineStyle) -> Vec<[egui::Pos2; 2]> {
+    let (dash, gap) = match style {
+        LineStyle::Solid => return vec![[a, b]],
+        LineStyle::Dashed => (6.0, 4.0),
+        LineStyle::Dotted => (1.5, 3.0),
+    };
+    let len = (b - a).length();
+    if len <= 0.0 {
+        return Vec::new();
+    }
+    let dir = (b - a) / len;
+    let mut segments = Vec::new();
+    let mut t = 0.0;
+    while t < len {
+        let end = (t + dash).min(len);
+        segments.push([a + dir * t, a + dir * end]);
+        t += dash + gap;
+    }
+    segments
+}
+
 fn calculate_plot_ranges(
     config: &crate::plotting::PlotConfig,
-    data: &crate::plotting::PlotData,
+    x_data: &[f64],
+    y_data: &[f64],
 ) -> (f64, f64, f64, f64) {
     use crate::config::DOMAIN_BOUNDS;
     use crate::plotting::PlotType;
@@ -693,11 +1059,11 @@ fn calculate_plot_ranges(
             let x_min = -(DOMAIN_BOUNDS as f64);
             let x_max = DOMAIN_BOUNDS as f64;
 
-            let (y_min, y_max) = if data.y_data.is_empty() {
+            let (y_min, y_max) = if y_data.is_empty() {
                 (0.0, 1.0)
             } else {
-                let data_y_min = data.y_data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-                let data_y_max = data.y_data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+                let data_y_min = y_data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+                let data_y_max = y_data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
                 let y_range = (data_y_max - data_y_min).max(0.001);
                 let y_padding = y_range * 0.05;
                 (data_y_min - y_padding, data_y_max + y_padding)
@@ -710,11 +1076,11 @@ fn calculate_plot_ranges(
             let x_min = -(DOMAIN_BOUNDS as f64);
             let x_max = DOMAIN_BOUNDS as f64;
 
-            let (y_min, y_max) = if data.y_data.is_empty() {
+            let (y_min, y_max) = if y_data.is_empty() {
                 (0.0, 1.0)
             } else {
-                let data_y_min = data.y_data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-                let data_y_max = data.y_data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+                let data_y_min = y_data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+                let data_y_max = y_data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
                 let y_range = (data_y_max - data_y_min).max(0.001);
                 let y_padding = y_range * 0.05;
                 (data_y_min - y_padding, data_y_max + y_padding)
@@ -724,13 +1090,13 @@ fn calculate_plot_ranges(
         }
         _ => {
             // For all other plot types, use data bounds with padding
-            if data.x_data.is_empty() || data.y_data.is_empty() {
+            if x_data.is_empty() || y_data.is_empty() {
                 (0.0, 1.0, 0.0, 1.0)
             } else {
-                let data_x_min = data.x_data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-                let data_x_max = data.x_data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-                let data_y_min = data.y_data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-                let data_y_max = data.y_data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+                let data_x_min = x_data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+                let data_x_max = x_data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+                let data_y_min = y_data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+                let data_y_max = y_data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
 
                 let x_range = (data_x_max - data_x_min).max(0.001);
                 let y_range = (data_y_max - data_y_min).max(0.001);
@@ -747,3 +1113,23 @@ fn calculate_plot_ranges(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log10_points_skips_zero_and_negative_values() {
+        let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let y = [100.0, 0.0, -5.0, f64::NAN, 0.1];
+        let (xs, ys, skipped) = log10_points(&x, &y);
+        assert_eq!(skipped, 3);
+        assert_eq!(xs, vec![0.0, 4.0]);
+        assert!((ys[0] - 2.0).abs() < 1e-12);
+        assert!((ys[1] + 1.0).abs() < 1e-12);
+
+        let (xs, ys, skipped) = log10_points(&[], &[]);
+        assert!(xs.is_empty() && ys.is_empty());
+        assert_eq!(skipped, 0);
+    }
+}
@@ -15,6 +15,10 @@ pub enum PlotType {
     SpatialProfileX, // Mean quantity vs X position
     SpatialProfileY, // Mean quantity vs Y position
     TimeSeries,      // Quantity vs time
+    /// Active switch-charging step vs time with the resolved setpoint overlaid
+    SwitchTimeline,
+    /// Number density of one species on a 2D grid over the domain
+    ConcentrationMap,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -28,6 +32,14 @@ pub enum Quantity {
     LocalFieldStrength,
     CellVoltage,
     ElectricPotential,
+    /// Mean nearest-neighbour distance over all bodies
+    NearestNeighborDistance,
+    /// Mean squared displacement (Å²) of one species since the MSD origin
+    MeanSquaredDisplacement(Species),
+    /// Count of a species within the interface band of any foil
+    InterfaceSpeciesCount(Species),
+    /// Count of a species outside every foil's interface band
+    BulkSpeciesCount(Species),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +51,20 @@ pub struct PlotConfig {
     pub spatial_bins: usize,
     pub time_window: f32,      // seconds
     pub update_frequency: f32, // Hz
+    /// Draw the Y axis on a log10 scale (non-positive samples are skipped)
+    #[serde(default)]
+    pub y_log: bool,
+    #[serde(default)]
+    pub line_style: LineStyle,
+}
+
+/// Stroke pattern used to draw a plot window's data line.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum LineStyle {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -55,6 +81,12 @@ pub struct PlotData {
     pub y_data: Vec<f64>,
     pub timestamps: Vec<f64>,
     pub metadata: HashMap<String, String>,
+    /// Secondary series drawn on its own scale over `y_data` (switch setpoints)
+    #[serde(default)]
+    pub overlay: Vec<f64>,
+    /// Latest grid of a concentration map plot
+    #[serde(default)]
+    pub concentration_map: Option<analysis::ConcentrationMap>,
 }
 
 #[derive(Debug, Clone)]
@@ -65,11 +97,19 @@ pub struct PlotWindow {
     pub data: PlotData,
     pub is_open: bool,
     pub last_update: f32,
+    /// Time-averaged profile being accumulated for export (spatial plots only)
+    pub profile_average: Option<analysis::ProfileAccumulator>,
 }
 
 pub struct PlottingSystem {
     pub windows: HashMap<String, PlotWindow>,
     pub next_window_id: usize,
+    /// Frames per time-averaged profile started from the GUI
+    pub average_frames: usize,
+    /// Shared by all MSD plot windows; records only while one is open
+    pub msd: analysis::MsdCollector,
+    /// Distance (Å) from any foil body that counts as interfacial
+    pub interface_band_width: f32,
 }
 
 impl PlottingSystem {
@@ -77,6 +117,9 @@ impl PlottingSystem {
         Self {
             windows: HashMap::new(),
             next_window_id: 0,
+            average_frames: 100,
+            msd: analysis::MsdCollector::default(),
+            interface_band_width: 10.0,
         }
     }
 
@@ -93,9 +136,12 @@ impl PlottingSystem {
                 y_data: Vec::new(),
                 timestamps: Vec::new(),
                 metadata: HashMap::new(),
+                overlay: Vec::new(),
+                concentration_map: None,
             },
             is_open: true,
             last_update: 0.0,
+            profile_average: None,
         };
 
         self.windows.insert(window_id.clone(), window);
@@ -111,6 +157,13 @@ impl PlottingSystem {
         domain_height: f32,
         coulomb_constant: f32,
     ) {
+        let tracks_msd = self.windows.values().any(|w| {
+            w.is_open && matches!(w.config.quantity, Quantity::MeanSquaredDisplacement(_))
+        });
+        if tracks_msd {
+            self.msd.record(bodies, current_time);
+        }
+
         let window_ids: Vec<String> = self.windows.keys().cloned().collect();
 
         for window_id in window_ids {
@@ -164,6 +217,21 @@ impl PlottingSystem {
                                 foils,
                                 current_time,
                                 coulomb_constant,
+                                &self.msd,
+                                self.interface_band_width,
+                            );
+                        }
+                        PlotType::SwitchTimeline => {
+                            let segments = crate::renderer::state::SWITCH_TIMELINE.lock();
+                            Self::update_switch_timeline_static(window, &segments, current_time);
+                        }
+                        PlotType::ConcentrationMap => {
+                            Self::update_concentration_map_static(
+                                window,
+                                bodies,
+                                current_time,
+                                domain_width,
+                                domain_height,
                             );
                         }
                     }
@@ -341,6 +409,10 @@ impl PlottingSystem {
             }
         }
 
+        if let Some(average) = window.profile_average.as_mut() {
+            average.add_frame(&window.data.x_data, &window.data.y_data);
+        }
+
         // Handle timestamps for spatial profiles
         if matches!(window.config.sampling_mode, SamplingMode::SingleTimestep) {
             window.data.timestamps.clear();
@@ -348,12 +420,57 @@ impl PlottingSystem {
         window.data.timestamps.push(current_time as f64);
     }
 
+    /// Replace the window's series with the step function of `segments`:
+    /// step index on Y and the resolved setpoint as the overlay.
+    fn update_switch_timeline_static(
+        window: &mut PlotWindow,
+        segments: &[crate::switch_charging::TimelineSegment],
+        current_time: f32,
+    ) {
+        let points = crate::switch_charging::SwitchTimeline::step_function(segments);
+        window.data.x_data = points.iter().map(|p| p.0).collect();
+        window.data.y_data = points.iter().map(|p| p.1).collect();
+        window.data.overlay = points.iter().map(|p| p.2).collect();
+        window.data.timestamps.clear();
+        window.data.timestamps.push(current_time as f64);
+    }
+
+    /// Replace the window's grid with the current number density of the
+    /// plotted species. `spatial_bins` sets the cell count along the longer
+    /// domain axis; the other axis gets as many cells as keep them square.
+    fn update_concentration_map_static(
+        window: &mut PlotWindow,
+        bodies: &[Body],
+        current_time: f32,
+        domain_width: f32,
+        domain_height: f32,
+    ) {
+        let Quantity::TotalSpeciesCount(species) = window.config.quantity else {
+            return;
+        };
+        let bins = window.config.spatial_bins.max(1) as f32;
+        let cell_size = 2.0 * domain_width.max(domain_height) / bins;
+        let map = analysis::calculate_concentration_map(
+            bodies,
+            species,
+            domain_width,
+            domain_height,
+            cell_size,
+        );
+        window.data.metadata.extend(map.metadata());
+        window.data.concentration_map = Some(map);
+        window.data.timestamps.clear();
+        window.data.timestamps.push(current_time as f64);
+    }
+
     fn update_time_series_static(
         window: &mut PlotWindow,
         bodies: &[Body],
         foils: &[Foil],
         current_time: f32,
         coulomb_constant: f32,
+        msd: &analysis::MsdCollector,
+        interface_band_width: f32,
     ) {
         let value = match window.config.quantity {
             Quantity::CellVoltage => {
@@ -375,6 +492,16 @@ impl PlottingSystem {
                 // Use analysis function for electron hop rate
                 analysis::calculate_electron_hop_rate(bodies, 0.016) // Assume ~60fps timestep
             }
+            Quantity::NearestNeighborDistance => analysis::nearest_neighbor_stats(bodies, None).1,
+            Quantity::MeanSquaredDisplacement(species) => msd.latest(species).unwrap_or(0.0),
+            Quantity::InterfaceSpeciesCount(species) => {
+                analysis::interface_bulk_counts(bodies, foils, interface_band_width, species).0
+                    as f32
+            }
+            Quantity::BulkSpeciesCount(species) => {
+                analysis::interface_bulk_counts(bodies, foils, interface_band_width, species).1
+                    as f32
+            }
             _ => {
                 // Calculate aggregate values
                 let total: f32 = bodies
@@ -419,6 +546,42 @@ impl PlottingSystem {
         self.windows.remove(window_id);
     }
 
+    /// Begin accumulating a time-averaged profile of `average_frames` frames
+    /// for a spatial plot window, discarding any previous average.
+    pub fn start_profile_average(&mut self, window_id: &str) {
+        let frames = self.average_frames;
+        if let Some(window) = self.windows.get_mut(window_id) {
+            if matches!(
+                window.config.plot_type,
+                PlotType::SpatialProfileX | PlotType::SpatialProfileY
+            ) {
+                window.profile_average = Some(analysis::ProfileAccumulator::new(frames));
+            }
+        }
+    }
+
+    pub fn stop_profile_average(&mut self, window_id: &str) {
+        if let Some(window) = self.windows.get_mut(window_id) {
+            window.profile_average = None;
+        }
+    }
+
+    /// Write the time-averaged profile of a window as CSV.
+    pub fn export_profile_average(&self, window_id: &str) -> Result<String, String> {
+        let window = self.windows.get(window_id).ok_or("Window not found")?;
+        match &window.profile_average {
+            Some(average) if average.frames > 0 => {
+                export::export_averaged_profile(&window.config, average)
+            }
+            _ => Err("No averaged profile recorded".to_string()),
+        }
+    }
+
+    /// Restart MSD measurement from the current positions.
+    pub fn reset_msd(&mut self) {
+        self.msd.reset();
+    }
+
     pub fn export_data(&self, window_id: &str, format: ExportFormat) -> Result<String, String> {
         if let Some(window) = self.windows.get(window_id) {
             export::export_plot_data(&window.data, format)
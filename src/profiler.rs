@@ -71,6 +71,7 @@ mod profiling_impl {
                 ("input_handling", "renderer/"),
                 // File I/O
                 ("save_state", "io/"),
+                ("export_xyz", "io/"),
                 ("load_state", "io/"),
                 // Configuration
                 ("config_update", "config/"),
@@ -78,9 +79,13 @@ mod profiling_impl {
                 ("diagnostics_transference", "diagnostics/"),
                 ("diagnostics_solvation", "diagnostics/"),
                 ("diagnostics_foil_electron", "diagnostics/"),
+                ("diagnostics_resistance", "diagnostics/"),
+                ("diagnostics_molarity", "diagnostics/"),
                 ("transference_calculation_internal", "diagnostics/"),
                 ("solvation_calculation_internal", "diagnostics/"),
                 ("foil_electron_calculation_internal", "diagnostics/"),
+                ("resistance_calculation_internal", "diagnostics/"),
+                ("molarity_calculation_internal", "diagnostics/"),
                 // Electron updates
                 ("electron_updates", "body/"),
             ];
@@ -5,6 +5,8 @@ pub use node::Node;
 
 mod quadtree;
 pub use quadtree::Quadtree;
+pub mod validation;
+pub use validation::FieldSample;
 
 #[cfg(test)]
 mod tests;
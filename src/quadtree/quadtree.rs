@@ -242,13 +242,13 @@ impl Quadtree {
                     idle_iterations = 0; // Reset idle counter when work is found
 
                     #[cfg(feature = "debug_quadtree")]
-                    println!("Quadtree::build: processing node {}", node);
+                    crate::sim_log!(Physics, Debug, "Quadtree::build: processing node {}", node);
                     let range = quadtree.nodes[node].bodies.clone();
                     let len = quadtree.nodes[node].bodies.len();
 
                     if range.len() >= quadtree.thread_capacity {
                         #[cfg(feature = "debug_quadtree")]
-                        println!("Quadtree::build: subdividing node {}", node);
+                        crate::sim_log!(Physics, Debug, "Quadtree::build: subdividing node {}", node);
                         // Try to claim this node for subdivision to prevent duplicate work
                         let claimed = claims[node]
                             .compare_exchange(0, 1, Ordering::AcqRel, Ordering::Acquire)
@@ -306,7 +306,7 @@ impl Quadtree {
                             continue;
                         }
                         #[cfg(feature = "debug_quadtree")]
-                        println!("Quadtree::build: subdividing node {} in stack", node);
+                        crate::sim_log!(Physics, Debug, "Quadtree::build: subdividing node {} in stack", node);
                         // Claim before subdividing to prevent duplicate subdivision across workers
                         let claimed = claims[node]
                             .compare_exchange(0, 1, Ordering::AcqRel, Ordering::Acquire)
@@ -406,6 +406,28 @@ impl Quadtree {
         acc
     }
 
+    /// Number of nodes `acc_pos` visits for a probe at `pos`; a gauge of how
+    /// leaf/thread capacity trade tree depth against per-leaf work.
+    pub fn visit_count(&self, pos: Vec2, radius: f32) -> usize {
+        let mut visits = 0;
+        let mut node = Self::ROOT;
+        while node < self.nodes.len() {
+            visits += 1;
+            let n = &self.nodes[node];
+            let dist_adj = ((pos - n.pos).mag() - radius).max(0.0);
+            let far = n.quad.size * n.quad.size < dist_adj.powi(2) * self.t_sq;
+            if far || n.is_leaf() {
+                if n.next == 0 {
+                    break;
+                }
+                node = n.next;
+            } else {
+                node = n.children;
+            }
+        }
+        visits
+    }
+
     pub fn _acc(&self, bodies: &mut Vec<Body>, k_e: f32) {
         let bodies_ptr = std::ptr::addr_of_mut!(*bodies) as usize;
 
@@ -136,4 +136,64 @@ mod tests {
             "Field should be finite for overlapping bodies"
         );
     }
+
+    #[test]
+    fn field_error_decreases_with_theta() {
+        // Fixed jittered lattice of alternating charges
+        let bodies: Vec<Body> = (0..400)
+            .map(|i| {
+                let (x, y) = ((i % 20) as f32, (i / 20) as f32);
+                let jitter = Vec2::new((i as f32 * 1.7).sin(), (i as f32 * 2.3).cos()) * 0.3;
+                let charge = if i % 3 == 0 { -1.0 } else { 1.0 };
+                Body::new(
+                    Vec2::new(x, y) * 4.0 + jitter,
+                    Vec2::zero(),
+                    1.0,
+                    1.0,
+                    charge,
+                    Species::LithiumIon,
+                )
+            })
+            .collect();
+
+        let mean_error = |theta: f32| {
+            let mut bodies = bodies.clone();
+            let mut quadtree = Quadtree::new(theta, 2.0, 1, 1024);
+            quadtree.build(&mut bodies);
+            let samples = quadtree.validate_field(&bodies, 50, COULOMB_CONSTANT);
+            assert_eq!(samples.len(), 50);
+            samples.iter().map(|s| s.rel_error).sum::<f32>() / samples.len() as f32
+        };
+
+        let coarse = mean_error(1.0);
+        let medium = mean_error(0.5);
+        let fine = mean_error(0.2);
+        let exact = mean_error(0.0);
+        assert!(coarse > medium && medium > fine, "{} {} {}", coarse, medium, fine);
+        assert!(exact < 1e-4, "theta = 0 should match the pairwise sum, got {}", exact);
+    }
+
+    #[test]
+    fn leaves_honor_configured_capacity() {
+        let bodies: Vec<Body> = (0..500)
+            .map(|i| {
+                let t = i as f32;
+                let pos = Vec2::new((t * 0.37).sin() * 80.0, (t * 0.53).cos() * 60.0 + t * 0.01);
+                Body::new(pos, Vec2::zero(), 1.0, 1.0, 1.0, Species::LithiumIon)
+            })
+            .collect();
+
+        let mut visits = Vec::new();
+        for leaf_capacity in [1, 4, 16] {
+            let mut bodies = bodies.clone();
+            let mut quadtree = Quadtree::new(0.5, 1.0, leaf_capacity, 64);
+            quadtree.build(&mut bodies);
+            let used = 4 * quadtree.atomic_len.load(std::sync::atomic::Ordering::Relaxed) + 1;
+            let leaves = quadtree.nodes[..used].iter().filter(|n| n.is_leaf());
+            let fullest = leaves.map(|n| n.bodies.len()).max().unwrap();
+            assert!(fullest <= leaf_capacity, "leaf holds {} > {}", fullest, leaf_capacity);
+            visits.push(quadtree.visit_count(bodies[0].pos, bodies[0].radius));
+        }
+        assert!(visits[0] > visits[2], "bigger leaves mean a shallower walk: {:?}", visits);
+    }
 }
@@ -0,0 +1,80 @@
+// quadtree/validation.rs
+// Compare the Barnes-Hut field against an exact pairwise sum
+
+use super::Quadtree;
+use crate::body::Body;
+use std::io::Write;
+use std::path::Path;
+use ultraviolet::Vec2;
+
+/// Exact vs. approximated field at one sample point.
+#[derive(Clone, Copy, Debug)]
+pub struct FieldSample {
+    pub pos: Vec2,
+    pub exact: Vec2,
+    pub approx: Vec2,
+    /// `|approx - exact| / |exact|` (absolute error when the exact field vanishes).
+    pub rel_error: f32,
+}
+
+impl Quadtree {
+    /// Direct O(N) field at `pos` using the same softened kernel, minimum
+    /// separation and self-exclusion as the leaf branch of `acc_pos`.
+    pub fn exact_field_at(&self, pos: Vec2, radius: f32, bodies: &[Body], k_e: f32) -> Vec2 {
+        let mut field = Vec2::zero();
+        for body in bodies {
+            if (body.pos - pos).mag_sq() < 1e-6 {
+                continue;
+            }
+            let d = pos - body.pos;
+            let r_eff = d.mag().max(radius + body.radius);
+            let denom = (r_eff * r_eff + self.e_sq) * r_eff;
+            field += d * (k_e * body.charge / denom);
+        }
+        field
+    }
+
+    /// Sample up to `samples` bodies (evenly strided) and compare the
+    /// quadtree field at each with the exact pairwise field for the current
+    /// theta. The tree must have been built from `bodies`.
+    pub fn validate_field(&self, bodies: &[Body], samples: usize, k_e: f32) -> Vec<FieldSample> {
+        if bodies.is_empty() || samples == 0 {
+            return Vec::new();
+        }
+        let stride = (bodies.len() / samples.min(bodies.len())).max(1);
+        bodies
+            .iter()
+            .step_by(stride)
+            .take(samples)
+            .map(|b| {
+                let approx = self.acc_pos(b.pos, 1.0, b.radius, bodies, k_e);
+                let exact = self.exact_field_at(b.pos, b.radius, bodies, k_e);
+                let err = (approx - exact).mag();
+                let norm = exact.mag();
+                FieldSample {
+                    pos: b.pos,
+                    exact,
+                    approx,
+                    rel_error: if norm > 1e-12 { err / norm } else { err },
+                }
+            })
+            .collect()
+    }
+}
+
+/// Write field validation samples as CSV.
+pub fn write_field_validation_csv(path: &Path, samples: &[FieldSample]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    writeln!(file, "x,y,exact_ex,exact_ey,approx_ex,approx_ey,rel_error")?;
+    for s in samples {
+        writeln!(
+            file,
+            "{:.6},{:.6},{:.6e},{:.6e},{:.6e},{:.6e},{:.6e}",
+            s.pos.x, s.pos.y, s.exact.x, s.exact.y, s.approx.x, s.approx.y, s.rel_error
+        )?;
+    }
+    file.flush()
+}
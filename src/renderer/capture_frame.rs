@@ -0,0 +1,86 @@
+//! Fixed-resolution framing for region captures.
+//!
+//! Maps a world-space capture region onto an output image of a chosen pixel
+//! size, independent of the live window. With `Letterbox` the region keeps
+//! its aspect ratio and is centred inside the full target size (bars fill
+//! the rest); with `RegionAspect` the output height is derived from the
+//! target width so the image is exactly the region.
+
+use ultraviolet::Vec2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FrameFit {
+    #[default]
+    Letterbox,
+    RegionAspect,
+}
+
+/// Output image size and the pixel rectangle the region occupies within it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameLayout {
+    pub width: u32,
+    pub height: u32,
+    /// `(x, y, w, h)` of the region content, y down from the top-left corner
+    pub content: (u32, u32, u32, u32),
+}
+
+/// Lay out the world rectangle spanned by `a` and `b` in a `target_w` x
+/// `target_h` image. Returns `None` for a degenerate region or target.
+pub fn frame_layout(a: Vec2, b: Vec2, target_w: u32, target_h: u32, fit: FrameFit) -> Option<FrameLayout> {
+    let size = a.max_by_component(b) - a.min_by_component(b);
+    if size.x <= 0.0 || size.y <= 0.0 || target_w == 0 || target_h == 0 {
+        return None;
+    }
+    let region_aspect = size.x / size.y;
+    match fit {
+        FrameFit::RegionAspect => {
+            let height = ((target_w as f32 / region_aspect).round() as u32).max(1);
+            Some(FrameLayout {
+                width: target_w,
+                height,
+                content: (0, 0, target_w, height),
+            })
+        }
+        FrameFit::Letterbox => {
+            let target_aspect = target_w as f32 / target_h as f32;
+            let (w, h) = if region_aspect >= target_aspect {
+                (target_w, ((target_w as f32 / region_aspect).round() as u32).clamp(1, target_h))
+            } else {
+                (((target_h as f32 * region_aspect).round() as u32).clamp(1, target_w), target_h)
+            };
+            Some(FrameLayout {
+                width: target_w,
+                height: target_h,
+                content: ((target_w - w) / 2, (target_h - h) / 2, w, h),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn region_maps_into_letterboxed_and_native_frames() {
+        // 200 x 100 Å region into a 1920 x 1080 frame: width-limited, bars top/bottom
+        let (a, b) = (Vec2::new(-100.0, 50.0), Vec2::new(100.0, -50.0));
+        let layout = frame_layout(a, b, 1920, 1080, FrameFit::Letterbox).unwrap();
+        assert_eq!((layout.width, layout.height), (1920, 1080));
+        assert_eq!(layout.content, (0, 60, 1920, 960));
+        // 9.6 px per Å on both axes, so the region is not stretched
+        let (_, _, w, h) = layout.content;
+        assert_eq!((w as f32 / 200.0, h as f32 / 100.0), (9.6, 9.6));
+
+        // Tall region: bars left/right
+        let tall = frame_layout(Vec2::zero(), Vec2::new(10.0, 20.0), 800, 800, FrameFit::Letterbox).unwrap();
+        assert_eq!(tall.content, (200, 0, 400, 800));
+
+        // Honouring the region's ratio changes the output height instead
+        let native = frame_layout(a, b, 1920, 1080, FrameFit::RegionAspect).unwrap();
+        assert_eq!((native.width, native.height), (1920, 960));
+        assert_eq!(native.content, (0, 0, 1920, 960));
+
+        assert_eq!(frame_layout(a, a, 1920, 1080, FrameFit::Letterbox), None);
+    }
+}
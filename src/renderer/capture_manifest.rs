@@ -0,0 +1,90 @@
+//! Frame manifest for screen-capture sessions.
+//!
+//! Every saved capture appends one JSON line to `manifest.jsonl` in the
+//! capture folder with the file name, simulation time and the view transform
+//! used, so frames can be assembled into a video with correct timing. A
+//! resumed session keeps appending to the same file.
+
+#![cfg_attr(not(feature = "screen_capture"), allow(dead_code))] // Written only by the capture backend
+
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use ultraviolet::Vec2;
+
+/// Manifest written next to the captured PNGs for assembling videos.
+pub const CAPTURE_MANIFEST_FILE: &str = "manifest.jsonl";
+
+/// One captured frame: file, simulation time and the view it was taken with.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CaptureManifestEntry {
+    pub file: String,
+    pub sim_time_fs: f32,
+    /// Timed capture interval (fs) in effect for this frame
+    pub capture_interval: f32,
+    pub pixel_width: u32,
+    pub pixel_height: u32,
+    /// Full domain extents (Å)
+    pub domain_width: f32,
+    pub domain_height: f32,
+    /// Camera center (Å) and half-height of the view (Å)
+    pub view_center: Vec2,
+    pub view_scale: f32,
+    /// Whether the frame was cropped to the capture region
+    pub cropped: bool,
+}
+
+impl CaptureManifestEntry {
+    /// Append this entry as one JSON line to the manifest at `path`,
+    /// creating the file if missing. Earlier lines are never rewritten.
+    pub fn append_to(&self, path: &Path) -> Result<(), String> {
+        let line = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        writeln!(file, "{}", line).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_manifest_entries_round_trip_and_append() {
+        let entry = |file: &str, t: f32| CaptureManifestEntry {
+            file: file.to_string(),
+            sim_time_fs: t,
+            capture_interval: 1.0,
+            pixel_width: 800,
+            pixel_height: 600,
+            domain_width: 300.0,
+            domain_height: 200.0,
+            view_center: Vec2::new(5.0, -2.5),
+            view_scale: 150.0,
+            cropped: false,
+        };
+        let json = serde_json::to_string(&entry("a.png", 10.0)).unwrap();
+        assert!(json.contains("\"sim_time_fs\":10.0"));
+        let parsed: CaptureManifestEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, entry("a.png", 10.0));
+
+        // A second session appends to the existing manifest
+        let dir = std::env::temp_dir().join(format!("particlesim_capture_manifest_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CAPTURE_MANIFEST_FILE);
+        let _ = std::fs::remove_file(&path);
+        entry("a.png", 10.0).append_to(&path).unwrap();
+        entry("b.png", 20.0).append_to(&path).unwrap();
+        let frames: Vec<CaptureManifestEntry> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(frames, [entry("a.png", 10.0), entry("b.png", 20.0)]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,46 +1,138 @@
+use crate::body::Body;
 use crate::renderer::Renderer;
 use rayon::prelude::*;
 use ultraviolet::Vec2;
 
-impl Renderer {
-    /// Draw a simple charge density heatmap.
-    pub fn draw_charge_density(&mut self, ctx: &mut quarkstrom::RenderContext) {
-        let grid_spacing = 5.0;
-        let smoothing = 5.0;
+const CHARGE_GRID_SPACING: f32 = 5.0;
+const CHARGE_SMOOTHING: f32 = 5.0;
+
+/// Gaussian-smoothed charge density sampled at cell centres of a regular grid.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ChargeGrid {
+    pub min: Vec2,
+    pub spacing: f32,
+    pub nx: usize,
+    pub ny: usize,
+    /// Row-major, `nx` columns
+    pub values: Vec<f32>,
+}
+
+impl ChargeGrid {
+    /// Grid covering `min..max` with cells of `spacing`.
+    pub fn covering(bodies: &[Body], min: Vec2, max: Vec2, spacing: f32, smoothing: f32) -> Self {
+        let nx = ((max.x - min.x) / spacing).ceil() as usize + 1;
+        let ny = ((max.y - min.y) / spacing).ceil() as usize + 1;
+        Self::on_layout(bodies, min, spacing, nx, ny, smoothing)
+    }
+
+    /// Sample `bodies` on an explicit layout, e.g. that of a held snapshot.
+    pub fn on_layout(bodies: &[Body], min: Vec2, spacing: f32, nx: usize, ny: usize, smoothing: f32) -> Self {
+        let mut values = vec![0.0f32; nx * ny];
+        values.par_iter_mut().enumerate().for_each(|(i, sample)| {
+            let pos = Vec2::new(
+                min.x + ((i % nx) as f32 + 0.5) * spacing,
+                min.y + ((i / nx) as f32 + 0.5) * spacing,
+            );
+            *sample = bodies
+                .iter()
+                .map(|body| {
+                    let dist2 = (pos - body.pos).mag_sq();
+                    body.charge * (-dist2 / (smoothing * smoothing)).exp()
+                })
+                .sum();
+        });
+        Self { min, spacing, nx, ny, values }
+    }
+
+    pub fn max_abs(&self) -> f32 {
+        self.values.iter().fold(0.0f32, |m, v| m.max(v.abs()))
+    }
+
+    /// `self - earlier` cell by cell, `None` when the layouts differ.
+    pub fn difference(&self, earlier: &ChargeGrid) -> Option<ChargeGrid> {
+        if self.nx != earlier.nx
+            || self.ny != earlier.ny
+            || self.min != earlier.min
+            || self.spacing != earlier.spacing
+        {
+            return None;
+        }
+        let values = self
+            .values
+            .iter()
+            .zip(&earlier.values)
+            .map(|(live, held)| live - held)
+            .collect();
+        Some(ChargeGrid { values, ..self.clone() })
+    }
+}
 
+/// Red for positive, blue for negative, `norm` in [-1, 1].
+fn diverging_color(norm: f32, alpha: u8) -> [u8; 4] {
+    let norm = norm.clamp(-1.0, 1.0);
+    [(norm.max(0.0) * 255.0) as u8, 0, ((-norm).max(0.0) * 255.0) as u8, alpha]
+}
+
+impl Renderer {
+    fn charge_view_bounds(&self) -> (Vec2, Vec2) {
         let half_view = Vec2::new(
             self.scale * (self.window_width as f32 / self.window_height as f32),
             self.scale,
         );
-        let min = self.pos - half_view;
-        let max = self.pos + half_view;
+        (self.pos - half_view, self.pos + half_view)
+    }
 
-        let nx = ((max.x - min.x) / grid_spacing).ceil() as usize + 1;
-        let ny = ((max.y - min.y) / grid_spacing).ceil() as usize + 1;
+    fn draw_charge_grid(ctx: &mut quarkstrom::RenderContext, grid: &ChargeGrid, max_abs: f32, alpha: u8) {
+        for ix in 0..grid.nx.saturating_sub(1) {
+            for iy in 0..grid.ny.saturating_sub(1) {
+                let density = grid.values[iy * grid.nx + ix];
+                let rect_min = Vec2::new(
+                    grid.min.x + ix as f32 * grid.spacing,
+                    grid.min.y + iy as f32 * grid.spacing,
+                );
+                let rect_max = rect_min + Vec2::new(grid.spacing, grid.spacing);
+                ctx.draw_rect(rect_min, rect_max, diverging_color(density / max_abs, alpha));
+            }
+        }
+    }
 
-        let mut samples = vec![0.0f32; nx * ny];
-        let max_abs = samples
-            .par_iter_mut()
-            .enumerate()
-            .map(|(i, sample)| {
-                let ix = i % nx;
-                let iy = i / nx;
-                let x = min.x + (ix as f32 + 0.5) * grid_spacing;
-                let y = min.y + (iy as f32 + 0.5) * grid_spacing;
-                let pos = Vec2::new(x, y);
-                let mut density = 0.0f32;
-                for body in &self.bodies {
-                    let r = pos - body.pos;
-                    let dist2 = r.mag_sq();
-                    let weight = (-dist2 / (smoothing * smoothing)).exp();
-                    density += body.charge * weight;
+    /// Hold the charge density of the current view for later comparison.
+    pub fn capture_charge_snapshot(&mut self) {
+        let (min, max) = self.charge_view_bounds();
+        self.charge_snapshot = Some(ChargeGrid::covering(
+            &self.bodies,
+            min,
+            max,
+            CHARGE_GRID_SPACING,
+            CHARGE_SMOOTHING,
+        ));
+    }
+
+    /// Draw a simple charge density heatmap, or `live - snapshot` over the
+    /// snapshot's region when a snapshot is held and difference mode is on.
+    pub fn draw_charge_density(&mut self, ctx: &mut quarkstrom::RenderContext) {
+        if self.show_charge_difference {
+            if let Some(held) = &self.charge_snapshot {
+                let live = ChargeGrid::on_layout(
+                    &self.bodies,
+                    held.min,
+                    held.spacing,
+                    held.nx,
+                    held.ny,
+                    CHARGE_SMOOTHING,
+                );
+                if let Some(diff) = live.difference(held) {
+                    // Red where charge accumulated, blue where it depleted
+                    Self::draw_charge_grid(ctx, &diff, diff.max_abs().max(1e-6), 110);
                 }
-                *sample = density;
-                density.abs()
-            })
-            .reduce(|| 0.0f32, f32::max);
+                return;
+            }
+        }
 
-        let raw_max = max_abs.max(1e-6);
+        let (min, max) = self.charge_view_bounds();
+        let grid = ChargeGrid::covering(&self.bodies, min, max, CHARGE_GRID_SPACING, CHARGE_SMOOTHING);
+
+        let raw_max = grid.max_abs().max(1e-6);
         // Smooth the normalization scale: rise fast (track peaks),
         // decay slowly (prevent flickering when peaks disappear).
         let alpha_rise = 0.3;
@@ -49,23 +141,7 @@ impl Renderer {
         let alpha = if raw_max > prev { alpha_rise } else { alpha_decay };
         let smoothed = prev + alpha * (raw_max - prev);
         self.charge_density_max_abs_smoothed = smoothed.max(1e-6);
-        let max_abs = self.charge_density_max_abs_smoothed;
-
-        for ix in 0..nx - 1 {
-            for iy in 0..ny - 1 {
-                let density = samples[iy * nx + ix];
-                let norm = (density / max_abs).clamp(-1.0, 1.0);
-                let r = norm.max(0.0);
-                let b = (-norm).max(0.0);
-                let color = [(r * 255.0) as u8, 0, (b * 255.0) as u8, 80];
 
-                let rect_min = Vec2::new(
-                    min.x + ix as f32 * grid_spacing,
-                    min.y + iy as f32 * grid_spacing,
-                );
-                let rect_max = rect_min + Vec2::new(grid_spacing, grid_spacing);
-                ctx.draw_rect(rect_min, rect_max, color);
-            }
-        }
+        Self::draw_charge_grid(ctx, &grid, self.charge_density_max_abs_smoothed, 80);
     }
 }
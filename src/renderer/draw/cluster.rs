@@ -0,0 +1,108 @@
+use crate::body::{Body, Species};
+use crate::cell_list::CellList;
+use crate::renderer::Renderer;
+use palette::{Hsluv, IntoColor, Srgba};
+use std::collections::VecDeque;
+
+/// Render swaps between cluster recomputations.
+const CLUSTER_REFRESH_SWAPS: usize = 10;
+/// Color for metal bodies that touch no other metal.
+pub(crate) const SINGLETON_COLOR: [u8; 4] = [140, 140, 140, 255];
+
+fn is_metal(species: Species) -> bool {
+    matches!(species, Species::LithiumMetal | Species::FoilMetal)
+}
+
+/// Label connected metal clusters with the same contact rule as the foil
+/// connectivity BFS (centres within 1.1x the summed radii). Entry `i` is the
+/// cluster of body `i`, numbered from 0 in order of first body; non-metal
+/// bodies and isolated metal get `None`.
+pub(crate) fn metal_cluster_ids(bodies: &[Body]) -> Vec<Option<u32>> {
+    let mut ids = vec![None; bodies.len()];
+    let metal: Vec<usize> = (0..bodies.len()).filter(|&i| is_metal(bodies[i].species)).collect();
+    if metal.len() < 2 {
+        return ids;
+    }
+    let (mut half_w, mut half_h, mut max_radius) = (1.0f32, 1.0f32, 0.0f32);
+    for &i in &metal {
+        half_w = half_w.max(bodies[i].pos.x.abs());
+        half_h = half_h.max(bodies[i].pos.y.abs());
+        max_radius = max_radius.max(bodies[i].radius);
+    }
+    let mut cells = CellList::new(half_w, half_h, (2.2 * max_radius).max(0.1));
+    cells.rebuild(bodies);
+
+    let mut visited = vec![false; bodies.len()];
+    let mut next_id = 0u32;
+    let mut queue = VecDeque::new();
+    for &seed in &metal {
+        if visited[seed] {
+            continue;
+        }
+        visited[seed] = true;
+        queue.push_back(seed);
+        let mut members = Vec::new();
+        while let Some(idx) = queue.pop_front() {
+            members.push(idx);
+            let body = &bodies[idx];
+            for j in cells.find_neighbors_within(bodies, idx, body.radius * 2.2) {
+                if visited[j] || !is_metal(bodies[j].species) {
+                    continue;
+                }
+                if (body.pos - bodies[j].pos).mag() <= (body.radius + bodies[j].radius) * 1.1 {
+                    visited[j] = true;
+                    queue.push_back(j);
+                }
+            }
+        }
+        if members.len() > 1 {
+            for idx in members {
+                ids[idx] = Some(next_id);
+            }
+            next_id += 1;
+        }
+    }
+    ids
+}
+
+/// Distinct hue per cluster, stepping around the wheel by the golden angle.
+pub(crate) fn cluster_color(id: u32) -> [u8; 4] {
+    let hue = (id as f32 * 137.507_77) % 360.0;
+    let rgba: Srgba = Hsluv::new(hue, 90.0, 65.0).into_color();
+    [
+        (rgba.red * 255.0) as u8,
+        (rgba.green * 255.0) as u8,
+        (rgba.blue * 255.0) as u8,
+        255,
+    ]
+}
+
+impl Renderer {
+    /// Recompute the cached cluster labels on every `CLUSTER_REFRESH_SWAPS`th
+    /// render swap, or straight away when the body count changed.
+    pub(crate) fn refresh_metal_clusters(&mut self) {
+        if !self.show_metal_clusters {
+            self.metal_clusters.clear();
+            return;
+        }
+        self.metal_cluster_swaps += 1;
+        if self.metal_clusters.len() == self.bodies.len()
+            && self.metal_cluster_swaps < CLUSTER_REFRESH_SWAPS
+        {
+            return;
+        }
+        self.metal_cluster_swaps = 0;
+        self.metal_clusters = metal_cluster_ids(&self.bodies);
+    }
+
+    /// Cluster color for metal body `index`, or `None` to keep the species color.
+    pub(crate) fn metal_cluster_color(&self, index: usize) -> Option<[u8; 4]> {
+        if !self.show_metal_clusters || self.metal_clusters.len() != self.bodies.len() {
+            return None;
+        }
+        if !is_metal(self.bodies[index].species) {
+            return None;
+        }
+        Some(self.metal_clusters[index].map_or(SINGLETON_COLOR, cluster_color))
+    }
+}
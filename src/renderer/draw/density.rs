@@ -3,6 +3,53 @@ use crate::renderer::Renderer;
 use rayon::prelude::*;
 use ultraviolet::Vec2;
 
+/// Separable Gaussian blur of an `nx` x `ny` row-major grid with standard
+/// deviation `sigma` in cells. Weights are renormalised at the edges so a
+/// uniform grid stays uniform; `sigma <= 0` returns the grid unchanged.
+pub fn gaussian_blur_grid(samples: &[f32], nx: usize, ny: usize, sigma: f32) -> Vec<f32> {
+    if sigma <= 0.0 || nx == 0 || ny == 0 {
+        return samples.to_vec();
+    }
+    let radius = (3.0 * sigma).ceil() as isize;
+    let kernel: Vec<f32> = (-radius..=radius)
+        .map(|k| (-(k * k) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let blur_axis = |src: &[f32], horizontal: bool| -> Vec<f32> {
+        let mut out = vec![0.0f32; src.len()];
+        for iy in 0..ny {
+            for ix in 0..nx {
+                let (mut sum, mut wsum) = (0.0f32, 0.0f32);
+                for (k, w) in (-radius..=radius).zip(&kernel) {
+                    let (jx, jy) = if horizontal {
+                        (ix as isize + k, iy as isize)
+                    } else {
+                        (ix as isize, iy as isize + k)
+                    };
+                    if jx < 0 || jy < 0 || jx >= nx as isize || jy >= ny as isize {
+                        continue;
+                    }
+                    sum += w * src[jy as usize * nx + jx as usize];
+                    wsum += w;
+                }
+                out[iy * nx + ix] = sum / wsum;
+            }
+        }
+        out
+    };
+    let rows = blur_axis(samples, true);
+    blur_axis(&rows, false)
+}
+
+/// Grid covering `±domain_width` x `±domain_height` with square cells of
+/// `grid_spacing`: returns the lower-left corner and the cell counts. Cell
+/// `(ix, iy)` is centred at `min + (i + 0.5) * grid_spacing`.
+pub(crate) fn domain_grid(domain_width: f32, domain_height: f32, grid_spacing: f32) -> (Vec2, usize, usize) {
+    let min = Vec2::new(-domain_width, -domain_height);
+    let nx = ((2.0 * domain_width) / grid_spacing).ceil() as usize + 1;
+    let ny = ((2.0 * domain_height) / grid_spacing).ceil() as usize + 1;
+    (min, nx, ny)
+}
+
 impl Renderer {
     /// Calculate 2D domain density for selected species across the entire domain.
     /// Returns a grid of density values and the maximum density for normalization.
@@ -16,12 +63,9 @@ impl Renderer {
         let domain_height = self.domain_height;
 
         // Calculate grid from center outward to cover full domain
-        let min = Vec2::new(-domain_width, -domain_height);
+        let (min, nx, ny) = domain_grid(domain_width, domain_height, grid_spacing);
         let max = Vec2::new(domain_width, domain_height);
 
-        let nx = ((max.x - min.x) / grid_spacing).ceil() as usize + 1;
-        let ny = ((max.y - min.y) / grid_spacing).ceil() as usize + 1;
-
         let mut samples = vec![0.0f32; nx * ny];
         let max_density = samples
             .par_iter_mut()
@@ -146,13 +190,34 @@ impl Renderer {
     }
 
     /// Draw the 2D domain density heatmap.
-    pub fn draw_2d_domain_density(&self, ctx: &mut quarkstrom::RenderContext) {
+    pub fn draw_2d_domain_density(&mut self, ctx: &mut quarkstrom::RenderContext) {
         let grid_spacing = 8.0; // Slightly larger grid for domain-wide view
         let smoothing = 8.0; // Slightly more smoothing for cleaner visualization
 
-        let (samples, max_density, nx, ny, min, _max) =
+        let (mut samples, mut max_density, nx, ny, min, _max) =
             self.calculate_2d_domain_density(grid_spacing, smoothing);
 
+        // Optional spatial blur, then blend with the previous frame's grid
+        if self.density_smoothing_spatial > 0.0 {
+            samples = gaussian_blur_grid(&samples, nx, ny, self.density_smoothing_spatial);
+        }
+        let alpha = self.density_smoothing_temporal.clamp(0.0, 0.99);
+        if alpha > 0.0 {
+            if let Some((cx, cy, prev)) = &self.density_grid_cache {
+                if (*cx, *cy) == (nx, ny) {
+                    for (s, p) in samples.iter_mut().zip(prev) {
+                        *s = p * alpha + *s * (1.0 - alpha);
+                    }
+                }
+            }
+            self.density_grid_cache = Some((nx, ny, samples.clone()));
+        } else {
+            self.density_grid_cache = None;
+        }
+        if self.density_smoothing_spatial > 0.0 || alpha > 0.0 {
+            max_density = samples.iter().copied().fold(0.0f32, f32::max);
+        }
+
         // Avoid division by zero
         let max_density = max_density.max(1e-6);
 
@@ -0,0 +1,76 @@
+/// Tunable mapping from normalized depth to the drawn size and color of a body.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DepthCue {
+    /// Relative radius change per unit of `strength * z`
+    pub size_gain: f32,
+    /// Relative brightness change per unit of `strength * z`
+    pub brightness_gain: f32,
+    /// Strength above which near bodies are tinted warm and far ones cool
+    pub tint_threshold: f32,
+    /// Strength above which bodies away from z = 0 fade out
+    pub alpha_threshold: f32,
+}
+
+impl Default for DepthCue {
+    fn default() -> Self {
+        Self {
+            size_gain: 0.3,
+            brightness_gain: 0.6,
+            tint_threshold: 2.0,
+            alpha_threshold: 1.5,
+        }
+    }
+}
+
+impl DepthCue {
+    /// Radius and color of a body at `z_normalized` in [-1, 1] (positive is
+    /// toward the camera) for the global cue `strength`.
+    pub fn apply(&self, z_normalized: f32, strength: f32, radius: f32, mut color: [u8; 4]) -> (f32, [u8; 4]) {
+        // Closer bodies appear larger, but never vanish
+        let size_factor = 1.0 + strength * self.size_gain * z_normalized;
+        let radius = radius * size_factor.max(0.3);
+
+        // Higher z is brighter; z = 0 is darkened a little for contrast
+        let brightness = (1.0 + strength * self.brightness_gain * z_normalized).clamp(0.2, 2.0);
+        let z_zero_darkening = if z_normalized.abs() < 0.1 {
+            1.0 - strength * 0.3
+        } else {
+            1.0
+        };
+        let final_brightness = brightness * z_zero_darkening.clamp(0.3, 1.0);
+
+        if strength > self.tint_threshold {
+            let excess = strength - self.tint_threshold;
+            if z_normalized > 0.0 {
+                // Closer bodies get a warmer tint
+                let warm = z_normalized * excess * 0.1;
+                color[0] = (color[0] as f32 * (1.0 + warm)).clamp(0.0, 255.0) as u8;
+                color[1] = (color[1] as f32 * (1.0 + warm * 0.5)).clamp(0.0, 255.0) as u8;
+            } else {
+                // Farther bodies get a cooler tint
+                let cool = -z_normalized * excess * 0.1;
+                color[2] = (color[2] as f32 * (1.0 + cool)).clamp(0.0, 255.0) as u8;
+                color[1] = (color[1] as f32 * (1.0 + cool * 0.5)).clamp(0.0, 255.0) as u8;
+            }
+        }
+
+        for channel in &mut color[..3] {
+            *channel = (*channel as f32 * final_brightness).clamp(0.0, 255.0) as u8;
+        }
+
+        if strength > self.alpha_threshold {
+            let alpha_factor = 1.0 - z_normalized.abs() * (strength - self.alpha_threshold) * 0.2;
+            color[3] = (color[3] as f32 * alpha_factor.clamp(0.5, 1.0)) as u8;
+        }
+        (radius, color)
+    }
+}
+
+/// Whether a body at depth `z` is drawn under `slice`, inclusive bounds in
+/// either order; `None` draws everything.
+pub fn in_z_slice(z: f32, slice: Option<(f32, f32)>) -> bool {
+    match slice {
+        Some((a, b)) => (a.min(b)..=a.max(b)).contains(&z),
+        None => true,
+    }
+}
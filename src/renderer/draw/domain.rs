@@ -0,0 +1,40 @@
+use crate::renderer::Renderer;
+use ultraviolet::Vec2;
+
+impl Renderer {
+    /// Outline the simulation box. `DOMAIN_WIDTH`/`DOMAIN_HEIGHT` hold the
+    /// full size, so the box spans ±half of each. In side view the X extent is
+    /// drawn against the out-of-plane limits ±`max_z` instead of Y.
+    pub(super) fn draw_domain_boundary(&self, ctx: &mut quarkstrom::RenderContext) {
+        let half_w = self.domain_width * 0.5;
+        let (min, max) = if self.side_view_mode {
+            let half_z = if self.sim_config.enable_out_of_plane {
+                self.sim_config.max_z
+            } else {
+                0.0
+            };
+            (Vec2::new(-half_w, -half_z), Vec2::new(half_w, half_z))
+        } else {
+            let half_h = self.domain_height * 0.5;
+            (Vec2::new(-half_w, -half_h), Vec2::new(half_w, half_h))
+        };
+
+        let color = self.theme.overlay_color([200, 200, 200, 160]);
+        let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+        for i in 0..4 {
+            ctx.draw_line(corners[i], corners[(i + 1) % 4], color);
+        }
+
+        // Side view: mark z = 0 so the depth extent reads as ± around the plane
+        if self.side_view_mode && max.y > 0.0 {
+            let dash = [120, 180, 255, 120];
+            let step = (2.0 * half_w / 40.0).max(1.0);
+            let mut x = -half_w;
+            while x < half_w {
+                let end = (x + 0.5 * step).min(half_w);
+                ctx.draw_line(Vec2::new(x, 0.0), Vec2::new(end, 0.0), dash);
+                x += step;
+            }
+        }
+    }
+}
@@ -6,6 +6,7 @@ use ultraviolet::Vec2;
 
 impl Renderer {
     /// Draw electric field isolines using a simple marching squares algorithm.
+    /// Levels inside the local-refinement band are traced on subdivided cells.
     pub fn draw_field_isolines(&mut self, ctx: &mut quarkstrom::RenderContext) {
         // Use full current viewport and aspect-aware bounds
         let half_view = Vec2::new(
@@ -151,6 +152,13 @@ impl Renderer {
                 && (level_t - 0.5).abs()
                     <= (self.sim_config.isoline_local_refine_band.clamp(0.1, 1.0) * 0.5);
 
+            if !refine_enabled {
+                for (a, b) in marching_squares(&field_grid, nx, ny, min, grid_spacing, iso) {
+                    ctx.draw_line(a, b, color);
+                }
+                continue;
+            }
+
             for ix in 0..nx - 1 {
                 for iy in 0..ny - 1 {
                     let i00 = iy * nx + ix;
@@ -186,7 +194,7 @@ impl Renderer {
                         || (v11 - iso) * (v01 - iso) < 0.0
                         || (v01 - iso) * (v00 - iso) < 0.0;
 
-                    if refine_enabled && coarse_crosses {
+                    if coarse_crosses {
                         let n = self.sim_config.isoline_local_refine_factor.max(2);
                         let inv_n = 1.0 / n as f32;
                         for sx in 0..n {
@@ -218,49 +226,19 @@ impl Renderer {
                                     p00.y + (p10.y - p00.y) * fx1 + (p01.y - p00.y) * fy1,
                                 );
 
-                                let mut sub_pts = Vec::new();
-                                if (pv00 - iso) * (pv10 - iso) < 0.0 {
-                                    let t = (iso - pv00) / (pv10 - pv00);
-                                    sub_pts.push(lerp(pp00, pp10, t));
-                                }
-                                if (pv10 - iso) * (pv11 - iso) < 0.0 {
-                                    let t = (iso - pv10) / (pv11 - pv10);
-                                    sub_pts.push(lerp(pp10, pp11, t));
-                                }
-                                if (pv11 - iso) * (pv01 - iso) < 0.0 {
-                                    let t = (iso - pv11) / (pv01 - pv11);
-                                    sub_pts.push(lerp(pp11, pp01, t));
-                                }
-                                if (pv01 - iso) * (pv00 - iso) < 0.0 {
-                                    let t = (iso - pv01) / (pv00 - pv01);
-                                    sub_pts.push(lerp(pp01, pp00, t));
-                                }
-                                if sub_pts.len() == 2 {
-                                    ctx.draw_line(sub_pts[0], sub_pts[1], color);
+                                if let Some((a, b)) = cell_segment(
+                                    [pv00, pv10, pv11, pv01],
+                                    [pp00, pp10, pp11, pp01],
+                                    iso,
+                                ) {
+                                    ctx.draw_line(a, b, color);
                                 }
                             }
                         }
-                    } else {
-                        let mut pts = Vec::new();
-                        if (v00 - iso) * (v10 - iso) < 0.0 {
-                            let t = (iso - v00) / (v10 - v00);
-                            pts.push(lerp(p00, p10, t));
-                        }
-                        if (v10 - iso) * (v11 - iso) < 0.0 {
-                            let t = (iso - v10) / (v11 - v10);
-                            pts.push(lerp(p10, p11, t));
-                        }
-                        if (v11 - iso) * (v01 - iso) < 0.0 {
-                            let t = (iso - v11) / (v01 - v11);
-                            pts.push(lerp(p11, p01, t));
-                        }
-                        if (v01 - iso) * (v00 - iso) < 0.0 {
-                            let t = (iso - v01) / (v00 - v01);
-                            pts.push(lerp(p01, p00, t));
-                        }
-                        if pts.len() == 2 {
-                            ctx.draw_line(pts[0], pts[1], color);
-                        }
+                    } else if let Some((a, b)) =
+                        cell_segment([v00, v10, v11, v01], [p00, p10, p11, p01], iso)
+                    {
+                        ctx.draw_line(a, b, color);
                     }
                 }
             }
@@ -295,6 +273,73 @@ pub fn compute_field_at_point(
     field
 }
 
+/// Net force a hypothetical `body` would feel at its position: Coulomb force
+/// from `compute_field_at_point` plus LJ contributions from nearby bodies.
+/// Read-only; `bodies` are not modified.
+pub fn preview_force_on(body: &Body, bodies: &[Body], config: &crate::config::SimConfig) -> Vec2 {
+    let mut force =
+        compute_field_at_point(bodies, body.pos, config) * (config.coulomb_constant * body.charge);
+    if body.species.lj_enabled() {
+        let cutoff = crate::species::max_lj_cutoff();
+        let max_force =
+            crate::config::COLLISION_PASSES as f32 * crate::config::LJ_FORCE_MAX;
+        for other in bodies {
+            if other.species.lj_enabled() && (other.pos - body.pos).mag_sq() < cutoff * cutoff {
+                force += crate::simulation::forces::lj_pair_force(other, body, max_force);
+            }
+        }
+    }
+    force
+}
+
+/// Marching-squares segment for one cell. Corner values and positions are
+/// given counter-clockwise from the lower-left corner. Returns `None` when the
+/// level does not cross the cell or the cell is an ambiguous saddle.
+fn cell_segment(v: [f32; 4], p: [Vec2; 4], iso: f32) -> Option<(Vec2, Vec2)> {
+    let mut pts = [Vec2::zero(); 4];
+    let mut n = 0;
+    for k in 0..4 {
+        let (va, vb) = (v[k], v[(k + 1) % 4]);
+        if (va - iso) * (vb - iso) < 0.0 {
+            pts[n] = lerp(p[k], p[(k + 1) % 4], (iso - va) / (vb - va));
+            n += 1;
+        }
+    }
+    (n == 2).then(|| (pts[0], pts[1]))
+}
+
+/// Contour segments of level `iso` over a row-major `nx × ny` grid whose
+/// sample `(ix, iy)` sits at `origin + spacing * (ix, iy)`.
+pub fn marching_squares(
+    grid: &[f32],
+    nx: usize,
+    ny: usize,
+    origin: Vec2,
+    spacing: f32,
+    iso: f32,
+) -> Vec<(Vec2, Vec2)> {
+    let mut segments = Vec::new();
+    if nx < 2 || ny < 2 || grid.len() < nx * ny {
+        return segments;
+    }
+    let at = |ix: usize, iy: usize| origin + Vec2::new(ix as f32, iy as f32) * spacing;
+    for iy in 0..ny - 1 {
+        for ix in 0..nx - 1 {
+            let v = [
+                grid[iy * nx + ix],
+                grid[iy * nx + ix + 1],
+                grid[(iy + 1) * nx + ix + 1],
+                grid[(iy + 1) * nx + ix],
+            ];
+            let p = [at(ix, iy), at(ix + 1, iy), at(ix + 1, iy + 1), at(ix, iy + 1)];
+            if let Some(seg) = cell_segment(v, p, iso) {
+                segments.push(seg);
+            }
+        }
+    }
+    segments
+}
+
 fn lerp(a: Vec2, b: Vec2, t: f32) -> Vec2 {
     a + (b - a) * t
 }
@@ -380,3 +425,44 @@ pub fn compute_potential_at_point(
         0.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(nx: usize, ny: usize, origin: Vec2, spacing: f32, f: impl Fn(Vec2) -> f32) -> Vec<f32> {
+        let mut g = Vec::with_capacity(nx * ny);
+        for iy in 0..ny {
+            for ix in 0..nx {
+                g.push(f(origin + Vec2::new(ix as f32, iy as f32) * spacing));
+            }
+        }
+        g
+    }
+
+    #[test]
+    fn linear_potential_gives_straight_equipotential() {
+        // V = x: the V = 2.5 contour is the vertical line x = 2.5
+        let g = grid(6, 5, Vec2::zero(), 1.0, |p| p.x);
+        let segs = marching_squares(&g, 6, 5, Vec2::zero(), 1.0, 2.5);
+        assert_eq!(segs.len(), 4);
+        for (a, b) in segs {
+            assert!((a.x - 2.5).abs() < 1e-5 && (b.x - 2.5).abs() < 1e-5);
+            assert!((a.y - b.y).abs() > 0.99);
+        }
+    }
+
+    #[test]
+    fn point_charge_potential_gives_closed_ring() {
+        // V = 1/r: the V = 0.25 contour is the circle r = 4
+        let origin = Vec2::new(-10.0, -10.0);
+        let g = grid(81, 81, origin, 0.25, |p| 1.0 / p.mag().max(1e-3));
+        let segs = marching_squares(&g, 81, 81, origin, 0.25, 0.25);
+        assert!(segs.len() > 50);
+        for (a, b) in segs {
+            for p in [a, b] {
+                assert!((p.mag() - 4.0).abs() < 0.05, "point {:?} off the ring", p);
+            }
+        }
+    }
+}
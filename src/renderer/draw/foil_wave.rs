@@ -16,16 +16,7 @@ impl Renderer {
         let time = *crate::renderer::state::SIM_TIME.lock();
         for id in &self.selected_foil_ids {
             if let Some(foil) = self.foils.iter().find(|f| f.id == *id) {
-                let effective_current = if foil.switch_hz > 0.0 {
-                    let ac_component = if (time * foil.switch_hz) % 1.0 < 0.5 {
-                        foil.ac_current
-                    } else {
-                        -foil.ac_current
-                    };
-                    foil.dc_current + ac_component
-                } else {
-                    foil.dc_current
-                };
+                let effective_current = foil.dc_current + foil.ac_component(time);
 
                 let state = if effective_current.abs() > f32::EPSILON {
                     effective_current.signum()
@@ -67,6 +58,7 @@ impl Renderer {
         let base_x = self.pos.x - self.scale;
         let base_y = self.pos.y - self.scale + spacing;
         let x_scale = (2.0 * self.scale) / max_time;
+        let trace = self.theme.overlay_color([255, 255, 255, 255]);
 
         for (idx, id) in self.selected_foil_ids.iter().enumerate() {
             if let Some(history) = self.foil_wave_history.get(id) {
@@ -82,7 +74,7 @@ impl Renderer {
                         ctx.draw_line(
                             Vec2::new(x0, y_base + pv * amplitude),
                             Vec2::new(x1, y_base + pv * amplitude),
-                            [255, 255, 255, 255],
+                            trace,
                         );
                     }
                     prev = Some((t, state));
@@ -94,7 +86,7 @@ impl Renderer {
                     ctx.draw_line(
                         Vec2::new(x0, y_base + pv * amplitude),
                         Vec2::new(x1, y_base + pv * amplitude),
-                        [255, 255, 255, 255],
+                        trace,
                     );
                 } else if let Some(&(_, last)) = history.last() {
                     let x0 = base_x;
@@ -102,7 +94,7 @@ impl Renderer {
                     ctx.draw_line(
                         Vec2::new(x0, y_base + last * amplitude),
                         Vec2::new(x1, y_base + last * amplitude),
-                        [255, 255, 255, 255],
+                        trace,
                     );
                 }
             }
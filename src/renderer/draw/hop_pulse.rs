@@ -0,0 +1,70 @@
+use crate::renderer::Renderer;
+use crate::simulation::electron_hopping::HopEvent;
+use std::collections::VecDeque;
+use std::time::Instant;
+use ultraviolet::Vec2;
+
+/// Upper bound on simultaneously drawn hop pulses; the oldest are dropped first.
+pub(crate) const MAX_HOP_PULSES: usize = 512;
+
+/// Short-lived ring at a hop donor or acceptor position.
+pub(crate) struct HopPulse {
+    pub pos: Vec2,
+    pub donor: bool,
+    pub born: Instant,
+}
+
+/// Add donor and acceptor pulses for `events`, keeping at most `cap` pulses.
+pub(crate) fn push_hop_pulses(
+    pulses: &mut VecDeque<HopPulse>,
+    events: impl IntoIterator<Item = HopEvent>,
+    now: Instant,
+    cap: usize,
+) {
+    for event in events {
+        pulses.push_back(HopPulse { pos: event.donor, donor: true, born: now });
+        pulses.push_back(HopPulse { pos: event.acceptor, donor: false, born: now });
+    }
+    while pulses.len() > cap {
+        pulses.pop_front();
+    }
+}
+
+/// Drop pulses older than `decay_s` seconds.
+pub(crate) fn expire_hop_pulses(pulses: &mut VecDeque<HopPulse>, now: Instant, decay_s: f32) {
+    pulses.retain(|p| now.duration_since(p.born).as_secs_f32() < decay_s);
+}
+
+impl Renderer {
+    /// Consume hop events published by the simulation and age existing pulses.
+    pub fn update_hop_pulses(&mut self) {
+        let events: Vec<HopEvent> = crate::renderer::state::HOP_EVENTS.lock().drain(..).collect();
+        if !self.show_hop_pulses {
+            self.hop_pulses.clear();
+            return;
+        }
+        let now = Instant::now();
+        push_hop_pulses(&mut self.hop_pulses, events, now, MAX_HOP_PULSES);
+        expire_hop_pulses(&mut self.hop_pulses, now, self.hop_pulse_decay);
+    }
+
+    /// Draw each pulse as an expanding ring that fades over the decay time.
+    pub fn draw_hop_pulses(&self, ctx: &mut quarkstrom::RenderContext) {
+        if !self.show_hop_pulses || self.hop_pulses.is_empty() {
+            return;
+        }
+        let now = Instant::now();
+        let decay = self.hop_pulse_decay.max(1e-3);
+        for pulse in &self.hop_pulses {
+            let t = (now.duration_since(pulse.born).as_secs_f32() / decay).min(1.0);
+            let alpha = ((1.0 - t) * 255.0) as u8;
+            // Donors flash yellow, acceptors cyan
+            let color = if pulse.donor {
+                [255, 230, 80, alpha]
+            } else {
+                [80, 230, 255, alpha]
+            };
+            super::draw_ring(ctx, pulse.pos, 1.0 + 3.0 * t, color);
+        }
+    }
+}
@@ -1,9 +1,16 @@
 // Drawing routines split into focused modules
 
-mod charge;
-mod density;
+pub(crate) mod charge;
+pub(crate) mod cluster;
+pub(crate) mod density;
+pub(crate) mod depth_cue;
+mod domain;
 mod field;
 mod foil_wave;
+pub(crate) mod hop_pulse;
+pub(crate) mod speed_highlight;
+pub(crate) mod streamline;
+mod svg;
 
 pub use field::compute_field_at_point;
 
@@ -13,9 +20,28 @@ use crate::profile_scope;
 use crate::quadtree::Quadtree;
 use palette::{Hsluv, IntoColor, Srgba};
 use rayon::prelude::*;
+use std::collections::HashMap;
 use std::sync::atomic::Ordering;
 use ultraviolet::Vec2;
 
+const RING_SEGMENTS: usize = 16;
+
+/// Circle outline of `radius` around `center`, drawn as line segments.
+pub(crate) fn draw_ring(
+    ctx: &mut quarkstrom::RenderContext,
+    center: Vec2,
+    radius: f32,
+    color: [u8; 4],
+) {
+    let mut prev = center + Vec2::new(radius, 0.0);
+    for i in 1..=RING_SEGMENTS {
+        let angle = i as f32 / RING_SEGMENTS as f32 * std::f32::consts::TAU;
+        let next = center + Vec2::new(angle.cos(), angle.sin()) * radius;
+        ctx.draw_line(prev, next, color);
+        prev = next;
+    }
+}
+
 impl super::Renderer {
     pub fn draw(&mut self, ctx: &mut quarkstrom::RenderContext, width: u16, height: u16) {
         profile_scope!("draw_particles");
@@ -38,6 +64,8 @@ impl super::Renderer {
                 std::mem::swap(&mut self.foils, &mut FOILS.lock());
                 self.frame = self.frame.wrapping_add(1);
                 self.update_foil_wave_history();
+                self.update_hop_pulses();
+                self.refresh_metal_clusters();
 
                 // Update plotting system with new data
                 let current_time = *crate::renderer::state::SIM_TIME.lock();
@@ -56,29 +84,91 @@ impl super::Renderer {
                     profile_scope!("diagnostics_transference");
                     diagnostic.calculate(&self.bodies);
                 }
+                if let Some(ref mut diag) = self.resistance_diagnostic {
+                    profile_scope!("diagnostics_resistance");
+                    let (group_a, group_b) = {
+                        let shared = crate::simulation::eis::EIS_RESULTS.lock();
+                        (shared.group_a_ids.clone(), shared.group_b_ids.clone())
+                    };
+                    diag.calculate(
+                        &self.bodies,
+                        &self.foils,
+                        &group_a,
+                        &group_b,
+                        coulomb_constant,
+                        current_time,
+                    );
+                }
+                if self.show_molarity_meter {
+                    profile_scope!("diagnostics_molarity");
+                    self.molarity_meter
+                        .calculate(&self.bodies, self.domain_width, self.domain_height);
+                }
+                let lod_factor = crate::diagnostics::lod_factor(
+                    self.bodies.len(),
+                    self.diagnostic_lod_scaling,
+                );
                 if let Some(ref mut diag) = self.foil_electron_fraction_diagnostic {
                     profile_scope!("diagnostics_foil_electron");
                     // Create a temporary quadtree for diagnostic calculation
                     let mut temp_quadtree = crate::quadtree::Quadtree::new(1.0, 2.0, 1, 1024);
                     temp_quadtree.nodes = self.quadtree.clone();
-                    // Throttle at 0.25 fs for more responsive UI
+                    // Throttle at the base interval (0.25 fs default), stretched on large systems
                     let current_time = *crate::renderer::state::SIM_TIME.lock();
                     let _ = diag.calculate_if_needed(
                         &self.bodies,
                         &self.foils,
                         &temp_quadtree,
                         current_time,
-                        0.25,
+                        self.diagnostic_lod_base_fs * lod_factor,
+                    );
+                }
+                if let Some(ref mut diag) = self.surface_roughness_diagnostic {
+                    profile_scope!("diagnostics_surface_roughness");
+                    let mut temp_quadtree = crate::quadtree::Quadtree::new(1.0, 2.0, 1, 1024);
+                    temp_quadtree.nodes = self.quadtree.clone();
+                    let current_time = *crate::renderer::state::SIM_TIME.lock();
+                    let _ = diag.calculate_if_needed(
+                        &self.bodies,
+                        &self.foils,
+                        &temp_quadtree,
+                        current_time,
+                        1.0,
                     );
                 }
                 if let Some(ref mut diag) = self.solvation_diagnostic {
                     profile_scope!("diagnostics_solvation");
-                    // Only run solvation diagnostic every 10th frame to improve performance
-                    if self.frame % 10 == 0 {
+                    // Only run solvation diagnostic every N frames, N growing with body count
+                    let interval = crate::diagnostics::lod_interval_frames(
+                        self.diagnostic_lod_base_frames,
+                        self.bodies.len(),
+                        self.diagnostic_lod_scaling,
+                    );
+                    if self.frame % interval == 0 {
                         // Use optimized quadtree-based calculation for much better performance
                         let mut temp_quadtree = crate::quadtree::Quadtree::new(1.0, 2.0, 1, 1024);
                         temp_quadtree.nodes = self.quadtree.clone();
                         diag.calculate(&self.bodies, &temp_quadtree);
+                        if self.cip_lifetime_enabled {
+                            self.cip_lifetimes.update(
+                                diag.cip_pairs.iter().map(|(cation, anion, _, _)| (*cation, *anion)),
+                                current_time,
+                            );
+                        }
+                        if self.solvation_transference_enabled {
+                            let drift = self
+                                .transference_number_diagnostic
+                                .as_ref()
+                                .map_or(Vec2::new(1.0, 0.0), |t| t.drift_direction);
+                            self.solvation_transference.record(
+                                crate::diagnostics::SolvationTransferenceSample::compute(
+                                    &self.bodies,
+                                    diag,
+                                    drift,
+                                    current_time,
+                                ),
+                            );
+                        }
                     }
 
                     // Periodic CSV logging of Solvation State
@@ -338,7 +428,9 @@ impl super::Renderer {
                                             self.solvation_csv_last_write_fs = current_time;
                                         }
                                         Err(e) => {
-                                            eprintln!(
+                                            crate::sim_log!(
+                                                Diagnostics,
+                                                Error,
                                                 "✗ Failed to write solvation CSV ({}): {}",
                                                 path.display(),
                                                 e
@@ -348,7 +440,7 @@ impl super::Renderer {
                                     }
                                 }
                                 Err(e) => {
-                                    eprintln!("✗ Failed to resolve solvation CSV path: {}", e);
+                                    crate::sim_log!(Diagnostics, Error, "✗ Failed to resolve solvation CSV path: {}", e);
                                     self.solvation_csv_last_write_fs = current_time;
                                 }
                             }
@@ -370,10 +462,33 @@ impl super::Renderer {
         ctx.clear_circles();
         ctx.clear_lines();
         ctx.clear_rects();
+        // Scripted flythrough overrides the manual camera while playing
+        if self.camera_path_playing {
+            let time_fs = *crate::renderer::state::SIM_TIME.lock();
+            if let Some((pos, scale)) = self.camera_path.sample(time_fs) {
+                self.pos = pos;
+                self.scale = scale;
+            }
+            // Picked up by `handle_screen_capture` once the frame is drawn
+            if self.camera_path_capture {
+                self.should_capture_next_frame = true;
+            }
+        }
         ctx.set_view_pos(self.pos);
         ctx.set_view_scale(self.scale);
 
+        // The renderer clears to black; other themes paint their background first
+        if self.theme.paints_background() {
+            let half_view = Vec2::new(self.scale * (width as f32 / height as f32), self.scale);
+            ctx.draw_rect(self.pos - half_view, self.pos + half_view, self.theme.clear_color());
+        }
+
         if !self.bodies.is_empty() {
+            // Per-body overlays follow the same z-slice filter as the bodies
+            let z_slice = self.z_slice;
+            let in_slice = move |b: &&crate::body::Body| depth_cue::in_z_slice(b.z, z_slice);
+            let slice_body = |id: u64| self.bodies.iter().find(|b| b.id == id).filter(in_slice);
+
             // --- Ion Classification Overlay (Draw halos BEFORE particles) ---
             if let Some(ref solvation_diag) = self.solvation_diagnostic {
                 // Draw CIP pairs with blue for cation, dark blue for anion, light blue for solvents
@@ -382,7 +497,7 @@ impl super::Renderer {
                         &solvation_diag.cip_pairs
                     {
                         // Draw cation
-                        if let Some(body) = self.bodies.iter().find(|b| b.id == cation_id) {
+                        if let Some(body) = slice_body(cation_id) {
                             ctx.draw_circle(
                                 self.get_display_position(body),
                                 body.radius * 2.0,
@@ -390,7 +505,7 @@ impl super::Renderer {
                             );
                         }
                         // Draw anion
-                        if let Some(body) = self.bodies.iter().find(|b| b.id == anion_id) {
+                        if let Some(body) = slice_body(anion_id) {
                             ctx.draw_circle(
                                 self.get_display_position(body),
                                 body.radius * 2.0,
@@ -399,7 +514,7 @@ impl super::Renderer {
                         }
                         // Draw cation solvents
                         for &solvent_id in cation_solvents {
-                            if let Some(body) = self.bodies.iter().find(|b| b.id == solvent_id) {
+                            if let Some(body) = slice_body(solvent_id) {
                                 ctx.draw_circle(
                                     self.get_display_position(body),
                                     body.radius * 1.5,
@@ -409,7 +524,7 @@ impl super::Renderer {
                         }
                         // Draw anion solvents
                         for &solvent_id in anion_solvents {
-                            if let Some(body) = self.bodies.iter().find(|b| b.id == solvent_id) {
+                            if let Some(body) = slice_body(solvent_id) {
                                 ctx.draw_circle(
                                     self.get_display_position(body),
                                     body.radius * 1.5,
@@ -426,7 +541,7 @@ impl super::Renderer {
                         &solvation_diag.sip_pairs
                     {
                         // Draw cation
-                        if let Some(body) = self.bodies.iter().find(|b| b.id == cation_id) {
+                        if let Some(body) = slice_body(cation_id) {
                             ctx.draw_circle(
                                 self.get_display_position(body),
                                 body.radius * 2.0,
@@ -434,7 +549,7 @@ impl super::Renderer {
                             );
                         }
                         // Draw anion
-                        if let Some(body) = self.bodies.iter().find(|b| b.id == anion_id) {
+                        if let Some(body) = slice_body(anion_id) {
                             ctx.draw_circle(
                                 self.get_display_position(body),
                                 body.radius * 2.0,
@@ -443,7 +558,7 @@ impl super::Renderer {
                         }
                         // Draw cation solvents
                         for &solvent_id in cation_solvents {
-                            if let Some(body) = self.bodies.iter().find(|b| b.id == solvent_id) {
+                            if let Some(body) = slice_body(solvent_id) {
                                 ctx.draw_circle(
                                     self.get_display_position(body),
                                     body.radius * 1.5,
@@ -453,7 +568,7 @@ impl super::Renderer {
                         }
                         // Draw anion solvents
                         for &solvent_id in anion_solvents {
-                            if let Some(body) = self.bodies.iter().find(|b| b.id == solvent_id) {
+                            if let Some(body) = slice_body(solvent_id) {
                                 ctx.draw_circle(
                                     self.get_display_position(body),
                                     body.radius * 1.5,
@@ -470,7 +585,7 @@ impl super::Renderer {
                         &solvation_diag.s2ip_pairs
                     {
                         // Draw cation
-                        if let Some(body) = self.bodies.iter().find(|b| b.id == cation_id) {
+                        if let Some(body) = slice_body(cation_id) {
                             ctx.draw_circle(
                                 self.get_display_position(body),
                                 body.radius * 2.0,
@@ -478,7 +593,7 @@ impl super::Renderer {
                             );
                         }
                         // Draw anion
-                        if let Some(body) = self.bodies.iter().find(|b| b.id == anion_id) {
+                        if let Some(body) = slice_body(anion_id) {
                             ctx.draw_circle(
                                 self.get_display_position(body),
                                 body.radius * 2.0,
@@ -487,7 +602,7 @@ impl super::Renderer {
                         }
                         // Draw cation solvents
                         for &solvent_id in cation_solvents {
-                            if let Some(body) = self.bodies.iter().find(|b| b.id == solvent_id) {
+                            if let Some(body) = slice_body(solvent_id) {
                                 ctx.draw_circle(
                                     self.get_display_position(body),
                                     body.radius * 1.5,
@@ -497,7 +612,7 @@ impl super::Renderer {
                         }
                         // Draw anion solvents
                         for &solvent_id in anion_solvents {
-                            if let Some(body) = self.bodies.iter().find(|b| b.id == solvent_id) {
+                            if let Some(body) = slice_body(solvent_id) {
                                 ctx.draw_circle(
                                     self.get_display_position(body),
                                     body.radius * 1.5,
@@ -512,7 +627,7 @@ impl super::Renderer {
                 if self.show_fd_ions {
                     for &(cation_id, ref cation_solvents) in &solvation_diag.fd_cations {
                         // Draw cation
-                        if let Some(body) = self.bodies.iter().find(|b| b.id == cation_id) {
+                        if let Some(body) = slice_body(cation_id) {
                             ctx.draw_circle(
                                 self.get_display_position(body),
                                 body.radius * 2.0,
@@ -521,7 +636,7 @@ impl super::Renderer {
                         }
                         // Draw cation solvents
                         for &solvent_id in cation_solvents {
-                            if let Some(body) = self.bodies.iter().find(|b| b.id == solvent_id) {
+                            if let Some(body) = slice_body(solvent_id) {
                                 ctx.draw_circle(
                                     self.get_display_position(body),
                                     body.radius * 1.5,
@@ -537,9 +652,15 @@ impl super::Renderer {
                 // Get active region data for SOC-based coloring (fallback)
                 let active_region_data = crate::renderer::state::ACTIVE_REGION_RENDER_DATA.lock();
                 
-                for body in &self.bodies {
+                for (body_index, body) in self.bodies.iter().enumerate() {
+                    if !in_slice(&body) {
+                        continue;
+                    }
                     let mut color = body.species.color();
                     let mut draw_radius = body.radius;
+                    if let Some(cluster_color) = self.metal_cluster_color(body_index) {
+                        color = cluster_color;
+                    }
                     
                     // Apply SOC-based coloring for intercalation electrode materials
                     // Use lithium_content field directly for SOC visualization
@@ -580,63 +701,14 @@ impl super::Renderer {
                         }
                     }
 
+                    color = self.theme.species_color(color);
+
                     if SHOW_Z_VISUALIZATION.load(Ordering::Relaxed) {
                         let max_z = self.sim_config.max_z.max(1.0);
                         let z_strength = *crate::renderer::state::Z_VISUALIZATION_STRENGTH.lock();
-
-                        // Normalize z-coordinate to -1.0 to 1.0 range
                         let z_normalized = (body.z / max_z).clamp(-1.0, 1.0);
-
-                        // Apply elegant z-depth effects:
-                        // 1. Size scaling: particles closer to camera (positive z) appear larger
-                        let size_factor = 1.0 + (z_strength * 0.3 * z_normalized);
-                        draw_radius *= size_factor.max(0.3); // Don't let particles disappear
-
-                        // 2. INVERTED Color brightness: particles at higher z are brighter, z=0 is darker for contrast
-                        let brightness_factor = 1.0 + (z_strength * 0.6 * z_normalized); // Positive z gets brighter
-                        let brightness = brightness_factor.clamp(0.2, 2.0); // Allow overbrightening, but keep minimum visibility
-
-                        // 3. Additional contrast: make z=0 depth darker
-                        let z_zero_darkening = if z_normalized.abs() < 0.1 {
-                            // Close to z=0
-                            1.0 - (z_strength * 0.3) // Darken z=0 particles
-                        } else {
-                            1.0
-                        };
-                        let final_brightness = brightness * z_zero_darkening.clamp(0.3, 1.0);
-
-                        // 4. Optional color tinting based on depth
-                        if z_strength > 2.0 {
-                            if z_normalized > 0.0 {
-                                // Closer particles get warmer tint (more red/yellow)
-                                let warm_factor = z_normalized * (z_strength - 2.0) * 0.1;
-                                color[0] =
-                                    (color[0] as f32 * (1.0 + warm_factor)).clamp(0.0, 255.0) as u8;
-                                color[1] = (color[1] as f32 * (1.0 + warm_factor * 0.5))
-                                    .clamp(0.0, 255.0)
-                                    as u8;
-                            } else {
-                                // Further particles get cooler tint (more blue)
-                                let cool_factor = (-z_normalized) * (z_strength - 2.0) * 0.1;
-                                color[2] =
-                                    (color[2] as f32 * (1.0 + cool_factor)).clamp(0.0, 255.0) as u8;
-                                color[1] = (color[1] as f32 * (1.0 + cool_factor * 0.5))
-                                    .clamp(0.0, 255.0)
-                                    as u8;
-                            }
-                        }
-
-                        // Apply brightness scaling (can now make particles brighter than original)
-                        color[0] = (color[0] as f32 * final_brightness).clamp(0.0, 255.0) as u8;
-                        color[1] = (color[1] as f32 * final_brightness).clamp(0.0, 255.0) as u8;
-                        color[2] = (color[2] as f32 * final_brightness).clamp(0.0, 255.0) as u8;
-
-                        // 5. Optional alpha transparency for depth
-                        if z_strength > 1.5 {
-                            let alpha_factor =
-                                1.0 - (z_normalized.abs() * (z_strength - 1.5) * 0.2);
-                            color[3] = (color[3] as f32 * alpha_factor.clamp(0.5, 1.0)) as u8;
-                        }
+                        (draw_radius, color) =
+                            self.depth_cue.apply(z_normalized, z_strength, draw_radius, color);
                     }
 
                     if body.species == Species::LithiumIon {
@@ -672,7 +744,7 @@ impl super::Renderer {
                                 ctx.draw_circle(
                                     self.get_display_position(body),
                                     body.radius * 1.1,
-                                    [255, 255, 0, 32],
+                                    self.theme.overlay_color([255, 255, 0, 32]),
                                 );
                             }
 
@@ -740,6 +812,15 @@ impl super::Renderer {
 
                     ctx.draw_circle(self.get_display_position(body), draw_radius, color);
 
+                    if speed_highlight::exceeds_speed(
+                        body.vel,
+                        body.vz,
+                        self.highlight_velocity_threshold,
+                    ) {
+                        let center = self.get_display_position(body);
+                        speed_highlight::draw_speed_ring(ctx, center, draw_radius * 1.4);
+                    }
+
                     // Visualize electron count for FoilMetal
                     if self.show_foil_electron_deficiency && body.species == Species::FoilMetal {
                         let neutral_electrons = crate::config::FOIL_NEUTRAL_ELECTRONS;
@@ -781,7 +862,7 @@ impl super::Renderer {
             if self.sim_config.show_velocity_vectors {
                 let scale = self.velocity_vector_scale;
                 let color = [0, 255, 0, 255];
-                for body in &self.bodies {
+                for body in self.bodies.iter().filter(in_slice) {
                     let end = body.pos + body.vel * scale;
                     ctx.draw_line(body.pos, end, color);
                 }
@@ -790,7 +871,7 @@ impl super::Renderer {
             // --- Dipole Overlay for EC/DMC ---
             if self.show_dipoles {
                 use crate::body::Species;
-                for body in &self.bodies {
+                for body in self.bodies.iter().filter(in_slice) {
                     if !(matches!(body.species, Species::EC | Species::DMC)
                         && !body.electrons.is_empty())
                     {
@@ -813,36 +894,56 @@ impl super::Renderer {
                         Species::DMC => [50, 255, 150, 220],
                         _ => [200, 200, 200, 200],
                     };
-                    ctx.draw_line(start, end, color);
+                    ctx.draw_line(start, end, self.theme.overlay_color(color));
                 }
             }
 
+            let pending = self.theme.overlay_color([0xff; 4]);
             if let Some(body) = &self.confirmed_bodies {
-                ctx.draw_circle(self.get_display_position(body), body.radius, [0xff; 4]);
-                ctx.draw_line(body.pos, body.pos + body.vel, [0xff; 4]);
+                ctx.draw_circle(self.get_display_position(body), body.radius, pending);
+                ctx.draw_line(body.pos, body.pos + body.vel, pending);
             }
 
             if let Some(body) = &self.spawn_body {
-                ctx.draw_circle(self.get_display_position(body), body.radius, [0xff; 4]);
-                ctx.draw_line(body.pos, body.pos + body.vel, [0xff; 4]);
+                ctx.draw_circle(self.get_display_position(body), body.radius, pending);
+                ctx.draw_line(body.pos, body.pos + body.vel, pending);
+
+                // Net force the pending body would feel; the live bodies are only read
+                self.spawn_force_preview = self
+                    .show_spawn_force_preview
+                    .then(|| field::preview_force_on(body, &self.bodies, &self.sim_config));
+                if let Some(force) = self.spawn_force_preview {
+                    let mag = force.mag();
+                    if mag > 1e-12 {
+                        let dir = force / mag;
+                        let tip = body.pos + force * self.spawn_force_preview_scale;
+                        let head = body.radius.max(0.5);
+                        let back = tip - dir * head;
+                        let side = Vec2::new(-dir.y, dir.x) * (head * 0.5);
+                        let color = [255, 80, 255, 255];
+                        ctx.draw_line(body.pos, tip, color);
+                        ctx.draw_line(tip, back + side, color);
+                        ctx.draw_line(tip, back - side, color);
+                    }
+                }
             }
 
             if let Some(id) = self.selected_particle_id {
-                if let Some(body) = self.bodies.iter().find(|b| b.id == id) {
+                if let Some(body) = slice_body(id) {
                     ctx.draw_circle(
                         self.get_display_position(body),
                         body.radius * 1.5,
-                        [255, 255, 0, 32],
+                        self.theme.overlay_color([255, 255, 0, 32]),
                     );
                 }
             }
 
             for id in &self.selected_particle_ids {
-                if let Some(body) = self.bodies.iter().find(|b| b.id == *id) {
+                if let Some(body) = slice_body(*id) {
                     ctx.draw_circle(
                         self.get_display_position(body),
                         body.radius * 3.0,
-                        [255, 255, 0, 128],
+                        self.theme.overlay_color([255, 255, 0, 128]),
                     );
                 }
             }
@@ -933,36 +1034,82 @@ impl super::Renderer {
             let color = [255, 0, 0, 255];
 
             let half_view = Vec2::new(self.scale * (width as f32 / height as f32), self.scale);
-            let min = self.pos - half_view;
+            let mut min = self.pos - half_view;
             let max = self.pos + half_view;
 
+            // Smoothing keys samples by world cell, so snap the grid to the world
+            let smoothing = self.field_vector_smoothing.clamp(0.0, 0.99);
+            if smoothing > 0.0 {
+                min = Vec2::new(
+                    (min.x / grid_spacing).floor() * grid_spacing,
+                    (min.y / grid_spacing).floor() * grid_spacing,
+                );
+            } else {
+                self.field_vector_cache.clear();
+            }
+
             let nx = ((max.x - min.x) / grid_spacing).ceil() as usize;
             let ny = ((max.y - min.y) / grid_spacing).ceil() as usize;
-            let mut lines = vec![(Vec2::zero(), Vec2::zero()); nx * ny];
+            let mut samples = vec![(Vec2::zero(), Vec2::zero()); nx * ny];
 
-            lines.par_iter_mut().enumerate().for_each(|(i, line)| {
+            samples.par_iter_mut().enumerate().for_each(|(i, sample)| {
                 let ix = i % nx;
                 let iy = i / nx;
                 let x = min.x + ix as f32 * grid_spacing;
                 let y = min.y + iy as f32 * grid_spacing;
                 let pos = Vec2::new(x, y);
                 let field = compute_field_at_point(&self.bodies, pos, &self.sim_config);
-                *line = (pos, pos + field * field_scale);
+                *sample = (pos, field);
             });
 
-            for (start, end) in lines {
-                ctx.draw_line(start, end, color);
+            if smoothing > 0.0 {
+                // Exponential filter per cell; cells that leave the view are dropped
+                let mut cache = HashMap::with_capacity(samples.len());
+                for (pos, field) in samples.iter_mut() {
+                    let key = (
+                        (pos.x / grid_spacing).round() as i64,
+                        (pos.y / grid_spacing).round() as i64,
+                    );
+                    if let Some(prev) = self.field_vector_cache.get(&key) {
+                        *field = *prev * smoothing + *field * (1.0 - smoothing);
+                    }
+                    cache.insert(key, *field);
+                }
+                self.field_vector_cache = cache;
             }
+
+            for (pos, field) in samples {
+                ctx.draw_line(pos, pos + field * field_scale, color);
+            }
+        }
+
+        if self.show_streamlines {
+            self.draw_streamlines(ctx);
+        }
+
+        if self.show_domain_boundary {
+            self.draw_domain_boundary(ctx);
         }
 
         if !self.selected_foil_ids.is_empty() {
             self.draw_foil_square_waves(ctx);
         }
 
+        self.draw_hop_pulses(ctx);
+
         if self.current_tab == super::GuiTab::Measurement {
             if let (Some(start), Some(cursor)) = (self.measurement_start, self.measurement_cursor) {
                 self.draw_measurement_overlay(ctx, start, cursor);
             }
+            // Halo around particles the endpoints are snapped to
+            for id in [self.measurement_start_snapped_id, self.measurement_snapped_id]
+                .into_iter()
+                .flatten()
+            {
+                if let Some(body) = self.bodies.iter().find(|b| b.id == id) {
+                    ctx.draw_circle(body.pos, body.radius * 1.6, [255, 220, 0, 90]);
+                }
+            }
         }
 
         // Draw manual measurement visualization
@@ -995,7 +1142,17 @@ impl super::Renderer {
             }
         }
 
-        // Screen capture overlays removed
+        // Outline of the world region that captures are cropped to
+        if let Some((a, b)) = self.capture_region {
+            let (lo, hi) = (a.min_by_component(b), a.max_by_component(b));
+            let color = self.theme.overlay_color([255, 200, 0, 200]);
+            let corners = [lo, Vec2::new(hi.x, lo.y), hi, Vec2::new(lo.x, hi.y)];
+            for (&p, &q) in corners.iter().zip(corners.iter().cycle().skip(1)) {
+                ctx.draw_line(p, q, color);
+            }
+        }
+
+        self.handle_screen_capture(*SIM_TIME.lock(), width, height);
     }
 }
 
@@ -0,0 +1,19 @@
+use ultraviolet::Vec2;
+
+const RING_COLOR: [u8; 4] = [255, 40, 40, 230];
+
+/// Whether a body with in-plane velocity `vel` and out-of-plane `vz` moves
+/// strictly faster than `threshold` (Å/fs); `None` highlights nothing.
+/// A non-finite speed always counts as too fast so blown-up bodies stand out.
+pub fn exceeds_speed(vel: Vec2, vz: f32, threshold: Option<f32>) -> bool {
+    let Some(limit) = threshold else {
+        return false;
+    };
+    let speed_sq = vel.mag_sq() + vz * vz;
+    !speed_sq.is_finite() || speed_sq > limit * limit
+}
+
+/// Red outline drawn around a fast mover.
+pub(crate) fn draw_speed_ring(ctx: &mut quarkstrom::RenderContext, center: Vec2, radius: f32) {
+    super::draw_ring(ctx, center, radius, RING_COLOR);
+}
@@ -0,0 +1,132 @@
+use super::density::domain_grid;
+use crate::body::Body;
+use crate::renderer::Renderer;
+use ultraviolet::Vec2;
+
+/// Cell size (Å) of the binned velocity field.
+const STREAMLINE_CELL: f32 = 5.0;
+/// Seed spacing (Å) at a streamline density of 1.
+const STREAMLINE_BASE_SEED_SPACING: f32 = 20.0;
+/// Euler steps per streamline, each half a cell long.
+const STREAMLINE_MAX_STEPS: usize = 200;
+/// Streamlines stop where the speed falls below this fraction of the maximum.
+const STREAMLINE_MIN_SPEED_FRACTION: f32 = 0.02;
+
+/// Mean body velocity per cell on the density grid layout.
+pub(crate) struct VelocityGrid {
+    pub min: Vec2,
+    pub spacing: f32,
+    pub nx: usize,
+    pub ny: usize,
+    pub cells: Vec<Vec2>,
+}
+
+impl VelocityGrid {
+    /// Bin body velocities over `±half_width` x `±half_height`; empty cells
+    /// hold zero velocity.
+    pub fn from_bodies(bodies: &[Body], half_width: f32, half_height: f32, spacing: f32) -> Self {
+        let (min, nx, ny) = domain_grid(half_width, half_height, spacing);
+        let mut sums = vec![Vec2::zero(); nx * ny];
+        let mut counts = vec![0u32; nx * ny];
+        for body in bodies {
+            let fx = (body.pos.x - min.x) / spacing;
+            let fy = (body.pos.y - min.y) / spacing;
+            if fx < 0.0 || fy < 0.0 || fx >= nx as f32 || fy >= ny as f32 {
+                continue;
+            }
+            let i = fy as usize * nx + fx as usize;
+            sums[i] += body.vel;
+            counts[i] += 1;
+        }
+        let cells = sums
+            .into_iter()
+            .zip(counts)
+            .map(|(v, n)| if n > 0 { v / n as f32 } else { Vec2::zero() })
+            .collect();
+        Self { min, spacing, nx, ny, cells }
+    }
+
+    /// Bilinear velocity at `pos` from the cell centres, `None` outside the grid.
+    pub fn sample(&self, pos: Vec2) -> Option<Vec2> {
+        let gx = (pos.x - self.min.x) / self.spacing - 0.5;
+        let gy = (pos.y - self.min.y) / self.spacing - 0.5;
+        if gx < -0.5 || gy < -0.5 || gx > self.nx as f32 - 0.5 || gy > self.ny as f32 - 0.5 {
+            return None;
+        }
+        let gx = gx.clamp(0.0, (self.nx - 1) as f32);
+        let gy = gy.clamp(0.0, (self.ny - 1) as f32);
+        let (x0, y0) = (gx.floor() as usize, gy.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(self.nx - 1), (y0 + 1).min(self.ny - 1));
+        let (tx, ty) = (gx - x0 as f32, gy - y0 as f32);
+        let at = |x: usize, y: usize| self.cells[y * self.nx + x];
+        let bottom = at(x0, y0) * (1.0 - tx) + at(x1, y0) * tx;
+        let top = at(x0, y1) * (1.0 - tx) + at(x1, y1) * tx;
+        Some(bottom * (1.0 - ty) + top * ty)
+    }
+
+    pub fn max_speed(&self) -> f32 {
+        self.cells.iter().map(|v| v.mag()).fold(0.0, f32::max)
+    }
+
+    /// Follow the field from `seed` with fixed-length Euler steps of `step`
+    /// along the local flow direction. Returns the visited points with the
+    /// speed at each; tracing stops outside the grid or once the speed drops
+    /// below `min_speed`.
+    pub fn trace(&self, seed: Vec2, step: f32, max_steps: usize, min_speed: f32) -> Vec<(Vec2, f32)> {
+        let mut points = Vec::new();
+        let mut pos = seed;
+        for _ in 0..=max_steps {
+            let Some(vel) = self.sample(pos) else {
+                break;
+            };
+            let speed = vel.mag();
+            if speed < min_speed {
+                break;
+            }
+            points.push((pos, speed));
+            pos += vel / speed * step;
+        }
+        points
+    }
+}
+
+/// Blue (slow) to red (fast) ramp for `t` in [0, 1].
+fn speed_color(t: f32) -> [u8; 4] {
+    let t = t.clamp(0.0, 1.0);
+    let green = 80.0 + 100.0 * (1.0 - (2.0 * t - 1.0).abs());
+    [(255.0 * t) as u8, green as u8, (255.0 * (1.0 - t)) as u8, 220]
+}
+
+impl Renderer {
+    /// Trace streamlines of the binned mean velocity field from a regular seed
+    /// grid and draw them colored by local speed.
+    pub fn draw_streamlines(&self, ctx: &mut quarkstrom::RenderContext) {
+        let grid = VelocityGrid::from_bodies(
+            &self.bodies,
+            self.domain_width,
+            self.domain_height,
+            STREAMLINE_CELL,
+        );
+        let max_speed = grid.max_speed();
+        if max_speed <= 0.0 {
+            return;
+        }
+        let min_speed = max_speed * STREAMLINE_MIN_SPEED_FRACTION;
+        let seed_spacing = STREAMLINE_BASE_SEED_SPACING / self.streamline_density.max(0.05);
+        let seeds_x = ((2.0 * self.domain_width) / seed_spacing).ceil().max(1.0) as usize;
+        let seeds_y = ((2.0 * self.domain_height) / seed_spacing).ceil().max(1.0) as usize;
+        for sy in 0..seeds_y {
+            for sx in 0..seeds_x {
+                let seed = Vec2::new(
+                    -self.domain_width + (sx as f32 + 0.5) * seed_spacing,
+                    -self.domain_height + (sy as f32 + 0.5) * seed_spacing,
+                );
+                let points = grid.trace(seed, 0.5 * STREAMLINE_CELL, STREAMLINE_MAX_STEPS, min_speed);
+                for pair in points.windows(2) {
+                    let (a, speed) = pair[0];
+                    ctx.draw_line(a, pair[1].0, speed_color(speed / max_speed));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::body::Species;
+use crate::renderer::scale_bar::ScaleBar;
+use crate::renderer::Renderer;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+use ultraviolet::Vec2;
+
+/// Above this many circles the export still runs but warns about file size.
+const SVG_LARGE_CIRCLE_COUNT: usize = 50_000;
+
+fn svg_color(color: [u8; 4]) -> String {
+    format!(
+        "fill=\"rgb({},{},{})\" fill-opacity=\"{:.3}\"",
+        color[0],
+        color[1],
+        color[2],
+        color[3] as f32 / 255.0
+    )
+}
+
+impl Renderer {
+    /// World-space corners `(min, max)` of the current view, matching the
+    /// transform the GPU renderer applies from `pos` and `scale`.
+    fn svg_view_bounds(&self, width: f32, height: f32) -> (Vec2, Vec2) {
+        let half_view = Vec2::new(self.scale * (width / height), self.scale);
+        (self.pos - half_view, self.pos + half_view)
+    }
+
+    /// SVG of the visible frame: domain box, foil and particle selection
+    /// halos and one circle per body in view, in screen pixels of the current
+    /// window. Returns the document and its circle count.
+    pub fn svg_document(&self) -> (String, usize) {
+        let width = self.window_width.max(1) as f32;
+        let height = self.window_height.max(1) as f32;
+        let (min, max) = self.svg_view_bounds(width, height);
+        let px_per_unit = height / (max.y - min.y);
+        let to_px = |p: Vec2| ((p.x - min.x) * px_per_unit, (max.y - p.y) * px_per_unit);
+        let in_view = |p: Vec2, r: f32| {
+            p.x + r >= min.x && p.x - r <= max.x && p.y + r >= min.y && p.y - r <= max.y
+        };
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+            w = width,
+            h = height
+        );
+        let background = self.theme.clear_color();
+        let _ = writeln!(
+            out,
+            "<rect width=\"100%\" height=\"100%\" fill=\"rgb({},{},{})\"/>",
+            background[0], background[1], background[2]
+        );
+
+        // Domain box, with the same side-view extents as `draw_domain_boundary`
+        if self.show_domain_boundary {
+            let half_w = 0.5 * self.domain_width;
+            let half_h = if !self.side_view_mode {
+                0.5 * self.domain_height
+            } else if self.sim_config.enable_out_of_plane {
+                self.sim_config.max_z
+            } else {
+                0.0
+            };
+            let (x0, y0) = to_px(Vec2::new(-half_w, half_h));
+            let _ = writeln!(
+                out,
+                "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"none\" stroke=\"rgb(200,200,200)\" stroke-opacity=\"0.63\"/>",
+                x0,
+                y0,
+                2.0 * half_w * px_per_unit,
+                2.0 * half_h * px_per_unit
+            );
+        }
+
+        let theme = self.theme;
+        let mut circles = 0;
+        let mut circle = |out: &mut String, pos: Vec2, radius: f32, color: [u8; 4]| {
+            if !in_view(pos, radius) {
+                return;
+            }
+            let (cx, cy) = to_px(pos);
+            let _ = writeln!(
+                out,
+                "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" {}/>",
+                cx,
+                cy,
+                radius * px_per_unit,
+                svg_color(color)
+            );
+            circles += 1;
+        };
+
+        let in_slice = |b: &&crate::body::Body| super::depth_cue::in_z_slice(b.z, self.z_slice);
+        let selected_foil_bodies: HashSet<u64> = self
+            .foils
+            .iter()
+            .filter(|f| self.selected_foil_ids.contains(&f.id))
+            .flat_map(|f| f.body_ids.iter().copied())
+            .collect();
+        for (index, body) in self.bodies.iter().enumerate() {
+            if !in_slice(&body) {
+                continue;
+            }
+            let pos = self.get_display_position(body);
+            if body.species == Species::FoilMetal && selected_foil_bodies.contains(&body.id) {
+                circle(&mut out, pos, body.radius * 1.1, theme.overlay_color([255, 255, 0, 32]));
+            }
+            let color = self
+                .metal_cluster_color(index)
+                .unwrap_or_else(|| body.species.color());
+            circle(&mut out, pos, body.radius, theme.species_color(color));
+        }
+        for body in self
+            .bodies
+            .iter()
+            .filter(|b| self.selected_particle_ids.contains(&b.id))
+            .filter(in_slice)
+        {
+            let halo = theme.overlay_color([255, 255, 0, 128]);
+            circle(&mut out, self.get_display_position(body), body.radius * 3.0, halo);
+        }
+
+        // Same corner layout as the on-screen scale bar overlay
+        let ink = theme.overlay_color([255, 255, 255, 255]);
+        let ink = format!("rgb({},{},{})", ink[0], ink[1], ink[2]);
+        let mut baseline = height - 20.0;
+        if self.show_scale_bar {
+            if let Some(bar) = ScaleBar::for_view(px_per_unit, width, self.scale_bar_unit) {
+                let _ = writeln!(
+                    out,
+                    "<line x1=\"20\" y1=\"{y:.2}\" x2=\"{x2:.2}\" y2=\"{y:.2}\" stroke=\"{ink}\" stroke-width=\"3\"/>",
+                    y = baseline,
+                    x2 = 20.0 + bar.length_px,
+                    ink = ink
+                );
+                let _ = writeln!(
+                    out,
+                    "<text x=\"{:.2}\" y=\"{:.2}\" fill=\"{}\" font-size=\"16\" text-anchor=\"middle\">{}</text>",
+                    20.0 + 0.5 * bar.length_px,
+                    baseline - 8.0,
+                    ink,
+                    bar.label()
+                );
+                baseline -= 32.0;
+            }
+        }
+        if self.show_time_stamp {
+            let time_fs = *crate::renderer::state::SIM_TIME.lock();
+            let _ = writeln!(
+                out,
+                "<text x=\"20\" y=\"{:.2}\" fill=\"{}\" font-size=\"16\">{}</text>",
+                baseline,
+                ink,
+                self.time_stamp_unit.label(time_fs)
+            );
+        }
+
+        out.push_str("</svg>\n");
+        (out, circles)
+    }
+
+    /// Write the visible frame as SVG, returning the number of circles.
+    pub fn export_svg(&self, path: &Path) -> std::io::Result<usize> {
+        let (svg, circles) = self.svg_document();
+        if circles > SVG_LARGE_CIRCLE_COUNT {
+            crate::sim_log!(
+                Io,
+                Warn,
+                "SVG export has {} circles (~{} MB); vector editors may be slow",
+                circles,
+                svg.len() / (1024 * 1024)
+            );
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, svg)?;
+        Ok(circles)
+    }
+}
@@ -269,14 +269,14 @@ impl super::super::Renderer {
         use crate::renderer::state::{SimCommand, SIM_COMMAND_SENDER};
         use crate::body::Species;
         
-        println!("🔋 Generating intercalation cell:");
-        println!("  Anode: {} at {:.0}% SOC", 
+        crate::sim_log!(Charging, Info, "🔋 Generating intercalation cell:");
+        crate::sim_log!(Charging, Info, "  Anode: {} at {:.0}% SOC", 
             self.selected_anode_material.display_name(),
             self.initial_anode_soc * 100.0);
-        println!("  Cathode: {} at {:.0}% SOC",
+        crate::sim_log!(Charging, Info, "  Cathode: {} at {:.0}% SOC",
             self.selected_cathode_material.display_name(),
             self.initial_cathode_soc * 100.0);
-        println!("  Layers: {}", self.intercalation_layer_count);
+        crate::sim_log!(Charging, Info, "  Layers: {}", self.intercalation_layer_count);
         
         // Pause simulation
         crate::renderer::state::PAUSED.store(true, std::sync::atomic::Ordering::Relaxed);
@@ -390,11 +390,11 @@ impl super::super::Renderer {
         
         std::thread::sleep(std::time::Duration::from_millis(100));
         
-        println!("✓ Generated {} electrode layers with {} active regions",
+        crate::sim_log!(Charging, Info, "✓ Generated {} electrode layers with {} active regions",
             total_electrodes,
             self.active_material_regions.len());
-        println!("  Anode foils (Group A): {:?}", anode_foil_ids);
-        println!("  Cathode foils (Group B): {:?}", cathode_foil_ids);
+        crate::sim_log!(Charging, Info, "  Anode foils (Group A): {:?}", anode_foil_ids);
+        crate::sim_log!(Charging, Info, "  Cathode foils (Group B): {:?}", cathode_foil_ids);
     }
 }
 
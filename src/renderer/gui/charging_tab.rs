@@ -529,6 +529,8 @@ impl super::super::Renderer {
             super::super::ChargingUiMode::SwitchCharging => {
                 ui.label("Switch Charging mode uses step-based role assignments (Anode/ Cathode A/B) and run control.");
                 ui.add_space(4.0);
+                ui.checkbox(&mut self.sim_config.switch_timeline, "Record switch timeline")
+                    .on_hover_text("Record the active step and setpoint over time for the Switch Step Timeline plot (Analysis tab)");
                 // Embed the existing switch charging UI right here for minimal duplication
                 crate::switch_charging::ui_switch_charging(ui, &mut self.switch_ui_state);
             }
@@ -572,6 +574,117 @@ impl super::super::Renderer {
                                 }
                             }
                         });
+                        ui.horizontal(|ui| {
+                            use crate::body::foil::AcWaveform;
+                            ui.label("AC waveform:");
+                            let mut waveform = foil.ac_waveform;
+                            for (value, label) in [
+                                (AcWaveform::Square, "Square"),
+                                (AcWaveform::Sine, "Sine"),
+                                (AcWaveform::Triangle, "Triangle"),
+                            ] {
+                                ui.radio_value(&mut waveform, value, label);
+                            }
+                            if waveform != foil.ac_waveform {
+                                if let Some(tx) = crate::renderer::state::SIM_COMMAND_SENDER.lock().as_ref() {
+                                    let _ = tx.send(crate::renderer::state::SimCommand::SetFoilACWaveform { foil_id: foil.id, waveform });
+                                }
+                            }
+                        });
+
+                        // Per-body electron capacity (small vs large electrodes)
+                        ui.horizontal(|ui| {
+                            let mut custom = foil.max_electrons_override.is_some();
+                            let mut max = foil.max_electrons();
+                            ui.checkbox(&mut custom, "Capacity override:")
+                                .on_hover_text(format!("Electrons per foil body; default {}", crate::config::FOIL_MAX_ELECTRONS));
+                            ui.add_enabled(custom, egui::DragValue::new(&mut max).clamp_range(1..=1000));
+                            let wanted = custom.then_some(max);
+                            if wanted != foil.max_electrons_override {
+                                if let Some(tx) = crate::renderer::state::SIM_COMMAND_SENDER.lock().as_ref() {
+                                    let _ = tx.send(crate::renderer::state::SimCommand::SetFoilMaxElectrons { foil_id: foil.id, max_electrons: wanted });
+                                }
+                            }
+                        });
+
+                        // Activation schedule: (start, end, setpoint) windows, zero current between them
+                        ui.collapsing(format!("Schedule ({} segments)", foil.schedule.len()), |ui| {
+                            let mut schedule = foil.schedule.clone();
+                            let mut remove = None;
+                            for (i, seg) in schedule.iter_mut().enumerate() {
+                                ui.horizontal(|ui| {
+                                    ui.label("Start/End (fs):");
+                                    ui.add(egui::DragValue::new(&mut seg.start_fs).speed(10.0).clamp_range(0.0..=f32::MAX));
+                                    ui.add(egui::DragValue::new(&mut seg.end_fs).speed(10.0).clamp_range(0.0..=f32::MAX));
+                                    ui.label("Setpoint:");
+                                    ui.add(egui::DragValue::new(&mut seg.setpoint).speed(0.05));
+                                    if ui.small_button("✖").clicked() {
+                                        remove = Some(i);
+                                    }
+                                });
+                            }
+                            if let Some(i) = remove {
+                                schedule.remove(i);
+                            }
+                            if ui.button("➕ Add Segment").clicked() {
+                                let start = schedule.last().map_or(0.0, |seg| seg.end_fs);
+                                schedule.push(crate::body::foil::ScheduleSegment { start_fs: start, end_fs: start + 1000.0, setpoint: foil.dc_current });
+                            }
+                            if schedule != foil.schedule {
+                                if let Some(tx) = crate::renderer::state::SIM_COMMAND_SENDER.lock().as_ref() {
+                                    let _ = tx.send(crate::renderer::state::SimCommand::SetFoilSchedule { foil_id: foil.id, schedule });
+                                }
+                            }
+                        });
+
+                        // Current → overpotential handoff once a deposit or ratio threshold is reached
+                        let handoff_title = if foil.handoff.is_some() { "Handoff (armed)" } else { "Handoff" };
+                        egui::CollapsingHeader::new(handoff_title)
+                            .id_source(("foil_handoff", foil.id))
+                            .show(ui, |ui| {
+                                use crate::body::foil::{HandoffCondition, HandoffRule};
+                                let mut armed = foil.handoff.is_some();
+                                ui.checkbox(&mut armed, "Switch to overpotential hold when")
+                                    .on_hover_text("Checked each step while the foil is in current mode; fires once");
+                                let mut rule = foil.handoff;
+                                if armed {
+                                    let r = rule.get_or_insert(HandoffRule {
+                                        condition: HandoffCondition::MetalCountAtLeast(100),
+                                        target_ratio: 1.05,
+                                    });
+                                    ui.horizontal(|ui| {
+                                        let c = &mut r.condition;
+                                        if ui.radio(matches!(c, HandoffCondition::MetalCountAtLeast(_)), "Li metal ≥").clicked() {
+                                            *c = HandoffCondition::MetalCountAtLeast(100);
+                                        }
+                                        if ui.radio(matches!(c, HandoffCondition::RatioAtLeast(_)), "Ratio ≥").clicked() {
+                                            *c = HandoffCondition::RatioAtLeast(1.05);
+                                        }
+                                        if ui.radio(matches!(c, HandoffCondition::RatioAtMost(_)), "Ratio ≤").clicked() {
+                                            *c = HandoffCondition::RatioAtMost(0.95);
+                                        }
+                                        match c {
+                                            HandoffCondition::MetalCountAtLeast(n) => {
+                                                ui.add(egui::DragValue::new(n).clamp_range(1..=100_000));
+                                            }
+                                            HandoffCondition::RatioAtLeast(x) | HandoffCondition::RatioAtMost(x) => {
+                                                ui.add(egui::DragValue::new(x).speed(0.005).clamp_range(0.0..=2.0));
+                                            }
+                                        }
+                                    });
+                                    ui.horizontal(|ui| {
+                                        ui.label("Hold target ratio:");
+                                        ui.add(egui::DragValue::new(&mut r.target_ratio).speed(0.005).clamp_range(0.0..=2.0));
+                                    });
+                                } else {
+                                    rule = None;
+                                }
+                                if rule != foil.handoff {
+                                    if let Some(tx) = crate::renderer::state::SIM_COMMAND_SENDER.lock().as_ref() {
+                                        let _ = tx.send(crate::renderer::state::SimCommand::SetFoilHandoff { foil_id: foil.id, rule });
+                                    }
+                                }
+                            });
 
                         // Charging mode
                         ui.horizontal(|ui| {
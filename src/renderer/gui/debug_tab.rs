@@ -22,6 +22,329 @@ impl super::super::Renderer {
                 &mut self.show_switching_role_halos,
                 "Show Switching Role Halos",
             );
+            ui.checkbox(&mut self.show_foil_role_labels, "Show Foil Role Labels")
+                .on_hover_text("Always label foils with their switching roles (shown automatically in Switch Charging mode)");
+        });
+
+        ui.separator();
+
+        ui.checkbox(&mut self.sim_config.pause_on_nan, "⛔ Pause on NaN")
+            .on_hover_text("Pause and log the offending body when any position, velocity or charge becomes non-finite");
+
+        let mut highlighting = self.highlight_velocity_threshold.is_some();
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut highlighting, "🔴 Highlight fast movers above")
+                .on_hover_text("Outline bodies whose speed exceeds the threshold in red");
+            ui.add(
+                egui::DragValue::new(&mut self.highlight_velocity_value)
+                    .speed(0.01)
+                    .clamp_range(0.0..=100.0)
+                    .suffix(" Å/fs"),
+            );
+        });
+        self.highlight_velocity_threshold = highlighting.then_some(self.highlight_velocity_value);
+
+        ui.separator();
+
+        // Foil charge-conservation audit
+        ui.group(|ui| {
+            ui.label("🧾 Foil Charge Audit");
+            ui.checkbox(
+                &mut self.sim_config.foil_charge_audit,
+                "Record intended vs. achieved electron transfers",
+            );
+            if !self.sim_config.foil_charge_audit {
+                return;
+            }
+            let audit = crate::renderer::state::FOIL_CHARGE_AUDIT.lock().clone();
+            if audit.is_empty() {
+                ui.small("No foil steps recorded yet.");
+                return;
+            }
+            egui::Grid::new("foil_charge_audit_grid")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Foil");
+                    ui.strong("Intended");
+                    ui.strong("Achieved");
+                    ui.strong("Shortfall");
+                    ui.strong("Total int./ach.");
+                    ui.strong("Short steps");
+                    ui.end_row();
+                    for a in &audit {
+                        ui.label(a.foil_id.to_string());
+                        ui.label(a.intended.to_string());
+                        ui.label(a.achieved.to_string());
+                        ui.label(match a.reason {
+                            Some(reason) => format!("{:?}", reason),
+                            None => "—".to_string(),
+                        });
+                        ui.label(format!("{} / {}", a.total_intended, a.total_achieved));
+                        ui.label(a.shortfall_steps.to_string());
+                        ui.end_row();
+                    }
+                });
+        });
+
+        ui.separator();
+
+        // Barnes-Hut tree granularity
+        ui.group(|ui| {
+            ui.label("🌳 Quadtree Granularity");
+            ui.horizontal(|ui| {
+                ui.label("Leaf capacity:");
+                ui.add(
+                    egui::DragValue::new(&mut self.sim_config.quadtree_leaf_capacity)
+                        .clamp_range(1..=256),
+                )
+                .on_hover_text("Max bodies per leaf; larger = shallower tree, more pairwise work");
+                ui.label("Thread capacity:");
+                ui.add(
+                    egui::DragValue::new(&mut self.sim_config.quadtree_thread_capacity)
+                        .clamp_range(1..=1_000_000),
+                )
+                .on_hover_text("Nodes with more bodies than this are built in parallel");
+            });
+            match *crate::renderer::state::QUADTREE_VISIT_STATS.lock() {
+                Some(stats) => ui.label(format!(
+                    "Visits per body: {:.1} mean, {} max ({} nodes)",
+                    stats.mean_visits, stats.max_visits, stats.nodes
+                )),
+                None => ui.small("No tree built yet."),
+            };
+        });
+
+        ui.separator();
+
+        // Cell list vs. quadtree cross-check
+        ui.group(|ui| {
+            ui.label("🧭 Neighbor Search Self-Check");
+            ui.checkbox(
+                &mut self.sim_config.neighbor_self_check,
+                "Compare cell-list and quadtree neighbors",
+            )
+            .on_hover_text("Discrepancies are logged under Diagnostics");
+            if self.sim_config.neighbor_self_check {
+                ui.horizontal(|ui| {
+                    ui.label("Every");
+                    ui.add(
+                        egui::DragValue::new(&mut self.sim_config.neighbor_self_check_interval)
+                            .clamp_range(1..=100_000)
+                            .suffix(" frames"),
+                    );
+                    ui.label("on");
+                    ui.add(
+                        egui::DragValue::new(&mut self.sim_config.neighbor_self_check_samples)
+                            .clamp_range(1..=10_000)
+                            .suffix(" bodies"),
+                    );
+                });
+            }
+        });
+
+        ui.separator();
+
+        // Console logging filter
+        ui.group(|ui| {
+            ui.label("📝 Console Logging");
+            egui::ComboBox::from_label("Level")
+                .selected_text(format!("{:?}", self.sim_config.log_level))
+                .show_ui(ui, |ui| {
+                    for level in crate::logging::LogLevel::ALL {
+                        ui.selectable_value(
+                            &mut self.sim_config.log_level,
+                            level,
+                            format!("{:?}", level),
+                        );
+                    }
+                });
+            let categories = &mut self.sim_config.log_categories;
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut categories.physics, "Physics");
+                ui.checkbox(&mut categories.charging, "Charging");
+                ui.checkbox(&mut categories.io, "I/O");
+                ui.checkbox(&mut categories.diagnostics, "Diagnostics");
+            });
+        });
+
+        ui.separator();
+
+        // Electron transfer event stream for validating foil currents
+        ui.group(|ui| {
+            ui.label("⚡ Electron Transfer Events");
+            ui.checkbox(
+                &mut self.sim_config.electron_event_log,
+                "Record every foil electron add/remove",
+            );
+            if !self.sim_config.electron_event_log {
+                return;
+            }
+            ui.horizontal(|ui| {
+                ui.label("Buffer size:");
+                ui.add(
+                    egui::DragValue::new(&mut self.sim_config.electron_event_buffer)
+                        .speed(1000.0)
+                        .clamp_range(1..=10_000_000),
+                );
+            });
+            ui.checkbox(
+                &mut self.sim_config.electron_event_flush,
+                "Flush full buffers to doe_results/electron_events_stream.csv",
+            )
+            .on_hover_text("When off, the oldest events are dropped once the buffer is full");
+            if ui.button("Export buffered events CSV").clicked() {
+                if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                    let _ = sender.send(SimCommand::ExportElectronEvents {
+                        path: std::path::Path::new("doe_results").join("electron_events.csv"),
+                    });
+                }
+            }
+        });
+
+        ui.separator();
+
+        // Packing sanity check
+        ui.group(|ui| {
+            ui.label("📏 Nearest-Neighbor Distance");
+            ui.horizontal(|ui| {
+                let label = self
+                    .nn_stats_species
+                    .map_or("All species".to_string(), |s| format!("{:?}", s));
+                egui::ComboBox::from_id_source("nn_stats_species")
+                    .selected_text(label)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.nn_stats_species, None, "All species");
+                        for species in [
+                            crate::body::Species::LithiumIon,
+                            crate::body::Species::LithiumMetal,
+                            crate::body::Species::FoilMetal,
+                            crate::body::Species::ElectrolyteAnion,
+                            crate::body::Species::EC,
+                            crate::body::Species::DMC,
+                        ] {
+                            ui.selectable_value(
+                                &mut self.nn_stats_species,
+                                Some(species),
+                                format!("{:?}", species),
+                            );
+                        }
+                    });
+                if ui.button("Compute").clicked() {
+                    self.nn_stats = Some(crate::plotting::analysis::nearest_neighbor_stats(
+                        &self.bodies,
+                        self.nn_stats_species,
+                    ));
+                }
+            });
+            match self.nn_stats {
+                Some((_, mean, _)) if mean.is_nan() => {
+                    ui.label("Need at least two particles of the selected species.");
+                }
+                Some((min, mean, max)) => {
+                    ui.label(format!("Min: {:.3} Å  Mean: {:.3} Å  Max: {:.3} Å", min, mean, max));
+                }
+                None => {
+                    ui.small("Press Compute; plot the mean over time via a Time Series plot.");
+                }
+            }
+        });
+
+        ui.separator();
+
+        // Barnes-Hut approximation quality at the current theta
+        ui.group(|ui| {
+            ui.label("🎯 Barnes-Hut Field Validation");
+            ui.horizontal(|ui| {
+                ui.label("Samples:");
+                ui.add(
+                    egui::DragValue::new(&mut self.field_validation_samples)
+                        .clamp_range(1..=10_000),
+                );
+                if ui.button("Export CSV").clicked() {
+                    if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                        let _ = sender.send(SimCommand::ValidateField {
+                            samples: self.field_validation_samples,
+                        });
+                    }
+                }
+            });
+            ui.small("Writes exact vs. quadtree field and relative error to doe_results/.");
+        });
+
+        ui.separator();
+
+        // Probe particle: live neighbourhood and local field
+        ui.group(|ui| {
+            ui.label("🛰️ Probe Particle");
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        self.selected_particle_id.is_some(),
+                        egui::Button::new("Probe selected"),
+                    )
+                    .clicked()
+                {
+                    self.probe_particle_id = self.selected_particle_id;
+                }
+                if ui
+                    .add_enabled(self.probe_particle_id.is_some(), egui::Button::new("Clear"))
+                    .clicked()
+                {
+                    self.probe_particle_id = None;
+                }
+            });
+            ui.add(egui::Slider::new(&mut self.probe_radius, 1.0..=50.0).text("Radius (Å)"));
+
+            let Some(probe_id) = self.probe_particle_id else {
+                ui.small("Select a particle and press Probe selected.");
+                return;
+            };
+            let Some(probe) = self.bodies.iter().find(|b| b.id == probe_id) else {
+                ui.label(format!("Probe particle {} no longer exists.", probe_id));
+                return;
+            };
+            let field = crate::renderer::draw::compute_field_at_point(
+                &self.bodies,
+                probe.pos,
+                &self.sim_config,
+            );
+            ui.label(format!(
+                "ID {} ({:?}) at ({:.2}, {:.2}), charge {:.3}",
+                probe.id, probe.species, probe.pos.x, probe.pos.y, probe.charge
+            ));
+            ui.label(format!(
+                "Local field: ({:.4}, {:.4}), |E| = {:.4}",
+                field.x,
+                field.y,
+                field.mag()
+            ));
+
+            let neighbors = crate::plotting::analysis::probe_neighbors(
+                &self.bodies,
+                probe_id,
+                self.probe_radius,
+            )
+            .unwrap_or_default();
+            ui.label(format!("{} neighbours within {:.1} Å", neighbors.len(), self.probe_radius));
+            egui::ScrollArea::vertical()
+                .id_source("probe_neighbors")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("probe_neighbors_grid")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("ID");
+                            ui.strong("Species");
+                            ui.strong("Distance (Å)");
+                            ui.end_row();
+                            for (id, species, dist) in &neighbors {
+                                ui.label(id.to_string());
+                                ui.label(format!("{:?}", species));
+                                ui.label(format!("{:.3}", dist));
+                                ui.end_row();
+                            }
+                        });
+                });
         });
     }
 }
@@ -61,6 +61,45 @@ impl super::super::Renderer {
 
         ui.separator();
 
+        // Particle-count-based level of detail for the costlier diagnostics
+        ui.group(|ui| {
+            ui.label("🐢 Diagnostic Level of Detail");
+            ui.horizontal(|ui| {
+                ui.label("Solvation base (frames):");
+                ui.add(
+                    egui::DragValue::new(&mut self.diagnostic_lod_base_frames)
+                        .clamp_range(1..=1000),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label("Foil ratio base (fs):");
+                ui.add(
+                    egui::DragValue::new(&mut self.diagnostic_lod_base_fs)
+                        .speed(0.05)
+                        .clamp_range(0.0..=100.0),
+                );
+            });
+            ui.add(
+                egui::Slider::new(&mut self.diagnostic_lod_scaling, 0.0..=2.0)
+                    .text("Scaling per 1000 bodies"),
+            );
+            let n = self.bodies.len();
+            let factor = crate::diagnostics::lod_factor(n, self.diagnostic_lod_scaling);
+            ui.label(format!(
+                "Effective: solvation every {} frames, foil ratio every {:.2} fs ({} bodies, ×{:.2})",
+                crate::diagnostics::lod_interval_frames(
+                    self.diagnostic_lod_base_frames,
+                    n,
+                    self.diagnostic_lod_scaling
+                ),
+                self.diagnostic_lod_base_fs * factor,
+                n,
+                factor
+            ));
+        });
+
+        ui.separator();
+
         // Foil electron fraction diagnostic
         ui.group(|ui| {
             ui.label("🔋 Foil Electron Ratio");
@@ -81,6 +120,204 @@ impl super::super::Renderer {
 
         ui.separator();
 
+        // Deposit surface roughness diagnostic
+        ui.group(|ui| {
+            ui.label("🏔️ Deposit Surface Roughness");
+
+            if let Some(diag) = &self.surface_roughness_diagnostic {
+                for foil in &self.foils {
+                    if let Some(r) = diag.roughness.get(&foil.id) {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("Foil {}:", foil.id));
+                            ui.label(format!("{:.3} Å", r));
+                            if let Some(series) = diag.history.get(&foil.id) {
+                                let peak = series.iter().fold(0.0f32, |m, &(_, v)| m.max(v));
+                                ui.small(format!("(peak {:.3} Å over {} samples)", peak, series.len()));
+                            }
+                        });
+                    }
+                }
+                ui.small("Std. dev. of the outermost connected-metal distance across lateral bins; 0 = flat or no deposit.");
+            } else {
+                ui.label("❌ No diagnostic data available.");
+            }
+        });
+
+        ui.separator();
+
+        // Cation distance to the nearest foil surface (snapshot)
+        ui.group(|ui| {
+            ui.label("📏 Cation–Electrode Distance");
+            ui.horizontal(|ui| {
+                ui.label("Bin width (Å):");
+                ui.add(
+                    egui::DragValue::new(&mut self.electrode_distance_diagnostic.bin_width)
+                        .speed(0.1)
+                        .clamp_range(0.1..=100.0),
+                );
+                if ui.button("Compute").clicked() {
+                    let mut quadtree = crate::quadtree::Quadtree::new(1.0, 2.0, 1, 1024);
+                    quadtree.nodes = self.quadtree.clone();
+                    self.electrode_distance_diagnostic.calculate(&self.bodies, &quadtree);
+                }
+                let diag = &self.electrode_distance_diagnostic;
+                if ui
+                    .add_enabled(!diag.distances.is_empty(), egui::Button::new("Export CSV"))
+                    .clicked()
+                {
+                    match diag.write_csv(std::path::Path::new("doe_results"), "cation_electrode_distance") {
+                        Ok((raw, hist)) => crate::sim_log!(
+                            Io,
+                            Info,
+                            "✓ Cation distances -> {} / {}",
+                            raw.display(),
+                            hist.display()
+                        ),
+                        Err(e) => crate::sim_log!(Io, Error, "✗ Failed to write cation distance CSV: {}", e),
+                    }
+                }
+            });
+            let diag = &self.electrode_distance_diagnostic;
+            if diag.distances.is_empty() {
+                ui.small("No cations measured (press Compute; needs foil bodies and Li+).");
+            } else {
+                let mean = diag.distances.iter().map(|(_, d)| d).sum::<f32>() / diag.distances.len() as f32;
+                ui.label(format!("{} cations, mean distance {:.2} Å", diag.distances.len(), mean));
+                // Simple bar chart of the histogram
+                let peak = diag.histogram.iter().copied().max().unwrap_or(1).max(1);
+                let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 80.0), egui::Sense::hover());
+                let painter = ui.painter_at(rect);
+                let bar_w = rect.width() / diag.histogram.len().max(1) as f32;
+                for (i, &count) in diag.histogram.iter().enumerate() {
+                    let h = rect.height() * count as f32 / peak as f32;
+                    let x0 = rect.left() + i as f32 * bar_w;
+                    painter.rect_filled(
+                        egui::Rect::from_min_max(
+                            egui::pos2(x0, rect.bottom() - h),
+                            egui::pos2(x0 + (bar_w - 1.0).max(1.0), rect.bottom()),
+                        ),
+                        0.0,
+                        egui::Color32::from_rgb(90, 160, 230),
+                    );
+                }
+                ui.small(format!(
+                    "0 – {:.0} Å, {:.1} Å bins; tallest bar = {} cations",
+                    diag.histogram.len() as f32 * diag.bin_width,
+                    diag.bin_width,
+                    peak
+                ));
+            }
+        });
+
+        ui.separator();
+
+        // Effective electrolyte resistance between foil groups A and B
+        ui.group(|ui| {
+            ui.label("🔌 Inter-Electrode Resistance");
+
+            if let Some(diag) = &mut self.resistance_diagnostic {
+                ui.horizontal(|ui| {
+                    ui.label("Smoothing window (samples):");
+                    ui.add(egui::DragValue::new(&mut diag.window).clamp_range(1..=10_000));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Min |current| (e/fs):");
+                    ui.add(
+                        egui::DragValue::new(&mut diag.min_current)
+                            .speed(1e-5)
+                            .clamp_range(1e-8..=1.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("V_A - V_B:");
+                    ui.label(format!("{:.4}", diag.voltage));
+                    ui.label("I_ion:");
+                    ui.label(format!("{:.6} e/fs", diag.current));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Resistance:");
+                    match diag.resistance {
+                        Some(r) => ui.strong(format!("{:.4e} Ω-eq", r)),
+                        None => ui.label("— (current too small)"),
+                    };
+                });
+                ui.horizontal(|ui| {
+                    ui.small(format!("{} samples recorded", diag.history.len()));
+                    if ui.button("Export CSV").clicked() {
+                        let path = std::path::Path::new("doe_results").join("resistance_estimate.csv");
+                        match diag.write_csv(&path) {
+                            Ok(()) => crate::sim_log!(Diagnostics, Info, "Saved resistance estimate to {}", path.display()),
+                            Err(e) => crate::sim_log!(Diagnostics, Error, "Failed to write {}: {e}", path.display()),
+                        }
+                    }
+                    if ui.button("Reset").clicked() {
+                        diag.reset();
+                    }
+                });
+                ui.small("Measured group voltage / ionic drift current between groups A and B (set in the Charging tab). Ω-eq = potential units · fs / e.");
+            } else {
+                ui.label("❌ No diagnostic data available.");
+            }
+        });
+
+        ui.separator();
+
+        // Commanded vs. measured foil current residual
+        ui.group(|ui| {
+            ui.label("🎯 Foil Current Residual");
+            ui.checkbox(
+                &mut self.sim_config.current_residual_tracking,
+                "Track commanded vs. measured current",
+            );
+            ui.horizontal(|ui| {
+                ui.label("Sample window (fs):");
+                ui.add(
+                    egui::DragValue::new(&mut self.sim_config.current_residual_window_fs)
+                        .speed(1.0)
+                        .clamp_range(1.0..=100_000.0),
+                );
+            });
+            let summary = CURRENT_RESIDUAL_SUMMARY.lock().clone();
+            if summary.is_empty() {
+                ui.small("No residual samples recorded yet.");
+            } else {
+                egui::Grid::new("current_residual_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Foil");
+                        ui.strong("RMS (e/fs)");
+                        ui.strong("Mean bias (e/fs)");
+                        ui.strong("Samples");
+                        ui.end_row();
+                        for s in &summary {
+                            ui.label(s.foil_id.to_string());
+                            ui.label(format!("{:.4e}", s.rms));
+                            ui.label(format!("{:+.4e}", s.mean_bias));
+                            ui.label(s.samples.to_string());
+                            ui.end_row();
+                        }
+                    });
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Export CSV").clicked() {
+                    if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                        let path = std::path::Path::new("doe_results").join("current_residual.csv");
+                        let _ = sender.send(SimCommand::ExportCurrentResidual { path });
+                    }
+                }
+                if ui.button("Reset").clicked() {
+                    if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                        let _ = sender.send(SimCommand::ClearCurrentResidual);
+                    }
+                }
+            });
+            ui.small("Residual = electrons moved / window − mean commanded current; bias > 0 means the foil overshoots its command.");
+            ui.checkbox(&mut self.sim_config.current_ledger, "Record current ledger (CSV)")
+                .on_hover_text("Every step: each foil's resolved current with its link/group role, appended to doe_results/current_ledger_*.csv; untick to flush");
+        });
+
+        ui.separator();
+
         // Solvation State UI moved to Measurement tab
 
         // Additional diagnostic information
@@ -130,6 +367,22 @@ impl super::super::Renderer {
 
         ui.separator();
 
+        // Body ID integrity (duplicates break ID-based lookups)
+        ui.group(|ui| {
+            ui.label("🆔 Body IDs");
+            if ui
+                .button("Repair Duplicate IDs")
+                .on_hover_text("Give repeated body IDs fresh ones; runs automatically after loading a state")
+                .clicked()
+            {
+                if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                    let _ = sender.send(SimCommand::DedupeIds);
+                }
+            }
+        });
+
+        ui.separator();
+
         // 2D Domain Density Calculation
         ui.group(|ui| {
             ui.label("🗺️ 2D Domain Density");
@@ -1389,8 +1389,8 @@ impl super::super::Renderer {
         }
         let path = "eis_results.csv";
         match std::fs::write(path, &csv) {
-            Ok(_) => println!("EIS data exported to {}", path),
-            Err(e) => eprintln!("Failed to export EIS CSV: {}", e),
+            Ok(_) => crate::sim_log!(Diagnostics, Info, "EIS data exported to {}", path),
+            Err(e) => crate::sim_log!(Diagnostics, Error, "Failed to export EIS CSV: {}", e),
         }
     }
 }
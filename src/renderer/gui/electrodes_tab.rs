@@ -248,127 +248,34 @@ impl super::super::Renderer {
         // Give a moment for foils to be created and IDs assigned
         std::thread::sleep(std::time::Duration::from_millis(100));
         
-        println!("✓ Applied electrode configuration: {} electrodes", self.electrode_count);
-        println!("  Metal: {:.1}×{:.1} Å, Foil: {:.1}×{:.1} Å, Spacing: {:.1} Å",
+        crate::sim_log!(Charging, Info, "✓ Applied electrode configuration: {} electrodes", self.electrode_count);
+        crate::sim_log!(Charging, Info, "  Metal: {:.1}×{:.1} Å, Foil: {:.1}×{:.1} Å, Spacing: {:.1} Å",
             self.electrode_metal_width, self.electrode_metal_height,
             self.electrode_foil_width, self.electrode_foil_height,
             self.electrode_spacing);
-        println!("⚠ Foil IDs reset - new foils start at ID 1");
-        println!("⚠ Measurement points will need to be regenerated.");
-        println!("  Go to Measurement tab and click 'Generate' to create new points.");
+        crate::sim_log!(Charging, Warn, "⚠ Foil IDs reset - new foils start at ID 1");
+        crate::sim_log!(Charging, Warn, "⚠ Measurement points will need to be regenerated.");
+        crate::sim_log!(Charging, Info, "  Go to Measurement tab and click 'Generate' to create new points.");
     }
     
     /// Add default electrolyte (1M LiPF6 in EC:DMC 1:1)
     /// Public so other tabs can reuse this functionality
     pub fn add_default_electrolyte(&self) {
         use crate::renderer::state::{SimCommand, SIM_COMMAND_SENDER};
-        use crate::body::Species;
-        
+
         let domain_width = *crate::renderer::state::DOMAIN_WIDTH.lock();
         let domain_height = *crate::renderer::state::DOMAIN_HEIGHT.lock();
-        
+
         if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
-            let molarity = 1.0;
-            let total: usize = 5471;
-            
-            let solvent_to_salt_ratio = 15.0;
-            let salt_fraction = 1.0 / (1.0 + solvent_to_salt_ratio);
-            let lipf6_count = (total as f32 * salt_fraction * molarity / 1.0).round() as usize;
-            let li_count = lipf6_count;
-            let pf6_count = lipf6_count;
-            let remaining = total.saturating_sub(li_count + pf6_count);
-            
-            // Calculate EC and DMC counts based on 1:1 volume ratio
-            // This accounts for different densities and molar masses
-            let solvent_parts = vec![
-                (Species::EC, 1.0),   // 1 part by volume
-                (Species::DMC, 1.0),  // 1 part by volume
-            ];
-            let solvent_counts = crate::species::calculate_solvent_particle_counts(&solvent_parts, remaining);
-            
-            let ec_count = solvent_counts.iter()
-                .find(|(s, _)| *s == Species::EC)
-                .map(|(_, c)| *c)
-                .unwrap_or(0);
-            let dmc_count = solvent_counts.iter()
-                .find(|(s, _)| *s == Species::DMC)
-                .map(|(_, c)| *c)
-                .unwrap_or(0);
-            
-            // Add Li+ ions
-            if li_count > 0 {
-                let li_body = crate::body::Body::new(
-                    ultraviolet::Vec2::zero(),
-                    ultraviolet::Vec2::zero(),
-                    Species::LithiumIon.mass(),
-                    Species::LithiumIon.radius(),
-                    1.0,
-                    Species::LithiumIon,
-                );
-                let _ = sender.send(SimCommand::AddRandom {
-                    body: li_body,
-                    count: li_count,
-                    domain_width,
-                    domain_height,
-                });
-            }
-            
-            // Add PF6- anions
-            if pf6_count > 0 {
-                let pf6_body = crate::body::Body::new(
-                    ultraviolet::Vec2::zero(),
-                    ultraviolet::Vec2::zero(),
-                    Species::ElectrolyteAnion.mass(),
-                    Species::ElectrolyteAnion.radius(),
-                    -1.0,
-                    Species::ElectrolyteAnion,
-                );
-                let _ = sender.send(SimCommand::AddRandom {
-                    body: pf6_body,
-                    count: pf6_count,
-                    domain_width,
-                    domain_height,
-                });
-            }
-            
-            // Add EC solvent
-            if ec_count > 0 {
-                let ec_body = crate::body::Body::new(
-                    ultraviolet::Vec2::zero(),
-                    ultraviolet::Vec2::zero(),
-                    Species::EC.mass(),
-                    Species::EC.radius(),
-                    0.0,
-                    Species::EC,
-                );
-                let _ = sender.send(SimCommand::AddRandom {
-                    body: ec_body,
-                    count: ec_count,
-                    domain_width,
-                    domain_height,
-                });
-            }
-            
-            // Add DMC solvent
-            if dmc_count > 0 {
-                let dmc_body = crate::body::Body::new(
-                    ultraviolet::Vec2::zero(),
-                    ultraviolet::Vec2::zero(),
-                    Species::DMC.mass(),
-                    Species::DMC.radius(),
-                    0.0,
-                    Species::DMC,
-                );
-                let _ = sender.send(SimCommand::AddRandom {
-                    body: dmc_body,
-                    count: dmc_count,
-                    domain_width,
-                    domain_height,
-                });
-            }
-            
-            println!("✓ Added default electrolyte: {} Li+, {} PF6-, {} EC, {} DMC",
-                li_count, pf6_count, ec_count, dmc_count);
+            let _ = sender.send(SimCommand::AddElectrolyte {
+                total: 5471,
+                molarity: 1.0,
+                ec_parts: 1.0,
+                dmc_parts: 1.0,
+                domain_width,
+                domain_height,
+            });
+            crate::sim_log!(Charging, Info, "✓ Added default electrolyte: 1M LiPF6 in EC:DMC 1:1");
         }
     }
 }
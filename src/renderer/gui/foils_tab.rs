@@ -18,6 +18,33 @@ impl super::super::Renderer {
         ui.group(|ui| {
             ui.label("🎯 Foil Selection for Linking");
             ui.label("Select foils by clicking on them in the simulation, or use the list below:");
+            ui.horizontal(|ui| {
+                ui.label("Shift+click picks:");
+                let label = self
+                    .pick_species_filter
+                    .map_or("Any species".to_string(), |s| format!("{:?}", s));
+                egui::ComboBox::from_id_source("pick_species_filter")
+                    .selected_text(label)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.pick_species_filter, None, "Any species");
+                        for species in [
+                            crate::body::Species::FoilMetal,
+                            crate::body::Species::LithiumMetal,
+                            crate::body::Species::LithiumIon,
+                            crate::body::Species::ElectrolyteAnion,
+                            crate::body::Species::EC,
+                            crate::body::Species::DMC,
+                        ] {
+                            ui.selectable_value(
+                                &mut self.pick_species_filter,
+                                Some(species),
+                                format!("{:?}", species),
+                            );
+                        }
+                    })
+                    .response
+                    .on_hover_text("Skip other species when selecting in a dense mix");
+            });
 
             let foils = FOILS.lock();
             if !foils.is_empty() {
@@ -304,6 +331,94 @@ impl super::super::Renderer {
                 ui.label("Select exactly 2 foils above to link them together.");
                 ui.label("Linked foils share current settings - one controls both.");
             }
+            ui.checkbox(
+                &mut self.sim_config.auto_link_opposite_foils,
+                "Auto-link new foils (opposite)",
+            )
+            .on_hover_text(
+                "Pair each added or detected foil opposite with the nearest unlinked foil of opposite current sign",
+            );
+        });
+
+        ui.separator();
+
+        // Foil merge controls
+        ui.group(|ui| {
+            ui.label("🧩 Merge Foils");
+            if self.selected_foil_ids.len() >= 2 {
+                let target = *self.selected_foil_ids.iter().min().unwrap();
+                ui.label(format!(
+                    "Merge {} selected foils into Foil {}",
+                    self.selected_foil_ids.len(),
+                    target
+                ));
+                if ui.button("🧩 Merge Selected").clicked() {
+                    if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                        let _ = sender.send(SimCommand::MergeFoils {
+                            ids: self.selected_foil_ids.clone(),
+                        });
+                    }
+                    self.selected_foil_ids = vec![target];
+                }
+            } else {
+                ui.label("Select 2 or more foils above to merge them into one.");
+            }
+        });
+
+        ui.separator();
+
+        // Foil detection from loose FoilMetal particles
+        ui.group(|ui| {
+            ui.label("🔍 Detect Foils");
+            ui.horizontal(|ui| {
+                ui.label("Connection radius:");
+                ui.add(
+                    egui::DragValue::new(&mut self.foil_detect_radius)
+                        .speed(0.1)
+                        .clamp_range(0.1..=50.0)
+                        .suffix(" Å"),
+                );
+            });
+            if ui
+                .button("Group unassigned FoilMetal into foils")
+                .on_hover_text("Each connected cluster of FoilMetal particles not already in a foil becomes a new foil")
+                .clicked()
+            {
+                if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                    let _ = sender.send(SimCommand::DetectFoils {
+                        connection_radius: self.foil_detect_radius,
+                    });
+                }
+            }
+        });
+
+        ui.separator();
+
+        // Lightweight save/restore of every foil's charging setpoints
+        ui.group(|ui| {
+            ui.label("📋 Foil Charging Config");
+            let path = std::path::Path::new("doe_results").join("foil_config.csv");
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Export CSV")
+                    .on_hover_text("Mode, DC/AC current, switch Hz and PID settings per foil id")
+                    .clicked()
+                {
+                    if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                        let _ = sender.send(SimCommand::ExportFoilConfig { path: path.clone() });
+                    }
+                }
+                if ui
+                    .add_enabled(path.exists(), egui::Button::new("Import CSV"))
+                    .on_hover_text("Apply rows to foils with matching ids; unknown ids are skipped")
+                    .clicked()
+                {
+                    if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                        let _ = sender.send(SimCommand::ImportFoilConfig { path: path.clone() });
+                    }
+                }
+            });
+            ui.small(path.display().to_string());
         });
 
         ui.separator();
@@ -61,6 +61,90 @@ impl super::super::Renderer {
                 "CIP: {:.3}\nSIP: {:.3}\nS2IP: {:.3}\nFD: {:.3}",
                 diag.cip_fraction, diag.sip_fraction, diag.s2ip_fraction, diag.fd_fraction
             ));
+            if ui
+                .button("Export solvation shells (JSON)")
+                .on_hover_text("Per cation: classification, coordinating solvent IDs and paired anion")
+                .clicked()
+            {
+                let time_fs = *crate::renderer::state::SIM_TIME.lock();
+                let path = std::path::Path::new("doe_results")
+                    .join(format!("solvation_shells_{:.0}fs.json", time_fs));
+                match diag.export_shells_json(&path) {
+                    Ok(()) => crate::sim_log!(Diagnostics, Info, "✓ Solvation shells -> {}", path.display()),
+                    Err(e) => crate::sim_log!(Diagnostics, Error, "✗ Failed to write solvation shells: {}", e),
+                }
+            }
+
+            // CIP pair lifetimes across solvation updates
+            ui.horizontal(|ui| {
+                if ui
+                    .checkbox(&mut self.cip_lifetime_enabled, "Track CIP lifetimes")
+                    .on_hover_text("Follow cation-anion contact pairs by ID; lifetimes scale with the solvation update interval")
+                    .changed()
+                {
+                    self.cip_lifetimes.reset();
+                }
+                if self.cip_lifetime_enabled {
+                    ui.label(match self.cip_lifetimes.mean_lifetime() {
+                        Some(mean) => format!(
+                            "{} closed, mean {:.1} fs, {} live",
+                            self.cip_lifetimes.lifetimes.len(),
+                            mean,
+                            self.cip_lifetimes.active_pairs()
+                        ),
+                        None => format!("{} live pairs", self.cip_lifetimes.active_pairs()),
+                    });
+                }
+            });
+            if self.cip_lifetime_enabled {
+                ui.horizontal(|ui| {
+                    ui.label("Bin:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.cip_lifetime_bin_fs)
+                            .speed(1.0)
+                            .clamp_range(0.1..=1.0e6)
+                            .suffix(" fs"),
+                    );
+                    if ui.button("Export CIP lifetime histogram").clicked() {
+                        let path = std::path::Path::new("doe_results").join("cip_lifetimes.csv");
+                        match self.cip_lifetimes.write_histogram_csv(&path, self.cip_lifetime_bin_fs) {
+                            Ok(()) => crate::sim_log!(Diagnostics, Info, "✓ CIP lifetimes -> {}", path.display()),
+                            Err(e) => crate::sim_log!(Diagnostics, Error, "✗ Failed to write CIP lifetimes: {}", e),
+                        }
+                    }
+                });
+            }
+
+            // Transference number resolved by solvation class
+            ui.horizontal(|ui| {
+                if ui
+                    .checkbox(&mut self.solvation_transference_enabled, "Track transference by class")
+                    .on_hover_text("Split the Li+ transference number into CIP/SIP/S2IP/FD contributions at each solvation update")
+                    .changed()
+                {
+                    self.solvation_transference.reset();
+                }
+                if let Some(sample) = self.solvation_transference.latest() {
+                    let [cip, sip, s2ip, fd] = sample.contributions;
+                    ui.label(format!(
+                        "t+ {:.3} (CIP {:.3}, SIP {:.3}, S2IP {:.3}, FD {:.3})",
+                        sample.total(),
+                        cip,
+                        sip,
+                        s2ip,
+                        fd
+                    ));
+                }
+            });
+            if self.solvation_transference_enabled
+                && ui.button("Export transference by class").clicked()
+            {
+                let path = std::path::Path::new("doe_results").join("solvation_transference.csv");
+                match self.solvation_transference.write_csv(&path) {
+                    Ok(()) => crate::sim_log!(Diagnostics, Info, "✓ Transference by class -> {}", path.display()),
+                    Err(e) => crate::sim_log!(Diagnostics, Error, "✗ Failed to write transference by class: {}", e),
+                }
+            }
 
             // Visual overlays
             ui.separator();
@@ -205,6 +289,36 @@ impl super::super::Renderer {
                         }
                     }
                 });
+                ui.collapsing("Fixed probe points", |ui| {
+                    ui.small("Logged to <Point-based CSV>_probes.csv: charge density, field and species counts per point.");
+                    let config = &mut self.manual_measurement_ui_config;
+                    ui.horizontal(|ui| {
+                        ui.label("Probe radius (Å):");
+                        ui.add(
+                            egui::DragValue::new(&mut config.probe_radius)
+                                .speed(0.5)
+                                .clamp_range(0.5..=500.0),
+                        );
+                    });
+                    let mut remove = None;
+                    for (i, point) in config.probe_points.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("p{}", i));
+                            ui.add(egui::DragValue::new(&mut point.x).speed(0.5).prefix("x: "));
+                            ui.add(egui::DragValue::new(&mut point.y).speed(0.5).prefix("y: "));
+                            if ui.small_button("✖").clicked() {
+                                remove = Some(i);
+                            }
+                        });
+                    }
+                    if let Some(i) = remove {
+                        config.probe_points.remove(i);
+                    }
+                    if ui.button("➕ Add probe point").clicked() {
+                        config.probe_points.push(ultraviolet::Vec2::zero());
+                    }
+                    ui.small("Changes apply the next time point-based recording starts.");
+                });
             });
 
             // Stack filename inputs, left-aligned
@@ -292,6 +406,90 @@ impl super::super::Renderer {
                     crate::renderer::state::FOIL_METRICS_INCLUDE_LI_METAL
                         .store(inc_li, std::sync::atomic::Ordering::Relaxed);
                 });
+            ui.horizontal(|ui| {
+                ui.label("Li Metal Count traverses:");
+                ui.radio_value(
+                    &mut self.sim_config.connectivity_species_filter,
+                    crate::config::ConnectivityFilter::AllMetal,
+                    "All metal",
+                )
+                .on_hover_text("Li reached through any Li or foil metal");
+                ui.radio_value(
+                    &mut self.sim_config.connectivity_species_filter,
+                    crate::config::ConnectivityFilter::LithiumOnly,
+                    "Li deposit only",
+                )
+                .on_hover_text("Only Li contiguous with this foil through other Li");
+            });
+
+            // Narrow per-foil current CSV, independent of the foil metrics fields
+            let mut foil_current = crate::renderer::state::FOIL_CURRENT_CSV_ENABLED
+                .load(std::sync::atomic::Ordering::Relaxed);
+            if ui
+                .checkbox(&mut foil_current, "Enable Foil current CSV")
+                .on_hover_text("time_fs plus one commanded-current column per foil")
+                .changed()
+            {
+                crate::renderer::state::FOIL_CURRENT_CSV_ENABLED
+                    .store(foil_current, std::sync::atomic::Ordering::Relaxed);
+            }
+
+            let mut pid_state = crate::renderer::state::PID_STATE_CSV_ENABLED
+                .load(std::sync::atomic::Ordering::Relaxed);
+            if ui
+                .checkbox(&mut pid_state, "Enable Foil PID state CSV")
+                .on_hover_text("Error, integral, derivative, output, target and actual ratio per master foil")
+                .changed()
+            {
+                crate::renderer::state::PID_STATE_CSV_ENABLED
+                    .store(pid_state, std::sync::atomic::Ordering::Relaxed);
+            }
+
+            let mut field_csv = crate::renderer::state::FIELD_CSV_ENABLED
+                .load(std::sync::atomic::Ordering::Relaxed);
+            if ui
+                .checkbox(&mut field_csv, "Enable applied field CSV")
+                .on_hover_text("Manual, smoothed induced and total external field vectors")
+                .changed()
+            {
+                crate::renderer::state::FIELD_CSV_ENABLED
+                    .store(field_csv, std::sync::atomic::Ordering::Relaxed);
+            }
+
+            // One-shot export of species counts over the playback history
+            if ui
+                .button("Export species counts CSV")
+                .on_hover_text("One row per history frame, one column per species ever present")
+                .clicked()
+            {
+                if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                    let _ = sender.send(SimCommand::ExportSpeciesCounts {
+                        path: std::path::Path::new("doe_results").join("species_counts.csv"),
+                    });
+                }
+            }
+
+            // XYZ trajectory for VMD/Ovito
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Export XYZ trajectory")
+                    .on_hover_text("Playback history as XYZ frames (z = 0 in 2D runs)")
+                    .clicked()
+                {
+                    if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                        let _ = sender.send(SimCommand::ExportXyz {
+                            path: std::path::Path::new("doe_results").join("trajectory.xyz"),
+                            stride: self.xyz_export_stride,
+                        });
+                    }
+                }
+                ui.label("every");
+                ui.add(
+                    egui::DragValue::new(&mut self.xyz_export_stride)
+                        .clamp_range(1..=10_000)
+                        .suffix(" frames"),
+                );
+            });
 
             ui.separator();
 
@@ -385,13 +583,13 @@ impl super::super::Renderer {
                             // Check if the foil actually exists
                             let foil_exists = self.foils.iter().any(|f| f.id == foil_id);
                             if !foil_exists {
-                                println!("⚠ Warning: Selected Foil {} no longer exists!", foil_id);
-                                println!("  Available foil IDs: {:?}", self.foils.iter().map(|f| f.id).collect::<Vec<_>>());
-                                println!("  Please select a different foil from the dropdown.");
+                                crate::sim_log!(Diagnostics, Warn, "⚠ Warning: Selected Foil {} no longer exists!", foil_id);
+                                crate::sim_log!(Diagnostics, Info, "  Available foil IDs: {:?}", self.foils.iter().map(|f| f.id).collect::<Vec<_>>());
+                                crate::sim_log!(Diagnostics, Info, "  Please select a different foil from the dropdown.");
                                 // Try to select the first available foil
                                 if let Some(first_foil) = self.foils.first() {
                                     self.gen_selected_foil = Some(first_foil.id);
-                                    println!("  Auto-selected Foil {} instead.", first_foil.id);
+                                    crate::sim_log!(Diagnostics, Info, "  Auto-selected Foil {} instead.", first_foil.id);
                                 }
                             }
                             
@@ -606,22 +804,29 @@ impl super::super::Renderer {
             let copy_button =
                 ui.add_enabled(copy_enabled, egui::Button::new("📋 Copy History (.csv)"));
             if copy_button.clicked() {
-                let mut csv = String::from("step,time_fs,distance,switch_step,switch_mode,switch_value,pos_role,neg_role\n");
-                for record in &self.measurement_history {
-                    let ss = record.switch_step.map(|v| v.to_string()).unwrap_or_default();
-                    let sm = record.switch_mode.clone().unwrap_or_default();
-                    let sv = record.switch_value.map(|v| format!("{:.6}", v)).unwrap_or_default();
-                    let pr = record.pos_role.clone().unwrap_or_default();
-                    let nr = record.neg_role.clone().unwrap_or_default();
-                    csv.push_str(&format!(
-                        "{},{:.6},{:.6},{},{},{},{},{}\n",
-                        record.step, record.time_fs, record.distance, ss, sm, sv, pr, nr
-                    ));
-                }
+                let csv = crate::measurement_csv::measurement_history_csv(&self.measurement_history);
                 ui.output_mut(|o| o.copied_text = csv);
                 ui.label(RichText::new("History copied to clipboard").italics());
             }
 
+            let export_button =
+                ui.add_enabled(copy_enabled, egui::Button::new("💾 Export History"));
+            if export_button.clicked() {
+                let path = std::path::Path::new(&self.measurement_export_path);
+                let metadata = crate::measurement_csv::MeasurementMetadata {
+                    domain_width: self.domain_width,
+                    domain_height: self.domain_height,
+                };
+                match crate::measurement_csv::write_measurement_history(
+                    path,
+                    &self.measurement_history,
+                    metadata,
+                ) {
+                    Ok(()) => crate::sim_log!(Io, Info, "✓ Measurement history -> {}", path.display()),
+                    Err(e) => crate::sim_log!(Io, Error, "✗ Failed to write measurement history: {}", e),
+                }
+            }
+
             let clear_button = ui.add_enabled(copy_enabled, egui::Button::new("🧹 Clear History"));
             if clear_button.clicked() {
                 self.clear_measurement();
@@ -636,6 +841,39 @@ impl super::super::Renderer {
             }
         });
 
+        ui.horizontal(|ui| {
+            ui.label("Export to:");
+            ui.text_edit_singleline(&mut self.measurement_export_path)
+                .on_hover_text("History CSV with domain size as # metadata lines");
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.measurement_snap, "🧲 Snap to particle centers")
+                .on_hover_text("Endpoints jump to the nearest particle center within the tolerance");
+            if self.measurement_snap {
+                ui.add(
+                    egui::DragValue::new(&mut self.measurement_snap_tolerance_px)
+                        .speed(0.5)
+                        .clamp_range(1.0..=100.0)
+                        .suffix(" px"),
+                );
+            }
+        });
+        for (label, id) in [
+            ("Start snapped to", self.measurement_start_snapped_id),
+            ("Cursor snapped to", self.measurement_snapped_id),
+        ] {
+            if let Some(id) = id {
+                let species = self
+                    .bodies
+                    .iter()
+                    .find(|b| b.id == id)
+                    .map(|b| format!("{:?}", b.species))
+                    .unwrap_or_default();
+                ui.small(format!("{} particle #{} {}", label, id, species));
+            }
+        }
+
         if self.measurement_selecting_start {
             ui.label(
                 RichText::new("Click in the simulation view to set the starting point.").italics(),
@@ -19,6 +19,7 @@ pub mod measurement_tab;
 pub mod physics_tab;
 pub mod pid_controller;
 pub mod scenario_tab;
+pub mod screen_capture_tab;
 pub mod simulation_tab;
 pub mod soft_dynamics_tab;
 pub mod species_tab;
@@ -142,6 +143,11 @@ impl super::Renderer {
                             super::GuiTab::Debug,
                             "🐛 Debug",
                         );
+                        ui.selectable_value(
+                            &mut self.current_tab,
+                            super::GuiTab::ScreenCapture,
+                            "📷 Capture",
+                        );
                     });
                 });
 
@@ -178,6 +184,7 @@ impl super::Renderer {
                         super::GuiTab::Diagnostics => self.show_diagnostics_tab(ui),
                         super::GuiTab::SoftDynamics => self.show_soft_dynamics_tab(ui),
                         super::GuiTab::Legend => self.show_legend_tab(ui),
+                        super::GuiTab::ScreenCapture => self.show_screen_capture_tab(ui),
                     });
             });
 
@@ -204,6 +211,189 @@ impl super::Renderer {
 
         // Show PID graph window if enabled
         self.show_pid_graph(ctx);
+
+        self.show_foil_role_labels(ctx);
+        self.show_molarity_hud(ctx);
+        self.show_scale_bar_overlay(ctx);
+    }
+
+    /// Scale bar and simulation time in the lower-left corner, so recorded
+    /// frames carry their own length and time reference.
+    fn show_scale_bar_overlay(&self, ctx: &egui::Context) {
+        use crate::renderer::scale_bar::ScaleBar;
+        if !(self.show_scale_bar || self.show_time_stamp) {
+            return;
+        }
+        // egui paints in points, so measure the view in the same space
+        let screen_rect = ctx.screen_rect();
+        let (width, height) = (screen_rect.width(), screen_rect.height());
+        if height <= 0.0 {
+            return;
+        }
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("scale_bar_overlay"),
+        ));
+        let font = egui::FontId::proportional(16.0);
+        let [r, g, b, a] = self.theme.overlay_color([255, 255, 255, 255]);
+        let color = egui::Color32::from_rgba_unmultiplied(r, g, b, a);
+        let left = screen_rect.min.x + 20.0;
+        let mut baseline = screen_rect.max.y - 20.0;
+
+        if self.show_scale_bar {
+            let px_per_angstrom = height / (2.0 * self.scale);
+            if let Some(bar) = ScaleBar::for_view(px_per_angstrom, width, self.scale_bar_unit) {
+                let stroke = egui::Stroke::new(3.0, color);
+                let end = left + bar.length_px;
+                painter.line_segment([egui::pos2(left, baseline), egui::pos2(end, baseline)], stroke);
+                for x in [left, end] {
+                    painter.line_segment(
+                        [egui::pos2(x, baseline - 6.0), egui::pos2(x, baseline + 2.0)],
+                        stroke,
+                    );
+                }
+                painter.text(
+                    egui::pos2(left + 0.5 * bar.length_px, baseline - 8.0),
+                    egui::Align2::CENTER_BOTTOM,
+                    bar.label(),
+                    font.clone(),
+                    color,
+                );
+                baseline -= 32.0;
+            }
+        }
+        if self.show_time_stamp {
+            let time_fs = *crate::renderer::state::SIM_TIME.lock();
+            painter.text(
+                egui::pos2(left, baseline),
+                egui::Align2::LEFT_BOTTOM,
+                self.time_stamp_unit.label(time_fs),
+                font,
+                color,
+            );
+        }
+    }
+
+    /// Small overlay comparing bulk Li+ molarity with the electrolyte target.
+    fn show_molarity_hud(&self, ctx: &egui::Context) {
+        if !self.show_molarity_meter {
+            return;
+        }
+        let meter = &self.molarity_meter;
+        let target = self.electrolyte_molarity;
+        let (text, color) = match (meter.molarity, meter.on_target(target)) {
+            (Some(m), Some(ok)) => (
+                format!("Bulk {:.2} M / target {:.2} M", m, target),
+                if ok {
+                    egui::Color32::LIGHT_GREEN
+                } else {
+                    egui::Color32::from_rgb(255, 170, 60)
+                },
+            ),
+            _ => (
+                format!("Bulk -- M / target {:.2} M", target),
+                egui::Color32::GRAY,
+            ),
+        };
+        egui::Area::new("molarity_meter_hud")
+            .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-10.0, 10.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.colored_label(color, text);
+                    ui.small(format!(
+                        "{} Li+ in {:.0} Å² bulk",
+                        meter.cation_count, meter.bulk_area
+                    ));
+                });
+            });
+    }
+
+    /// Paint switching role labels ("+A", "-B", ...) next to each assigned
+    /// foil's centroid. Text stays a fixed screen size at every zoom level.
+    fn show_foil_role_labels(&self, ctx: &egui::Context) {
+        use crate::switch_charging::Role;
+        let switch_mode = matches!(self.charging_ui_mode, super::ChargingUiMode::SwitchCharging);
+        if !(switch_mode || self.show_foil_role_labels) {
+            return;
+        }
+        let config = &self.switch_ui_state.config;
+        let active = (*crate::renderer::state::SWITCH_STEP.lock())
+            .map(crate::switch_charging::roles_for_step);
+        // egui paints in points, so place labels in the same space
+        let screen_rect = ctx.screen_rect();
+        if screen_rect.height() <= 0.0 {
+            return;
+        }
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Background,
+            egui::Id::new("foil_role_labels"),
+        ));
+
+        // Roles per labelled foil, and which of those foils each body belongs to
+        let labelled: Vec<(&crate::body::foil::Foil, Vec<Role>)> = self
+            .foils
+            .iter()
+            .map(|foil| {
+                let roles: Vec<Role> = Role::ALL
+                    .iter()
+                    .copied()
+                    .filter(|role| config.foils_for_role(*role).contains(&foil.id))
+                    .collect();
+                (foil, roles)
+            })
+            .filter(|(_, roles)| !roles.is_empty())
+            .collect();
+        let body_to_label: std::collections::HashMap<u64, usize> = labelled
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (foil, _))| foil.body_ids.iter().map(move |&id| (id, i)))
+            .collect();
+        let mut centroids = vec![(Vec2::zero(), 0usize); labelled.len()];
+        for body in &self.bodies {
+            if let Some(&i) = body_to_label.get(&body.id) {
+                centroids[i].0 += self.get_display_position(body);
+                centroids[i].1 += 1;
+            }
+        }
+
+        for ((_, roles), (sum, n)) in labelled.iter().zip(centroids) {
+            if n == 0 {
+                continue;
+            }
+            let centroid = sum / n as f32;
+            let screen = self.world_to_screen(centroid, screen_rect.width(), screen_rect.height());
+            let pos = screen_rect.min + egui::vec2(screen.x, screen.y);
+            if !screen_rect.contains(pos) {
+                continue;
+            }
+
+            let text = roles
+                .iter()
+                .map(|r| r.display())
+                .collect::<Vec<_>>()
+                .join(" ");
+            // Match the halo colours: green/red for the active step, gray otherwise
+            let color = match active {
+                Some((pos_role, _)) if roles.contains(&pos_role) => {
+                    egui::Color32::from_rgb(80, 255, 80)
+                }
+                Some((_, neg_role)) if roles.contains(&neg_role) => {
+                    egui::Color32::from_rgb(255, 90, 90)
+                }
+                _ => egui::Color32::LIGHT_GRAY,
+            };
+            let font = egui::FontId::proportional(16.0);
+            // Dark drop shadow keeps the label legible over bright particles
+            painter.text(
+                pos + egui::vec2(1.0, 1.0),
+                egui::Align2::CENTER_CENTER,
+                &text,
+                font.clone(),
+                egui::Color32::BLACK,
+            );
+            painter.text(pos, egui::Align2::CENTER_CENTER, text, font, color);
+        }
     }
 
     fn show_splash_screen(&mut self, ctx: &egui::Context) {
@@ -370,6 +560,14 @@ impl super::Renderer {
             ui.label(history_label);
             ui.separator();
             ui.colored_label(color, mode_text);
+            if status.lightweight {
+                ui.separator();
+                ui.colored_label(
+                    egui::Color32::LIGHT_GRAY,
+                    format!("Reduced history ({} snapshots)", status.lightweight_len),
+                )
+                .on_hover_text("Lightweight history mode: foils and scalar diagnostics only, no body playback");
+            }
             if status.is_playing {
                 ui.separator();
                 ui.label(format!("Speed: {:.1}×", status.speed));
@@ -422,6 +620,14 @@ impl super::Renderer {
                             }
                         };
                         ui.colored_label(color, label);
+                        if let Some((step, dwell)) = self.switch_ui_state.last_step_status {
+                            ui.separator();
+                            ui.small(format!(
+                                "Step {} • {:.3e} s left",
+                                step + 1,
+                                self.switch_ui_state.config.steps_to_seconds(dwell)
+                            ));
+                        }
                     }
                 },
             }
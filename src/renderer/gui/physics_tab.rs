@@ -4,6 +4,39 @@ impl super::super::Renderer {
     pub fn show_physics_tab(&mut self, ui: &mut egui::Ui) {
         ui.heading("⚛️ Physics Models");
 
+        // Save/load the whole config plus species parameters (no bodies)
+        ui.group(|ui| {
+            ui.label("💾 Config Presets");
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.text_edit_singleline(&mut self.config_preset_name);
+            });
+            let path = std::path::Path::new("config_presets")
+                .join(format!("{}.json", self.config_preset_name.trim()));
+            ui.horizontal(|ui| {
+                let valid = !self.config_preset_name.trim().is_empty();
+                if ui
+                    .add_enabled(valid, egui::Button::new("Save Preset"))
+                    .on_hover_text("Write SimConfig and species parameters to config_presets/")
+                    .clicked()
+                {
+                    if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                        let _ = sender.send(SimCommand::SaveConfigPreset { path: path.clone() });
+                    }
+                }
+                if ui
+                    .add_enabled(valid && path.exists(), egui::Button::new("Load Preset"))
+                    .clicked()
+                {
+                    if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                        let _ = sender.send(SimCommand::LoadConfigPreset { path: path.clone() });
+                    }
+                }
+            });
+        });
+
+        ui.separator();
+
         // Butler-Volmer Parameters
         ui.group(|ui| {
             ui.label("🔋 Butler-Volmer Parameters");
@@ -181,24 +214,6 @@ impl super::super::Renderer {
 
         ui.separator();
 
-        // Coulomb constant control
-        ui.group(|ui| {
-            ui.label("🔌 Coulomb Constant");
-            ui.add(
-                egui::Slider::new(&mut self.sim_config.coulomb_constant, 0.01..=1000.0)
-                    .text("k_e")
-                    .step_by(0.1)
-                    .logarithmic(true),
-            );
-            ui.label(format!(
-                "💡 Theoretical value: {:.3}",
-                crate::units::COULOMB_CONSTANT
-            ));
-            ui.label("Scale up for stronger interactions");
-        });
-
-        ui.separator();
-
         // Dipole model selection
         ui.group(|ui| {
             ui.label("🧲 Polar Solvent Dipole Model (EC/DMC)");
@@ -229,29 +244,53 @@ impl super::super::Renderer {
                 }
             });
             ui.small("Single offset: field difference nucleus vs electron (original). Conjugate pair: explicit ±q dipoles enabling dipole–dipole interactions.");
+            if ui
+                .add(
+                    egui::Slider::new(&mut self.sim_config.solvent_dipole_strength, 0.0..=10.0)
+                        .text("Ion–dipole orientation strength"),
+                )
+                .on_hover_text("Torque turning EC/DMC dipoles toward nearby Li+ and away from anions (0 = off)")
+                .changed()
+            {
+                *crate::config::LJ_CONFIG.lock() = self.sim_config.clone();
+            }
         });
 
         ui.separator();
 
-        // Induced field from foil charging
+        // Coulomb strength and the induced external field from foil charging
         ui.group(|ui| {
-            ui.label("📡 Induced External Field");
-            ui.small("Automatically add an external field based on foil charging setpoints.");
+            ui.label("⚡ Field & Electrostatics");
+            ui.add(
+                egui::Slider::new(&mut self.sim_config.coulomb_constant, 0.01..=1000.0)
+                    .text("Coulomb constant k_e")
+                    .step_by(0.1)
+                    .logarithmic(true),
+            )
+            .on_hover_text(format!(
+                "Scales every charge–charge force and field. Theoretical value: {:.3}",
+                crate::units::COULOMB_CONSTANT
+            ));
+
+            ui.small("Induced field: an external field added from foil charging setpoints.");
             ui.add(
                 egui::Slider::new(&mut self.sim_config.induced_field_gain, 0.0..=5_000_000.0)
                     .text("Induced Field Gain")
                     .step_by(0.1)
                     .logarithmic(true),
-            );
+            )
+            .on_hover_text("|E| per unit of average foil drive; 0 turns the induced field off");
             ui.add(
                 egui::Slider::new(&mut self.sim_config.induced_field_smoothing, 0.0..=0.999)
                     .text("Induced Field Smoothing α")
                     .step_by(0.001),
-            );
+            )
+            .on_hover_text("Exponential smoothing per step: E = α·E_prev + (1−α)·E_new; higher reacts slower");
             ui.checkbox(
                 &mut self.sim_config.induced_field_use_direction,
                 "Use foil-based direction (neg→pos)",
-            );
+            )
+            .on_hover_text("Point the field from negative to positive foil centroids instead of the manual field direction");
             ui.add(
                 egui::Slider::new(
                     &mut self.sim_config.induced_field_overpot_scale,
@@ -260,10 +299,17 @@ impl super::super::Renderer {
                 .text("Overpotential→Drive Scale")
                 .step_by(1.0)
                 .logarithmic(true),
-            );
-            ui.small("Drive magnitude: |current| or |target_ratio−1|×scale.");
+            )
+            .on_hover_text("Drive of an overpotential foil is |target_ratio−1| × this scale; current foils use |current|");
+            ui.checkbox(
+                &mut self.sim_config.capacitive_coupling,
+                "Capacitor model (groups A→B)",
+            )
+            .on_hover_text("Replace the heuristic with a parallel-plate field from the net charge imbalance between foil groups A (+) and B (−).");
         });
 
+        ui.separator();
+
         // Stack Pressure (Cell Casing) Controls
         ui.group(|ui| {
             ui.label("📦 Stack Pressure (Cell Casing)");
@@ -294,6 +340,114 @@ impl super::super::Renderer {
             }
         });
 
+        // Uniform body force (gravity / sedimentation)
+        ui.group(|ui| {
+            ui.label("⬇️ Body Force");
+            ui.small("Uniform gravity-like acceleration on all particles (Å/fs²)");
+            ui.add(
+                egui::Slider::new(&mut self.sim_config.body_force.x, -0.01..=0.01)
+                    .text("g_x"),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.sim_config.body_force.y, -0.01..=0.01)
+                    .text("g_y"),
+            );
+            ui.add_enabled(
+                self.sim_config.enable_out_of_plane,
+                egui::Slider::new(&mut self.sim_config.body_force_z, -0.01..=0.01).text("g_z"),
+            );
+            ui.checkbox(
+                &mut self.sim_config.body_force_buoyant,
+                "Buoyant (scale by mass excess over mean)",
+            );
+        });
+
+        // Electron cloud stiffness (polarizability)
+        ui.group(|ui| {
+            ui.label("🌀 Electron Cloud Stiffness");
+            ui.small("Restoring spring on electron offsets; lower = more polarizable (0 = none)");
+            ui.add(
+                egui::Slider::new(&mut self.sim_config.electron_stiffness, 0.0..=10.0)
+                    .text("Default"),
+            );
+            let default_k = self.sim_config.electron_stiffness;
+            for (label, value) in [
+                ("Metal override", &mut self.sim_config.electron_stiffness_metal),
+                ("Solvent override", &mut self.sim_config.electron_stiffness_solvent),
+            ] {
+                ui.horizontal(|ui| {
+                    let mut enabled = value.is_some();
+                    if ui.checkbox(&mut enabled, label).changed() {
+                        *value = enabled.then_some(default_k);
+                    }
+                    if let Some(v) = value.as_mut() {
+                        ui.add(egui::Slider::new(v, 0.0..=10.0));
+                    }
+                });
+            }
+            ui.horizontal(|ui| {
+                ui.label("Max displacement:");
+                ui.add(
+                    egui::DragValue::new(&mut self.sim_config.max_electron_displacement)
+                        .speed(0.01)
+                        .clamp_range(0.0..=10.0)
+                        .suffix(" Å"),
+                )
+                .on_hover_text("Clamp on electron offsets from their host; 0 = unclamped");
+            });
+        });
+
+        // Chemistry master switch for pure transport studies
+        ui.group(|ui| {
+            ui.label("⚗️ Reactions");
+            ui.checkbox(&mut self.sim_config.reactions_enabled, "Enable reactions")
+                .on_hover_text("Electron hopping, redox, SEI formation and intercalation");
+            ui.add_enabled(
+                !self.sim_config.reactions_enabled,
+                egui::Checkbox::new(
+                    &mut self.sim_config.reactions_off_skips_foils,
+                    "Also pause foil currents",
+                ),
+            );
+        });
+
+        ui.checkbox(
+            &mut self.sim_config.deterministic_foil_electrons,
+            "🎯 Deterministic foil electron placement",
+        )
+        .on_hover_text("Add to the foil body with the fewest electrons (remove from the most) at its cloud centroid instead of a random host");
+        ui.horizontal(|ui| {
+            ui.label("Max electrons/step per foil:");
+            ui.add(
+                egui::DragValue::new(&mut self.sim_config.max_electrons_per_step_per_foil)
+                    .speed(1.0)
+                    .clamp_range(1..=1_000_000),
+            )
+            .on_hover_text("Caps overpotential-mode transfers per foil each step to smooth aggressive controller gains");
+        });
+
+        ui.checkbox(
+            &mut self.sim_config.deterministic_collisions,
+            "🎯 Deterministic collision order",
+        )
+        .on_hover_text("Resolve overlapping pairs sequentially in body-id order instead of in parallel; reproducible but slower");
+
+        // Soft start for overlapping initial configurations
+        ui.group(|ui| {
+            ui.label("🪶 Soft Start");
+            ui.small("Caps LJ/repulsive pair forces for the first N frames so overlaps relax gently");
+            ui.add(
+                egui::Slider::new(&mut self.sim_config.soft_start_frames, 0..=5000)
+                    .text("Frames (0 = off)"),
+            );
+            ui.add_enabled(
+                self.sim_config.soft_start_frames > 0,
+                egui::Slider::new(&mut self.sim_config.soft_start_force_cap, 0.1..=1000.0)
+                    .text("Force Cap")
+                    .logarithmic(true),
+            );
+        });
+
         ui.group(|ui| {
             ui.label("🌡️ Simulation Temperature");
             let mut temp = self.sim_config.temperature;
@@ -327,6 +481,34 @@ impl super::super::Renderer {
             });
             ui.small("How often to enforce temperature constraint");
             ui.small("Lower = more frequent, higher = more natural dynamics");
+
+            ui.separator();
+
+            ui.label("Thermostatted species");
+            ui.horizontal_wrapped(|ui| {
+                for species in [
+                    Species::LithiumIon,
+                    Species::ElectrolyteAnion,
+                    Species::EC,
+                    Species::DMC,
+                    Species::VC,
+                    Species::FEC,
+                    Species::EMC,
+                    Species::LithiumMetal,
+                    Species::FoilMetal,
+                ] {
+                    let list = &mut self.sim_config.thermostat_species;
+                    let mut included = list.contains(&species);
+                    if ui.checkbox(&mut included, format!("{:?}", species)).changed() {
+                        if included {
+                            list.push(species);
+                        } else {
+                            list.retain(|&s| s != species);
+                        }
+                    }
+                }
+            });
+            ui.small("Unticked species keep their velocities when the thermostat rescales");
         });
 
         ui.separator();
@@ -343,6 +343,37 @@ impl super::super::Renderer {
 
         ui.separator();
 
+        // Species freezing for equilibration
+        ui.group(|ui| {
+            ui.label("🧊 Freeze Species");
+            ui.small("Frozen species are held in place and skipped by the thermostat");
+            ui.horizontal_wrapped(|ui| {
+                for species in [
+                    Species::FoilMetal,
+                    Species::LithiumMetal,
+                    Species::SEI,
+                    Species::LithiumIon,
+                    Species::ElectrolyteAnion,
+                    Species::EC,
+                    Species::DMC,
+                ] {
+                    let mut frozen = self.frozen_species.contains(&species);
+                    if ui.checkbox(&mut frozen, format!("{:?}", species)).changed() {
+                        if frozen {
+                            self.frozen_species.insert(species);
+                        } else {
+                            self.frozen_species.remove(&species);
+                        }
+                        if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                            let _ = sender.send(SimCommand::FreezeSpecies { species, frozen });
+                        }
+                    }
+                }
+            });
+        });
+
+        ui.separator();
+
         // Domain Size Controls
         ui.group(|ui| {
             ui.label("🌐 Computational Domain");
@@ -389,6 +420,42 @@ impl super::super::Renderer {
                 }
             });
             ui.label("⚠️ Particles outside domain will be removed");
+
+            ui.checkbox(&mut self.sim_config.auto_grow_domain, "Auto-grow domain")
+                .on_hover_text("Widen the domain when particles approach a wall; positions are kept");
+            if self.sim_config.auto_grow_domain {
+                ui.horizontal(|ui| {
+                    ui.label("Margin (Å):");
+                    ui.add(
+                        egui::DragValue::new(&mut self.sim_config.auto_grow_margin)
+                            .speed(0.5)
+                            .clamp_range(0.0..=100.0),
+                    );
+                    ui.label("Increment (Å/side):");
+                    ui.add(
+                        egui::DragValue::new(&mut self.sim_config.auto_grow_increment)
+                            .speed(1.0)
+                            .clamp_range(1.0..=500.0),
+                    );
+                    ui.label("Max size (Å):");
+                    ui.add(
+                        egui::DragValue::new(&mut self.sim_config.auto_grow_max_size)
+                            .speed(10.0)
+                            .clamp_range(100.0..=20000.0),
+                    );
+                });
+            }
+            ui.horizontal(|ui| {
+                ui.label("Re-center every");
+                ui.add(
+                    egui::DragValue::new(&mut self.sim_config.recenter_interval_frames)
+                        .speed(10.0)
+                        .clamp_range(0..=1_000_000),
+                )
+                .on_hover_text("Shift the center of mass back to the origin and remove net momentum; 0 = off");
+                ui.label("frames");
+                ui.checkbox(&mut self.sim_config.recenter_include_frozen, "Include frozen");
+            });
         });
 
         ui.separator();
@@ -433,6 +500,28 @@ impl super::super::Renderer {
                     });
             });
 
+            // Right-click placement preview
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.show_spawn_force_preview, "Preview force on right-click spawn")
+                    .on_hover_text("While placing a particle, draw the net Coulomb + LJ force it would feel");
+                if self.show_spawn_force_preview {
+                    ui.add(
+                        egui::DragValue::new(&mut self.spawn_force_preview_scale)
+                            .speed(0.5)
+                            .clamp_range(0.0..=1.0e4)
+                            .prefix("Arrow scale: "),
+                    );
+                }
+            });
+            if let Some(force) = self.spawn_force_preview {
+                ui.small(format!(
+                    "Pending particle force: ({:.4}, {:.4}), |F| = {:.4}",
+                    force.x,
+                    force.y,
+                    force.mag()
+                ));
+            }
+
             // Common Width/Height controls (used by Rectangle and Foil)
             ui.horizontal(|ui| {
                 ui.label("Width:");
@@ -461,6 +550,8 @@ impl super::super::Renderer {
                             x: self.scenario_x,
                             y: self.scenario_y,
                             radius: self.scenario_radius,
+                            electron_count_override: None,
+                            charge_override: None,
                         })
                         .unwrap();
                 }
@@ -560,6 +651,26 @@ impl super::super::Renderer {
                 );
                 ui.label("M LiPF6");
             });
+            ui.checkbox(&mut self.show_molarity_meter, "📟 Show bulk molarity meter")
+                .on_hover_text("Li+ count away from the electrodes, as mol/L for a slab one particle diameter deep");
+            if self.show_molarity_meter {
+                ui.horizontal(|ui| {
+                    ui.label("Electrode band:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.molarity_meter.electrode_band)
+                            .speed(0.5)
+                            .clamp_range(0.0..=200.0)
+                            .suffix(" Å"),
+                    );
+                    ui.label("Slab depth:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.molarity_meter.thickness)
+                            .speed(0.1)
+                            .clamp_range(0.1..=100.0)
+                            .suffix(" Å"),
+                    );
+                });
+            }
             ui.horizontal(|ui| {
                 ui.label("Total particles:");
                 ui.add(
@@ -567,7 +678,69 @@ impl super::super::Renderer {
                         .speed(25.0)
                         .clamp_range(0..=50_000),
                 );
+                ui.label("Wall margin:");
+                ui.add(
+                    egui::DragValue::new(&mut self.sim_config.spawn_wall_margin)
+                        .speed(0.1)
+                        .clamp_range(0.0..=50.0)
+                        .suffix(" Å"),
+                )
+                .on_hover_text("Spawned bodies keep at least this gap from the domain walls");
+                ui.label("Min separation:");
+                ui.add(
+                    egui::DragValue::new(&mut self.sim_config.spawn_min_separation)
+                        .speed(0.01)
+                        .clamp_range(0.1..=2.0)
+                        .suffix("× radii"),
+                )
+                .on_hover_text(
+                    "Spawned bodies keep their centres at least this fraction of the summed radii apart",
+                );
+            });
+            // Quick LiPF6 in EC:DMC mix: salt molarity and solvent ratio set independently
+            ui.horizontal(|ui| {
+                ui.label("EC:DMC (vol):");
+                ui.add(
+                    egui::DragValue::new(&mut self.electrolyte_ec_parts)
+                        .speed(0.1)
+                        .clamp_range(0.0..=10.0),
+                );
+                ui.label(":");
+                ui.add(
+                    egui::DragValue::new(&mut self.electrolyte_dmc_parts)
+                        .speed(0.1)
+                        .clamp_range(0.0..=10.0),
+                );
+            });
+            let quick = crate::species::electrolyte_counts(
+                self.electrolyte_total_particles,
+                self.electrolyte_molarity,
+                self.electrolyte_ec_parts,
+                self.electrolyte_dmc_parts,
+            );
+            ui.horizontal(|ui| {
+                ui.small(format!(
+                    "{} Li+, {} PF6-, {} EC, {} DMC",
+                    quick.lithium, quick.anion, quick.ec, quick.dmc
+                ));
+                if ui
+                    .button("Add EC:DMC Electrolyte")
+                    .on_hover_text("Uses the molarity, total and EC:DMC ratio above, ignoring the component table.")
+                    .clicked()
+                {
+                    if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
+                        let _ = sender.send(SimCommand::AddElectrolyte {
+                            total: self.electrolyte_total_particles,
+                            molarity: self.electrolyte_molarity,
+                            ec_parts: self.electrolyte_ec_parts,
+                            dmc_parts: self.electrolyte_dmc_parts,
+                            domain_width: self.domain_width,
+                            domain_height: self.domain_height,
+                        });
+                    }
+                }
             });
+
             ui.add_space(6.0);
             show_electrolyte_component_table(ui, self);
 
@@ -612,6 +785,47 @@ impl super::super::Renderer {
                     delete_all_electrolyte();
                 }
             });
+
+            // Steady-state counts: spawn/cull in the bulk at a fixed interval
+            ui.add_space(6.0);
+            ui.label("⚖️ Species Count Targets");
+            for (species, name) in [
+                (Species::LithiumIon, "Li+"),
+                (Species::ElectrolyteAnion, "PF6-"),
+                (Species::EC, "EC"),
+                (Species::DMC, "DMC"),
+                (Species::EMC, "EMC"),
+                (Species::VC, "VC"),
+                (Species::FEC, "FEC"),
+            ] {
+                ui.horizontal(|ui| {
+                    let current = self.sim_config.species_targets.get(&species).copied();
+                    let mut hold = current.is_some();
+                    if ui.checkbox(&mut hold, format!("Hold {}", name)).changed() {
+                        if hold {
+                            let n = self.bodies.iter().filter(|b| b.species == species).count();
+                            self.sim_config.species_targets.insert(species, n);
+                        } else {
+                            self.sim_config.species_targets.remove(&species);
+                        }
+                    }
+                    if let Some(target) = self.sim_config.species_targets.get_mut(&species) {
+                        ui.add(egui::DragValue::new(target).speed(1.0).clamp_range(0..=50_000));
+                    }
+                });
+            }
+            if !self.sim_config.species_targets.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.label("Balance every:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.sim_config.species_balance_interval_fs)
+                            .speed(1.0)
+                            .clamp_range(1.0..=100_000.0)
+                            .suffix(" fs"),
+                    );
+                });
+                ui.small("Charged ions are paired with their counter-ion unless it has its own target.");
+            }
         });
 
         ui.separator();
@@ -1181,7 +1395,9 @@ fn spawn_electrolyte_plan(
     }
 
     let total_added: usize = plan.entries.iter().map(|entry| entry.count).sum();
-    eprintln!(
+    crate::sim_log!(
+        Io,
+        Info,
         "[Electrolyte] Added {} particles: {}",
         total_added,
         plan
@@ -30,13 +30,13 @@ impl super::super::Renderer {
                         self.last_capture_time = *crate::renderer::state::SIM_TIME.lock();
                         // Ensure capture folder exists
                         if let Err(e) = std::fs::create_dir_all(&self.capture_folder) {
-                            println!("Warning: Could not create capture folder '{}': {}", self.capture_folder, e);
+                            crate::sim_log!(Io, Warn, "Could not create capture folder '{}': {}", self.capture_folder, e);
                         } else {
-                            println!("🔴 Screen capture recording started - will capture every {:.1} fs to folder: {}",
-                                    self.capture_interval, self.capture_folder);
+                            crate::sim_log!(Io, Info, "🔴 Screen capture recording started - will capture every {:.1} fs to folder: {}",
+                                    self.capture_interval, self.capture_folder);
                         }
                     } else {
-                        println!("⏹️ Screen capture recording stopped");
+                        crate::sim_log!(Io, Info, "⏹️ Screen capture recording stopped");
                     }
                 }
                 
@@ -44,12 +44,26 @@ impl super::super::Renderer {
                     // Trigger immediate capture
                     self.should_capture_next_frame = true;
                     let current_time = *crate::renderer::state::SIM_TIME.lock();
-                    println!("📷 Manual capture triggered at simulation time {:.2} fs", current_time);
+                    crate::sim_log!(Io, Info, "📷 Manual capture triggered at simulation time {:.2} fs", current_time);
+                }
+                
+                if ui
+                    .button("🖋 Export SVG")
+                    .on_hover_text("Vector copy of the current view (bodies, foils, highlights, domain box)")
+                    .clicked()
+                {
+                    let current_time = *crate::renderer::state::SIM_TIME.lock();
+                    let path = std::path::Path::new(&self.capture_folder)
+                        .join(format!("frame_{:.1}fs.svg", current_time));
+                    match self.export_svg(&path) {
+                        Ok(circles) => crate::sim_log!(Io, Info, "🖋 Exported {} circles to {}", circles, path.display()),
+                        Err(e) => crate::sim_log!(Io, Warn, "SVG export to '{}' failed: {}", path.display(), e),
+                    }
                 }
             });
             
             ui.horizontal(|ui| {
-                ui.label("Capture interval (fs):");
+                ui.label("Capture interval (fs):");
                 ui.add(egui::DragValue::new(&mut self.capture_interval)
                     .clamp_range(0.1..=10.0)
                     .speed(0.1));
@@ -60,9 +74,9 @@ impl super::super::Renderer {
                 if ui.text_edit_singleline(&mut self.capture_folder).changed() {
                     // Ensure the folder path is valid and create if it doesn't exist
                     if let Err(e) = std::fs::create_dir_all(&self.capture_folder) {
-                        println!("Warning: Could not create capture folder '{}': {}", self.capture_folder, e);
+                        crate::sim_log!(Io, Warn, "Could not create capture folder '{}': {}", self.capture_folder, e);
                     } else {
-                        println!("Capture folder set to: {}", self.capture_folder);
+                        crate::sim_log!(Io, Info, "Capture folder set to: {}", self.capture_folder);
                     }
                 }
             });
@@ -80,7 +94,7 @@ impl super::super::Renderer {
                     self.is_selecting_region = false;
                     self.selection_start = None;
                     self.selection_end = None;
-                    println!("📺 Capture region set to full screen");
+                    crate::sim_log!(Io, Info, "📺 Capture region set to full screen");
                 }
                 
                 let region_button_text = if self.is_selecting_region {
@@ -97,13 +111,13 @@ impl super::super::Renderer {
                     if self.is_selecting_region {
                         // Cancel region selection
                         self.cancel_region_selection();
-                        println!("❌ Region selection cancelled");
+                        crate::sim_log!(Io, Info, "❌ Region selection cancelled");
                     } else {
                         // Start region selection
                         self.is_selecting_region = true;
                         self.selection_start = None;
                         self.selection_end = None;
-                        println!("📐 Region selection started - click and drag in the simulation view");
+                        crate::sim_log!(Io, Info, "📐 Region selection started - click and drag in the simulation view");
                     }
                 }
             });
@@ -133,6 +147,37 @@ impl super::super::Renderer {
             }
         });
         
+        ui.separator();
+
+        // Fixed-size frames for region captures, independent of the window size
+        ui.group(|ui| {
+            use crate::renderer::capture_frame::{frame_layout, FrameFit};
+            ui.checkbox(&mut self.capture_fixed_resolution, "Fixed output resolution")
+                .on_hover_text("Scale region captures to the same pixel size whatever the window size");
+            if !self.capture_fixed_resolution {
+                return;
+            }
+            let (width, height) = &mut self.capture_resolution;
+            ui.horizontal(|ui| {
+                ui.label("Frame:");
+                ui.add(egui::DragValue::new(width).clamp_range(16..=7680).suffix(" px"));
+                ui.label("×");
+                ui.add(egui::DragValue::new(height).clamp_range(16..=4320).suffix(" px"));
+            });
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.capture_frame_fit, FrameFit::Letterbox, "Letterbox")
+                    .on_hover_text("Keep the full frame size and pad the region with bars");
+                ui.radio_value(&mut self.capture_frame_fit, FrameFit::RegionAspect, "Region aspect")
+                    .on_hover_text("Keep the frame width and derive the height from the region");
+            });
+            let (w, h) = self.capture_resolution;
+            let fit = self.capture_frame_fit;
+            match self.capture_region.and_then(|(a, b)| frame_layout(a, b, w, h, fit)) {
+                Some(layout) => ui.label(format!("Output: {} × {} px", layout.width, layout.height)),
+                None => ui.small("Applies once a capture region is set."),
+            };
+        });
+
         ui.separator();
         
         // Statistics and info
@@ -146,13 +191,13 @@ impl super::super::Renderer {
             
             ui.horizontal(|ui| {
                 ui.label("Last capture time:");
-                ui.label(format!("{:.2} fs", self.last_capture_time));
+                ui.label(format!("{:.2} fs", self.last_capture_time));
             });
             
             ui.horizontal(|ui| {
                 ui.label("Current simulation time:");
                 let current_time = *crate::renderer::state::SIM_TIME.lock();
-                ui.label(format!("{:.2} fs", current_time));
+                ui.label(format!("{:.2} fs", current_time));
             });
             
             if self.screen_capture_enabled {
@@ -161,7 +206,7 @@ impl super::super::Renderer {
                 if time_until_next > 0.0 {
                     ui.horizontal(|ui| {
                         ui.label("Next capture in:");
-                        ui.label(format!("{:.1} fs", time_until_next));
+                        ui.label(format!("{:.1} fs", time_until_next));
                     });
                 }
             }
@@ -101,6 +101,91 @@ impl super::super::Renderer {
                 );
             });
 
+            ui.horizontal(|ui| {
+                ui.label("History capture:");
+                ui.radio_value(&mut self.sim_config.history_mode, crate::config::HistoryMode::Full, "Full");
+                ui.radio_value(
+                    &mut self.sim_config.history_mode,
+                    crate::config::HistoryMode::Lightweight,
+                    "Lightweight",
+                )
+                .on_hover_text("Record only foil states, species counts, net charge and switch step");
+            });
+            if self.sim_config.history_mode == crate::config::HistoryMode::Lightweight {
+                ui.horizontal(|ui| {
+                    ui.label("Every");
+                    ui.add(
+                        egui::DragValue::new(&mut self.sim_config.lightweight_history_stride)
+                            .clamp_range(1..=100_000),
+                    );
+                    ui.label("frames");
+                    if ui.button("Export JSON").clicked() {
+                        if let Some(sender) = sender_opt.clone() {
+                            let _ = sender.send(SimCommand::ExportLightweightHistory {
+                                path: std::path::PathBuf::from("doe_results/lightweight_history.json"),
+                            });
+                        }
+                    }
+                });
+                ui.small(format!(
+                    "Reduced mode: {} snapshots recorded; the body playback slider only covers frames captured in Full mode.",
+                    playback_status.lightweight_len
+                ));
+                self.show_lightweight_playback(ui, sender_opt.as_ref());
+            }
+
+            egui::CollapsingHeader::new("🗜 Compressed History File")
+                .id_source("compressed_history")
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Keyframe every");
+                        ui.add(
+                            egui::DragValue::new(
+                                &mut self.sim_config.compressed_history_keyframe_interval,
+                            )
+                            .clamp_range(1..=100_000),
+                        );
+                        ui.label("frames");
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Tolerance: pos");
+                        ui.add(
+                            egui::DragValue::new(
+                                &mut self.sim_config.compressed_history_position_tolerance,
+                            )
+                            .speed(0.001)
+                            .clamp_range(0.0..=10.0)
+                            .suffix(" Å"),
+                        );
+                        ui.label("vel");
+                        ui.add(
+                            egui::DragValue::new(
+                                &mut self.sim_config.compressed_history_velocity_tolerance,
+                            )
+                            .speed(0.0001)
+                            .clamp_range(0.0..=1.0)
+                            .suffix(" Å/fs"),
+                        )
+                        .on_hover_text("Bodies that moved less than this since their last stored value are skipped");
+                    });
+                    let path = std::path::PathBuf::from("saved_state/history.delta.gz");
+                    ui.horizontal(|ui| {
+                        if ui.button("💾 Save").clicked() {
+                            if let Some(sender) = sender_opt.clone() {
+                                let _ = sender
+                                    .send(SimCommand::SaveCompressedHistory { path: path.clone() });
+                            }
+                        }
+                        if ui.button("📂 Load").clicked() {
+                            if let Some(sender) = sender_opt.clone() {
+                                let _ = sender
+                                    .send(SimCommand::LoadCompressedHistory { path: path.clone() });
+                            }
+                        }
+                        ui.small(path.display().to_string());
+                    });
+                });
+
             // Display 1-based frame number for friendlier UX
             ui.label(format!(
                 "Currently viewing frame {} ({:.2} fs, Δt {:.2} fs)",
@@ -186,10 +271,184 @@ impl super::super::Renderer {
             );
 
             if ui.button("Step Simulation").clicked() {
-                if let Some(sender) = sender_opt {
+                if let Some(sender) = sender_opt.clone() {
                     let _ = sender.send(SimCommand::StepOnce);
                 }
             }
+
+            // Hold at frame: re-run the last step after tweaking parameters
+            ui.horizontal(|ui| {
+                if ui
+                    .checkbox(&mut self.step_replay_armed, "Hold at frame")
+                    .on_hover_text("Snapshot bodies before each step so the step can be replayed")
+                    .changed()
+                {
+                    if let Some(sender) = sender_opt.clone() {
+                        let _ = sender.send(SimCommand::SetStepReplay {
+                            armed: self.step_replay_armed,
+                        });
+                    }
+                }
+                if ui
+                    .add_enabled(self.step_replay_armed, egui::Button::new("Replay Step"))
+                    .on_hover_text("Restore the pre-step state and run the same step again")
+                    .clicked()
+                {
+                    if let Some(sender) = sender_opt.clone() {
+                        let _ = sender.send(SimCommand::ReplayStep);
+                    }
+                }
+            });
+
+            // Time-reversal check: forward N steps, reverse velocities, back N steps
+            ui.horizontal(|ui| {
+                ui.label("Reversibility steps:");
+                ui.add(egui::DragValue::new(&mut self.reversibility_steps).clamp_range(1..=100_000));
+                if ui
+                    .button("Run Reversibility Test")
+                    .on_hover_text("RMS distance from the start after running forward and back; near zero without damping")
+                    .clicked()
+                {
+                    if let Some(sender) = sender_opt.clone() {
+                        let _ = sender.send(SimCommand::RunReversibilityTest {
+                            steps: self.reversibility_steps,
+                        });
+                    }
+                }
+            });
+            if let Some((steps, rms)) = *crate::renderer::state::REVERSIBILITY_RESULT.lock() {
+                ui.label(format!("Return error after {} steps: {:.6} Å", steps, rms));
+            }
+
+            // Measure until converged: auto-pause once the observable settles
+            ui.checkbox(&mut self.sim_config.convergence_monitor, "Pause when converged")
+                .on_hover_text("Pause once the observable stays within the tolerance over the window; untick to re-arm");
+            if self.sim_config.convergence_monitor {
+                use crate::config::ConvergenceObservable;
+                ui.horizontal(|ui| {
+                    let observable = &mut self.sim_config.convergence_observable;
+                    ui.radio_value(observable, ConvergenceObservable::Temperature, "Temperature");
+                    ui.radio_value(observable, ConvergenceObservable::NetDipole, "Net dipole");
+                    let is_count = matches!(observable, ConvergenceObservable::SpeciesCount(_));
+                    if ui.radio(is_count, "Species count").clicked() && !is_count {
+                        *observable = ConvergenceObservable::SpeciesCount(crate::body::Species::LithiumMetal);
+                    }
+                    if let ConvergenceObservable::SpeciesCount(species) = observable {
+                        egui::ComboBox::from_id_source("convergence_species")
+                            .selected_text(format!("{:?}", species))
+                            .show_ui(ui, |ui| {
+                                for s in [
+                                    crate::body::Species::LithiumIon,
+                                    crate::body::Species::LithiumMetal,
+                                    crate::body::Species::ElectrolyteAnion,
+                                    crate::body::Species::SEI,
+                                ] {
+                                    ui.selectable_value(species, s, format!("{:?}", s));
+                                }
+                            });
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Tolerance:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.sim_config.convergence_tolerance)
+                            .speed(0.01)
+                            .clamp_range(0.0..=1.0e6),
+                    );
+                    ui.label("Window (fs):");
+                    ui.add(
+                        egui::DragValue::new(&mut self.sim_config.convergence_window_fs)
+                            .speed(10.0)
+                            .clamp_range(1.0..=1.0e7),
+                    );
+                });
+                match *crate::renderer::state::CONVERGENCE_RESULT.lock() {
+                    Some(result) => ui.label(format!(
+                        "✓ Converged to {:.4} at frame {} ({:.1} fs)",
+                        result.value, result.frame, result.time_fs
+                    )),
+                    None => ui.label("Waiting for the observable to settle…"),
+                };
+            }
+        });
+    }
+
+    /// Scrub or play the reduced history: foil currents, counts and the
+    /// switch step per snapshot, with no body cloud.
+    fn show_lightweight_playback(
+        &mut self,
+        ui: &mut egui::Ui,
+        sender: Option<&std::sync::mpsc::Sender<SimCommand>>,
+    ) {
+        let path = std::path::PathBuf::from("doe_results/lightweight_history.json");
+        ui.horizontal(|ui| {
+            ui.label("Playback:");
+            if ui.button("Use recorded").clicked() {
+                if let Some(sender) = sender {
+                    let _ = sender.send(SimCommand::LoadLightweightPlayback { path: None });
+                }
+            }
+            if ui.button("Load JSON").clicked() {
+                if let Some(sender) = sender {
+                    let _ = sender.send(SimCommand::LoadLightweightPlayback { path: Some(path) });
+                }
+            }
+        });
+
+        let snapshots = LIGHTWEIGHT_PLAYBACK.lock();
+        if snapshots.is_empty() {
+            return;
+        }
+        let last = snapshots.len() - 1;
+        if self.lightweight_playing {
+            if self.lightweight_cursor < last {
+                self.lightweight_cursor += 1;
+                ui.ctx().request_repaint();
+            } else {
+                self.lightweight_playing = false;
+            }
+        }
+        self.lightweight_cursor = self.lightweight_cursor.min(last);
+        ui.horizontal(|ui| {
+            let label = if self.lightweight_playing { "⏸" } else { "▶" };
+            if ui.button(label).clicked() {
+                if !self.lightweight_playing && self.lightweight_cursor == last {
+                    self.lightweight_cursor = 0;
+                }
+                self.lightweight_playing = !self.lightweight_playing;
+            }
+            ui.add(egui::Slider::new(&mut self.lightweight_cursor, 0..=last).text("Snapshot"));
+        });
+
+        let snapshot = &snapshots[self.lightweight_cursor];
+        let step = snapshot
+            .switch_step
+            .map_or_else(|| "off".to_string(), |s| (s + 1).to_string());
+        ui.label(format!(
+            "t = {:.1} fs (frame {}), switch step {}, net charge {:.2}",
+            snapshot.sim_time, snapshot.frame, step, snapshot.net_charge
+        ));
+        ui.small(
+            snapshot
+                .species_counts
+                .iter()
+                .map(|(species, n)| format!("{:?}: {}", species, n))
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+        egui::Grid::new("lightweight_playback_foils").striped(true).show(ui, |ui| {
+            ui.strong("Foil");
+            ui.strong("Bodies");
+            ui.strong("DC current");
+            ui.strong("Mode");
+            ui.end_row();
+            for foil in &snapshot.foils {
+                ui.label(foil.id.to_string());
+                ui.label(foil.body_ids.len().to_string());
+                ui.label(format!("{:.3}", foil.dc_current));
+                ui.label(format!("{:?}", foil.charging_mode));
+                ui.end_row();
+            }
         });
     }
 }
@@ -76,6 +76,29 @@ impl super::super::Renderer {
                 changed = true;
             }
 
+            // Collision radius override (rendering keeps using Radius)
+            let mut separate_collision = current_props.collision_radius.is_some();
+            if ui
+                .checkbox(&mut separate_collision, "Separate collision radius")
+                .on_hover_text("Use a different radius for contact detection than for drawing")
+                .changed()
+            {
+                current_props.collision_radius = separate_collision.then_some(current_props.radius);
+                changed = true;
+            }
+            if let Some(collision_radius) = current_props.collision_radius.as_mut() {
+                if ui
+                    .add(
+                        egui::Slider::new(collision_radius, 0.1..=10.0)
+                            .text("Collision Radius")
+                            .step_by(0.01),
+                    )
+                    .changed()
+                {
+                    changed = true;
+                }
+            }
+
             // Damping control
             if ui
                 .add(
@@ -88,6 +111,29 @@ impl super::super::Renderer {
                 changed = true;
             }
 
+            // Out-of-plane participation (only matters with out-of-plane enabled)
+            let mut z_mobility = current_props
+                .z_mobility
+                .unwrap_or_else(|| crate::species::ZMobility::default_for(self.selected_lj_species));
+            let mut z_changed = ui
+                .checkbox(&mut z_mobility.mobile, "Moves out of plane (z)")
+                .on_hover_text("Immobile species stay at z = 0 when out-of-plane motion is enabled")
+                .changed();
+            if z_mobility.mobile {
+                z_changed |= ui
+                    .add(
+                        egui::Slider::new(&mut z_mobility.damping_scale, 0.0..=10.0)
+                            .text("Z Damping ×")
+                            .step_by(0.01),
+                    )
+                    .on_hover_text("Multiplier on the global z-damping for this species")
+                    .changed();
+            }
+            if z_changed {
+                current_props.z_mobility = Some(z_mobility);
+                changed = true;
+            }
+
             // Color picker
             let mut c = egui::Color32::from_rgba_unmultiplied(
                 current_props.color[0],
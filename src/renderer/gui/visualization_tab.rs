@@ -8,12 +8,29 @@ impl super::super::Renderer {
         // Display Options
         ui.group(|ui| {
             ui.label("🖼️ Display Options");
+            ui.horizontal(|ui| {
+                ui.label("Theme:");
+                egui::ComboBox::from_id_source("render_theme")
+                    .selected_text(self.theme.label())
+                    .show_ui(ui, |ui| {
+                        for theme in crate::renderer::theme::Theme::ALL {
+                            ui.selectable_value(&mut self.theme, theme, theme.label());
+                        }
+                    });
+            });
             ui.checkbox(&mut self.show_bodies, "Show Bodies");
             ui.checkbox(&mut self.show_quadtree, "Show Quadtree");
             if ui.checkbox(&mut self.side_view_mode, "📐 Side View (X-Z)")
                 .on_hover_text("Toggle between top-down view (X-Y) and side view (X-Z) to visualize particle motion in the Z dimension").clicked() {
                 // Optional: Add any side effects when toggling view mode
             }
+            if ui
+                .button("🔍 Zoom to Fit")
+                .on_hover_text("Frame all particles (shortcut: F)")
+                .clicked()
+            {
+                self.zoom_to_fit();
+            }
 
             if self.show_quadtree {
                 let range = &mut self.depth_range;
@@ -43,14 +60,83 @@ impl super::super::Renderer {
                 &mut self.sim_config.show_charge_density,
                 "Show Charge Density",
             );
+            if self.sim_config.show_charge_density {
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Snapshot")
+                        .on_hover_text("Hold the charge density of the current view")
+                        .clicked()
+                    {
+                        self.capture_charge_snapshot();
+                        self.show_charge_difference = true;
+                    }
+                    let held = self.charge_snapshot.is_some();
+                    if ui.add_enabled(held, egui::Button::new("Clear snapshot")).clicked() {
+                        self.charge_snapshot = None;
+                        self.show_charge_difference = false;
+                    }
+                    ui.add_enabled(
+                        held,
+                        egui::Checkbox::new(&mut self.show_charge_difference, "Show live − snapshot"),
+                    )
+                    .on_hover_text("Red: charge accumulated since the snapshot; blue: depleted");
+                });
+            }
             ui.checkbox(
                 &mut self.sim_config.show_2d_domain_density,
                 "Show 2D Domain Density",
             );
+            if self.sim_config.show_2d_domain_density {
+                ui.add(
+                    egui::Slider::new(&mut self.density_smoothing_temporal, 0.0..=0.95)
+                        .text("Density temporal smoothing"),
+                )
+                .on_hover_text("Blend the density grid with the previous frame (0 = off)");
+                ui.add(
+                    egui::Slider::new(&mut self.density_smoothing_spatial, 0.0..=4.0)
+                        .text("Density spatial blur (cells)"),
+                )
+                .on_hover_text("Gaussian blur of the density grid before coloring (0 = off)");
+            }
+            ui.horizontal(|ui| {
+                ui.label("Continuum grid:");
+                ui.add(
+                    egui::DragValue::new(&mut self.continuum_grid_spacing)
+                        .speed(0.5)
+                        .clamp_range(1.0..=200.0)
+                        .suffix(" Å"),
+                );
+                if ui
+                    .button("Export continuum fields")
+                    .on_hover_text("Number density, charge density and mean velocity per cell")
+                    .clicked()
+                {
+                    let grid = crate::plotting::analysis::ContinuumGrid {
+                        domain_width: self.domain_width / 2.0,
+                        domain_height: self.domain_height / 2.0,
+                        spacing: self.continuum_grid_spacing,
+                    };
+                    let fields = crate::plotting::analysis::coarse_grain(&self.bodies, &grid);
+                    let time_fs = *crate::renderer::state::SIM_TIME.lock();
+                    let path = std::path::Path::new("doe_results")
+                        .join(format!("continuum_fields_{:.0}fs.csv", time_fs));
+                    match fields.write_csv(&path, time_fs) {
+                        Ok(()) => crate::sim_log!(Io, Info, "✓ Continuum fields -> {}", path.display()),
+                        Err(e) => crate::sim_log!(Io, Error, "✗ Failed to write continuum fields: {}", e),
+                    }
+                }
+            });
             ui.checkbox(
                 &mut self.sim_config.show_field_vectors,
                 "Show Field Vectors",
             );
+            if self.sim_config.show_field_vectors {
+                ui.add(
+                    egui::Slider::new(&mut self.field_vector_smoothing, 0.0..=0.95)
+                        .text("Field vector smoothing"),
+                )
+                .on_hover_text("Blend each grid cell with the previous frame to reduce flicker (0 = off)");
+            }
 
             let mut depth = SHOW_Z_VISUALIZATION.load(Ordering::Relaxed);
             if ui.checkbox(&mut depth, "Show Depth Cue").changed() {
@@ -59,6 +145,66 @@ impl super::super::Renderer {
                     let _ = sender.send(SimCommand::ToggleZVisualization { enabled: depth });
                 }
             }
+            if depth {
+                ui.indent("depth_cue", |ui| {
+                    let defaults = crate::renderer::draw::depth_cue::DepthCue::default();
+                    let cue = &mut self.depth_cue;
+                    ui.add(egui::Slider::new(&mut cue.size_gain, 0.0..=2.0).text("Size gain"))
+                        .on_hover_text("Radius change per unit of strength × normalized z");
+                    ui.add(egui::Slider::new(&mut cue.brightness_gain, 0.0..=2.0).text("Brightness gain"))
+                        .on_hover_text("Brightness change per unit of strength × normalized z");
+                    ui.add(egui::Slider::new(&mut cue.tint_threshold, 0.0..=5.0).text("Tint threshold"))
+                        .on_hover_text("Above this strength, near bodies are tinted warm and far ones cool");
+                    ui.add(egui::Slider::new(&mut cue.alpha_threshold, 0.0..=5.0).text("Alpha threshold"))
+                        .on_hover_text("Above this strength, bodies away from z = 0 fade out");
+                    if ui.button("Reset").clicked() {
+                        *cue = defaults;
+                    }
+                });
+            }
+
+            let mut slicing = self.z_slice.is_some();
+            ui.checkbox(&mut slicing, "Only Draw Z-Slice")
+                .on_hover_text("Hide bodies whose out-of-plane z lies outside the range");
+            if slicing {
+                let max_z = self.sim_config.max_z.max(1.0);
+                let (lo, hi) = &mut self.z_slice_bounds;
+                ui.horizontal(|ui| {
+                    ui.label("z from");
+                    ui.add(egui::DragValue::new(lo).speed(0.05).clamp_range(-max_z..=max_z));
+                    ui.label("to");
+                    ui.add(egui::DragValue::new(hi).speed(0.05).clamp_range(-max_z..=max_z));
+                });
+                self.z_slice = Some(self.z_slice_bounds);
+            } else {
+                self.z_slice = None;
+            }
+
+            if ui
+                .checkbox(&mut self.show_hop_pulses, "Show Electron Hop Pulses")
+                .on_hover_text("Yellow ring at the donor, cyan at the acceptor of each hop")
+                .changed()
+            {
+                HOP_PULSES_ENABLED.store(self.show_hop_pulses, Ordering::Relaxed);
+            }
+            if self.show_hop_pulses {
+                ui.add(
+                    egui::Slider::new(&mut self.hop_pulse_decay, 0.1..=3.0)
+                        .text("Pulse decay (s)"),
+                );
+            }
+
+            ui.checkbox(&mut self.show_streamlines, "Show Velocity Streamlines")
+                .on_hover_text("Streamlines of the cell-averaged velocity field, blue = slow, red = fast");
+            if self.show_streamlines {
+                ui.add(
+                    egui::Slider::new(&mut self.streamline_density, 0.25..=4.0)
+                        .text("Streamline density"),
+                );
+            }
+
+            ui.checkbox(&mut self.show_metal_clusters, "Color Metal Clusters")
+                .on_hover_text("Each connected metal cluster gets its own color; isolated metal is gray");
 
             egui::ComboBox::from_label("Isoline Field Mode")
                 .selected_text(format!("{:?}", self.sim_config.isoline_field_mode))
@@ -168,6 +314,81 @@ impl super::super::Renderer {
             if self.show_dipoles {
                 ui.add(egui::Slider::new(&mut self.dipole_scale, 0.1..=5.0).text("Dipole scale"));
             }
+            ui.checkbox(&mut self.show_domain_boundary, "Show domain boundary")
+                .on_hover_text("Outline of the simulation box; in side view, the ±max_z extent");
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.show_scale_bar, "Scale bar")
+                    .on_hover_text("Round-length bar in the lower-left corner, also written to SVG exports");
+                egui::ComboBox::from_id_source("scale_bar_unit")
+                    .selected_text(self.scale_bar_unit.symbol())
+                    .show_ui(ui, |ui| {
+                        for unit in crate::renderer::scale_bar::LengthUnit::ALL {
+                            ui.selectable_value(&mut self.scale_bar_unit, unit, unit.symbol());
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.show_time_stamp, "Time stamp");
+                egui::ComboBox::from_id_source("time_stamp_unit")
+                    .selected_text(self.time_stamp_unit.symbol())
+                    .show_ui(ui, |ui| {
+                        for unit in crate::renderer::scale_bar::TimeUnit::ALL {
+                            ui.selectable_value(&mut self.time_stamp_unit, unit, unit.symbol());
+                        }
+                    });
+            });
+
+            // Scripted camera flythrough
+            ui.collapsing("🎥 Camera Path", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("File:");
+                    ui.text_edit_singleline(&mut self.camera_path_file);
+                });
+                ui.horizontal(|ui| {
+                    let path = std::path::PathBuf::from(&self.camera_path_file);
+                    if ui.button("📂 Load").clicked() {
+                        match crate::renderer::camera_path::CameraPath::load(&path) {
+                            Ok(camera_path) => self.camera_path = camera_path,
+                            Err(e) => crate::sim_log!(Io, Error, "{}", e),
+                        }
+                    }
+                    if ui.button("💾 Save").clicked() {
+                        if let Err(e) = self.camera_path.save(&path) {
+                            crate::sim_log!(Io, Error, "{}", e);
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("➕ Keyframe at current view")
+                        .on_hover_text("Uses the current simulation time")
+                        .clicked()
+                    {
+                        let time_fs = *crate::renderer::state::SIM_TIME.lock();
+                        self.camera_path
+                            .add_keyframe(crate::renderer::camera_path::CameraKeyframe {
+                                time_fs,
+                                pos: self.pos,
+                                scale: self.scale,
+                            });
+                    }
+                    if ui.button("🗑 Clear").clicked() {
+                        self.camera_path.keyframes.clear();
+                    }
+                });
+                let label = if self.camera_path_playing { "⏹ Stop path" } else { "▶ Play path" };
+                ui.add_enabled_ui(!self.camera_path.keyframes.is_empty(), |ui| {
+                    if ui.button(label).clicked() {
+                        self.camera_path_playing = !self.camera_path_playing;
+                    }
+                });
+                ui.checkbox(&mut self.camera_path_capture, "📷 Capture every frame while playing")
+                    .on_hover_text("Saves a screenshot per frame to the capture folder (Capture tab)");
+                ui.small(format!(
+                    "{} keyframes; follows simulation time, including history playback",
+                    self.camera_path.keyframes.len()
+                ));
+            });
 
             // Velocity vector scale control
             ui.add(
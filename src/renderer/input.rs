@@ -10,7 +10,92 @@ use crate::profile_scope;
 use quarkstrom::winit_input_helper::WinitInputHelper;
 use ultraviolet::Vec2;
 
+/// View half-height used when there is nothing to frame.
+pub const DEFAULT_VIEW_SCALE: f32 = 500.0;
+/// Fractional margin added around the bounding box by zoom-to-fit.
+const FIT_MARGIN: f32 = 0.1;
+
+/// View centre and scale that frame the world-space box `min..max`.
+///
+/// `scale` is the half-height of the visible region in world units and the
+/// half-width is `scale * aspect` (see the mouse-to-world transform below),
+/// so the box is fitted on whichever axis is tighter.
+pub fn fit_view(min: Vec2, max: Vec2, aspect: f32, margin: f32) -> (Vec2, f32) {
+    let center = (min + max) * 0.5;
+    let half = (max - min) * 0.5;
+    let aspect = if aspect > 0.0 { aspect } else { 1.0 };
+    let scale = half.y.max(half.x / aspect).max(1.0) * (1.0 + margin);
+    (center, scale)
+}
+
+/// Candidate `(id, center)` nearest to `cursor` within `tolerance` world
+/// units, used to snap measurement endpoints to particle centers.
+pub fn snap_to_nearest(
+    candidates: impl IntoIterator<Item = (u64, Vec2)>,
+    cursor: Vec2,
+    tolerance: f32,
+) -> Option<(u64, Vec2)> {
+    candidates
+        .into_iter()
+        .map(|(id, pos)| (id, pos, (pos - cursor).mag_sq()))
+        .filter(|&(_, _, d2)| d2 <= tolerance * tolerance)
+        .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(id, pos, _)| (id, pos))
+}
+
+/// Body picked by a click at `cursor`: the nearest one whose display
+/// position lies within twice its radius, considering only `filter`'s
+/// species when set.
+pub fn pick_body<'a>(
+    bodies: impl IntoIterator<Item = (&'a crate::body::Body, Vec2)>,
+    cursor: Vec2,
+    filter: Option<Species>,
+) -> Option<u64> {
+    bodies
+        .into_iter()
+        .filter(|(body, _)| filter.map_or(true, |species| body.species == species))
+        .map(|(body, pos)| (body, (pos - cursor).mag()))
+        .filter(|(body, dist)| *dist < body.radius * 2.0)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(body, _)| body.id)
+}
+
 impl super::Renderer {
+    /// Screen position (y down) of a world point in a `width`×`height` view;
+    /// the inverse of the mouse-to-world transform in `handle_input`.
+    pub fn world_to_screen(&self, world: Vec2, width: f32, height: f32) -> Vec2 {
+        let ndc = (world - self.pos) / self.scale;
+        Vec2::new(
+            (ndc.x + width / height) * height * 0.5,
+            (1.0 - ndc.y) * height * 0.5,
+        )
+    }
+
+    /// Frame all bodies in the view, or reset to the default view if empty.
+    pub fn zoom_to_fit(&mut self) {
+        let mut bounds: Option<(Vec2, Vec2)> = None;
+        for body in &self.bodies {
+            let p = self.get_display_position(body);
+            let r = Vec2::broadcast(body.radius);
+            bounds = Some(match bounds {
+                Some((lo, hi)) => (lo.min_by_component(p - r), hi.max_by_component(p + r)),
+                None => (p - r, p + r),
+            });
+        }
+        match bounds {
+            Some((min, max)) => {
+                let aspect = self.window_width as f32 / self.window_height.max(1) as f32;
+                let (pos, scale) = fit_view(min, max, aspect, FIT_MARGIN);
+                self.pos = pos;
+                self.scale = scale;
+            }
+            None => {
+                self.pos = Vec2::zero();
+                self.scale = DEFAULT_VIEW_SCALE;
+            }
+        }
+    }
+
     pub fn handle_input(&mut self, input: &WinitInputHelper, width: u16, height: u16) {
         profile_scope!("input_handling");
         if width == 0 || height == 0 {
@@ -63,6 +148,11 @@ impl super::Renderer {
             }
         }
 
+        // `F` — zoom to fit all particles
+        if input.key_pressed(VirtualKeyCode::F) {
+            self.zoom_to_fit();
+        }
+
         if input.key_pressed(VirtualKeyCode::Back) {
             self.selected_particle_id = None;
             self.selected_foil_ids.clear();
@@ -115,7 +205,20 @@ impl super::Renderer {
             self.pos.y += mdy / height as f32 * self.scale * 2.0;
         }
 
-        // Screen capture removed
+        // Drag out a capture region once selection is armed in the Capture tab
+        if self.is_selecting_region {
+            if let Some((mx, my)) = input.mouse() {
+                let cursor = Vec2::new(mx, my);
+                if input.mouse_pressed(0) {
+                    self.start_region_selection(cursor);
+                } else if input.mouse_held(0) {
+                    self.update_region_selection(cursor);
+                } else if input.mouse_released(0) && self.selection_start.is_some() {
+                    self.finish_region_selection(width, height);
+                }
+            }
+            return;
+        }
 
         // Mouse to world conversion
         let world_mouse = || -> Vec2 {
@@ -153,6 +256,18 @@ impl super::Renderer {
 
         if self.current_tab == GuiTab::Measurement {
             self.measurement_cursor = input.mouse().map(|_| world_mouse());
+            self.measurement_snapped_id = None;
+            if self.measurement_snap {
+                if let Some(cursor) = self.measurement_cursor {
+                    // Pixel tolerance to world units at the current zoom
+                    let tolerance = self.measurement_snap_tolerance_px * 2.0 * self.scale / height as f32;
+                    let candidates = self.bodies.iter().map(|b| (b.id, b.pos));
+                    if let Some((id, pos)) = snap_to_nearest(candidates, cursor, tolerance) {
+                        self.measurement_cursor = Some(pos);
+                        self.measurement_snapped_id = Some(id);
+                    }
+                }
+            }
 
             if input.mouse_pressed(1) {
                 self.current_tab = self.last_non_measurement_tab;
@@ -165,6 +280,7 @@ impl super::Renderer {
                 if input.mouse_pressed(0) {
                     if let Some(pos) = self.measurement_cursor {
                         self.measurement_start = Some(pos);
+                        self.measurement_start_snapped_id = self.measurement_snapped_id;
                         self.measurement_selecting_start = false;
                         // After choosing start, allow defining direction next if requested
                     }
@@ -240,6 +356,7 @@ impl super::Renderer {
                         step: self.frame,
                         time_fs,
                         distance,
+                        view_scale: self.scale,
                         switch_step: switch_step_opt,
                         switch_mode,
                         switch_value,
@@ -262,16 +379,11 @@ impl super::Renderer {
                 if input.key_held(VirtualKeyCode::LShift) || input.key_held(VirtualKeyCode::RShift)
                 {
                     let mouse_pos = world_mouse();
-                    let mut closest = None;
-                    let mut min_dist = f32::MAX;
-                    for body in &self.bodies {
-                        let display_pos = self.get_display_position(body);
-                        let dist = (display_pos - mouse_pos).mag();
-                        if dist < min_dist && dist < body.radius * 2.0 {
-                            min_dist = dist;
-                            closest = Some(body.id);
-                        }
-                    }
+                    let closest = pick_body(
+                        self.bodies.iter().map(|b| (b, self.get_display_position(b))),
+                        mouse_pos,
+                        self.pick_species_filter,
+                    );
                     self.selected_particle_id = closest;
                     if let Some(id) = closest {
                         if let Some(body) = self.bodies.iter().find(|b| b.id == id) {
@@ -292,14 +404,16 @@ impl super::Renderer {
 
                     if let Some(id) = closest {
                         if let Some(body) = self.bodies.iter().find(|b| b.id == id) {
-                            println!(
+                            crate::sim_log!(
+                                Physics,
+                                Debug,
                                 "Selected Body: id={}, pos={:?}, vel={:?}, acc={:?}, charge={}, electrons={}, species={:?}",
                                 body.id, body.pos, body.vel, body.acc, body.charge, body.electrons.len(), body.species
                             );
                         }
                     }
 
-                    println!()
+                    crate::sim_log!(Physics, Debug, "")
 
                 // If shift is not held, spawn a new body
                 } else {
@@ -339,9 +453,14 @@ impl super::Renderer {
             }
         // If the mouse is released, confirm the body
         } else if input.mouse_released(1) {
+            self.spawn_force_preview = None;
             if let Some(body) = self.spawn_body.take() {
                 if let Some(sender) = SIM_COMMAND_SENDER.lock().as_ref() {
-                    let _ = sender.send(SimCommand::AddBody { body });
+                    let _ = sender.send(SimCommand::AddBody {
+                        body,
+                        electron_count_override: None,
+                        charge_override: None,
+                    });
                 }
             }
         }
@@ -1,7 +1,16 @@
+pub mod camera_path;
+pub mod capture_frame;
+pub mod capture_manifest;
 pub mod draw;
 pub mod gui;
 pub mod input;
+pub mod scale_bar;
+#[cfg(feature = "screen_capture")]
+mod screen_capture;
+#[cfg(not(feature = "screen_capture"))]
+mod screen_capture_stub;
 pub mod state;
+pub mod theme;
 
 use crate::body::{foil::Foil, Body, Species};
 use crate::config::SimConfig;
@@ -78,6 +87,8 @@ pub struct MeasurementRecord {
     pub step: usize,
     pub time_fs: f32,
     pub distance: f32,
+    // View half-height (world units) when the measurement was taken
+    pub view_scale: f32,
     // Optional switching metadata captured at the moment of measurement
     pub switch_step: Option<u8>,     // 0..3
     pub switch_mode: Option<String>, // "Current" | "Overpotential"
@@ -118,6 +129,7 @@ pub enum GuiTab {
     Diagnostics,
     SoftDynamics,
     Legend,
+    ScreenCapture,
 }
 
 impl Default for GuiTab {
@@ -142,14 +154,28 @@ impl Default for ChargingUiMode {
 pub struct Renderer {
     pos: Vec2,
     scale: f32,
+    /// Scripted camera keyframes for flythroughs
+    pub camera_path: camera_path::CameraPath,
+    pub camera_path_playing: bool,
+    /// Save a screen capture on every frame while the camera path plays
+    pub camera_path_capture: bool,
+    pub camera_path_file: String,
     scale_factor: f32,
     settings_window_open: bool,
     show_bodies: bool,
     show_quadtree: bool,
+    /// Background and colour adjustments for presentation
+    pub theme: theme::Theme,
     species_dark_mode_enabled: bool,
     species_dark_mode_strength: f32,
     depth_range: (usize, usize),
     spawn_body: Option<Body>,
+    /// Draw the net force on the pending spawn body before it is placed.
+    pub show_spawn_force_preview: bool,
+    /// Length of the preview arrow per unit force (Å).
+    pub spawn_force_preview_scale: f32,
+    /// Force shown for the pending spawn body; `None` when not spawning.
+    pub spawn_force_preview: Option<Vec2>,
     angle: Option<f32>,
     total: Option<f32>,
     confirmed_bodies: Option<Body>,
@@ -160,6 +186,18 @@ pub struct Renderer {
     //foils: Vec<crate::body::foil::Foil>,
     selected_foil_ids: Vec<u64>,
     selected_particle_ids: Vec<u64>,
+    /// Shift+click only picks bodies of this species (`None` = any species)
+    pick_species_filter: Option<Species>,
+    /// Species filter and last result for the Debug-tab nearest-neighbour readout
+    nn_stats_species: Option<Species>,
+    nn_stats: Option<(f32, f32, f32)>,
+    // Probe particle whose neighbourhood and local field are shown live in the Debug tab
+    pub probe_particle_id: Option<u64>,
+    pub probe_radius: f32,
+    // Sample count for the Barnes-Hut field validation export
+    pub field_validation_samples: usize,
+    /// GUI mirror of the species frozen in the simulation
+    frozen_species: std::collections::HashSet<Species>,
     selected_pid_foil_id: Option<u64>, // For PID graph foil selection
     switch_ui_state: switch_charging::SwitchUiState,
     sim_config: SimConfig,
@@ -169,6 +207,9 @@ pub struct Renderer {
     playback_cursor: usize,
     playback_speed: f32,
     playback_follow_live: bool,
+    /// Snapshot shown by the lightweight history player, and whether it advances
+    lightweight_cursor: usize,
+    lightweight_playing: bool,
     playback_auto_resume: bool,
     /// History of on/off states for selected foils
     foil_wave_history: HashMap<u64, Vec<(f32, f32)>>,
@@ -182,12 +223,26 @@ pub struct Renderer {
     scenario_random_count: usize,
     //pub scenario_charge: i32,
     pub velocity_vector_scale: f32,
+    /// Exponential smoothing weight for the field-vector overlay (0 = off)
+    pub field_vector_smoothing: f32,
+    /// Previous frame's smoothed field per world-aligned grid cell
+    field_vector_cache: HashMap<(i64, i64), Vec2>,
+    /// Exponential smoothing weight for the 2D domain density grid (0 = off)
+    pub density_smoothing_temporal: f32,
+    /// Gaussian blur of the density grid, sigma in grid cells (0 = off)
+    pub density_smoothing_spatial: f32,
+    /// Previous frame's smoothed density grid as (nx, ny, samples)
+    density_grid_cache: Option<(usize, usize, Vec<f32>)>,
     //scenario_current: f32,
     pub window_width: u16,
     pub window_height: u16,
     pub show_foil_electron_deficiency: bool,
     pub show_metal_electron_deficiency: bool,
     pub show_switching_role_halos: bool,
+    /// Draw "+A"/"-B" role labels at foil centroids outside the switch-charging mode
+    pub show_foil_role_labels: bool,
+    /// Neighbor distance used when grouping loose FoilMetal into foils
+    pub foil_detect_radius: f32,
     // State saving/loading UI
     pub save_state_name: String,
     pub load_state_selected: Option<String>,
@@ -216,6 +271,52 @@ pub struct Renderer {
     pub transference_number_diagnostic: Option<TransferenceNumberDiagnostic>,
     pub foil_electron_fraction_diagnostic: Option<FoilElectronFractionDiagnostic>,
     pub solvation_diagnostic: Option<crate::diagnostics::SolvationDiagnostic>,
+    /// CIP pair lifetimes, updated with each solvation calculation when enabled
+    pub cip_lifetime_enabled: bool,
+    pub cip_lifetimes: crate::diagnostics::PairLifetimeTracker,
+    /// Histogram bin width for the CIP lifetime export (fs)
+    pub cip_lifetime_bin_fs: f32,
+    /// Transference number split by solvation class, one sample per solvation update
+    pub solvation_transference_enabled: bool,
+    pub solvation_transference: crate::diagnostics::SolvationTransferenceLog,
+    pub surface_roughness_diagnostic: Option<crate::diagnostics::SurfaceRoughnessDiagnostic>,
+    /// One-shot cation-to-foil distance snapshot (computed on demand)
+    pub electrode_distance_diagnostic: crate::diagnostics::ElectrodeDistanceDiagnostic,
+    pub resistance_diagnostic: Option<crate::diagnostics::ResistanceDiagnostic>,
+    pub molarity_meter: crate::diagnostics::MolarityMeter,
+    /// Show the bulk vs target molarity HUD
+    pub show_molarity_meter: bool,
+    /// Frame stride for XYZ trajectory export
+    pub xyz_export_stride: usize,
+    /// Mirrors the simulation's hold-at-frame (step replay) arming
+    pub step_replay_armed: bool,
+    pub reversibility_steps: usize,
+    /// Flash rings where electron hops happen (mirrors HOP_PULSES_ENABLED)
+    pub show_hop_pulses: bool,
+    /// Size/brightness/tint/alpha mapping of the depth cue
+    pub depth_cue: draw::depth_cue::DepthCue,
+    /// Draw only bodies with `z` in this range (inclusive); `None` draws all
+    pub z_slice: Option<(f32, f32)>,
+    // Slice bounds kept while the slice is toggled off
+    pub z_slice_bounds: (f32, f32),
+    /// Ring bodies faster than this (Å/fs); `None` disables the highlight
+    pub highlight_velocity_threshold: Option<f32>,
+    // Threshold kept while the highlight is toggled off
+    pub highlight_velocity_value: f32,
+    /// Seconds a hop pulse stays visible
+    pub hop_pulse_decay: f32,
+    /// Draw streamlines of the binned mean velocity field
+    pub show_streamlines: bool,
+    /// Streamline seeds per 20 Å along each axis
+    pub streamline_density: f32,
+    /// Color each connected metal cluster distinctly
+    pub show_metal_clusters: bool,
+    /// Cached cluster label per body (see `draw::cluster`)
+    metal_clusters: Vec<Option<u32>>,
+    metal_cluster_swaps: usize,
+    /// File stem for config presets under config_presets/
+    pub config_preset_name: String,
+    hop_pulses: std::collections::VecDeque<draw::hop_pulse::HopPulse>,
     /// Per-foil EMA-smoothed overpotential (V), keyed by foil id.
     pub foil_eta_smoothed: std::collections::HashMap<u64, f32>,
     /// EMA coefficient for foil η. α = 1.0 disables smoothing.
@@ -229,6 +330,10 @@ pub struct Renderer {
     pub eta_window_fs: f32,
     /// Smoothed max_abs for charge density heatmap color normalization.
     pub charge_density_max_abs_smoothed: f32,
+    /// Held charge-density grid for before/after comparison
+    charge_snapshot: Option<draw::charge::ChargeGrid>,
+    /// Draw `live - snapshot` instead of the live charge density
+    pub show_charge_difference: bool,
     /// Toggle: highlight Li metals by their `surrounded_by_metal` flag.
     /// Blue = bulk (protected), orange = surface (eligible to oxidize).
     pub show_surround_diagnostic: bool,
@@ -239,6 +344,11 @@ pub struct Renderer {
     pub show_s2ip_ions: bool,
     pub show_fd_ions: bool,
 
+    // Diagnostic level of detail: base interval scaled up with body count
+    pub diagnostic_lod_base_frames: usize,
+    pub diagnostic_lod_base_fs: f32,
+    pub diagnostic_lod_scaling: f32,
+
     // Solvation CSV logging controls
     pub solvation_csv_enabled: bool,
     pub solvation_csv_interval_fs: f32,
@@ -260,15 +370,45 @@ pub struct Renderer {
     // Electrolyte solution controls
     pub electrolyte_molarity: f32,
     pub electrolyte_total_particles: usize,
+    pub electrolyte_ec_parts: f32,
+    pub electrolyte_dmc_parts: f32,
     pub electrolyte_components: Vec<ElectrolyteComponent>,
 
-    // (screen capture feature removed)
+    // Screen capture (the OS backend needs the `screen_capture` feature)
+    pub screen_capture_enabled: bool,
+    /// Simulated time (fs) between timed captures
+    pub capture_interval: f32,
+    pub capture_folder: String,
+    pub capture_counter: usize,
+    pub last_capture_time: f32,
+    pub should_capture_next_frame: bool,
+    /// World-space corners of the capture region; `None` captures the window
+    pub capture_region: Option<(Vec2, Vec2)>,
+    // Region corners as fractions of the window, for cropping screenshots
+    pub capture_region_ratio: Option<(Vec2, Vec2)>,
+    pub is_selecting_region: bool,
+    pub selection_start: Option<Vec2>,
+    pub selection_end: Option<Vec2>,
+    /// Scale region captures into a frame of `capture_resolution` pixels
+    pub capture_fixed_resolution: bool,
+    pub capture_resolution: (u32, u32),
+    pub capture_frame_fit: capture_frame::FrameFit,
     pub measurement_start: Option<Vec2>,
     pub measurement_selecting_start: bool,
     pub measurement_direction: Option<Vec2>,
     pub measurement_selecting_direction: bool,
     pub measurement_history: Vec<MeasurementRecord>,
+    pub measurement_export_path: String,
+    // Cell edge (Å) for the one-shot continuum field export
+    pub continuum_grid_spacing: f32,
     pub measurement_cursor: Option<Vec2>,
+    /// Snap measurement endpoints to the nearest particle center
+    pub measurement_snap: bool,
+    /// Snap search radius in screen pixels
+    pub measurement_snap_tolerance_px: f32,
+    /// Particle the cursor / start point is snapped to
+    pub measurement_snapped_id: Option<u64>,
+    pub measurement_start_snapped_id: Option<u64>,
     pub last_non_measurement_tab: GuiTab,
     // Splash screen state
     show_splash: bool,
@@ -293,7 +433,13 @@ pub struct Renderer {
     pub conv_pid_kd: f32,
     // Dipole visualization
     pub show_dipoles: bool,
+    pub show_domain_boundary: bool,
     pub dipole_scale: f32,
+    // Scale bar and time stamp drawn in the lower-left corner (and into SVG exports)
+    pub show_scale_bar: bool,
+    pub show_time_stamp: bool,
+    pub scale_bar_unit: scale_bar::LengthUnit,
+    pub time_stamp_unit: scale_bar::TimeUnit,
     // Manual measurement system
     //pub manual_measurement_recorder: Option<ManualMeasurementRecorder>,
     pub manual_measurement_last_results: Vec<MeasurementResult>,
@@ -482,15 +628,23 @@ impl quarkstrom::Renderer for Renderer {
 
         Self {
             pos: Vec2::zero(),
-            scale: 500.0,
+            scale: input::DEFAULT_VIEW_SCALE,
+            camera_path: camera_path::CameraPath::default(),
+            camera_path_playing: false,
+            camera_path_capture: false,
+            camera_path_file: "camera_path.json".to_string(),
             scale_factor: 1.0,
             settings_window_open: false,
             show_bodies: true,
             show_quadtree: false,
+            theme: theme::Theme::default(),
             species_dark_mode_enabled: false,
             species_dark_mode_strength: 0.5,
             depth_range: (0, 0),
             spawn_body: None,
+            show_spawn_force_preview: false,
+            spawn_force_preview_scale: 10.0,
+            spawn_force_preview: None,
             angle: None,
             total: None,
             confirmed_bodies: None,
@@ -500,7 +654,14 @@ impl quarkstrom::Renderer for Renderer {
             selected_particle_id: None,
             //foils: Vec::new(),
             selected_foil_ids: Vec::new(),
+            frozen_species: std::collections::HashSet::new(),
+            nn_stats_species: None,
+            nn_stats: None,
+            probe_particle_id: None,
+            probe_radius: 10.0,
+            field_validation_samples: 200,
             selected_particle_ids: Vec::new(),
+            pick_species_filter: None,
             selected_pid_foil_id: None, // Initialize PID graph foil selection to None
             switch_ui_state: switch_charging::SwitchUiState::new(),
             sim_config: crate::config::LJ_CONFIG.lock().clone(),
@@ -509,6 +670,8 @@ impl quarkstrom::Renderer for Renderer {
             playback_cursor: 0,
             playback_speed: 1.0,
             playback_follow_live: true,
+            lightweight_cursor: 0,
+            lightweight_playing: false,
             playback_auto_resume: true,
             foil_wave_history: HashMap::new(),
             scenario_radius: 1.0,
@@ -520,12 +683,19 @@ impl quarkstrom::Renderer for Renderer {
             scenario_random_count: 1,
             //scenario_charge: 0,
             velocity_vector_scale: 0.1,
+            field_vector_smoothing: 0.0,
+            field_vector_cache: HashMap::new(),
+            density_smoothing_temporal: 0.0,
+            density_smoothing_spatial: 0.0,
+            density_grid_cache: None,
             //scenario_current: 0.0,
             window_width: 800,  // default value, can be changed
             window_height: 600, // default value, can be changed
             show_foil_electron_deficiency: true,
             show_metal_electron_deficiency: false,
             show_switching_role_halos: false,
+            show_foil_role_labels: false,
+            foil_detect_radius: 2.5,
             save_state_name: String::new(),
             load_state_selected: None,
             // Initialize plotting system with simulation bounds using domain bounds
@@ -548,12 +718,43 @@ impl quarkstrom::Renderer for Renderer {
             transference_number_diagnostic: Some(TransferenceNumberDiagnostic::new()),
             foil_electron_fraction_diagnostic: Some(FoilElectronFractionDiagnostic::new()),
             solvation_diagnostic: Some(crate::diagnostics::SolvationDiagnostic::new()),
+            cip_lifetime_enabled: false,
+            cip_lifetimes: crate::diagnostics::PairLifetimeTracker::default(),
+            cip_lifetime_bin_fs: 10.0,
+            solvation_transference_enabled: false,
+            solvation_transference: crate::diagnostics::SolvationTransferenceLog::default(),
+            electrode_distance_diagnostic: Default::default(),
+            surface_roughness_diagnostic: Some(
+                crate::diagnostics::SurfaceRoughnessDiagnostic::new(),
+            ),
+            resistance_diagnostic: Some(crate::diagnostics::ResistanceDiagnostic::new()),
+            molarity_meter: crate::diagnostics::MolarityMeter::default(),
+            show_molarity_meter: false,
+            xyz_export_stride: 1,
+            step_replay_armed: false,
+            reversibility_steps: 500,
+            show_hop_pulses: false,
+            depth_cue: draw::depth_cue::DepthCue::default(),
+            z_slice: None,
+            z_slice_bounds: (-1.0, 1.0),
+            highlight_velocity_threshold: None,
+            highlight_velocity_value: 0.5,
+            hop_pulse_decay: 0.6,
+            show_streamlines: false,
+            streamline_density: 1.0,
+            show_metal_clusters: false,
+            metal_clusters: Vec::new(),
+            metal_cluster_swaps: 0,
+            config_preset_name: "default".to_string(),
+            hop_pulses: std::collections::VecDeque::new(),
             foil_eta_smoothed: std::collections::HashMap::new(),
             eta_lpf_alpha: 0.05,
             eta_lpf_last_sim_time: f32::NEG_INFINITY,
             foil_eta_ring: std::collections::HashMap::new(),
             eta_window_fs: 500.0,
             charge_density_max_abs_smoothed: 0.0,
+            charge_snapshot: None,
+            show_charge_difference: false,
             show_surround_diagnostic: false,
 
             // Solvation visualization flags - default to false
@@ -562,6 +763,11 @@ impl quarkstrom::Renderer for Renderer {
             show_s2ip_ions: false,
             show_fd_ions: false,
 
+            // Diagnostic LOD defaults (solvation every 10 frames, foil ratio every 0.25 fs)
+            diagnostic_lod_base_frames: 10,
+            diagnostic_lod_base_fs: 0.25,
+            diagnostic_lod_scaling: 0.1,
+
             // Solvation CSV logging defaults
             solvation_csv_enabled: true,
             solvation_csv_interval_fs: 1000.0,
@@ -583,6 +789,8 @@ impl quarkstrom::Renderer for Renderer {
             // Electrolyte solution controls
             electrolyte_molarity: 1.0,         // 1M default
             electrolyte_total_particles: 5471, // 5471 particles default
+            electrolyte_ec_parts: 1.0,         // EC:DMC 1:1 by volume
+            electrolyte_dmc_parts: 1.0,
             electrolyte_components: vec![
                 ElectrolyteComponent {
                     species: Species::EC,
@@ -600,12 +808,32 @@ impl quarkstrom::Renderer for Renderer {
                 },
             ],
 
+            screen_capture_enabled: false,
+            capture_interval: 1.0,
+            capture_folder: "captures".to_string(),
+            capture_counter: 0,
+            last_capture_time: 0.0,
+            should_capture_next_frame: false,
+            capture_region: None,
+            capture_region_ratio: None,
+            is_selecting_region: false,
+            selection_start: None,
+            selection_end: None,
+            capture_fixed_resolution: false,
+            capture_resolution: (1920, 1080),
+            capture_frame_fit: capture_frame::FrameFit::Letterbox,
             measurement_start: None,
             measurement_selecting_start: false,
             measurement_direction: None,
             measurement_selecting_direction: false,
             measurement_history: Vec::new(),
+            measurement_export_path: "doe_results/measurement_history.csv".to_string(),
+            continuum_grid_spacing: 10.0,
             measurement_cursor: None,
+            measurement_snap: false,
+            measurement_snap_tolerance_px: 12.0,
+            measurement_snapped_id: None,
+            measurement_start_snapped_id: None,
             last_non_measurement_tab: GuiTab::Simulation,
             show_splash: true,
             splash_chars,
@@ -625,7 +853,12 @@ impl quarkstrom::Renderer for Renderer {
             conv_pid_ki: 0.4,
             conv_pid_kd: 0.2,
             show_dipoles: false,
+            show_domain_boundary: false,
             dipole_scale: 20.0,
+            show_scale_bar: false,
+            show_time_stamp: false,
+            scale_bar_unit: scale_bar::LengthUnit::Nanometer,
+            time_stamp_unit: scale_bar::TimeUnit::Femtosecond,
             //manual_measurement_recorder: None,
             manual_measurement_last_results: Vec::new(),
             manual_measurement_ui_config: mm_cfg,
@@ -713,6 +946,7 @@ impl quarkstrom::Renderer for Renderer {
         self.sim_config.config_version += 1;
         self.cached_config_version = self.sim_config.config_version;
         *crate::config::LJ_CONFIG.lock() = self.sim_config.clone();
+        crate::logging::apply(self.sim_config.log_level, &self.sim_config.log_categories);
     }
 }
 
@@ -760,6 +994,12 @@ impl Renderer {
         if let Some(tgt) = *crate::renderer::state::PERSIST_UI_CONV_TARGET.lock() {
             self.conventional_target_ratio = tgt;
         }
+        // Config preset loaded on the simulation thread
+        if let Some(cfg) = crate::renderer::state::PERSIST_UI_SIM_CONFIG.lock().take() {
+            let version = self.sim_config.config_version;
+            self.sim_config = cfg;
+            self.sim_config.config_version = version;
+        }
     }
     fn available_scenarios() -> Vec<String> {
         let mut list = vec!["Default".to_string()];
@@ -832,6 +1072,8 @@ impl Renderer {
         self.measurement_selecting_start = false;
         self.measurement_selecting_direction = false;
         self.measurement_cursor = None;
+        self.measurement_snapped_id = None;
+        self.measurement_start_snapped_id = None;
     }
 
     fn update_splash_particles(
@@ -864,7 +1106,9 @@ impl Renderer {
                     charge: 1.0, // Positive charge
                 });
             }
-            println!(
+            crate::sim_log!(
+                Io,
+                Debug,
                 "Created {} particles distributed across window {}x{}",
                 self.splash_particles.len(),
                 width as u32,
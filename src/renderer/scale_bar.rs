@@ -0,0 +1,146 @@
+//! Scale bar and time stamp overlay for figures and captured frames.
+//!
+//! The bar length is the largest 1, 2 or 5 × 10ⁿ value of the chosen unit
+//! that fits in a fraction of the view width, so it reads as a round number
+//! at every zoom level. Conversions go through the base units in
+//! `crate::units` (Å and fs).
+
+use crate::units::{ANGSTROM, FEMTOSECOND};
+
+/// Largest fraction of the view width the bar may span.
+pub const SCALE_BAR_MAX_FRACTION: f32 = 0.25;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LengthUnit {
+    #[default]
+    Angstrom,
+    Nanometer,
+    Micrometer,
+}
+
+impl LengthUnit {
+    pub const ALL: [LengthUnit; 3] = [Self::Angstrom, Self::Nanometer, Self::Micrometer];
+
+    /// Size of one unit in meters.
+    fn meters(self) -> f64 {
+        match self {
+            Self::Angstrom => ANGSTROM,
+            Self::Nanometer => 1.0e-9,
+            Self::Micrometer => 1.0e-6,
+        }
+    }
+
+    /// Ångström per unit.
+    pub fn angstroms(self) -> f32 {
+        (self.meters() / ANGSTROM) as f32
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Angstrom => "Å",
+            Self::Nanometer => "nm",
+            Self::Micrometer => "µm",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimeUnit {
+    #[default]
+    Femtosecond,
+    Picosecond,
+    Nanosecond,
+}
+
+impl TimeUnit {
+    pub const ALL: [TimeUnit; 3] = [Self::Femtosecond, Self::Picosecond, Self::Nanosecond];
+
+    fn seconds(self) -> f64 {
+        match self {
+            Self::Femtosecond => FEMTOSECOND,
+            Self::Picosecond => 1.0e-12,
+            Self::Nanosecond => 1.0e-9,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Femtosecond => "fs",
+            Self::Picosecond => "ps",
+            Self::Nanosecond => "ns",
+        }
+    }
+
+    /// `time_fs` formatted in this unit, e.g. "t = 1.250 ps".
+    pub fn label(self, time_fs: f32) -> String {
+        let value = time_fs as f64 * FEMTOSECOND / self.seconds();
+        format!("t = {:.3} {}", value, self.symbol())
+    }
+}
+
+/// Largest 1, 2 or 5 × 10ⁿ not exceeding `max`; `None` for non-positive input.
+pub fn nice_round_length(max: f32) -> Option<f32> {
+    if !(max > 0.0 && max.is_finite()) {
+        return None;
+    }
+    let decade = 10f32.powi(max.log10().floor() as i32);
+    [5.0, 2.0, 1.0]
+        .into_iter()
+        .map(|m| m * decade)
+        .find(|&v| v <= max * (1.0 + 1e-6))
+}
+
+/// A scale bar resolved for the current view.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScaleBar {
+    /// Length in the chosen unit (a round number)
+    pub value: f32,
+    pub unit: LengthUnit,
+    /// On-screen length in pixels
+    pub length_px: f32,
+}
+
+impl ScaleBar {
+    /// Bar for a view `view_width_px` wide at `px_per_angstrom`, spanning at
+    /// most `SCALE_BAR_MAX_FRACTION` of the width.
+    pub fn for_view(px_per_angstrom: f32, view_width_px: f32, unit: LengthUnit) -> Option<Self> {
+        if !(px_per_angstrom > 0.0) {
+            return None;
+        }
+        let max_units = SCALE_BAR_MAX_FRACTION * view_width_px / px_per_angstrom / unit.angstroms();
+        let value = nice_round_length(max_units)?;
+        Some(Self {
+            value,
+            unit,
+            length_px: value * unit.angstroms() * px_per_angstrom,
+        })
+    }
+
+    pub fn label(&self) -> String {
+        format!("{} {}", self.value, self.unit.symbol())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bar_snaps_to_round_lengths_for_the_view_scale() {
+        assert_eq!(nice_round_length(7.3), Some(5.0));
+        assert_eq!(nice_round_length(3.0), Some(2.0));
+        assert_eq!(nice_round_length(0.15), Some(0.1));
+        assert_eq!(nice_round_length(20.0), Some(20.0));
+        assert_eq!(nice_round_length(0.0), None);
+
+        // 800 px wide at 4 px/Å: at most 50 Å, i.e. 5 nm
+        let bar = ScaleBar::for_view(4.0, 800.0, LengthUnit::Nanometer).unwrap();
+        assert_eq!(bar.value, 5.0);
+        assert!((bar.length_px - 200.0).abs() < 1e-3);
+        assert_eq!(bar.label(), "5 nm");
+        // Zooming out to 0.3 px/Å allows 666 Å, so 500 Å
+        let bar = ScaleBar::for_view(0.3, 800.0, LengthUnit::Angstrom).unwrap();
+        assert_eq!(bar.value, 500.0);
+        assert_eq!(TimeUnit::Picosecond.label(1250.0), "t = 1.250 ps");
+    }
+}
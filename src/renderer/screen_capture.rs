@@ -5,6 +5,8 @@ use std::fs;
 use std::path::Path;
 use chrono::Utc;
 use crate::profile_scope;
+use super::capture_frame::frame_layout;
+use super::capture_manifest::{CaptureManifestEntry, CAPTURE_MANIFEST_FILE};
 
 impl Renderer {
     pub fn handle_screen_capture(&mut self, current_time: f32, width: u16, height: u16) {
@@ -19,7 +21,7 @@ impl Renderer {
         if self.should_capture_next_frame {
             // Manual capture
             if let Err(e) = self.capture_current_frame() {
-                eprintln!("Manual screen capture failed: {}", e);
+                crate::sim_log!(Io, Error, "Manual screen capture failed: {}", e);
             }
             self.should_capture_next_frame = false;
         } else if self.screen_capture_enabled {
@@ -28,7 +30,7 @@ impl Renderer {
             if time_since_last >= self.capture_interval {
                 // Automatic timed capture
                 if let Err(e) = self.capture_current_frame() {
-                    eprintln!("Automatic screen capture failed: {}", e);
+                    crate::sim_log!(Io, Error, "Automatic screen capture failed: {}", e);
                 }
             }
         }
@@ -160,7 +162,7 @@ impl Renderer {
                 let crop_height = y2.saturating_sub(y1);
                 
                 if crop_width > 0 && crop_height > 0 {
-                    image_buffer.crop_imm(x1, y1, crop_width, crop_height)
+                    self.frame_region_image(image_buffer.crop_imm(x1, y1, crop_width, crop_height))
                 } else {
                     image_buffer
                 }
@@ -180,14 +182,13 @@ impl Renderer {
                     } else {
                         format!(" (full window)")
                     };
-                    println!("Window capture saved: {} ({}x{} pixels{})", 
+                    crate::sim_log!(Io, Info, "Window capture saved: {} ({}x{} pixels{})", 
                             filename, final_image.width(), final_image.height(), region_info);
-                    self.capture_counter += 1;
-                    self.last_capture_time = *crate::renderer::state::SIM_TIME.lock();
+                    self.record_captured_frame(&filename, final_image.width(), final_image.height(), self.capture_region.is_some());
                     Ok(())
                 }
                 Err(e) => {
-                    eprintln!("Failed to save window capture: {}", e);
+                    crate::sim_log!(Io, Error, "Failed to save window capture: {}", e);
                     Err(e.into())
                 }
             }
@@ -202,17 +203,17 @@ impl Renderer {
                     match screen.capture() {
                         Ok(capture) => capture,
                         Err(e) => {
-                            eprintln!("Failed to capture screen: {}", e);
+                            crate::sim_log!(Io, Error, "Failed to capture screen: {}", e);
                             return Err(format!("Screen capture failed: {}", e).into());
                         }
                     }
                 } else {
-                    eprintln!("No screens found for capture");
+                    crate::sim_log!(Io, Error, "No screens found for capture");
                     return Err("No screens available for capture".into());
                 }
             }
             Err(e) => {
-                eprintln!("Failed to enumerate screens: {}", e);
+                crate::sim_log!(Io, Error, "Failed to enumerate screens: {}", e);
                 return Err(format!("Failed to enumerate screens: {}", e).into());
             }
         };
@@ -228,7 +229,7 @@ impl Renderer {
             let dynamic_image = match image::ImageBuffer::from_raw(width, height, rgba_data.to_vec()) {
                 Some(img_buf) => image::DynamicImage::ImageRgba8(img_buf),
                 None => {
-                    eprintln!("Failed to convert screenshot to image buffer");
+                    crate::sim_log!(Io, Error, "Failed to convert screenshot to image buffer");
                     return Err("Image conversion failed".into());
                 }
             };
@@ -243,7 +244,9 @@ impl Renderer {
             let crop_height = y2.saturating_sub(y1);
             
             if crop_width > 0 && crop_height > 0 {
-                let cropped = dynamic_image.crop_imm(x1, y1, crop_width, crop_height);
+                let cropped =
+                    self.frame_region_image(dynamic_image.crop_imm(x1, y1, crop_width, crop_height));
+                let (crop_width, crop_height) = (cropped.width(), cropped.height());
                 
                 // Generate filename with timestamp
                 let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
@@ -253,19 +256,18 @@ impl Renderer {
                 // Save the cropped image
                 match cropped.save(&filepath) {
                     Ok(_) => {
-                        println!("Screen capture saved: {} ({}x{} pixels, world region converted to screen coords)", 
+                        crate::sim_log!(Io, Info, "Screen capture saved: {} ({}x{} pixels, world region converted to screen coords)", 
                                 filename, crop_width, crop_height);
-                        self.capture_counter += 1;
-                        self.last_capture_time = *crate::renderer::state::SIM_TIME.lock();
+                        self.record_captured_frame(&filename, crop_width, crop_height, true);
                         return Ok(());
                     }
                     Err(e) => {
-                        eprintln!("Failed to save cropped screen capture: {}", e);
+                        crate::sim_log!(Io, Error, "Failed to save cropped screen capture: {}", e);
                         return Err(e.into());
                     }
                 }
             } else {
-                eprintln!("Invalid crop region: width={}, height={}", crop_width, crop_height);
+                crate::sim_log!(Io, Error, "Invalid crop region: width={}, height={}", crop_width, crop_height);
                 return Err("Invalid crop region".into());
             }
         } else {
@@ -278,7 +280,7 @@ impl Renderer {
             let dynamic_image = match image::ImageBuffer::from_raw(width, height, rgba_data.to_vec()) {
                 Some(img_buf) => image::DynamicImage::ImageRgba8(img_buf),
                 None => {
-                    eprintln!("Failed to convert screenshot to image buffer");
+                    crate::sim_log!(Io, Error, "Failed to convert screenshot to image buffer");
                     return Err("Image conversion failed".into());
                 }
             };
@@ -291,20 +293,62 @@ impl Renderer {
             // Save the image
             match dynamic_image.save(&filepath) {
                 Ok(_) => {
-                    println!("Screen capture saved: {} ({}x{} pixels, full screen fallback)", 
+                    crate::sim_log!(Io, Info, "Screen capture saved: {} ({}x{} pixels, full screen fallback)", 
                             filename, width, height);
-                    self.capture_counter += 1;
-                    self.last_capture_time = *crate::renderer::state::SIM_TIME.lock();
+                    self.record_captured_frame(&filename, width, height, false);
                     Ok(())
                 }
                 Err(e) => {
-                    eprintln!("Failed to save screen capture: {}", e);
+                    crate::sim_log!(Io, Error, "Failed to save screen capture: {}", e);
                     Err(format!("Save failed: {}", e).into())
                 }
             }
         }
     }
 
+    /// Scale a cropped region into the configured fixed-size frame, with the
+    /// theme background filling any letterbox bars. Unchanged when no region
+    /// is set or the fixed resolution is off.
+    fn frame_region_image(&self, cropped: image::DynamicImage) -> image::DynamicImage {
+        let Some((a, b)) = self.capture_region.filter(|_| self.capture_fixed_resolution) else {
+            return cropped;
+        };
+        let (target_w, target_h) = self.capture_resolution;
+        let Some(layout) = frame_layout(a, b, target_w, target_h, self.capture_frame_fit) else {
+            return cropped;
+        };
+        let (x, y, w, h) = layout.content;
+        let content = cropped.resize_exact(w, h, image::imageops::FilterType::Triangle);
+        let background = image::Rgba(self.theme.clear_color());
+        let mut frame = image::RgbaImage::from_pixel(layout.width, layout.height, background);
+        image::imageops::overlay(&mut frame, &content, x as i64, y as i64);
+        image::DynamicImage::ImageRgba8(frame)
+    }
+
+    /// Advance the capture counters after a saved frame and add it to the
+    /// folder's manifest.
+    fn record_captured_frame(&mut self, filename: &str, pixel_width: u32, pixel_height: u32, cropped: bool) {
+        self.capture_counter += 1;
+        let sim_time_fs = *crate::renderer::state::SIM_TIME.lock();
+        self.last_capture_time = sim_time_fs;
+        let entry = CaptureManifestEntry {
+            file: filename.to_string(),
+            sim_time_fs,
+            capture_interval: self.capture_interval,
+            pixel_width,
+            pixel_height,
+            domain_width: self.domain_width,
+            domain_height: self.domain_height,
+            view_center: self.pos,
+            view_scale: self.scale,
+            cropped,
+        };
+        let path = Path::new(&self.capture_folder).join(CAPTURE_MANIFEST_FILE);
+        if let Err(e) = entry.append_to(&path) {
+            crate::sim_log!(Io, Error, "Failed to update capture manifest: {}", e);
+        }
+    }
+
     pub fn finish_region_selection(&mut self, width: u16, height: u16) {
         if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
             // Ensure we're using the absolute current window dimensions
@@ -319,7 +363,7 @@ impl Renderer {
             let start_ratio = Vec2::new(start.x / width as f32, start.y / height as f32);
             let end_ratio = Vec2::new(end.x / width as f32, end.y / height as f32);
             self.capture_region_ratio = Some((start_ratio, end_ratio));
-            println!(
+            crate::sim_log!(Io, Info,
                 "Capture region set: world ({:.2}, {:.2}) -> ({:.2}, {:.2}), ratios ({:.3}, {:.3}) -> ({:.3}, {:.3}) at {}x{}",
                 world_start.x,
                 world_start.y,
@@ -347,7 +391,7 @@ impl Renderer {
     pub fn clear_capture_region(&mut self) {
         self.capture_region = None;
         self.capture_region_ratio = None;
-        println!("Capture region cleared - now capturing full screen");
+        crate::sim_log!(Io, Info, "Capture region cleared - now capturing full screen");
     }
 
     pub fn screen_to_world(&self, screen_pos: Vec2, width: u16, height: u16) -> Vec2 {
@@ -436,7 +480,7 @@ impl Renderer {
                 unsafe {
                     let mut rect: winapi::shared::windef::RECT = std::mem::zeroed();
                     if winapi::um::winuser::GetWindowRect(hwnd as winapi::shared::windef::HWND, &mut rect) != 0 {
-                        println!("Simulation window position detected: ({}, {})", rect.left, rect.top);
+                        crate::sim_log!(Io, Debug, "Simulation window position detected: ({}, {})", rect.left, rect.top);
                         return (rect.left, rect.top);
                     }
                 }
@@ -444,7 +488,7 @@ impl Renderer {
         }
         
         // Fallback for non-Windows or if detection fails
-        println!("Could not detect simulation window position, using (0, 0)");
+        crate::sim_log!(Io, Warn, "Could not detect simulation window position, using (0, 0)");
         (0, 0)
     }
 
@@ -464,13 +508,13 @@ impl Renderer {
             let aspect_change = (new_aspect - old_aspect).abs() / old_aspect;
 
             if aspect_change > 0.05 {
-                println!("Window aspect ratio changed significantly ({:.3} -> {:.3}, change: {:.1}%), capture region may need adjustment",
+                crate::sim_log!(Io, Info, "Window aspect ratio changed significantly ({:.3} -> {:.3}, change: {:.1}%), capture region may need adjustment",
                         old_aspect, new_aspect, aspect_change * 100.0);
 
                 let screen_start = Vec2::new(ratio_start.x * new_width as f32, ratio_start.y * new_height as f32);
                 let screen_end = Vec2::new(ratio_end.x * new_width as f32, ratio_end.y * new_height as f32);
 
-                println!("Capture region screen coordinates updated: ({:.1}, {:.1}) to ({:.1}, {:.1}) for new window size {}x{}",
+                crate::sim_log!(Io, Info, "Capture region screen coordinates updated: ({:.1}, {:.1}) to ({:.1}, {:.1}) for new window size {}x{}",
                         screen_start.x, screen_start.y, screen_end.x, screen_end.y, new_width, new_height);
             }
             let start_world = self.screen_to_world(
@@ -490,34 +534,18 @@ impl Renderer {
         self.window_height = new_height;
     }
 
-    #[cfg(test)]
     pub fn start_region_selection(&mut self, start: Vec2) {
         self.is_selecting_region = true;
         self.selection_start = Some(start);
         self.selection_end = Some(start);
-        println!("Started region selection at ({:.1}, {:.1})", start.x, start.y);
+        crate::sim_log!(Io, Debug, "Started region selection at ({:.1}, {:.1})", start.x, start.y);
     }
 
-    #[cfg(test)]
     pub fn update_region_selection(&mut self, end: Vec2) {
         if self.is_selecting_region {
             self.selection_end = Some(end);
         }
     }
-
-    #[allow(dead_code)]
-    pub fn world_to_screen(&self, world_pos: Vec2, width: u16, height: u16) -> Vec2 {
-        // Apply inverse camera transformation
-        let aspect_ratio = width as f32 / height as f32;
-        let x = (world_pos.x - self.pos.x) / (self.scale * aspect_ratio);
-        let y = (world_pos.y - self.pos.y) / self.scale;
-        
-        // Convert from normalized device coordinates to screen coordinates
-        let screen_x = (x + 1.0) * width as f32 / 2.0;
-        let screen_y = (1.0 - y) * height as f32 / 2.0;
-        
-        Vec2::new(screen_x, screen_y)
-    }
 }
 
 #[cfg(test)]
@@ -13,6 +13,9 @@ pub static TIMESTEP: Lazy<Mutex<f32>> = Lazy::new(|| Mutex::new(config::DEFAULT_
 pub static FIELD_MAGNITUDE: Lazy<Mutex<f32>> = Lazy::new(|| Mutex::new(0.0));
 pub static FIELD_DIRECTION: Lazy<Mutex<f32>> = Lazy::new(|| Mutex::new(180.0));
 pub static PAUSED: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+/// Serializes tests that set or assert on `PAUSED`.
+#[cfg(test)]
+pub static PAUSED_TEST_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 /// One-shot single-step request. When PAUSED is true, setting this to true
 /// runs exactly one simulation step on the next loop iteration, then resets
 /// itself. Triggered by the `.` hotkey in the renderer input handler.
@@ -51,10 +54,18 @@ pub static SAVE_COMPRESS: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(true));
 pub static SAVE_INCLUDE_HISTORY: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(true));
 // Last applied thermostat scale factor for diagnostics
 pub static LAST_THERMOSTAT_SCALE: Lazy<Mutex<f32>> = Lazy::new(|| Mutex::new(1.0));
+// Periodic quadtree traversal cost, for tuning leaf/thread capacity
+pub static QUADTREE_VISIT_STATS: Lazy<
+    Mutex<Option<crate::simulation::quadtree_tuning::QuadtreeVisitStats>>,
+> = Lazy::new(|| Mutex::new(None));
 // Phase 4.2: latest morphology metrics snapshot. Sim thread writes after
 // each periodic compute; GUI thread reads to display live values.
 pub static MORPHOLOGY_LATEST: Lazy<Mutex<Option<crate::simulation::morphology::MorphologyMetrics>>> =
     Lazy::new(|| Mutex::new(None));
+/// Reduced history handed to the GUI for lightweight playback
+pub static LIGHTWEIGHT_PLAYBACK: Lazy<
+    Mutex<Vec<crate::simulation::lightweight_history::LightweightSnapshot>>,
+> = Lazy::new(|| Mutex::new(Vec::new()));
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SaveFormat {
@@ -93,6 +104,9 @@ pub struct PlaybackStatus {
     pub sim_time: f32,
     pub frame: usize,
     pub dt: f32,
+    /// Reduced history mode: only foil/scalar snapshots are being recorded
+    pub lightweight: bool,
+    pub lightweight_len: usize,
 }
 
 impl Default for PlaybackStatus {
@@ -107,6 +121,8 @@ impl Default for PlaybackStatus {
             sim_time: 0.0,
             frame: 0,
             dt: crate::config::DEFAULT_DT_FS,
+            lightweight: false,
+            lightweight_len: 0,
         }
     }
 }
@@ -131,9 +147,18 @@ pub static PERSIST_UI_CONV_IS_OVER: Lazy<Mutex<Option<bool>>> = Lazy::new(|| Mut
 pub static PERSIST_UI_CONV_CURRENT: Lazy<Mutex<Option<f32>>> = Lazy::new(|| Mutex::new(None));
 pub static PERSIST_UI_CONV_TARGET: Lazy<Mutex<Option<f32>>> = Lazy::new(|| Mutex::new(None));
 // When true, Renderer should sync persisted UI values once (typically after load)
+/// Config loaded from a preset on the simulation thread, adopted by the GUI
+pub static PERSIST_UI_SIM_CONFIG: Lazy<Mutex<Option<crate::config::SimConfig>>> =
+    Lazy::new(|| Mutex::new(None));
 pub static PERSIST_UI_DIRTY: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(false));
 
 // PID Graph state
+/// Whether the hopping routine publishes hop events for the renderer's pulses
+pub static HOP_PULSES_ENABLED: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+/// Hop events since the last rendered frame (bounded by `HOP_EVENT_CAPACITY`)
+pub static HOP_EVENTS: Lazy<Mutex<Vec<crate::simulation::electron_hopping::HopEvent>>> =
+    Lazy::new(|| Mutex::new(Vec::new()));
+pub const HOP_EVENT_CAPACITY: usize = 4096;
 pub static SHOW_PID_GRAPH: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
 //pub static PID_GRAPH_HISTORY_SIZE: Lazy<Mutex<usize>> = Lazy::new(|| Mutex::new(1000));
 
@@ -147,17 +172,31 @@ pub enum SimCommand {
     },
     AddBody {
         body: Body,
+        /// Initial electron count; `None` uses the species default.
+        electron_count_override: Option<usize>,
+        /// Initial net charge, converted to whole electrons; ignored when
+        /// `electron_count_override` is set.
+        charge_override: Option<f32>,
     },
     DeleteAll,
     ResetFoilIds,
     DeleteSpecies {
         species: crate::body::Species,
     },
+    /// Hold every body of `species` fixed (skip integration and thermostat)
+    FreezeSpecies {
+        species: crate::body::Species,
+        frozen: bool,
+    },
     AddCircle {
         body: crate::body::Body,
         x: f32,
         y: f32,
         radius: f32,
+        /// Initial electron count per body; `None` uses the species default.
+        electron_count_override: Option<usize>,
+        /// Initial net charge per body; ignored when `electron_count_override` is set.
+        charge_override: Option<f32>,
     },
     AddRing {
         body: crate::body::Body,
@@ -178,6 +217,16 @@ pub enum SimCommand {
         domain_width: f32,
         domain_height: f32,
     },
+    /// Spawn a LiPF6 in EC:DMC electrolyte with independent salt molarity
+    /// and solvent volume ratio; Li+ and anion counts always match.
+    AddElectrolyte {
+        total: usize,
+        molarity: f32,
+        ec_parts: f32,
+        dmc_parts: f32,
+        domain_width: f32,
+        domain_height: f32,
+    },
     AddFoil {
         width: f32,
         height: f32,
@@ -195,6 +244,14 @@ pub enum SimCommand {
         a: u64,
         b: u64,
     },
+    /// Merge several foils into the lowest-id one
+    MergeFoils {
+        ids: Vec<u64>,
+    },
+    /// Group unassigned FoilMetal bodies into foils by proximity
+    DetectFoils {
+        connection_radius: f32,
+    },
     SetFoilCurrent {
         foil_id: u64,
         current: f32,
@@ -211,6 +268,24 @@ pub enum SimCommand {
         foil_id: u64,
         switch_hz: f32,
     },
+    SetFoilACWaveform {
+        foil_id: u64,
+        waveform: crate::body::foil::AcWaveform,
+    },
+    /// Per-body electron capacity for one foil (`None` = global default)
+    SetFoilMaxElectrons {
+        foil_id: u64,
+        max_electrons: Option<usize>,
+    },
+    /// Replace one foil's activation schedule (empty disables it)
+    SetFoilSchedule {
+        foil_id: u64,
+        schedule: Vec<crate::body::foil::ScheduleSegment>,
+    },
+    SetFoilHandoff {
+        foil_id: u64,
+        rule: Option<crate::body::foil::HandoffRule>,
+    },
     SetFoilChargingMode {
         foil_id: u64,
         mode: crate::body::foil::ChargingMode,
@@ -256,6 +331,18 @@ pub enum SimCommand {
         path: String,
     },
     StepOnce,
+    /// Give bodies with repeated IDs fresh ones, keeping foil membership consistent
+    DedupeIds,
+    /// Snapshot the state at the top of every step so it can be replayed
+    SetStepReplay {
+        armed: bool,
+    },
+    /// Restore the last pre-step snapshot and run that step again
+    ReplayStep,
+    /// Run N steps forward and N back with reversed velocities, then restore
+    RunReversibilityTest {
+        steps: usize,
+    },
     SetDomainSize {
         width: f32,
         height: f32,
@@ -329,6 +416,62 @@ pub enum SimCommand {
     LoadInitConfigToml {
         path: std::path::PathBuf,
     },
+    /// Compare the Barnes-Hut field against the exact pairwise field at
+    /// `samples` bodies and write a CSV to `doe_results/`.
+    ValidateField {
+        samples: usize,
+    },
+    /// Write the species count matrix over the playback history as a wide CSV.
+    ExportSpeciesCounts {
+        path: std::path::PathBuf,
+    },
+    /// Write the buffered foil electron add/remove events as CSV
+    ExportElectronEvents {
+        path: std::path::PathBuf,
+    },
+    /// Write the reduced (lightweight) history as JSON
+    ExportLightweightHistory {
+        path: std::path::PathBuf,
+    },
+    /// Hand the reduced history to the GUI for playback, first replacing it
+    /// with a file written by `ExportLightweightHistory` when `path` is set
+    LoadLightweightPlayback {
+        path: Option<std::path::PathBuf>,
+    },
+    /// Write the playback history as delta-encoded keyframes
+    SaveCompressedHistory {
+        path: std::path::PathBuf,
+    },
+    /// Replace the playback history with one saved by `SaveCompressedHistory`
+    LoadCompressedHistory {
+        path: std::path::PathBuf,
+    },
+    /// Write each foil's charging mode, currents and controller settings as CSV
+    ExportFoilConfig {
+        path: std::path::PathBuf,
+    },
+    /// Write the per-foil commanded vs. measured current residual series as CSV
+    ExportCurrentResidual {
+        path: std::path::PathBuf,
+    },
+    ClearCurrentResidual,
+    /// Reapply a foil charging CSV, matching rows by foil id
+    ImportFoilConfig {
+        path: std::path::PathBuf,
+    },
+    /// Save the current config and species parameters as a JSON preset
+    SaveConfigPreset {
+        path: std::path::PathBuf,
+    },
+    /// Load a config preset saved by `SaveConfigPreset`
+    LoadConfigPreset {
+        path: std::path::PathBuf,
+    },
+    /// Write the playback history as an XYZ trajectory, every `stride`-th frame
+    ExportXyz {
+        path: std::path::PathBuf,
+        stride: usize,
+    },
 }
 
 pub static SIM_COMMAND_SENDER: Lazy<Mutex<Option<Sender<SimCommand>>>> =
@@ -338,6 +481,26 @@ pub static SIM_COMMAND_SENDER: Lazy<Mutex<Option<Sender<SimCommand>>>> =
 pub static MANUAL_MEASUREMENT_RESULTS: Lazy<Mutex<Vec<MeasurementResult>>> =
     Lazy::new(|| Mutex::new(Vec::new()));
 
+// Latest foil charge-conservation audit (sim thread publishes when the debug flag is on)
+pub static FOIL_CHARGE_AUDIT: Lazy<Mutex<Vec<crate::simulation::charge_audit::FoilChargeAudit>>> =
+    Lazy::new(|| Mutex::new(Vec::new()));
+
+// Latest per-foil commanded vs. measured current residual summary (sim thread publishes)
+pub static CURRENT_RESIDUAL_SUMMARY: Lazy<
+    Mutex<Vec<crate::simulation::current_residual::CurrentResidualSummary>>,
+> = Lazy::new(|| Mutex::new(Vec::new()));
+
+// Value and frame at which the convergence monitor paused the run (sim thread publishes)
+pub static CONVERGENCE_RESULT: Lazy<Mutex<Option<crate::simulation::convergence::ConvergenceResult>>> =
+    Lazy::new(|| Mutex::new(None));
+
+// Steps and RMS return error (Å) of the last reversibility test (sim thread publishes)
+pub static REVERSIBILITY_RESULT: Lazy<Mutex<Option<(usize, f32)>>> = Lazy::new(|| Mutex::new(None));
+
+// Switch-charging timeline segments for the timeline plot (sim thread appends)
+pub static SWITCH_TIMELINE: Lazy<Mutex<Vec<crate::switch_charging::TimelineSegment>>> =
+    Lazy::new(|| Mutex::new(Vec::new()));
+
 // Foil metrics logging global controls (GUI -> Simulation bridge)
 pub static FOIL_METRICS_ENABLED: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(true));
 pub static FOIL_METRICS_FILENAME_OVERRIDE: Lazy<Mutex<Option<String>>> =
@@ -346,6 +509,13 @@ pub static FOIL_METRICS_USE_SEPARATE_INTERVAL: Lazy<AtomicBool> =
     Lazy::new(|| AtomicBool::new(false));
 pub static FOIL_METRICS_INTERVAL_FS: Lazy<Mutex<f32>> = Lazy::new(|| Mutex::new(1000.0));
 
+// Narrow per-foil commanded-current CSV (independent of the foil metrics flags)
+pub static FOIL_CURRENT_CSV_ENABLED: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+// Per-foil overpotential PID internals CSV (same cadence as the foil current CSV)
+pub static PID_STATE_CSV_ENABLED: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+// Manual/induced/total external field CSV (same cadence as the foil current CSV)
+pub static FIELD_CSV_ENABLED: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+
 // Foil metrics field selection
 pub static FOIL_METRICS_INCLUDE_SETPOINT: Lazy<std::sync::atomic::AtomicBool> =
     Lazy::new(|| std::sync::atomic::AtomicBool::new(true));
@@ -16,11 +16,15 @@ mod tests {
             ac_current: 0.0,
             accum: 0.0,
             switch_hz: 0.0,
+            ac_waveform: crate::body::foil::AcWaveform::Square,
             link_id: None,
             mode: crate::body::foil::LinkMode::Parallel,
             charging_mode: crate::body::foil::ChargingMode::Current,
             overpotential_controller: None,
             slave_overpotential_current: 0.0,
+            max_electrons_override: None,
+            schedule: Vec::new(),
+            handoff: None,
             electron_delta_since_measure: 0,
         });
         r.selected_foil_ids.push(1);
@@ -51,4 +55,296 @@ mod tests {
             "Current values should be consistent for constant current"
         );
     }
+
+    #[test]
+    fn fit_view_respects_aspect_ratio() {
+        use crate::renderer::input::fit_view;
+        use ultraviolet::Vec2;
+
+        // Wide box in a square window: width limits the scale
+        let (pos, scale) = fit_view(Vec2::new(-100.0, -10.0), Vec2::new(100.0, 10.0), 1.0, 0.0);
+        assert_eq!(pos, Vec2::zero());
+        assert!((scale - 100.0).abs() < 1e-4);
+
+        // Same box in a 2:1 window: half-width is scale * aspect
+        let (pos, scale) = fit_view(Vec2::new(0.0, -10.0), Vec2::new(200.0, 10.0), 2.0, 0.1);
+        assert_eq!(pos, Vec2::new(100.0, 0.0));
+        assert!((scale - 55.0).abs() < 1e-3);
+        assert!(scale * 2.0 >= 100.0);
+    }
+
+    #[test]
+    fn world_to_screen_maps_view_corners() {
+        use ultraviolet::Vec2;
+
+        // 800x400 window, view centred at (10, 20) with half-height 100
+        let mut r = Renderer::new();
+        r.pos = Vec2::new(10.0, 20.0);
+        r.scale = 100.0;
+        let center = r.world_to_screen(r.pos, 800.0, 400.0);
+        assert!((center - Vec2::new(400.0, 200.0)).mag() < 1e-3);
+
+        // Top-left of the view: half-width is scale * aspect, y flips
+        let top_left = r.world_to_screen(r.pos + Vec2::new(-200.0, 100.0), 800.0, 400.0);
+        assert!(top_left.mag() < 1e-3);
+    }
+
+    #[test]
+    fn zoom_to_fit_empty_resets_view() {
+        use crate::renderer::input::DEFAULT_VIEW_SCALE;
+        let mut r = Renderer::new();
+        r.pos = ultraviolet::Vec2::new(5.0, 5.0);
+        r.scale = 3.0;
+        r.zoom_to_fit();
+        assert_eq!(r.pos, ultraviolet::Vec2::zero());
+        assert_eq!(r.scale, DEFAULT_VIEW_SCALE);
+    }
+
+    #[test]
+    fn hop_pulses_are_bounded_and_expire() {
+        use crate::renderer::draw::hop_pulse::{expire_hop_pulses, push_hop_pulses};
+        use crate::simulation::electron_hopping::HopEvent;
+        use std::time::{Duration, Instant};
+        use ultraviolet::Vec2;
+
+        let start = Instant::now();
+        let mut pulses = std::collections::VecDeque::new();
+        let events = (0..10).map(|i| HopEvent {
+            donor: Vec2::new(i as f32, 0.0),
+            acceptor: Vec2::new(i as f32, 1.0),
+        });
+        push_hop_pulses(&mut pulses, events, start, 8);
+        assert_eq!(pulses.len(), 8);
+        // Oldest dropped first: the last hop's acceptor is kept
+        assert_eq!(pulses.back().unwrap().pos, Vec2::new(9.0, 1.0));
+        assert!(pulses.front().unwrap().donor);
+
+        expire_hop_pulses(&mut pulses, start + Duration::from_millis(100), 0.5);
+        assert_eq!(pulses.len(), 8);
+        expire_hop_pulses(&mut pulses, start + Duration::from_secs(1), 0.5);
+        assert!(pulses.is_empty());
+    }
+
+    #[test]
+    fn density_blur_spreads_spike_and_keeps_uniform_grid() {
+        use crate::renderer::draw::density::gaussian_blur_grid;
+
+        let (nx, ny) = (9, 9);
+        let mut spike = vec![0.0f32; nx * ny];
+        spike[4 * nx + 4] = 1.0;
+        assert_eq!(gaussian_blur_grid(&spike, nx, ny, 0.0), spike);
+
+        let blurred = gaussian_blur_grid(&spike, nx, ny, 1.0);
+        let total: f32 = blurred.iter().sum();
+        assert!((total - 1.0).abs() < 1e-4, "interior kernel conserves mass");
+        assert!(blurred[4 * nx + 4] < 1.0 && blurred[4 * nx + 4] > blurred[4 * nx + 5]);
+        assert!((blurred[4 * nx + 3] - blurred[4 * nx + 5]).abs() < 1e-6);
+        assert!((blurred[3 * nx + 4] - blurred[4 * nx + 3]).abs() < 1e-6);
+
+        let uniform = vec![2.0f32; nx * ny];
+        for v in gaussian_blur_grid(&uniform, nx, ny, 1.5) {
+            assert!((v - 2.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn measurement_snaps_to_nearest_center_within_tolerance() {
+        use crate::renderer::input::snap_to_nearest;
+        use ultraviolet::Vec2;
+
+        let bodies = [
+            (1, Vec2::new(0.0, 0.0)),
+            (2, Vec2::new(3.0, 0.0)),
+            (3, Vec2::new(10.0, 10.0)),
+        ];
+        assert_eq!(
+            snap_to_nearest(bodies, Vec2::new(1.9, 0.2), 2.0),
+            Some((2, Vec2::new(3.0, 0.0)))
+        );
+        assert_eq!(
+            snap_to_nearest(bodies, Vec2::new(0.5, -0.5), 2.0),
+            Some((1, Vec2::zero()))
+        );
+        // Nothing within tolerance leaves the cursor unsnapped
+        assert_eq!(snap_to_nearest(bodies, Vec2::new(6.0, 5.0), 2.0), None);
+    }
+
+    #[test]
+    fn pick_honors_species_filter_over_overlapping_bodies() {
+        use crate::body::{Body, Species};
+        use crate::renderer::input::pick_body;
+        use ultraviolet::Vec2;
+
+        let foil = Body::new(Vec2::new(0.0, 0.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::FoilMetal);
+        let solvent = Body::new(Vec2::new(0.6, 0.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::EC);
+        let bodies = [&foil, &solvent];
+        let cursor = Vec2::new(0.5, 0.0);
+        let candidates = || bodies.iter().map(|b| (*b, b.pos));
+
+        // Unfiltered, the nearer solvent molecule wins
+        assert_eq!(pick_body(candidates(), cursor, None), Some(solvent.id));
+        assert_eq!(pick_body(candidates(), cursor, Some(Species::FoilMetal)), Some(foil.id));
+        assert_eq!(pick_body(candidates(), cursor, Some(Species::LithiumIon)), None);
+    }
+
+    #[test]
+    fn streamline_follows_uniform_field_and_stops_in_still_regions() {
+        use crate::body::{Body, Species};
+        use crate::renderer::draw::streamline::VelocityGrid;
+        use ultraviolet::Vec2;
+
+        // One body per 5 Å cell, all moving +x at 2 Å/fs
+        let mut bodies = Vec::new();
+        for iy in 0..5 {
+            for ix in 0..9 {
+                let pos = Vec2::new(-20.0 + ix as f32 * 5.0 + 2.5, -10.0 + iy as f32 * 5.0 + 2.5);
+                bodies.push(Body::new(pos, Vec2::new(2.0, 0.0), 1.0, 1.0, 0.0, Species::EC));
+            }
+        }
+        let grid = VelocityGrid::from_bodies(&bodies, 20.0, 10.0, 5.0);
+        let seed = Vec2::new(-15.0, 1.0);
+        let points = grid.trace(seed, 2.5, 4, 0.1);
+        assert_eq!(points.len(), 5);
+        for (i, (pos, speed)) in points.iter().enumerate() {
+            assert!((pos.x - (seed.x + 2.5 * i as f32)).abs() < 1e-4);
+            assert!((pos.y - seed.y).abs() < 1e-4);
+            assert!((speed - 2.0).abs() < 1e-4);
+        }
+
+        // A still field yields no streamline at all
+        for body in &mut bodies {
+            body.vel = Vec2::zero();
+        }
+        let still = VelocityGrid::from_bodies(&bodies, 20.0, 10.0, 5.0);
+        assert!(still.trace(seed, 2.5, 4, 0.1).is_empty());
+    }
+
+    #[test]
+    fn metal_clusters_are_labelled_and_singletons_left_neutral() {
+        use crate::body::{Body, Species};
+        use crate::renderer::draw::cluster::metal_cluster_ids;
+        use ultraviolet::Vec2;
+
+        let metal = |x: f32, y: f32, species| Body::new(Vec2::new(x, y), Vec2::zero(), 1.0, 1.0, 0.0, species);
+        let bodies = vec![
+            // Foil backbone plus touching Li
+            metal(-20.0, 0.0, Species::FoilMetal),
+            metal(-18.0, 0.0, Species::FoilMetal),
+            metal(-16.0, 0.0, Species::LithiumMetal),
+            // Separate two-body dendrite
+            metal(10.0, 5.0, Species::LithiumMetal),
+            metal(10.0, 7.0, Species::LithiumMetal),
+            // Isolated metal and a non-metal bridging nothing
+            metal(30.0, -10.0, Species::LithiumMetal),
+            metal(-14.0, 0.0, Species::EC),
+        ];
+        let ids = metal_cluster_ids(&bodies);
+        assert_eq!(ids[0], Some(0));
+        assert_eq!(ids[1], Some(0));
+        assert_eq!(ids[2], Some(0));
+        assert_eq!(ids[3], Some(1));
+        assert_eq!(ids[4], Some(1));
+        assert_eq!(ids[5], None);
+        assert_eq!(ids[6], None);
+    }
+
+    #[test]
+    fn svg_export_has_one_circle_per_visible_body() {
+        use crate::body::{Body, Species};
+        use ultraviolet::Vec2;
+
+        let mut r = Renderer::new();
+        r.bodies = (0..5)
+            .map(|i| {
+                Body::new(Vec2::new(3.0 * i as f32, 0.0), Vec2::zero(), 1.0, 1.0, 1.0, Species::LithiumIon)
+            })
+            .collect();
+        let (svg, circles) = r.svg_document();
+        assert_eq!(circles, 5);
+        assert_eq!(svg.matches("<circle").count(), 5);
+        assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+
+        // A selected particle adds its highlight ring
+        r.selected_particle_ids.push(r.bodies[0].id);
+        assert_eq!(r.svg_document().0.matches("<circle").count(), 6);
+    }
+
+    #[test]
+    fn depth_cue_size_gain_scales_radius() {
+        use crate::renderer::draw::depth_cue::DepthCue;
+
+        let color = [100, 100, 100, 255];
+        let mut cue = DepthCue::default();
+        let (base, _) = cue.apply(0.5, 1.0, 2.0, color);
+        assert!((base - 2.0 * 1.15).abs() < 1e-5);
+
+        cue.size_gain = 0.6;
+        let (doubled, _) = cue.apply(0.5, 1.0, 2.0, color);
+        assert!((doubled - 2.0 * 1.3).abs() < 1e-5);
+        // Far side shrinks by the same amount, floored at 30%
+        assert!((cue.apply(-0.5, 1.0, 2.0, color).0 - 2.0 * 0.7).abs() < 1e-5);
+        cue.size_gain = 10.0;
+        assert!((cue.apply(-1.0, 1.0, 2.0, color).0 - 0.6).abs() < 1e-5);
+    }
+
+    #[test]
+    fn z_slice_selects_bodies_in_range() {
+        use crate::renderer::draw::depth_cue::in_z_slice;
+
+        let zs = [-2.0, -0.5, 0.0, 0.25, 0.5, 1.5];
+        let kept = |slice| zs.iter().filter(|&&z| in_z_slice(z, slice)).count();
+        assert_eq!(kept(None), zs.len());
+        assert_eq!(kept(Some((-0.5, 0.5))), 4, "bounds are inclusive");
+        assert_eq!(kept(Some((0.5, -0.5))), 4, "bounds may be given in either order");
+        assert_eq!(kept(Some((1.0, 2.0))), 1);
+        assert_eq!(kept(Some((3.0, 4.0))), 0);
+    }
+
+    #[test]
+    fn speed_highlight_selects_fast_movers() {
+        use crate::renderer::draw::speed_highlight::exceeds_speed;
+        use ultraviolet::Vec2;
+
+        let bodies = [
+            (Vec2::new(0.1, 0.0), 0.0),
+            (Vec2::new(0.5, 0.0), 0.0),
+            (Vec2::new(0.0, -0.6), 0.0),
+            (Vec2::new(0.3, 0.0), 0.45), // fast only out of plane
+            (Vec2::new(f32::NAN, 0.0), 0.0),
+            (Vec2::zero(), f32::INFINITY),
+        ];
+        let fast = |threshold| -> Vec<usize> {
+            (0..bodies.len())
+                .filter(|&i| exceeds_speed(bodies[i].0, bodies[i].1, threshold))
+                .collect()
+        };
+        assert!(fast(None).is_empty());
+        assert_eq!(fast(Some(0.5)), vec![2, 3, 4, 5], "the threshold itself is not highlighted");
+        assert_eq!(fast(Some(0.05)), vec![0, 1, 2, 3, 4, 5], "non-finite speeds count as fast");
+        assert_eq!(fast(Some(f32::MAX)), vec![4, 5]);
+    }
+
+    #[test]
+    fn charge_grid_difference_is_cellwise() {
+        use crate::renderer::draw::charge::ChargeGrid;
+        use ultraviolet::Vec2;
+
+        let grid = |values: Vec<f32>| ChargeGrid {
+            min: Vec2::new(-5.0, -5.0),
+            spacing: 5.0,
+            nx: 2,
+            ny: 2,
+            values,
+        };
+        let held = grid(vec![1.0, -2.0, 0.5, 0.0]);
+        let live = grid(vec![1.5, -2.0, -0.5, 3.0]);
+        let diff = live.difference(&held).unwrap();
+        assert_eq!(diff.values, vec![0.5, 0.0, -1.0, 3.0]);
+        assert_eq!((diff.nx, diff.ny, diff.min), (2, 2, held.min));
+        assert_eq!(diff.max_abs(), 3.0);
+
+        // Grids on different layouts are not comparable
+        let shifted = ChargeGrid { min: Vec2::zero(), ..held.clone() };
+        assert!(live.difference(&shifted).is_none());
+    }
 }
@@ -0,0 +1,96 @@
+//! Presentation themes: background colour plus how species and overlay
+//! colours are adjusted to stay legible on it.
+//!
+//! `Dark` is the original look and leaves every colour untouched. `Light`
+//! darkens particles and bright overlays (white lines, yellow halos) that
+//! would vanish on a pale background. `HighContrast` keeps the black
+//! background but pushes species to full brightness and doubles overlay
+//! opacity, for projectors.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+/// Perceived brightness of an RGB colour in [0, 1].
+fn luminance(color: [u8; 4]) -> f32 {
+    (0.299 * color[0] as f32 + 0.587 * color[1] as f32 + 0.114 * color[2] as f32) / 255.0
+}
+
+fn scale_rgb(color: [u8; 4], factor: f32) -> [u8; 4] {
+    let s = |c: u8| (c as f32 * factor).round().clamp(0.0, 255.0) as u8;
+    [s(color[0]), s(color[1]), s(color[2]), color[3]]
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Self::Dark, Self::Light, Self::HighContrast];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+            Self::HighContrast => "High contrast",
+        }
+    }
+
+    /// Colour the frame is cleared to.
+    pub fn clear_color(self) -> [u8; 4] {
+        match self {
+            Self::Dark | Self::HighContrast => [0, 0, 0, 255],
+            Self::Light => [245, 245, 240, 255],
+        }
+    }
+
+    /// Whether the background has to be painted over the renderer's black clear.
+    pub fn paints_background(self) -> bool {
+        self.clear_color() != Self::Dark.clear_color()
+    }
+
+    /// Default species colour adjusted for this background.
+    pub fn species_color(self, color: [u8; 4]) -> [u8; 4] {
+        match self {
+            Self::Dark => color,
+            Self::Light => scale_rgb(color, 0.75),
+            Self::HighContrast => {
+                let max = color[0].max(color[1]).max(color[2]);
+                if max == 0 {
+                    color
+                } else {
+                    scale_rgb(color, 255.0 / max as f32)
+                }
+            }
+        }
+    }
+
+    /// Overlay (halo, outline, trace) colour adjusted for this background.
+    pub fn overlay_color(self, color: [u8; 4]) -> [u8; 4] {
+        match self {
+            Self::Dark => color,
+            Self::Light if luminance(color) > 0.5 => scale_rgb(color, 0.45),
+            Self::Light => color,
+            Self::HighContrast => [color[0], color[1], color[2], color[3].saturating_mul(2)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_sets_clear_color_and_keeps_overlays_visible() {
+        assert_eq!(Theme::default().clear_color(), [0, 0, 0, 255]);
+        assert_eq!(Theme::Light.clear_color(), [245, 245, 240, 255]);
+        assert_eq!(Theme::HighContrast.clear_color(), [0, 0, 0, 255]);
+        assert!(Theme::Light.paints_background() && !Theme::HighContrast.paints_background());
+
+        let halo = [255, 255, 0, 32];
+        assert_eq!(Theme::Dark.overlay_color(halo), halo);
+        assert!(luminance(Theme::Light.overlay_color(halo)) < 0.5);
+        assert_eq!(Theme::HighContrast.overlay_color(halo)[3], 64);
+        assert_eq!(Theme::HighContrast.species_color([100, 50, 0, 255]), [255, 128, 0, 255]);
+    }
+}
@@ -25,12 +25,12 @@ pub fn load_and_apply_scenario() -> Result<(), Box<dyn std::error::Error>> {
     };
     let init_config = match load_result {
         Ok(config) => {
-            println!("Loaded initial configuration from {}", source_label);
+            crate::sim_log!(Io, Info, "Loaded initial configuration from {}", source_label);
             config
         }
         Err(e) => {
-            eprintln!("Failed to load {}: {}", source_label, e);
-            eprintln!("Using default hardcoded configuration");
+            crate::sim_log!(Io, Error, "Failed to load {}: {}", source_label, e);
+            crate::sim_log!(Io, Info, "Using default hardcoded configuration");
             return Err(e);
         }
     };
@@ -44,14 +44,14 @@ fn apply_configuration(init_config: InitConfig) -> Result<(), Box<dyn std::error
     let tx = SIM_COMMAND_SENDER.lock().as_ref().unwrap().clone();
 
     // Reset time to 0 when loading a new scenario
-    eprintln!("[scenario-debug] Sending ResetTime command");
+    crate::sim_log!(Io, Debug, "[scenario-debug] Sending ResetTime command");
     tx.send(SimCommand::ResetTime)?;
-    eprintln!("[scenario-debug] ResetTime sent successfully");
+    crate::sim_log!(Io, Debug, "[scenario-debug] ResetTime sent successfully");
 
     // Determine domain size from config or fallback constant
     let (global_width, global_height) = if let Some(ref sim_config) = init_config.simulation {
         let (width, height) = sim_config.domain_size();
-        println!("Setting domain size to {}x{}", width, height);
+        crate::sim_log!(Io, Info, "Setting domain size to {}x{}", width, height);
         *crate::renderer::state::DOMAIN_WIDTH.lock() = width;
         *crate::renderer::state::DOMAIN_HEIGHT.lock() = height;
         tx.send(SimCommand::SetDomainSize { width, height })?;
@@ -76,13 +76,17 @@ fn apply_configuration(init_config: InitConfig) -> Result<(), Box<dyn std::error
                     x: circle_config.x,
                     y: circle_config.y,
                     radius: circle_config.radius,
+                    electron_count_override: None,
+                    charge_override: None,
                 })?;
-                println!(
+                crate::sim_log!(
+                    Io,
+                    Info,
                     "Added circle: {} at ({}, {}) with radius {}",
                     circle_config.species, circle_config.x, circle_config.y, circle_config.radius
                 );
             }
-            Err(e) => eprintln!("Error in circle config: {}", e),
+            Err(e) => crate::sim_log!(Io, Error, "Error in circle config: {}", e),
         }
     }
 
@@ -99,7 +103,9 @@ fn apply_configuration(init_config: InitConfig) -> Result<(), Box<dyn std::error
                     width: rect_config.width,
                     height: rect_config.height,
                 })?;
-                println!(
+                crate::sim_log!(
+                    Io,
+                    Info,
                     "Added {} rectangle: {}x{} at center ({}, {})",
                     rect_config.species,
                     rect_config.width,
@@ -108,7 +114,7 @@ fn apply_configuration(init_config: InitConfig) -> Result<(), Box<dyn std::error
                     rect_config.y
                 );
             }
-            Err(e) => eprintln!("Error in metal rectangle config: {}", e),
+            Err(e) => crate::sim_log!(Io, Error, "Error in metal rectangle config: {}", e),
         }
     }
 
@@ -123,7 +129,9 @@ fn apply_configuration(init_config: InitConfig) -> Result<(), Box<dyn std::error
             particle_radius: Species::FoilMetal.radius(),
             current: foil_config.current,
         })?;
-        println!(
+        crate::sim_log!(
+            Io,
+            Info,
             "Added foil: {}x{} at center ({}, {}) with current {}",
             foil_config.width,
             foil_config.height,
@@ -140,7 +148,9 @@ fn apply_configuration(init_config: InitConfig) -> Result<(), Box<dyn std::error
                 let body = get_body_for_species(&body_templates, species);
                 let width = random_config.domain_width.unwrap_or(global_width);
                 let height = random_config.domain_height.unwrap_or(global_height);
-                eprintln!(
+                crate::sim_log!(
+                    Io,
+                    Debug,
                     "[scenario-debug] Sending AddRandom command for {} {} particles",
                     random_config.count, random_config.species
                 );
@@ -150,13 +160,15 @@ fn apply_configuration(init_config: InitConfig) -> Result<(), Box<dyn std::error
                     domain_width: width,
                     domain_height: height,
                 })?;
-                eprintln!("[scenario-debug] AddRandom sent successfully");
-                println!(
+                crate::sim_log!(Io, Debug, "[scenario-debug] AddRandom sent successfully");
+                crate::sim_log!(
+                    Io,
+                    Info,
                     "Added {} random {} particles in {}x{} domain",
                     random_config.count, random_config.species, width, height
                 );
             }
-            Err(e) => eprintln!("Error in random config: {}", e),
+            Err(e) => crate::sim_log!(Io, Error, "Error in random config: {}", e),
         }
     }
 
@@ -167,13 +179,15 @@ fn apply_configuration(init_config: InitConfig) -> Result<(), Box<dyn std::error
     if init_config.particles.random.is_empty() {
         add_default_electrolyte(tx.clone(), global_width, global_height)?;
     } else {
-        println!(
+        crate::sim_log!(
+            Io,
+            Info,
             "Skipping default electrolyte ({} [[particles.random]] entries supplied by scenario)",
             init_config.particles.random.len()
         );
     }
 
-    println!("Initial configuration loaded successfully!");
+    crate::sim_log!(Io, Info, "Initial configuration loaded successfully!");
     Ok(())
 }
 
@@ -284,7 +298,9 @@ fn add_default_electrolyte(
         })?;
     }
 
-    eprintln!(
+    crate::sim_log!(
+        Io,
+        Info,
         "[Electrolyte] Loaded default molarity={:.2}M: {} Li+, {} PF6-, {} EC, {} DMC (total {} particles)",
         molarity, li_count, pf6_count, ec_count, dmc_count, total
     );
@@ -482,26 +498,34 @@ pub fn load_hardcoded_scenario() -> Result<(), Box<dyn std::error::Error>> {
         x: left_center.x,
         y: left_center.y,
         radius: clump_radius,
+        electron_count_override: None,
+        charge_override: None,
     })?;
     tx.send(SimCommand::AddCircle {
         body: metal_body.clone(),
         x: right_center.x,
         y: right_center.y,
         radius: clump_radius,
+        electron_count_override: None,
+        charge_override: None,
     })?;
     tx.send(SimCommand::AddCircle {
         body: ion_body,
         x: center.x,
         y: center.y,
         radius: clump_radius,
+        electron_count_override: None,
+        charge_override: None,
     })?;
     tx.send(SimCommand::AddCircle {
         body: anion_body,
         x: center.x,
         y: bounds * 0.6,
         radius: clump_radius,
+        electron_count_override: None,
+        charge_override: None,
     })?;
 
-    println!("Hardcoded scenario loaded successfully!");
+    crate::sim_log!(Io, Info, "Hardcoded scenario loaded successfully!");
     Ok(())
 }
@@ -0,0 +1,143 @@
+// capacitive.rs
+// Parallel-plate capacitor model for the field between foil groups
+
+use super::simulation::Simulation;
+use std::collections::{HashMap, HashSet};
+use ultraviolet::Vec2;
+
+/// Geometry and net charge of one capacitor plate (a group of foils).
+#[derive(Clone, Copy, Debug)]
+pub struct PlateSummary {
+    pub centroid: Vec2,
+    pub charge: f32,
+}
+
+/// Field magnitude between two parallel plates carrying `q_pos` and `q_neg`.
+///
+/// The capacitor charge is half the imbalance, `Q = (q_pos - q_neg) / 2`, and
+/// the field is `E = 4πk·Q / L` where `L` is the plate length (the 2D stand-in
+/// for plate area). The sign follows the imbalance: positive means the field
+/// points from the positive plate toward the negative one.
+pub fn parallel_plate_field(q_pos: f32, q_neg: f32, plate_length: f32, coulomb_constant: f32) -> f32 {
+    if plate_length <= 0.0 {
+        return 0.0;
+    }
+    let q = 0.5 * (q_pos - q_neg);
+    4.0 * std::f32::consts::PI * coulomb_constant * q / plate_length
+}
+
+impl Simulation {
+    /// Centroid and summed body charge of all foils in `foil_ids`.
+    fn plate_summary(
+        &self,
+        foil_ids: &HashSet<u64>,
+        id_to_index: &HashMap<u64, usize>,
+    ) -> Option<(PlateSummary, Vec<Vec2>)> {
+        let mut positions = Vec::new();
+        let mut charge = 0.0f32;
+        for foil in self.foils.iter().filter(|f| foil_ids.contains(&f.id)) {
+            for &index in foil.body_ids.iter().filter_map(|id| id_to_index.get(id)) {
+                let b = &self.bodies[index];
+                positions.push(b.pos);
+                charge += b.charge;
+            }
+        }
+        if positions.is_empty() {
+            return None;
+        }
+        let centroid = positions.iter().fold(Vec2::zero(), |acc, &p| acc + p) / positions.len() as f32;
+        Some((PlateSummary { centroid, charge }, positions))
+    }
+
+    /// Inter-plate field from the charge imbalance between foil groups A
+    /// (positive plate) and B (negative plate), treating them as a parallel
+    /// plate capacitor. The separation axis runs between the group centroids
+    /// and the plate length is the groups' extent perpendicular to it.
+    /// Returns zero when either group is empty or the plates coincide.
+    pub fn capacitive_coupling_field(&self) -> Vec2 {
+        if self.group_a.is_empty() || self.group_b.is_empty() {
+            return Vec2::zero();
+        }
+        let id_to_index: HashMap<u64, usize> =
+            self.bodies.iter().enumerate().map(|(i, b)| (b.id, i)).collect();
+        let (Some((pos, pos_points)), Some((neg, neg_points))) = (
+            self.plate_summary(&self.group_a, &id_to_index),
+            self.plate_summary(&self.group_b, &id_to_index),
+        ) else {
+            return Vec2::zero();
+        };
+
+        let axis = neg.centroid - pos.centroid;
+        let separation = axis.mag();
+        if separation < 1e-6 {
+            return Vec2::zero();
+        }
+        let dir = axis / separation;
+        let lateral = Vec2::new(-dir.y, dir.x);
+
+        let mut s_min = f32::INFINITY;
+        let mut s_max = f32::NEG_INFINITY;
+        for p in pos_points.iter().chain(neg_points.iter()) {
+            let s = p.dot(lateral);
+            s_min = s_min.min(s);
+            s_max = s_max.max(s);
+        }
+        // Give single-row plates a finite length of one particle diameter
+        let plate_length = (s_max - s_min) + 2.0 * crate::body::Species::FoilMetal.radius();
+
+        dir * parallel_plate_field(pos.charge, neg.charge, plate_length, self.config.coulomb_constant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::{foil::Foil, Body, Species};
+
+    fn plate(sim: &mut Simulation, x: f32, charge: f32) -> u64 {
+        let mut ids = Vec::new();
+        for i in 0..5 {
+            let b = Body::new(
+                Vec2::new(x, -4.0 + 2.0 * i as f32),
+                Vec2::zero(),
+                1.0,
+                1.0,
+                charge,
+                Species::FoilMetal,
+            );
+            ids.push(b.id);
+            sim.bodies.push(b);
+        }
+        let foil = Foil::new(ids, Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+        let id = foil.id;
+        sim.foils.push(foil);
+        id
+    }
+
+    fn field_for_imbalance(q: f32) -> Vec2 {
+        let mut sim = Simulation::new();
+        let a = plate(&mut sim, -20.0, q);
+        let b = plate(&mut sim, 20.0, -q);
+        sim.group_a.insert(a);
+        sim.group_b.insert(b);
+        sim.capacitive_coupling_field()
+    }
+
+    #[test]
+    fn field_scales_linearly_with_charge_imbalance() {
+        let e1 = field_for_imbalance(0.2);
+        let e2 = field_for_imbalance(0.4);
+        let e4 = field_for_imbalance(0.8);
+        assert!(e1.x > 0.0, "field should point from positive to negative plate");
+        assert!(e1.y.abs() < 1e-6);
+        assert!((e2.x / e1.x - 2.0).abs() < 1e-4);
+        assert!((e4.x / e1.x - 4.0).abs() < 1e-4);
+        assert_eq!(field_for_imbalance(0.0), Vec2::zero());
+    }
+
+    #[test]
+    fn empty_groups_give_zero_field() {
+        let sim = Simulation::new();
+        assert_eq!(sim.capacitive_coupling_field(), Vec2::zero());
+    }
+}
@@ -0,0 +1,174 @@
+// charge_audit.rs
+// Per-step accounting of intended vs. achieved foil electron transfers
+
+use super::simulation::Simulation;
+use crate::body::foil::ChargingMode;
+
+/// Why a foil moved fewer electrons than it was commanded to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShortfallReason {
+    /// Every foil body already holds its foil's `max_electrons()`.
+    CapacityFull,
+    /// No foil body has an electron left to give.
+    CapacityEmpty,
+    /// Capacity was available but the transfer did not happen: no
+    /// charge-conserving partner, a blocked linked foil, or a randomly drawn
+    /// host that was full/empty.
+    Blocked,
+}
+
+/// Charge audit for one foil: the last step plus running totals.
+#[derive(Clone, Debug, Default)]
+pub struct FoilChargeAudit {
+    pub foil_id: u64,
+    /// Whole electrons the foil should have moved this step (signed, + = added).
+    pub intended: i32,
+    /// Electrons actually added (+) or removed (-) this step.
+    pub achieved: i32,
+    pub reason: Option<ShortfallReason>,
+    pub total_intended: i64,
+    pub total_achieved: i64,
+    /// Number of steps that ended with a shortfall.
+    pub shortfall_steps: u64,
+}
+
+impl Simulation {
+    /// Snapshot per-foil electron counters before foil processing, or `None`
+    /// when `foil_charge_audit` is off.
+    pub(crate) fn charge_audit_begin(&self) -> Option<Vec<i32>> {
+        self.config.foil_charge_audit.then(|| {
+            self.foils
+                .iter()
+                .map(|f| f.electron_delta_since_measure)
+                .collect()
+        })
+    }
+
+    /// Compare what each foil achieved since `charge_audit_begin` against its
+    /// command and record any shortfall.
+    ///
+    /// Current-mode foils decrement their accumulator once per electron moved,
+    /// so the whole-electron part left in `accum` is exactly what was not
+    /// delivered. Overpotential foils are commanded `trunc(output)` electrons
+    /// per step directly.
+    pub(crate) fn charge_audit_finish(&mut self, before: Option<Vec<i32>>) {
+        let Some(before) = before else {
+            return;
+        };
+        if before.len() != self.foils.len() {
+            return;
+        }
+        for (i, &start) in before.iter().enumerate() {
+            let foil = &self.foils[i];
+            let achieved = foil.electron_delta_since_measure - start;
+            let intended = match foil.charging_mode {
+                ChargingMode::Current => achieved + foil.accum.trunc() as i32,
+                ChargingMode::Overpotential => match &foil.overpotential_controller {
+                    Some(controller) => controller.last_output_current.trunc() as i32,
+                    None => foil.slave_overpotential_current.trunc() as i32,
+                },
+            };
+            let reason = if intended > 0 && achieved < intended {
+                Some(if self.foil_can_add(i) {
+                    ShortfallReason::Blocked
+                } else {
+                    ShortfallReason::CapacityFull
+                })
+            } else if intended < 0 && achieved > intended {
+                Some(if self.foil_can_remove(i) {
+                    ShortfallReason::Blocked
+                } else {
+                    ShortfallReason::CapacityEmpty
+                })
+            } else {
+                None
+            };
+
+            let foil_id = foil.id;
+            let entry = match self.foil_charge_audit.iter().position(|a| a.foil_id == foil_id) {
+                Some(pos) => &mut self.foil_charge_audit[pos],
+                None => {
+                    self.foil_charge_audit.push(FoilChargeAudit {
+                        foil_id,
+                        ..Default::default()
+                    });
+                    self.foil_charge_audit.last_mut().unwrap()
+                }
+            };
+            entry.intended = intended;
+            entry.achieved = achieved;
+            entry.reason = reason;
+            entry.total_intended += intended as i64;
+            entry.total_achieved += achieved as i64;
+            if reason.is_some() {
+                entry.shortfall_steps += 1;
+            }
+        }
+        let foils = &self.foils;
+        self.foil_charge_audit
+            .retain(|a| foils.iter().any(|f| f.id == a.foil_id));
+        *crate::renderer::state::FOIL_CHARGE_AUDIT.lock() = self.foil_charge_audit.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Electron, Species};
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn saturated_foil_reports_capacity_shortfall() {
+        let mut sim = Simulation::new();
+        sim.config.foil_charge_audit = true;
+
+        // Foil 1 is already full; foil 2 has electrons to give
+        for x in [-10.0, 10.0] {
+            let mut body = Body::new(
+                Vec2::new(x, 0.0),
+                Vec2::zero(),
+                1.0,
+                1.0,
+                0.0,
+                Species::FoilMetal,
+            );
+            body.electrons.clear();
+            for _ in 0..crate::config::FOIL_MAX_ELECTRONS {
+                body.electrons.push(Electron {
+                    rel_pos: Vec2::zero(),
+                    vel: Vec2::zero(),
+                });
+            }
+            sim.bodies.push(body);
+        }
+        let mut full = Foil::new(vec![sim.bodies[0].id], Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+        full.accum = 2.5;
+        let mut donor = Foil::new(vec![sim.bodies[1].id], Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+        donor.accum = -2.5;
+        let (full_id, donor_id) = (full.id, donor.id);
+        sim.foils.push(full);
+        sim.foils.push(donor);
+
+        let mut recipients = vec![false; sim.bodies.len()];
+        sim.process_foils_with_charge_conservation(0.0, &mut recipients);
+
+        let audit = |id: u64| {
+            sim.foil_charge_audit
+                .iter()
+                .find(|a| a.foil_id == id)
+                .cloned()
+                .unwrap()
+        };
+        let full = audit(full_id);
+        assert_eq!(full.intended, 2);
+        assert_eq!(full.achieved, 0);
+        assert_eq!(full.reason, Some(ShortfallReason::CapacityFull));
+        assert_eq!(full.shortfall_steps, 1);
+
+        // The donor could give electrons but had no partner to receive them
+        let donor = audit(donor_id);
+        assert_eq!(donor.intended, -2);
+        assert_eq!(donor.reason, Some(ShortfallReason::Blocked));
+    }
+}
@@ -59,111 +59,86 @@ fn apply_collision_modifiers(
     (base_weight_i, base_weight_j)
 }
 
+/// Hand a frozen body's share of the correction to its partner so frozen
+/// species never get displaced.
+fn pin_frozen((w1, w2): (f32, f32), frozen_i: bool, frozen_j: bool) -> (f32, f32) {
+    match (frozen_i, frozen_j) {
+        (true, _) => (0.0, w1 + w2),
+        (_, true) => (w1 + w2, 0.0),
+        _ => (w1, w2),
+    }
+}
+
 pub fn collide(sim: &mut Simulation) {
     profile_scope!("collision");
-    // DIAGNOSTIC: Validate inputs before building spatial tree to catch NaN/Inf or invalid radii
-    let mut invalid_bodies: Vec<(usize, &str)> = Vec::new();
-    for (idx, b) in sim.bodies.iter().enumerate() {
-        if !b.pos.x.is_finite() || !b.pos.y.is_finite() {
-            invalid_bodies.push((idx, "pos"));
-        }
-        if !b.vel.x.is_finite() || !b.vel.y.is_finite() {
-            invalid_bodies.push((idx, "vel"));
-        }
-        if !b.z.is_finite() {
-            invalid_bodies.push((idx, "z"));
-        }
-        if !b.vz.is_finite() {
-            invalid_bodies.push((idx, "vz"));
-        }
-        if !b.radius.is_finite() || b.radius <= 0.0 {
-            invalid_bodies.push((idx, "radius"));
-        }
-        if !b.mass.is_finite() || b.mass <= 0.0 {
-            invalid_bodies.push((idx, "mass"));
-        }
-        if !b.charge.is_finite() {
-            invalid_bodies.push((idx, "charge"));
-        }
-    }
-    if !invalid_bodies.is_empty() {
-        eprintln!(
-            "[DIAG][collision] Found {} invalid bodies before tree build",
-            invalid_bodies.len()
-        );
-        for (i, (idx, what)) in invalid_bodies.iter().take(8).enumerate() {
-            let b = &sim.bodies[*idx];
-            eprintln!(
-                "[DIAG][collision] #{i} idx={idx} bad={what} pos=({:.3},{:.3}) z={:.3} r={:.3} vel=({:.3},{:.3}) vz={:.3} mass={:.3} q={:.3}",
-                b.pos.x, b.pos.y, b.z, b.radius, b.vel.x, b.vel.y, b.vz, b.mass, b.charge
-            );
-        }
-        if invalid_bodies.len() > 8 {
-            eprintln!(
-                "[DIAG][collision] ... and {} more",
-                invalid_bodies.len() - 8
-            );
-        }
-    }
+    // Contact radius per body: the species override if set, else the body's own radius
+    let mut overrides: std::collections::HashMap<Species, Option<f32>> = Default::default();
+    let collision_radii: Vec<f32> = sim
+        .bodies
+        .iter()
+        .map(|b| {
+            overrides
+                .entry(b.species)
+                .or_insert_with(|| crate::species::get_species_props(b.species).collision_radius)
+                .unwrap_or(b.radius)
+        })
+        .collect();
     let mut rects = sim
         .bodies
         .iter()
         .enumerate()
         .map(|(index, body)| {
             let pos = body.pos;
-            let radius = body.radius;
+            let radius = collision_radii[index];
             let min = pos - Vec2::one() * radius;
             let max = pos + Vec2::one() * radius;
             (Rect::new(min.x, max.x, min.y, max.y), index)
         })
         .collect::<Vec<_>>();
-    // DIAGNOSTIC: sanity-check rects (recompute extents from bodies to avoid relying on Rect API)
-    let mut bad_rects = 0usize;
-    for (_rect, idx) in &rects {
-        let b = &sim.bodies[*idx];
-        let minx = b.pos.x - b.radius;
-        let maxx = b.pos.x + b.radius;
-        let miny = b.pos.y - b.radius;
-        let maxy = b.pos.y + b.radius;
-        let any_non_finite =
-            !(minx.is_finite() && maxx.is_finite() && miny.is_finite() && maxy.is_finite());
-        let bad_order = minx > maxx || miny > maxy;
-        if any_non_finite || bad_order {
-            if bad_rects < 8 {
-                eprintln!(
-                    "[DIAG][collision] bad rect for idx={} rect=({:.3},{:.3},{:.3},{:.3}) pos=({:.3},{:.3}) z={:.3} r={:.3}",
-                    idx, minx, maxx, miny, maxy, b.pos.x, b.pos.y, b.z, b.radius
-                );
+
+    let mut broccoli = broccoli::Tree::par_new(&mut rects);
+    let num_passes = *COLLISION_PASSES.lock();
+    if sim.config.deterministic_collisions {
+        // Gather every overlapping pair, then resolve in body-id order so the
+        // outcome does not depend on tree layout or thread scheduling
+        let mut pairs: Vec<(usize, usize)> = Vec::new();
+        broccoli.find_colliding_pairs(|i, j| {
+            pairs.push((*i.unpack_inner(), *j.unpack_inner()));
+        });
+        drop(broccoli);
+        for pair in pairs.iter_mut() {
+            if sim.bodies[pair.0].id > sim.bodies[pair.1].id {
+                *pair = (pair.1, pair.0);
             }
-            bad_rects += 1;
         }
+        pairs.sort_unstable_by_key(|&(i, j)| (sim.bodies[i].id, sim.bodies[j].id));
+        for (i, j) in pairs {
+            resolve(sim, i, j, num_passes, &collision_radii);
+        }
+        return;
     }
-    if bad_rects > 0 {
-        eprintln!(
-            "[DIAG][collision] Found {} bad rects before tree build",
-            bad_rects
-        );
-    }
-
-    let mut broccoli = broccoli::Tree::par_new(&mut rects);
     let ptr = sim as *mut Simulation as usize;
-    let num_passes = *COLLISION_PASSES.lock();
     broccoli.par_find_colliding_pairs(|i, j| {
         let sim = unsafe { &mut *(ptr as *mut Simulation) };
         let i = *i.unpack_inner();
         let j = *j.unpack_inner();
-        resolve(sim, i, j, num_passes);
+        resolve(sim, i, j, num_passes, &collision_radii);
     });
 }
 
-fn resolve(sim: &mut Simulation, i: usize, j: usize, num_passes: usize) {
+fn resolve(sim: &mut Simulation, i: usize, j: usize, num_passes: usize, radii: &[f32]) {
+    let frozen_i = sim.frozen_species.contains(&sim.bodies[i].species);
+    let frozen_j = sim.frozen_species.contains(&sim.bodies[j].species);
+    if frozen_i && frozen_j {
+        return;
+    }
     // Snapshot current state into locals (avoid holding immutable borrows)
     let mut p1 = sim.bodies[i].pos;
     let mut p2 = sim.bodies[j].pos;
     let mut z1 = sim.bodies[i].z;
     let mut z2 = sim.bodies[j].z;
-    let r1 = sim.bodies[i].radius;
-    let r2 = sim.bodies[j].radius;
+    let r1 = radii[i];
+    let r2 = radii[j];
     let mut d_xy = p2 - p1;
     let mut dz = z2 - z1;
     let r = r1 + r2;
@@ -240,8 +215,7 @@ fn resolve(sim: &mut Simulation, i: usize, j: usize, num_passes: usize) {
     let d_dot_v = d_xy.dot(v_xy) + dz * vz;
     let m1 = sim.bodies[i].mass;
     let m2 = sim.bodies[j].mass;
-    let weight1 = m2 / (m1 + m2);
-    let weight2 = m1 / (m1 + m2);
+    let (weight1, weight2) = pin_frozen((m2 / (m1 + m2), m1 / (m1 + m2)), frozen_i, frozen_j);
 
     if d_dot_v >= 0.0 && dist_sq > 0.0 && dist_sq.is_finite() {
         let dist = dist_sq.sqrt();
@@ -251,7 +225,11 @@ fn resolve(sim: &mut Simulation, i: usize, j: usize, num_passes: usize) {
         let sep_z = dz * corr;
 
         // Apply collision modifiers (metal stiffness + soft collisions)
-        let (mod_weight1, mod_weight2) = apply_collision_modifiers(sim, i, j, weight1, weight2);
+        let (mod_weight1, mod_weight2) = pin_frozen(
+            apply_collision_modifiers(sim, i, j, weight1, weight2),
+            frozen_i,
+            frozen_j,
+        );
 
         sim.bodies[i].pos.x -= mod_weight1 * sep_x;
         sim.bodies[i].pos.y -= mod_weight1 * sep_y;
@@ -270,12 +248,25 @@ fn resolve(sim: &mut Simulation, i: usize, j: usize, num_passes: usize) {
         let (s, c) = angle.sin_cos();
         let dir = Vec2::new(c, s);
         let sep = r * 1.001;
-        let mid = (sim.bodies[i].pos + sim.bodies[j].pos) * 0.5;
+        // A frozen body is the pivot and keeps its place
+        let mid = if frozen_i {
+            sim.bodies[i].pos
+        } else if frozen_j {
+            sim.bodies[j].pos
+        } else {
+            (sim.bodies[i].pos + sim.bodies[j].pos) * 0.5
+        };
         sim.bodies[i].pos = mid - dir * (sep * weight1);
         sim.bodies[j].pos = mid + dir * (sep * weight2);
         // Keep z together and clamped in range
         let depth = sim.domain_depth;
-        let midz = ((sim.bodies[i].z + sim.bodies[j].z) * 0.5).clamp(-depth, depth);
+        let midz = if frozen_i {
+            sim.bodies[i].z
+        } else if frozen_j {
+            sim.bodies[j].z
+        } else {
+            ((sim.bodies[i].z + sim.bodies[j].z) * 0.5).clamp(-depth, depth)
+        };
         sim.bodies[i].z = midz;
         sim.bodies[j].z = midz;
         // Zero any non-finite velocities
@@ -342,7 +333,11 @@ fn resolve(sim: &mut Simulation, i: usize, j: usize, num_passes: usize) {
     let sep_z = dz * scale;
 
     // Apply collision modifiers (metal stiffness + soft collisions)
-    let (mod_weight1, mod_weight2) = apply_collision_modifiers(sim, i, j, weight1, weight2);
+    let (mod_weight1, mod_weight2) = pin_frozen(
+        apply_collision_modifiers(sim, i, j, weight1, weight2),
+        frozen_i,
+        frozen_j,
+    );
 
     let v1x = v1.x + sep_x * mod_weight1;
     let v1y = v1.y + sep_y * mod_weight1;
@@ -370,3 +365,108 @@ fn resolve(sim: &mut Simulation, i: usize, j: usize, num_passes: usize) {
         if !b.vz.is_finite() { b.vz = 0.0; }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::Body;
+    use crate::species::{get_species_props, update_species_props, SpeciesProps};
+
+    /// Holds the species-props test lock and restores the species on drop,
+    /// so a failing assertion can't leak the override into other tests.
+    struct PropsOverride {
+        species: Species,
+        original: SpeciesProps,
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl Drop for PropsOverride {
+        fn drop(&mut self) {
+            update_species_props(self.species, self.original);
+        }
+    }
+
+    #[test]
+    fn collisions_use_collision_radius_not_render_radius() {
+        let species = Species::FEC;
+        let lock = crate::species::SPECIES_PROPS_TEST_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let original = get_species_props(species);
+        let _restore = PropsOverride { species, original, _lock: lock };
+        let render_radius = 0.5;
+
+        let separation_after = |collision_radius: Option<f32>| {
+            let mut props = original;
+            props.collision_radius = collision_radius;
+            update_species_props(species, props);
+
+            let mut sim = Simulation::new();
+            // Apart by more than the render radii, less than the collision radii
+            for x in [-1.0, 1.0] {
+                sim.bodies.push(Body::new(
+                    Vec2::new(x, 0.0),
+                    Vec2::zero(),
+                    1.0,
+                    render_radius,
+                    0.0,
+                    species,
+                ));
+            }
+            collide(&mut sim);
+            (sim.bodies[1].pos - sim.bodies[0].pos).mag()
+        };
+
+        let untouched = separation_after(None);
+        let pushed = separation_after(Some(2.0));
+        assert!((untouched - 2.0).abs() < 1e-5);
+        assert!((pushed - 4.0).abs() < 1e-3, "separation was {pushed}");
+        // The override also drives contact checks outside collision resolution
+        let body = Body::new(Vec2::zero(), Vec2::zero(), 1.0, render_radius, 0.0, species);
+        assert!((body.collision_radius() - 2.0).abs() < 1e-6);
+
+        update_species_props(species, original);
+        assert!((body.collision_radius() - render_radius).abs() < 1e-6);
+        assert!((Species::FEC.collision_radius() - Species::FEC.radius()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn deterministic_cluster_resolution_is_reproducible() {
+        let run = |threads: usize| -> Vec<(f32, f32, f32, f32)> {
+            let mut sim = Simulation::new();
+            sim.config.deterministic_collisions = true;
+            // Ring of mutually overlapping ions with crossing velocities
+            for k in 0..8 {
+                let angle = k as f32 * TAU / 8.0;
+                let dir = Vec2::new(angle.cos(), angle.sin());
+                let radius = Species::LithiumIon.radius();
+                sim.bodies.push(Body::new(
+                    dir * radius * 0.6,
+                    -dir * (0.1 + 0.01 * k as f32),
+                    Species::LithiumIon.mass(),
+                    radius,
+                    1.0,
+                    Species::LithiumIon,
+                ));
+            }
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| {
+                for _ in 0..3 {
+                    collide(&mut sim);
+                }
+            });
+            sim.bodies
+                .iter()
+                .map(|b| (b.pos.x, b.pos.y, b.vel.x, b.vel.y))
+                .collect()
+        };
+
+        let reference = run(1);
+        for threads in [1, 2, 4, 8] {
+            assert_eq!(run(threads), reference, "{} threads", threads);
+        }
+    }
+}
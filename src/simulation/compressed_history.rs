@@ -0,0 +1,425 @@
+// simulation/compressed_history.rs
+//
+// Delta-encoded playback history on disk. The in-memory history stays a ring
+// of full states (see `PLAYBACK_HISTORY_FRAMES`); for saving, it is encoded
+// as periodic keyframes followed by deltas that only carry bodies whose
+// position or velocity (or an electron's) moved past a tolerance since the
+// last stored value, or whose charge, lithium content, species lock or
+// metal-surround flag changed at all.
+// Reloading reconstructs full states and installs them as the playback
+// history. Any change in the body set, domain or foil membership forces a
+// keyframe; config and switch settings come from the preceding keyframe.
+
+use crate::body::foil::Foil;
+use crate::body::{Body, Electron};
+use crate::io::SimulationState;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use std::fmt;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use ultraviolet::Vec2;
+
+/// Keyframe spacing and per-body tolerances for delta encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeltaSettings {
+    /// Maximum frames between keyframes
+    pub keyframe_interval: usize,
+    /// Position change (Å) below which a body is left out of a delta
+    pub position_tolerance: f32,
+    /// Velocity change (Å/fs) below which a body is left out of a delta
+    pub velocity_tolerance: f32,
+}
+
+impl DeltaSettings {
+    pub fn from_config(config: &crate::config::SimConfig) -> Self {
+        Self {
+            keyframe_interval: config.compressed_history_keyframe_interval.max(1),
+            position_tolerance: config.compressed_history_position_tolerance,
+            velocity_tolerance: config.compressed_history_velocity_tolerance,
+        }
+    }
+}
+
+/// Per-frame state of one body that changed since the previous stored frame.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BodyDelta {
+    /// Index into the keyframe's body list
+    pub index: u32,
+    pub pos: Vec2,
+    pub z: f32,
+    pub vel: Vec2,
+    pub vz: f32,
+    pub charge: f32,
+    pub electrons: SmallVec<[Electron; 2]>,
+    pub lithium_content: f32,
+    pub species_lock_until: f32,
+    pub surrounded_by_metal: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FrameDelta {
+    pub frame: usize,
+    pub sim_time: f32,
+    pub dt: f32,
+    pub last_thermostat_time: f32,
+    pub switch_step: Option<u8>,
+    pub foils: Vec<Foil>,
+    pub bodies: Vec<BodyDelta>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum CompressedFrame {
+    Key(Box<SimulationState>),
+    Delta(FrameDelta),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CompressedHistory {
+    pub settings: DeltaSettings,
+    pub cursor: usize,
+    pub frames: Vec<CompressedFrame>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReconstructionError {
+    /// The file holds no frames
+    Empty,
+    /// A delta appeared before any keyframe
+    MissingKeyframe { frame_index: usize },
+    /// A delta refers to a body the keyframe does not have
+    BodyOutOfRange {
+        frame_index: usize,
+        body_index: usize,
+        bodies: usize,
+    },
+}
+
+impl fmt::Display for ReconstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "compressed history contains no frames"),
+            Self::MissingKeyframe { frame_index } => {
+                write!(f, "frame {} is a delta with no preceding keyframe", frame_index)
+            }
+            Self::BodyOutOfRange {
+                frame_index,
+                body_index,
+                bodies,
+            } => write!(
+                f,
+                "frame {} refers to body {} but the keyframe has {} bodies",
+                frame_index, body_index, bodies
+            ),
+        }
+    }
+}
+
+/// Whether `next` can be stored as a delta against `reference`.
+fn same_layout(reference: &SimulationState, next: &SimulationState) -> bool {
+    reference.bodies.len() == next.bodies.len()
+        && reference.bodies.iter().zip(&next.bodies).all(|(a, b)| {
+            a.id == b.id && a.species == b.species && a.electrons.len() == b.electrons.len()
+        })
+        && reference.domain_width == next.domain_width
+        && reference.domain_height == next.domain_height
+        && reference.domain_depth == next.domain_depth
+        && reference.body_to_foil == next.body_to_foil
+        && reference.group_a == next.group_a
+        && reference.group_b == next.group_b
+}
+
+/// Whether `b` drifted from the stored `a` enough to need a delta entry.
+fn body_changed(a: &Body, b: &Body, settings: DeltaSettings) -> bool {
+    let (pos_tol, vel_tol) = (settings.position_tolerance, settings.velocity_tolerance);
+    (a.pos - b.pos).mag() > pos_tol
+        || (a.z - b.z).abs() > pos_tol
+        || (a.vel - b.vel).mag() > vel_tol
+        || (a.vz - b.vz).abs() > vel_tol
+        || a.charge != b.charge
+        || a.lithium_content != b.lithium_content
+        || a.species_lock_until != b.species_lock_until
+        || a.surrounded_by_metal != b.surrounded_by_metal
+        || a.electrons.iter().zip(&b.electrons).any(|(ea, eb)| {
+            (ea.rel_pos - eb.rel_pos).mag() > pos_tol || (ea.vel - eb.vel).mag() > vel_tol
+        })
+}
+
+fn body_deltas(
+    reference: &SimulationState,
+    next: &SimulationState,
+    settings: DeltaSettings,
+) -> Vec<BodyDelta> {
+    reference
+        .bodies
+        .iter()
+        .zip(&next.bodies)
+        .enumerate()
+        .filter(|(_, (a, b))| body_changed(a, b, settings))
+        .map(|(i, (_, b))| BodyDelta {
+            index: i as u32,
+            pos: b.pos,
+            z: b.z,
+            vel: b.vel,
+            vz: b.vz,
+            charge: b.charge,
+            electrons: b.electrons.clone(),
+            lithium_content: b.lithium_content,
+            species_lock_until: b.species_lock_until,
+            surrounded_by_metal: b.surrounded_by_metal,
+        })
+        .collect()
+}
+
+/// Advance `state` by one delta. Shared by the encoder and decoder so both
+/// track the same reconstructed reference.
+fn apply_delta(
+    state: &mut SimulationState,
+    delta: &FrameDelta,
+    frame_index: usize,
+) -> Result<(), ReconstructionError> {
+    for d in &delta.bodies {
+        let bodies = state.bodies.len();
+        let body = state
+            .bodies
+            .get_mut(d.index as usize)
+            .ok_or(ReconstructionError::BodyOutOfRange {
+                frame_index,
+                body_index: d.index as usize,
+                bodies,
+            })?;
+        body.pos = d.pos;
+        body.z = d.z;
+        body.vel = d.vel;
+        body.vz = d.vz;
+        body.charge = d.charge;
+        body.electrons = d.electrons.clone();
+        body.lithium_content = d.lithium_content;
+        body.species_lock_until = d.species_lock_until;
+        body.surrounded_by_metal = d.surrounded_by_metal;
+    }
+    state.frame = delta.frame;
+    state.sim_time = delta.sim_time;
+    state.dt = delta.dt;
+    state.last_thermostat_time = delta.last_thermostat_time;
+    state.switch_step = delta.switch_step;
+    state.foils = delta.foils.clone();
+    Ok(())
+}
+
+impl CompressedHistory {
+    /// Delta-encode `frames`. Reconstructed body and electron positions and
+    /// velocities stay within the configured tolerances of the originals; all
+    /// other per-body state is exact.
+    pub fn encode<'a>(
+        frames: impl IntoIterator<Item = &'a SimulationState>,
+        cursor: usize,
+        settings: DeltaSettings,
+    ) -> Self {
+        let mut out = Vec::new();
+        let mut reference: Option<SimulationState> = None;
+        let mut since_key = 0;
+        for state in frames {
+            match reference.as_mut() {
+                Some(r) if since_key < settings.keyframe_interval && same_layout(r, state) => {
+                    let delta = FrameDelta {
+                        frame: state.frame,
+                        sim_time: state.sim_time,
+                        dt: state.dt,
+                        last_thermostat_time: state.last_thermostat_time,
+                        switch_step: state.switch_step,
+                        foils: state.foils.clone(),
+                        bodies: body_deltas(r, state, settings),
+                    };
+                    // Layout was checked above, so every index is in range
+                    let _ = apply_delta(r, &delta, out.len());
+                    out.push(CompressedFrame::Delta(delta));
+                    since_key += 1;
+                }
+                _ => {
+                    out.push(CompressedFrame::Key(Box::new(state.clone())));
+                    reference = Some(state.clone());
+                    since_key = 1;
+                }
+            }
+        }
+        Self {
+            settings,
+            cursor,
+            frames: out,
+        }
+    }
+
+    pub fn keyframe_count(&self) -> usize {
+        self.frames
+            .iter()
+            .filter(|f| matches!(f, CompressedFrame::Key(_)))
+            .count()
+    }
+
+    /// Rebuild the full state of every frame.
+    pub fn reconstruct(&self) -> Result<Vec<SimulationState>, ReconstructionError> {
+        if self.frames.is_empty() {
+            return Err(ReconstructionError::Empty);
+        }
+        let mut states = Vec::with_capacity(self.frames.len());
+        let mut current: Option<SimulationState> = None;
+        for (i, frame) in self.frames.iter().enumerate() {
+            match frame {
+                CompressedFrame::Key(state) => current = Some((**state).clone()),
+                CompressedFrame::Delta(delta) => {
+                    let state = current
+                        .as_mut()
+                        .ok_or(ReconstructionError::MissingKeyframe { frame_index: i })?;
+                    apply_delta(state, delta, i)?;
+                }
+            }
+            states.extend(current.clone());
+        }
+        Ok(states)
+    }
+
+    /// Write as gzip-compressed bincode.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+        let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::fast());
+        bincode::serialize_into(&mut encoder, self).map_err(|e| e.to_string())?;
+        encoder.finish().map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+        let decoder = GzDecoder::new(BufReader::new(file));
+        bincode::deserialize_from(decoder).map_err(|e| e.to_string())
+    }
+}
+
+impl super::Simulation {
+    /// Save the playback history delta-encoded, returning (frames, keyframes).
+    pub fn save_compressed_history(&self, path: &Path) -> Result<(usize, usize), String> {
+        let settings = DeltaSettings::from_config(&self.config);
+        let history =
+            CompressedHistory::encode(&self.simple_history, self.history_cursor, settings);
+        history.write(path)?;
+        Ok((history.frames.len(), history.keyframe_count()))
+    }
+
+    /// Replace the playback history with a reconstructed compressed history
+    /// and pause on its saved cursor. Returns the frame count.
+    pub fn load_compressed_history(&mut self, path: &Path) -> Result<usize, String> {
+        let history = CompressedHistory::read(path)?;
+        let states = history.reconstruct().map_err(|e| e.to_string())?;
+        let count = states.len();
+        self.history_capacity = self.history_capacity.max(count);
+        self.simple_history = states.into();
+        let cursor = history.cursor.min(count - 1);
+        self.apply_snapshot(cursor);
+        self.history_cursor = cursor;
+        self.history_dirty = false;
+        self.playback.reset();
+        self.publish_playback_status();
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::Species;
+    use crate::simulation::Simulation;
+
+    #[test]
+    fn round_trip_stays_within_tolerances() {
+        let mut sim = Simulation::new();
+        let li = Species::LithiumIon;
+        for i in 0..8 {
+            let pos = Vec2::new(i as f32 * 3.0, 0.0);
+            sim.bodies.push(Body::new(pos, Vec2::zero(), li.mass(), li.radius(), 1.0, li));
+        }
+        // A still metal body whose electron and lithium content change instead
+        let metal = Species::LithiumMetal;
+        let host_pos = Vec2::new(0.0, 10.0);
+        let mut host =
+            Body::new(host_pos, Vec2::zero(), metal.mass(), metal.radius(), 0.0, metal);
+        host.electrons.push(Electron {
+            rel_pos: Vec2::zero(),
+            vel: Vec2::zero(),
+        });
+        sim.bodies.push(host);
+        let mut frames = Vec::new();
+        for step in 0..25 {
+            // Even bodies drift below the position tolerance, odd ones above it
+            for (i, body) in sim.bodies.iter_mut().enumerate() {
+                let step_len = if i % 2 == 0 { 0.004 } else { 0.5 };
+                body.pos.x += step_len;
+                body.vel = Vec2::new(step_len, 0.0);
+            }
+            let host = sim.bodies.last_mut().unwrap();
+            host.pos.x -= 0.004;
+            host.vel = Vec2::zero();
+            host.electrons[0].rel_pos = Vec2::new(0.05 * step as f32, 0.0);
+            host.electrons[0].vel = Vec2::new(0.0, 0.01 * step as f32);
+            host.lithium_content = if step >= 12 { 0.5 } else { 0.0 };
+            host.surrounded_by_metal = step >= 18;
+            sim.frame = step;
+            sim.time = step as f32;
+            frames.push(SimulationState::from_simulation(&sim));
+        }
+        // Delete a body mid-run to force a layout keyframe
+        sim.bodies.remove(0);
+        frames.push(SimulationState::from_simulation(&sim));
+
+        let settings = DeltaSettings {
+            keyframe_interval: 10,
+            position_tolerance: 0.01,
+            velocity_tolerance: 0.001,
+        };
+        let history = CompressedHistory::encode(&frames, 3, settings);
+        // Keyframes at 0, 10, 20 and the body deletion
+        assert_eq!(history.keyframe_count(), 4);
+        let stored: usize = history
+            .frames
+            .iter()
+            .map(|f| match f {
+                CompressedFrame::Key(s) => s.bodies.len(),
+                CompressedFrame::Delta(d) => d.bodies.len(),
+            })
+            .sum();
+        let original: usize = frames.iter().map(|s| s.bodies.len()).sum();
+        assert!(stored < original);
+
+        let rebuilt = history.reconstruct().unwrap();
+        assert_eq!(rebuilt.len(), frames.len());
+        for (a, b) in frames.iter().zip(&rebuilt) {
+            assert_eq!(a.frame, b.frame);
+            assert_eq!(a.bodies.len(), b.bodies.len());
+            for (x, y) in a.bodies.iter().zip(&b.bodies) {
+                assert_eq!(x.id, y.id);
+                assert!((x.pos - y.pos).mag() <= settings.position_tolerance);
+                assert!((x.vel - y.vel).mag() <= settings.velocity_tolerance);
+                assert_eq!(x.electrons.len(), y.electrons.len());
+                for (ex, ey) in x.electrons.iter().zip(&y.electrons) {
+                    assert!((ex.rel_pos - ey.rel_pos).mag() <= settings.position_tolerance);
+                    assert!((ex.vel - ey.vel).mag() <= settings.velocity_tolerance);
+                }
+                assert_eq!(x.lithium_content, y.lithium_content);
+                assert_eq!(x.surrounded_by_metal, y.surrounded_by_metal);
+            }
+        }
+
+        let orphan = CompressedHistory {
+            settings,
+            cursor: 0,
+            frames: history.frames[1..].to_vec(),
+        };
+        assert_eq!(
+            orphan.reconstruct().err(),
+            Some(ReconstructionError::MissingKeyframe { frame_index: 0 })
+        );
+    }
+}
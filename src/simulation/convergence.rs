@@ -0,0 +1,141 @@
+// simulation/convergence.rs
+//
+// "Measure until converged" run mode. With `convergence_monitor` on, the
+// chosen observable is sampled at the end of every step; once its spread
+// (max - min) over the last `convergence_window_fs` is within
+// `convergence_tolerance`, the run pauses and the converged value and frame
+// are published for the GUI. Turning the monitor off re-arms it.
+
+use crate::body::Body;
+use crate::config::ConvergenceObservable;
+use std::collections::VecDeque;
+
+/// Where and at what value the observable settled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConvergenceResult {
+    /// Mean of the observable over the converged window
+    pub value: f32,
+    pub frame: usize,
+    pub time_fs: f32,
+}
+
+/// Sliding window of `(time_fs, value)` samples.
+#[derive(Clone, Debug, Default)]
+pub struct ConvergenceMonitor {
+    samples: VecDeque<(f32, f32)>,
+    pub result: Option<ConvergenceResult>,
+}
+
+impl ConvergenceMonitor {
+    /// Add a sample and return the mean once the window is fully covered and
+    /// its spread is within `tolerance`.
+    pub fn push(&mut self, time_fs: f32, value: f32, window_fs: f32, tolerance: f32) -> Option<f32> {
+        self.samples.push_back((time_fs, value));
+        let start = time_fs - window_fs.max(0.0);
+        // Keep one sample at or before the window start to know it is covered
+        while self.samples.len() >= 2 && self.samples[1].0 <= start {
+            self.samples.pop_front();
+        }
+        if self.samples.len() < 2 || self.samples[0].0 > start {
+            return None;
+        }
+        let (mut min, mut max, mut sum) = (f32::INFINITY, f32::NEG_INFINITY, 0.0f32);
+        for &(_, v) in &self.samples {
+            min = min.min(v);
+            max = max.max(v);
+            sum += v;
+        }
+        (max - min <= tolerance).then(|| sum / self.samples.len() as f32)
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.result = None;
+    }
+}
+
+/// Current value of `observable` over `bodies`.
+pub fn observable_value(observable: ConvergenceObservable, bodies: &[Body]) -> f32 {
+    match observable {
+        ConvergenceObservable::Temperature => super::utils::compute_temperature(bodies),
+        ConvergenceObservable::SpeciesCount(species) => {
+            bodies.iter().filter(|b| b.species == species).count() as f32
+        }
+        ConvergenceObservable::NetDipole => bodies
+            .iter()
+            .fold(ultraviolet::Vec2::zero(), |acc, b| acc + b.pos * b.charge)
+            .mag(),
+    }
+}
+
+impl super::Simulation {
+    /// Sample the configured observable and pause the run once it settles.
+    pub(crate) fn check_convergence(&mut self) {
+        if !self.config.convergence_monitor {
+            if self.convergence.result.is_some() {
+                *crate::renderer::state::CONVERGENCE_RESULT.lock() = None;
+            }
+            self.convergence.reset();
+            return;
+        }
+        if self.convergence.result.is_some() {
+            return;
+        }
+        let value = observable_value(self.config.convergence_observable, &self.bodies);
+        let Some(mean) = self.convergence.push(
+            self.time,
+            value,
+            self.config.convergence_window_fs,
+            self.config.convergence_tolerance,
+        ) else {
+            return;
+        };
+        let result = ConvergenceResult {
+            value: mean,
+            frame: self.frame,
+            time_fs: self.time,
+        };
+        self.convergence.result = Some(result);
+        *crate::renderer::state::CONVERGENCE_RESULT.lock() = Some(result);
+        crate::renderer::state::PAUSED.store(true, std::sync::atomic::Ordering::Relaxed);
+        crate::sim_log!(
+            Diagnostics,
+            Info,
+            "✓ {:?} converged to {:.4} at frame {} ({:.1} fs)",
+            self.config.convergence_observable,
+            mean,
+            self.frame,
+            self.time
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn damped_observable_converges_at_expected_time() {
+        // 300 + 100 e^{-t/100}: the spread over a 50 fs window is
+        // 100 e^{-t/100} (e^{0.5} - 1), which drops below 0.5 at t ≈ 486.8 fs
+        let mut monitor = ConvergenceMonitor::default();
+        let mut converged = None;
+        for step in 0..2000 {
+            let t = step as f32;
+            let value = 300.0 + 100.0 * (-t / 100.0).exp();
+            if let Some(mean) = monitor.push(t, value, 50.0, 0.5) {
+                converged = Some((t, mean));
+                break;
+            }
+        }
+        let (t, mean) = converged.expect("observable should converge");
+        assert!((486.0..=488.0).contains(&t), "converged at {}", t);
+        assert!((mean - 300.0).abs() < 1.0);
+
+        // A constant needs a full window before it counts
+        let mut monitor = ConvergenceMonitor::default();
+        assert_eq!(monitor.push(0.0, 1.0, 10.0, 0.1), None);
+        assert_eq!(monitor.push(5.0, 1.0, 10.0, 0.1), None);
+        assert_eq!(monitor.push(10.0, 1.0, 10.0, 0.1), Some(1.0));
+    }
+}
@@ -0,0 +1,68 @@
+// simulation/csv_stream.rs
+//
+// Shared plumbing for the CSVs appended at the manual-measurement cadence
+// (foil current, PID state, applied field). Each logger owns a `CsvStream`
+// for the header/append/flush work, and `stream_if_enabled` handles the GUI
+// toggle: a disabled stream is closed, an enabled one is opened on first use.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Append-only CSV file with a fixed header.
+pub struct CsvStream {
+    file: BufWriter<File>,
+    /// Short name used in write-error log lines
+    name: &'static str,
+}
+
+impl CsvStream {
+    /// Create the file at `path`, creating directories as needed, and write
+    /// `header` as its first line.
+    pub fn create(path: &Path, header: &str, name: &'static str) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "{}", header)?;
+        Ok(Self { file, name })
+    }
+
+    /// Append one data line and flush. Write errors are logged, not returned.
+    pub fn append(&mut self, line: &str) {
+        if let Err(e) = writeln!(self.file, "{}", line) {
+            crate::sim_log!(Io, Error, "{}: write failed: {e}", self.name);
+            return;
+        }
+        let _ = self.file.flush();
+    }
+}
+
+/// Close `slot` when `enabled` is off; otherwise open it at `path` with
+/// `open` if it isn't open yet. Returns the open logger, if any. `label`
+/// names the stream in log messages.
+pub fn stream_if_enabled<'a, T>(
+    slot: &'a mut Option<T>,
+    enabled: &AtomicBool,
+    path: &Path,
+    label: &str,
+    open: impl FnOnce(&Path) -> std::io::Result<T>,
+) -> Option<&'a mut T> {
+    if !enabled.load(Ordering::Relaxed) {
+        *slot = None;
+        return None;
+    }
+    if slot.is_none() {
+        match open(path) {
+            Ok(logger) => {
+                crate::sim_log!(Io, Info, "✓ Started {} recording to: {}", label, path.display());
+                *slot = Some(logger);
+            }
+            Err(e) => {
+                crate::sim_log!(Io, Error, "✗ Failed to open {} CSV: {}", label, e);
+            }
+        }
+    }
+    slot.as_mut()
+}
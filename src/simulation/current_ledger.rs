@@ -0,0 +1,241 @@
+// simulation/current_ledger.rs
+//
+// Per-step ledger of the current each foil actually drove and the control
+// path that produced it, for untangling links, groups, overpotential
+// master/slave pairs and switch charging when several act at once. Rows are
+// buffered in memory and appended to the CSV whenever the buffer fills, and
+// once more when the ledger is turned off.
+//
+// CSV schema:
+//   time_fs,foil_id,mode,effective_current,link_id,link_mode,group,role,switch_active
+// link_id/link_mode/group are blank for unlinked or ungrouped foils.
+
+use super::simulation::Simulation;
+use crate::body::foil::{ChargingMode, LinkMode};
+use crate::switch_charging::RunState;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+pub const CURRENT_LEDGER_HEADER: &str =
+    "time_fs,foil_id,mode,effective_current,link_id,link_mode,group,role,switch_active";
+
+/// Rows held in memory before they are appended to the file.
+pub const CURRENT_LEDGER_BUFFER_ROWS: usize = 4096;
+
+/// Which control path set a foil's current.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LedgerRole {
+    Independent,
+    /// Reference foil of a current-mode link, or overpotential foil with a controller driving a link
+    LinkMaster,
+    /// Follows its link partner (mirrored current or slave overpotential output)
+    LinkSlave,
+    /// Lowest foil ID of group A/B, whose setpoints the group copies
+    GroupMaster,
+    GroupFollower,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LedgerEntry {
+    pub time_fs: f32,
+    pub foil_id: u64,
+    pub mode: ChargingMode,
+    pub effective_current: f32,
+    pub link_id: Option<u64>,
+    pub link_mode: Option<LinkMode>,
+    pub group: Option<char>,
+    pub role: LedgerRole,
+    pub switch_active: bool,
+}
+
+impl LedgerEntry {
+    pub fn csv_row(&self) -> String {
+        format!(
+            "{:.3},{},{:?},{:.6},{},{},{},{:?},{}",
+            self.time_fs,
+            self.foil_id,
+            self.mode,
+            self.effective_current,
+            self.link_id.map(|id| id.to_string()).unwrap_or_default(),
+            self.link_mode.map(|m| format!("{:?}", m)).unwrap_or_default(),
+            self.group.map(String::from).unwrap_or_default(),
+            self.role,
+            self.switch_active as u8
+        )
+    }
+}
+
+/// Bounded row buffer in front of an append-only CSV.
+pub struct CurrentLedger {
+    path: PathBuf,
+    file: Option<BufWriter<File>>,
+    pub pending: Vec<LedgerEntry>,
+}
+
+impl CurrentLedger {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            file: None,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, entry: LedgerEntry) {
+        self.pending.push(entry);
+        if self.pending.len() >= CURRENT_LEDGER_BUFFER_ROWS {
+            self.flush();
+        }
+    }
+
+    /// Append buffered rows to the file, creating it with a header first.
+    pub fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        if self.file.is_none() {
+            let opened = self
+                .path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| OpenOptions::new().create(true).write(true).truncate(true).open(&self.path))
+                .and_then(|f| {
+                    let mut file = BufWriter::new(f);
+                    writeln!(file, "{}", CURRENT_LEDGER_HEADER)?;
+                    Ok(file)
+                });
+            match opened {
+                Ok(file) => {
+                    crate::sim_log!(Io, Info, "✓ Started current ledger: {}", self.path.display());
+                    self.file = Some(file);
+                }
+                Err(e) => {
+                    crate::sim_log!(Io, Error, "✗ Failed to open current ledger CSV: {}", e);
+                    self.pending.clear();
+                    return;
+                }
+            }
+        }
+        if let Some(file) = self.file.as_mut() {
+            for entry in self.pending.drain(..) {
+                if let Err(e) = writeln!(file, "{}", entry.csv_row()) {
+                    crate::sim_log!(Io, Error, "current_ledger: write failed: {e}");
+                    break;
+                }
+            }
+            let _ = file.flush();
+        }
+        self.pending.clear();
+    }
+}
+
+impl Simulation {
+    fn ledger_role(&self, i: usize) -> LedgerRole {
+        let foil = &self.foils[i];
+        if let Some(link_id) = foil.link_id {
+            let partner = self.foils.iter().position(|f| f.id == link_id);
+            return match (foil.charging_mode, partner) {
+                (ChargingMode::Overpotential, _) if foil.overpotential_controller.is_none() => {
+                    LedgerRole::LinkSlave
+                }
+                (ChargingMode::Overpotential, _) => LedgerRole::LinkMaster,
+                // Current-mode pairs mirror the earlier foil in the list
+                (ChargingMode::Current, Some(j)) if j < i => LedgerRole::LinkSlave,
+                (ChargingMode::Current, _) => LedgerRole::LinkMaster,
+            };
+        }
+        for group in [&self.group_a, &self.group_b] {
+            if group.contains(&foil.id) {
+                return if group.iter().min() == Some(&foil.id) {
+                    LedgerRole::GroupMaster
+                } else {
+                    LedgerRole::GroupFollower
+                };
+            }
+        }
+        LedgerRole::Independent
+    }
+
+    /// Record the current foil `i` resolved to this step when the ledger is on.
+    pub(crate) fn record_current_ledger(&mut self, i: usize, time: f32, effective_current: f32) {
+        if !self.config.current_ledger {
+            return;
+        }
+        let foil = &self.foils[i];
+        let entry = LedgerEntry {
+            time_fs: time,
+            foil_id: foil.id,
+            mode: foil.charging_mode,
+            effective_current,
+            link_id: foil.link_id,
+            link_mode: foil.link_id.map(|_| foil.mode),
+            group: if self.group_a.contains(&foil.id) {
+                Some('A')
+            } else if self.group_b.contains(&foil.id) {
+                Some('B')
+            } else {
+                None
+            },
+            role: self.ledger_role(i),
+            switch_active: matches!(self.switch_run_state, RunState::Running),
+        };
+        if self.current_ledger.is_none() {
+            let path = std::path::Path::new("doe_results")
+                .join(format!("current_ledger_{}", self.foil_metrics_filename_base()));
+            self.current_ledger = Some(CurrentLedger::new(path));
+        }
+        if let Some(ledger) = self.current_ledger.as_mut() {
+            ledger.push(entry);
+        }
+    }
+
+    /// Flush and close the ledger once it has been turned off.
+    pub(crate) fn close_current_ledger_if_disabled(&mut self) {
+        if !self.config.current_ledger {
+            if let Some(mut ledger) = self.current_ledger.take() {
+                ledger.flush();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Species};
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn opposite_linked_pair_records_mirrored_currents() {
+        let mut sim = Simulation::new();
+        for x in [-20.0, 20.0] {
+            let b = Body::new(Vec2::new(x, 0.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::FoilMetal);
+            sim.foils.push(Foil::new(vec![b.id], Vec2::zero(), 1.0, 1.0, 0.0, 0.0));
+            sim.bodies.push(b);
+        }
+        let (a, b) = (sim.foils[0].id, sim.foils[1].id);
+        sim.foils[0].dc_current = 1.5;
+        sim.foils[0].link_id = Some(b);
+        sim.foils[1].link_id = Some(a);
+        sim.foils[0].mode = LinkMode::Opposite;
+        sim.foils[1].mode = LinkMode::Opposite;
+        sim.config.current_ledger = true;
+
+        let mut recipients = vec![false; sim.bodies.len()];
+        sim.process_foils_with_charge_conservation(5.0, &mut recipients);
+
+        let rows = &sim.current_ledger.as_ref().unwrap().pending;
+        assert_eq!(rows.len(), 2);
+        let row = |id| rows.iter().find(|e| e.foil_id == id).unwrap();
+        assert_eq!(row(a).effective_current, 1.5);
+        assert_eq!(row(b).effective_current, -1.5);
+        assert_eq!((row(a).role, row(b).role), (LedgerRole::LinkMaster, LedgerRole::LinkSlave));
+        assert_eq!(row(b).link_id, Some(a));
+        assert_eq!(row(b).link_mode, Some(LinkMode::Opposite));
+        let csv = row(b).csv_row();
+        assert_eq!(csv.split(',').count(), CURRENT_LEDGER_HEADER.split(',').count());
+        assert!(csv.contains("Opposite") && csv.contains("LinkSlave"));
+    }
+}
@@ -0,0 +1,215 @@
+// simulation/current_residual.rs
+//
+// Commanded vs. measured foil current for controller tuning. Each foil's
+// commanded current (`Foil::commanded_current`) and the electrons it actually
+// moved are integrated over a configurable window; one sample per window is
+// kept so the residual series shows how faithfully the charge-conserving
+// transfer tracks the command. Currents are in electrons/fs, + = added.
+//
+// CSV schema:
+//   foil_id,time_fs,commanded,measured,residual   (residual = measured - commanded)
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::Path;
+
+/// Samples kept per foil before the oldest are dropped.
+pub const CURRENT_RESIDUAL_CAPACITY: usize = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResidualSample {
+    /// Simulation time at the end of the window (fs)
+    pub time_fs: f32,
+    /// Mean commanded current over the window
+    pub commanded: f32,
+    /// Electrons moved over the window divided by its length
+    pub measured: f32,
+}
+
+impl ResidualSample {
+    pub fn residual(&self) -> f32 {
+        self.measured - self.commanded
+    }
+}
+
+/// Residual series and the open integration window for one foil.
+#[derive(Clone, Debug, Default)]
+pub struct FoilCurrentResidual {
+    pub foil_id: u64,
+    pub samples: VecDeque<ResidualSample>,
+    window_start_fs: Option<f32>,
+    commanded_charge: f32,
+    electrons: i32,
+    /// Running count, mean and sum of squared deviations (Welford) of the
+    /// residual over every sample since the series was created
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl FoilCurrentResidual {
+    /// Add one step and close the window once it spans `window_fs`.
+    pub fn record_step(&mut self, time_fs: f32, dt: f32, commanded: f32, moved: i32, window_fs: f32) {
+        let start = *self.window_start_fs.get_or_insert(time_fs - dt);
+        self.commanded_charge += commanded * dt;
+        self.electrons += moved;
+        let span = time_fs - start;
+        if span >= window_fs.max(dt) && span > 0.0 {
+            if self.samples.len() >= CURRENT_RESIDUAL_CAPACITY {
+                self.samples.pop_front();
+            }
+            let sample = ResidualSample {
+                time_fs,
+                commanded: self.commanded_charge / span,
+                measured: self.electrons as f32 / span,
+            };
+            let r = sample.residual() as f64;
+            self.count += 1;
+            let delta = r - self.mean;
+            self.mean += delta / self.count as f64;
+            self.m2 += delta * (r - self.mean);
+            self.samples.push_back(sample);
+            self.window_start_fs = Some(time_fs);
+            self.commanded_charge = 0.0;
+            self.electrons = 0;
+        }
+    }
+
+    /// `(rms, mean_bias)` of `measured - commanded` over every sample
+    /// recorded; zero before the first window closes.
+    pub fn stats(&self) -> (f32, f32) {
+        if self.count == 0 {
+            return (0.0, 0.0);
+        }
+        let mean_sq = self.mean * self.mean + self.m2 / self.count as f64;
+        (mean_sq.sqrt() as f32, self.mean as f32)
+    }
+}
+
+/// Per-foil summary published for the Diagnostics tab.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CurrentResidualSummary {
+    pub foil_id: u64,
+    pub rms: f32,
+    pub mean_bias: f32,
+    pub samples: usize,
+}
+
+impl super::Simulation {
+    /// Snapshot per-foil electron counters before foil processing, or `None`
+    /// when residual tracking is off.
+    pub(crate) fn current_residual_begin(&self) -> Option<Vec<i32>> {
+        self.config.current_residual_tracking.then(|| {
+            self.foils
+                .iter()
+                .map(|f| f.electron_delta_since_measure)
+                .collect()
+        })
+    }
+
+    /// Record each foil's commanded current and the electrons it moved since
+    /// `current_residual_begin`, then publish the per-foil summaries.
+    pub(crate) fn current_residual_finish(&mut self, before: Option<Vec<i32>>, time: f32) {
+        let Some(before) = before else {
+            return;
+        };
+        if before.len() != self.foils.len() {
+            return;
+        }
+        let window_fs = self.config.current_residual_window_fs;
+        for (i, &start) in before.iter().enumerate() {
+            let foil = &self.foils[i];
+            let moved = foil.electron_delta_since_measure - start;
+            let commanded = foil.commanded_current(time);
+            let foil_id = foil.id;
+            let entry = match self.current_residuals.iter().position(|r| r.foil_id == foil_id) {
+                Some(pos) => &mut self.current_residuals[pos],
+                None => {
+                    self.current_residuals.push(FoilCurrentResidual {
+                        foil_id,
+                        ..Default::default()
+                    });
+                    self.current_residuals.last_mut().unwrap()
+                }
+            };
+            entry.record_step(time, self.dt, commanded, moved, window_fs);
+        }
+        let foils = &self.foils;
+        self.current_residuals
+            .retain(|r| foils.iter().any(|f| f.id == r.foil_id));
+        *crate::renderer::state::CURRENT_RESIDUAL_SUMMARY.lock() = self
+            .current_residuals
+            .iter()
+            .map(|r| {
+                let (rms, mean_bias) = r.stats();
+                CurrentResidualSummary {
+                    foil_id: r.foil_id,
+                    rms,
+                    mean_bias,
+                    samples: r.samples.len(),
+                }
+            })
+            .collect();
+    }
+
+    /// Write every foil's residual series as CSV, returning the row count.
+    pub fn export_current_residual(&self, path: &Path) -> std::io::Result<usize> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        writeln!(file, "foil_id,time_fs,commanded,measured,residual")?;
+        let mut rows = 0;
+        for series in &self.current_residuals {
+            for s in &series.samples {
+                writeln!(
+                    file,
+                    "{},{:.3},{:.6},{:.6},{:.6}",
+                    series.foil_id,
+                    s.time_fs,
+                    s.commanded,
+                    s.measured,
+                    s.residual()
+                )?;
+                rows += 1;
+            }
+        }
+        file.flush()?;
+        Ok(rows)
+    }
+
+    /// Drop all recorded residual samples.
+    pub fn clear_current_residual(&mut self) {
+        self.current_residuals.clear();
+        crate::renderer::state::CURRENT_RESIDUAL_SUMMARY.lock().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn residual_rms_and_bias_on_synthetic_series() {
+        // Measured overshoots by +1 then undershoots by -3: bias -1, RMS sqrt(5)
+        let mut series = FoilCurrentResidual::default();
+        assert_eq!(series.stats(), (0.0, 0.0));
+        series.record_step(1.0, 1.0, 2.0, 3, 1.0);
+        series.record_step(2.0, 1.0, 2.0, -1, 1.0);
+        let (rms, bias) = series.stats();
+        assert!((bias + 1.0).abs() < 1e-6);
+        assert!((rms - 5.0f32.sqrt()).abs() < 1e-6);
+
+        // A steady 0.5 e/fs command delivered as one electron every other
+        // 1 fs step tracks exactly once the window spans both steps
+        let mut series = FoilCurrentResidual::default();
+        for step in 1..=8 {
+            let moved = if step % 2 == 0 { 1 } else { 0 };
+            series.record_step(step as f32, 1.0, 0.5, moved, 2.0);
+        }
+        assert_eq!(series.samples.len(), 4);
+        assert!(series.samples.iter().all(|s| (s.commanded - 0.5).abs() < 1e-6));
+        let (rms, bias) = series.stats();
+        assert!(rms < 1e-6 && bias.abs() < 1e-6);
+    }
+}
@@ -0,0 +1,96 @@
+// simulation/domain_growth.rs
+//
+// Automatic domain growth for open-ended deposition. When any body comes
+// within `auto_grow_margin` of a wall, that axis is widened by
+// `auto_grow_increment` on both sides instead of the body being reflected.
+// Positions are left untouched, so existing geometry is preserved.
+
+/// New half extent for an axis whose outermost body sits at `max_abs_coord`,
+/// or `None` when it is still clear of the margin or already at `max_half`.
+pub fn grown_half_extent(
+    half: f32,
+    max_abs_coord: f32,
+    margin: f32,
+    increment: f32,
+    max_half: f32,
+) -> Option<f32> {
+    if increment <= 0.0 || half >= max_half || max_abs_coord < half - margin {
+        return None;
+    }
+    Some((half + increment).min(max_half))
+}
+
+impl super::Simulation {
+    /// Widen the domain on any axis where a body is near the wall. Returns
+    /// whether the domain changed.
+    pub fn grow_domain_if_needed(&mut self) -> bool {
+        if !self.config.auto_grow_domain || self.bodies.is_empty() {
+            return false;
+        }
+        let (mut max_x, mut max_y) = (0.0f32, 0.0f32);
+        for body in &self.bodies {
+            max_x = max_x.max(body.pos.x.abs() + body.radius);
+            max_y = max_y.max(body.pos.y.abs() + body.radius);
+        }
+        let margin = self.config.auto_grow_margin;
+        let increment = self.config.auto_grow_increment;
+        let max_half = 0.5 * self.config.auto_grow_max_size;
+        let new_w = grown_half_extent(self.domain_width, max_x, margin, increment, max_half);
+        let new_h = grown_half_extent(self.domain_height, max_y, margin, increment, max_half);
+        if new_w.is_none() && new_h.is_none() {
+            return false;
+        }
+        self.domain_width = new_w.unwrap_or(self.domain_width);
+        self.domain_height = new_h.unwrap_or(self.domain_height);
+        self.cell_list
+            .update_domain_size(self.domain_width, self.domain_height);
+        *crate::renderer::state::DOMAIN_WIDTH.lock() = self.domain_width * 2.0;
+        *crate::renderer::state::DOMAIN_HEIGHT.lock() = self.domain_height * 2.0;
+        crate::sim_log!(
+            Physics,
+            Info,
+            "Domain grown to {:.1} x {:.1} Å at {:.1} fs",
+            self.domain_width * 2.0,
+            self.domain_height * 2.0,
+            self.time
+        );
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::{Body, Species};
+    use crate::simulation::Simulation;
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn body_driven_to_the_edge_grows_the_domain() {
+        assert_eq!(grown_half_extent(100.0, 50.0, 5.0, 20.0, 500.0), None);
+        assert_eq!(grown_half_extent(100.0, 96.0, 5.0, 20.0, 500.0), Some(120.0));
+        assert_eq!(grown_half_extent(490.0, 489.0, 5.0, 20.0, 500.0), Some(500.0));
+        assert_eq!(grown_half_extent(500.0, 499.0, 5.0, 20.0, 500.0), None);
+
+        let mut sim = Simulation::new();
+        sim.config.auto_grow_domain = true;
+        sim.config.auto_grow_margin = 5.0;
+        sim.config.auto_grow_increment = 20.0;
+        let (w0, h0) = (sim.domain_width, sim.domain_height);
+        // Already inside the margin and still heading for the wall
+        sim.bodies.push(Body::new(
+            Vec2::new(w0 - 3.0, 0.0),
+            Vec2::new(5.0, 0.0),
+            1.0,
+            1.0,
+            0.0,
+            Species::EC,
+        ));
+
+        sim.step();
+        assert!(sim.domain_width > w0, "domain should widen near the wall");
+        assert_eq!(sim.domain_width, w0 + 20.0);
+        assert_eq!(sim.domain_height, h0, "y axis untouched");
+        assert!(sim.bodies[0].pos.x <= sim.domain_width);
+    }
+}
@@ -231,24 +231,32 @@ impl EisState {
         let total = config.frequencies.len();
         let est_time = config.estimated_total_fs();
         let mode = config.mode;
-        eprintln!(
+        crate::sim_log!(
+            Diagnostics,
+            Info,
             "[EIS] Started sweep ({:?}): {} frequencies from {:.2e} to {:.2e} (1/fs)",
             mode,
             total,
             config.frequencies.first().unwrap_or(&0.0),
             config.frequencies.last().unwrap_or(&0.0),
         );
-        eprintln!(
+        crate::sim_log!(
+            Diagnostics,
+            Info,
             "[EIS] Estimated total time: {:.0} fs ({:.1} ps), amplitude={:.4e}",
             est_time,
             est_time / 1000.0,
             config.amplitude,
         );
-        eprintln!(
+        crate::sim_log!(
+            Diagnostics,
+            Info,
             "[EIS] Group A (+ perturbation): {:?}, Group B (- perturbation): {:?}",
             group_a_ids, group_b_ids,
         );
-        eprintln!(
+        crate::sim_log!(
+            Diagnostics,
+            Info,
             "[EIS] Voltage probes: {} per group (A={}, B={})",
             config.voltage_probes,
             probe_a_ids.len(),
@@ -381,7 +389,9 @@ impl EisState {
         // Periodic logging (every 10000 steps)
         self.log_counter += 1;
         if self.log_counter % 10000 == 1 {
-            eprintln!(
+            crate::sim_log!(
+                Diagnostics,
+                Info,
                 "[EIS] freq {}/{} ({:.2e} 1/fs) phase={:?} elapsed={:.0}fs/{:.0}fs cycles={:.2} samples={} V={:.4e} I={:.4e}",
                 self.current_freq_idx + 1,
                 self.config.frequencies.len(),
@@ -496,7 +506,9 @@ impl EisState {
                     self.vcap_cos_acc = 0.0;
                     self.vcap_sq_acc = 0.0;
                     self.vcap_sum_acc = 0.0;
-                    eprintln!(
+                    crate::sim_log!(
+                        Diagnostics,
+                        Info,
                         "[EIS] Freq {}/{} ({:.2e}): settling complete, now recording",
                         self.current_freq_idx + 1,
                         self.config.frequencies.len(),
@@ -697,7 +709,9 @@ impl EisState {
         };
 
         let freq = self.config.frequencies[self.current_freq_idx];
-        eprintln!(
+        crate::sim_log!(
+            Diagnostics,
+            Info,
             "[EIS] Completed freq {}/{}: {:.2e} 1/fs -> Z = ({:.4e}, {:.4e}), |Z|={:.4e}, phase={:.1} deg, {} samples",
             self.current_freq_idx + 1,
             self.config.frequencies.len(),
@@ -708,7 +722,9 @@ impl EisState {
             phase_deg,
             self.sample_count,
         );
-        eprintln!(
+        crate::sim_log!(
+            Diagnostics,
+            Info,
             "[EIS]   Z_actual = ({:.4e}, {:.4e}), Z_cap = ({:.4e}, {:.4e})",
             z_actual_real, z_actual_imag, z_cap_real, z_cap_imag,
         );
@@ -805,7 +821,9 @@ impl EisState {
         let freq = self.repeat_buffer[0].frequency;
 
         if n > 1 {
-            eprintln!(
+            crate::sim_log!(
+                Diagnostics,
+                Info,
                 "[EIS] Averaged {} repeats for freq {:.2e}: Z = ({:.4e}, {:.4e})",
                 n, freq, z_real, z_imag,
             );
@@ -848,7 +866,7 @@ impl EisState {
         self.current_freq_idx += 1;
         if self.current_freq_idx >= self.config.frequencies.len() {
             self.finished = true;
-            eprintln!("[EIS] Sweep complete! {} points collected", self.results.len());
+            crate::sim_log!(Diagnostics, Info, "[EIS] Sweep complete! {} points collected", self.results.len());
             let mut shared = EIS_RESULTS.lock();
             shared.is_running = false;
             return true;
@@ -876,7 +894,7 @@ impl EisState {
         let dir_str = std::env::var("EIS_TS_DIR").unwrap_or_else(|_| "eis_timeseries".to_string());
         let dir = std::path::Path::new(&dir_str);
         if let Err(e) = std::fs::create_dir_all(dir) {
-            eprintln!("[EIS] Could not create {}: {}", dir.display(), e);
+            crate::sim_log!(Diagnostics, Error, "[EIS] Could not create {}: {}", dir.display(), e);
             return;
         }
         let filename = format!("eis_ts_{:03}_{:.3e}.csv", freq_idx + 1, freq);
@@ -948,11 +966,13 @@ impl EisState {
             ));
         }
         match std::fs::write(&path, &csv) {
-            Ok(_) => eprintln!(
+            Ok(_) => crate::sim_log!(
+                Diagnostics,
+                Info,
                 "[EIS] Saved time-series: {} ({} pts, v_dc={:.3e}, i_dc={:.3e})",
                 path.display(), n, v_mean, i_mean
             ),
-            Err(e) => eprintln!("[EIS] Failed to save {}: {}", path.display(), e),
+            Err(e) => crate::sim_log!(Diagnostics, Error, "[EIS] Failed to save {}: {}", path.display(), e),
         }
     }
 }
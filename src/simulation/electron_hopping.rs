@@ -10,6 +10,13 @@ use rayon::prelude::*;
 use ultraviolet::Vec2;
 use std::sync::atomic::{AtomicU64, Ordering};
 
+/// Positions of one successful hop, published for the renderer's hop pulses.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HopEvent {
+    pub donor: Vec2,
+    pub acceptor: Vec2,
+}
+
 // Debug counters for electrode hopping diagnostics
 static DEBUG_FRAME_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -179,14 +186,14 @@ impl Simulation {
                     ))
                     .count();
                 if electrode_neighbor_count == 0 {
-                    eprintln!("[HOPPING] Foil idx={} has {} electrons (excess {}) but NO electrode neighbors within hop_radius={:.2}",
+                    crate::sim_log!(Charging, Debug, "[HOPPING] Foil idx={} has {} electrons (excess {}) but NO electrode neighbors within hop_radius={:.2}",
                         src_idx, src_body.electrons.len(), src_diff, hop_radius);
                     // Check what neighbors it does have
                     let neighbor_species: Vec<_> = all_neighbors.iter()
                         .take(5)
                         .map(|&idx| format!("{:?}", self.bodies[idx].species))
                         .collect();
-                    eprintln!("[HOPPING]   Found {} neighbors, first few: {:?}", all_neighbors.len(), neighbor_species);
+                    crate::sim_log!(Charging, Debug, "[HOPPING]   Found {} neighbors, first few: {:?}", all_neighbors.len(), neighbor_species);
                 }
             }
             
@@ -195,6 +202,7 @@ impl Simulation {
                 .filter(|&dst_idx| dst_idx != src_idx && !received_electron[dst_idx])
                 .filter(|&dst_idx| {
                     let dst_body = &self.bodies[dst_idx];
+                    let foil_max_electrons = self.foil_max_electrons(dst_body.id);
                     let dst_diff =
                         dst_body.electrons.len() as i32 - dst_body.neutral_electron_count() as i32;
                     // Allow hop if donor is more excess than recipient
@@ -202,7 +210,7 @@ impl Simulation {
                         let kept = match dst_body.species {
                             // Standard species that can receive electrons
                             Species::LithiumMetal | Species::FoilMetal | Species::LithiumIon => {
-                                can_transfer_electron(src_body, dst_body)
+                                can_transfer_electron(src_body, dst_body, foil_max_electrons)
                             }
                             // Intercalation electrode materials can receive electrons
                             Species::Graphite
@@ -212,7 +220,9 @@ impl Simulation {
                             | Species::LFP
                             | Species::LMFP
                             | Species::NMC
-                            | Species::NCA => can_transfer_electron(src_body, dst_body),
+                            | Species::NCA => {
+                                can_transfer_electron(src_body, dst_body, foil_max_electrons)
+                            }
                             // Solvents (EC/DMC/VC/FEC/EMC), anions, SEI, solid electrolytes
                             // are not eligible electron acceptors via this hop path.
                             _ => false,
@@ -440,25 +450,36 @@ impl Simulation {
         
         // Debug summary
         if debug_this_frame {
-            eprintln!("[HOPPING] Frame {} Summary:", frame);
-            eprintln!("  Foils with excess electrons: {}", foil_with_excess);
-            eprintln!("  Foils with deficit (need electrons): {}", foil_with_deficit);
-            eprintln!("  Electrode particles with electrons: {}", electrode_with_electrons);
-            eprintln!("  Electrode neighbors found: {}", electrode_neighbors_found);
-            eprintln!("  Electrode hops attempted: {}", electrode_hops_attempted);
-            eprintln!("  Electrode->Foil attempted: {}", electrode_to_foil_attempted);
-            eprintln!("  Electrode->Foil succeeded: {}", electrode_to_foil_succeeded);
-            eprintln!("  Failed - alignment: {}", electrode_hops_failed_alignment);
-            eprintln!("  Failed - d_phi <= 0: {}", electrode_hops_failed_dphi);
-            eprintln!("  Failed - rate <= 0: {}", electrode_hops_failed_rate);
-            eprintln!("  Failed - probability: {}", electrode_hops_failed_prob);
-            eprintln!("  Succeeded: {}", electrode_hops_succeeded);
-            eprintln!("  Total hops this frame: {}", hops.len());
+            crate::sim_log!(Charging, Debug, "[HOPPING] Frame {} Summary:", frame);
+            crate::sim_log!(Charging, Debug, "  Foils with excess electrons: {}", foil_with_excess);
+            crate::sim_log!(Charging, Debug, "  Foils with deficit (need electrons): {}", foil_with_deficit);
+            crate::sim_log!(Charging, Debug, "  Electrode particles with electrons: {}", electrode_with_electrons);
+            crate::sim_log!(Charging, Debug, "  Electrode neighbors found: {}", electrode_neighbors_found);
+            crate::sim_log!(Charging, Debug, "  Electrode hops attempted: {}", electrode_hops_attempted);
+            crate::sim_log!(Charging, Debug, "  Electrode->Foil attempted: {}", electrode_to_foil_attempted);
+            crate::sim_log!(Charging, Debug, "  Electrode->Foil succeeded: {}", electrode_to_foil_succeeded);
+            crate::sim_log!(Charging, Debug, "  Failed - alignment: {}", electrode_hops_failed_alignment);
+            crate::sim_log!(Charging, Debug, "  Failed - d_phi <= 0: {}", electrode_hops_failed_dphi);
+            crate::sim_log!(Charging, Debug, "  Failed - rate <= 0: {}", electrode_hops_failed_rate);
+            crate::sim_log!(Charging, Debug, "  Failed - probability: {}", electrode_hops_failed_prob);
+            crate::sim_log!(Charging, Debug, "  Succeeded: {}", electrode_hops_succeeded);
+            crate::sim_log!(Charging, Debug, "  Total hops this frame: {}", hops.len());
         }
 
         // Track reduction hops (Li⁺ receiving electron) so we can snap
         // the newly-formed metal toward the donor after apply_redox.
-        let mut reduction_snaps: Vec<(usize, Vec2, f32)> = vec![];
+        let mut reduction_snaps: Vec<(usize, Vec2, f32, f32)> = vec![];
+
+        if !hops.is_empty()
+            && crate::renderer::state::HOP_PULSES_ENABLED.load(Ordering::Relaxed)
+        {
+            let mut events = crate::renderer::state::HOP_EVENTS.lock();
+            let room = crate::renderer::state::HOP_EVENT_CAPACITY.saturating_sub(events.len());
+            events.extend(hops.iter().take(room).map(|&(src_idx, dst_idx)| HopEvent {
+                donor: self.bodies[src_idx].pos,
+                acceptor: self.bodies[dst_idx].pos,
+            }));
+        }
 
         for (src_idx, dst_idx) in hops {
             if self.bodies[dst_idx].species == Species::LithiumIon {
@@ -466,6 +487,7 @@ impl Simulation {
                     dst_idx,
                     self.bodies[src_idx].pos,
                     self.bodies[src_idx].radius,
+                    self.bodies[src_idx].collision_radius(),
                 ));
             }
             if let Some(electron) = self.bodies[src_idx].electrons.pop() {
@@ -494,7 +516,7 @@ impl Simulation {
         // then apply position/velocity changes (mutable borrow).
         let hop_radius_factor = self.config.hop_radius_factor;
         let mut snap_actions: Vec<(usize, Vec2, Vec2, Vec<usize>)> = Vec::new();
-        for (dst_idx, donor_pos, donor_radius) in &reduction_snaps {
+        for (dst_idx, donor_pos, donor_radius, donor_contact) in &reduction_snaps {
             let dst_idx = *dst_idx;
             if self.bodies[dst_idx].species != Species::LithiumMetal {
                 continue;
@@ -505,7 +527,7 @@ impl Simulation {
             if dist < 1e-6 {
                 continue;
             }
-            let touching = *donor_radius + self.bodies[dst_idx].radius;
+            let touching = *donor_contact + self.bodies[dst_idx].collision_radius();
             if dist > touching {
                 let new_pos = *donor_pos + dir.normalized() * touching;
                 let lost_momentum = self.bodies[dst_idx].vel * self.bodies[dst_idx].mass;
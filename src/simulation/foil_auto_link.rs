@@ -0,0 +1,121 @@
+// simulation/foil_auto_link.rs
+// Pair freshly created foils opposite with their nearest free counterpart
+
+use super::simulation::Simulation;
+use crate::body::foil::LinkMode;
+use ultraviolet::Vec2;
+
+impl Simulation {
+    /// Mean position of a foil's bodies, `None` when none of them exist.
+    fn foil_centroid(&self, foil_id: u64) -> Option<Vec2> {
+        let foil = self.foils.iter().find(|f| f.id == foil_id)?;
+        let (sum, n) = self
+            .bodies
+            .iter()
+            .filter(|b| foil.body_ids.contains(&b.id))
+            .fold((Vec2::zero(), 0usize), |(s, n), b| (s + b.pos, n + 1));
+        (n > 0).then(|| sum / n as f32)
+    }
+
+    /// Whether `foil_id` may still be paired: not linked and not in a group.
+    fn is_free_for_auto_link(&self, foil_id: u64) -> bool {
+        !self.group_a.contains(&foil_id)
+            && !self.group_b.contains(&foil_id)
+            && self
+                .foils
+                .iter()
+                .any(|f| f.id == foil_id && f.link_id.is_none())
+    }
+
+    /// Opposite-link each of `new_ids` to the nearest free foil by centroid
+    /// distance when `auto_link_opposite_foils` is on.
+    ///
+    /// Candidates must carry the opposite DC polarity; a zero current counts
+    /// as unset and matches either sign. Linked or grouped foils are left
+    /// alone, so every foil ends up in at most one pair. Returns the pairs
+    /// created.
+    pub fn auto_link_new_foils(&mut self, new_ids: &[u64]) -> Vec<(u64, u64)> {
+        if !self.config.auto_link_opposite_foils {
+            return Vec::new();
+        }
+        let mut pairs = Vec::new();
+        for &id in new_ids {
+            if !self.is_free_for_auto_link(id) {
+                continue;
+            }
+            let (Some(centroid), Some(current)) = (
+                self.foil_centroid(id),
+                self.foils.iter().find(|f| f.id == id).map(|f| f.dc_current),
+            ) else {
+                continue;
+            };
+            let partner = self
+                .foils
+                .iter()
+                .filter(|f| f.id != id && f.dc_current * current <= 0.0)
+                .filter(|f| self.is_free_for_auto_link(f.id))
+                .filter_map(|f| {
+                    self.foil_centroid(f.id)
+                        .map(|c| (f.id, (c - centroid).mag_sq()))
+                })
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|(partner, _)| partner);
+            let Some(partner) = partner else {
+                continue;
+            };
+            for (foil_id, link_id) in [(id, partner), (partner, id)] {
+                if let Some(foil) = self.foils.iter_mut().find(|f| f.id == foil_id) {
+                    foil.link_id = Some(link_id);
+                    foil.mode = LinkMode::Opposite;
+                }
+            }
+            crate::sim_log!(Charging, Info, "Auto-linked foil {} opposite to foil {}", id, partner);
+            pairs.push((id, partner));
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Species};
+
+    fn push_foil(sim: &mut Simulation, x: f32, current: f32) -> u64 {
+        let body = Body::new(Vec2::new(x, 0.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::FoilMetal);
+        let foil = Foil::new(vec![body.id], Vec2::zero(), 1.0, 1.0, current, 0.0);
+        let id = foil.id;
+        sim.bodies.push(body);
+        sim.foils.push(foil);
+        id
+    }
+
+    #[test]
+    fn new_foils_pair_opposite_with_nearest_free_counterpart() {
+        let mut sim = Simulation::new();
+        sim.bodies.clear();
+        sim.foils.clear();
+        // An existing linked pair close by must be left alone
+        let kept_a = push_foil(&mut sim, 5.0, -1.0);
+        let kept_b = push_foil(&mut sim, 6.0, 1.0);
+        sim.foils[0].link_id = Some(kept_b);
+        sim.foils[1].link_id = Some(kept_a);
+        // Same polarity as the anode, so never its partner
+        let same_sign = push_foil(&mut sim, 1.0, 2.0);
+
+        assert!(sim.auto_link_new_foils(&[same_sign]).is_empty(), "off by default");
+        sim.config.auto_link_opposite_foils = true;
+        let anode = push_foil(&mut sim, 0.0, 1.0);
+        let cathode = push_foil(&mut sim, 40.0, -1.0);
+        let pairs = sim.auto_link_new_foils(&[anode, cathode]);
+
+        assert_eq!(pairs, vec![(anode, cathode)]);
+        let foil = |id| sim.foils.iter().find(|f| f.id == id).unwrap();
+        assert_eq!(foil(anode).link_id, Some(cathode));
+        assert_eq!(foil(cathode).link_id, Some(anode));
+        assert_eq!((foil(anode).mode, foil(cathode).mode), (LinkMode::Opposite, LinkMode::Opposite));
+        assert_eq!(foil(kept_a).link_id, Some(kept_b));
+        assert_eq!(foil(same_sign).link_id, None);
+    }
+}
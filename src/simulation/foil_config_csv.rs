@@ -0,0 +1,188 @@
+// simulation/foil_config_csv.rs
+//
+// Per-foil charging configuration as a small CSV, for reapplying a set of
+// setpoints to the same scenario without a full state save.
+//
+// CSV schema:
+//   foil_id,charging_mode,dc_current,ac_current,switch_hz,ac_waveform,
+//   target_ratio,kp,ki,kd,max_current
+// The controller columns are empty for current-mode foils.
+
+use super::Simulation;
+use crate::body::foil::{AcWaveform, ChargingMode, Foil};
+use std::path::Path;
+
+const CSV_HEADER: &str =
+    "foil_id,charging_mode,dc_current,ac_current,switch_hz,ac_waveform,target_ratio,kp,ki,kd,max_current";
+
+fn parse_mode(s: &str) -> Option<ChargingMode> {
+    match s {
+        "Current" => Some(ChargingMode::Current),
+        "Overpotential" => Some(ChargingMode::Overpotential),
+        _ => None,
+    }
+}
+
+fn parse_waveform(s: &str) -> Option<AcWaveform> {
+    match s {
+        "Square" => Some(AcWaveform::Square),
+        "Sine" => Some(AcWaveform::Sine),
+        "Triangle" => Some(AcWaveform::Triangle),
+        _ => None,
+    }
+}
+
+fn foil_row(foil: &Foil) -> String {
+    let controller = match &foil.overpotential_controller {
+        Some(c) => format!("{},{},{},{},{}", c.target_ratio, c.kp, c.ki, c.kd, c.max_current),
+        None => ",,,,".to_string(),
+    };
+    format!(
+        "{},{:?},{},{},{},{:?},{}",
+        foil.id,
+        foil.charging_mode,
+        foil.dc_current,
+        foil.ac_current,
+        foil.switch_hz,
+        foil.ac_waveform,
+        controller
+    )
+}
+
+/// Apply one CSV row to `foil`.
+fn apply_row(foil: &mut Foil, fields: &[&str]) -> Result<(), String> {
+    let num = |i: usize| -> Result<f32, String> {
+        fields[i]
+            .trim()
+            .parse::<f32>()
+            .map_err(|e| format!("column {}: {}", i + 1, e))
+    };
+    let mode = parse_mode(fields[1].trim()).ok_or_else(|| format!("unknown mode '{}'", fields[1]))?;
+    let waveform =
+        parse_waveform(fields[5].trim()).ok_or_else(|| format!("unknown waveform '{}'", fields[5]))?;
+    let (dc, ac, hz) = (num(2)?, num(3)?, num(4)?);
+    match mode {
+        ChargingMode::Current => foil.disable_overpotential_mode(),
+        ChargingMode::Overpotential => {
+            let (target, kp, ki, kd, max_current) = (num(6)?, num(7)?, num(8)?, num(9)?, num(10)?);
+            if foil.overpotential_controller.is_none() {
+                foil.enable_overpotential_mode(target);
+            }
+            foil.charging_mode = ChargingMode::Overpotential;
+            if let Some(c) = foil.overpotential_controller.as_mut() {
+                c.target_ratio = target;
+                c.kp = kp;
+                c.ki = ki;
+                c.kd = kd;
+                c.max_current = max_current;
+            }
+        }
+    }
+    foil.dc_current = dc;
+    foil.ac_current = ac;
+    foil.switch_hz = hz;
+    foil.ac_waveform = waveform;
+    Ok(())
+}
+
+impl Simulation {
+    /// Write every foil's charging configuration. Returns the number of foils.
+    pub fn export_foil_config(&self, path: &Path) -> std::io::Result<usize> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut out = String::from(CSV_HEADER);
+        out.push('\n');
+        for foil in &self.foils {
+            out.push_str(&foil_row(foil));
+            out.push('\n');
+        }
+        std::fs::write(path, out)?;
+        Ok(self.foils.len())
+    }
+
+    /// Apply a CSV written by `export_foil_config`, matching rows by foil id.
+    /// Rows for foils that do not exist (or fail to parse) are skipped with a
+    /// warning. Returns `(applied, skipped)`.
+    pub fn import_foil_config(&mut self, path: &Path) -> std::io::Result<(usize, usize)> {
+        let text = std::fs::read_to_string(path)?;
+        let (mut applied, mut skipped) = (0, 0);
+        for (line_no, line) in text.lines().enumerate().skip(1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').collect();
+            let id = match (fields.len(), fields[0].trim().parse::<u64>()) {
+                (11, Ok(id)) => id,
+                _ => {
+                    crate::sim_log!(Io, Warn, "Foil config line {}: malformed row skipped", line_no + 1);
+                    skipped += 1;
+                    continue;
+                }
+            };
+            let Some(foil) = self.foils.iter_mut().find(|f| f.id == id) else {
+                crate::sim_log!(Io, Warn, "Foil config: foil {} not in simulation, skipped", id);
+                skipped += 1;
+                continue;
+            };
+            match apply_row(foil, &fields) {
+                Ok(()) => applied += 1,
+                Err(e) => {
+                    crate::sim_log!(Io, Warn, "Foil config: foil {} skipped ({})", id, e);
+                    skipped += 1;
+                }
+            }
+        }
+        if applied > 0 {
+            self.mark_history_dirty();
+        }
+        Ok((applied, skipped))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn foil_charging_config_round_trips() {
+        let mut sim = Simulation::new();
+        let mut a = Foil::new(vec![], Vec2::zero(), 1.0, 1.0, 2.5, 0.0);
+        a.ac_current = 1.25;
+        a.switch_hz = 0.5;
+        a.ac_waveform = AcWaveform::Sine;
+        let mut b = Foil::new(vec![], Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+        b.enable_overpotential_mode(1.2);
+        if let Some(c) = b.overpotential_controller.as_mut() {
+            c.kp = 7.5;
+            c.ki = 0.125;
+            c.kd = 0.0;
+            c.max_current = 80.0;
+        }
+        sim.foils.push(a);
+        sim.foils.push(b);
+        let snapshot: Vec<String> = sim.foils.iter().map(foil_row).collect();
+
+        let path = std::env::temp_dir().join(format!("foil_config_{}.csv", std::process::id()));
+        assert_eq!(sim.export_foil_config(&path).unwrap(), 2);
+
+        // Scramble both foils, then restore from the CSV plus a stale row
+        for foil in &mut sim.foils {
+            foil.disable_overpotential_mode();
+            foil.dc_current = -9.0;
+            foil.ac_current = 0.0;
+            foil.switch_hz = 0.0;
+            foil.ac_waveform = AcWaveform::Triangle;
+        }
+        let mut text = std::fs::read_to_string(&path).unwrap();
+        text.push_str("999999,Current,1,0,0,Square,,,,,\n");
+        std::fs::write(&path, text).unwrap();
+
+        assert_eq!(sim.import_foil_config(&path).unwrap(), (2, 1));
+        let _ = std::fs::remove_file(&path);
+        let restored: Vec<String> = sim.foils.iter().map(foil_row).collect();
+        assert_eq!(restored, snapshot);
+        assert_eq!(sim.foils[1].charging_mode, ChargingMode::Overpotential);
+    }
+}
@@ -0,0 +1,98 @@
+// simulation/foil_current_log.rs
+//
+// Narrow per-foil current CSV. One row per recording interval with the
+// commanded current of every foil, independent of the wide foil-metrics CSV
+// and its field toggles.
+//
+// CSV schema:
+//   time_fs,current_f<ID>,current_f<ID>,...   (foil IDs ascending)
+
+use super::csv_stream::CsvStream;
+use crate::body::foil::Foil;
+use std::path::Path;
+
+/// Per-run foil current CSV writer state.
+pub struct FoilCurrentLogger {
+    stream: CsvStream,
+    /// Foil IDs captured when the file was opened; fixes the column order.
+    foil_ids: Vec<u64>,
+}
+
+/// Header line for the given foils, columns ordered by foil ID.
+pub fn foil_current_header(foil_ids: &[u64]) -> String {
+    let mut header = String::from("time_fs");
+    for id in foil_ids {
+        header.push_str(&format!(",current_f{}", id));
+    }
+    header
+}
+
+/// Data line for `time_fs`. Foils that no longer exist leave a blank cell so
+/// the column count always matches the header.
+pub fn foil_current_row(time_fs: f32, foil_ids: &[u64], foils: &[Foil]) -> String {
+    let mut line = format!("{:.3}", time_fs);
+    for id in foil_ids {
+        match foils.iter().find(|f| f.id == *id) {
+            Some(foil) => line.push_str(&format!(",{:.6}", foil.commanded_current(time_fs))),
+            None => line.push(','),
+        }
+    }
+    line
+}
+
+impl FoilCurrentLogger {
+    /// Open the log file at `path`, creating directories as needed and
+    /// writing a header covering every foil in `foils`.
+    pub fn open(path: &Path, foils: &[Foil]) -> std::io::Result<Self> {
+        let mut foil_ids: Vec<u64> = foils.iter().map(|f| f.id).collect();
+        foil_ids.sort_unstable();
+        let stream = CsvStream::create(path, &foil_current_header(&foil_ids), "foil_current_log")?;
+        Ok(Self { stream, foil_ids })
+    }
+
+    /// Append one row with the commanded current of each foil at `time_fs`.
+    pub fn write_row(&mut self, time_fs: f32, foils: &[Foil]) {
+        self.stream.append(&foil_current_row(time_fs, &self.foil_ids, foils));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn header_lists_all_foils_and_rows_match_column_count() {
+        let a = Foil::new(Vec::new(), Vec2::zero(), 1.0, 1.0, 2.0, 0.0);
+        let mut b = Foil::new(Vec::new(), Vec2::zero(), 1.0, 1.0, -1.5, 0.0);
+        b.ac_current = 0.5;
+        b.switch_hz = 0.001;
+        let foils = vec![b.clone(), a.clone()];
+
+        let dir = std::env::temp_dir().join("particlesim_foil_current_log_test");
+        let path = dir.join("foil_current.csv");
+        let _ = std::fs::remove_file(&path);
+
+        let mut logger = FoilCurrentLogger::open(&path, &foils).unwrap();
+        logger.write_row(0.0, &foils);
+        // Removing a foil must not change the row width
+        logger.write_row(100.0, &foils[..1]);
+        drop(logger);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+
+        let (lo, hi) = (a.id.min(b.id), a.id.max(b.id));
+        assert_eq!(lines[0], format!("time_fs,current_f{},current_f{}", lo, hi));
+        for line in &lines[1..] {
+            assert_eq!(line.split(',').count(), 3);
+        }
+
+        let first: Vec<f32> = lines[1].split(',').map(|c| c.parse().unwrap()).collect();
+        let col = |id: u64| if id == lo { 1 } else { 2 };
+        assert!((first[col(a.id)] - 2.0).abs() < 1e-5);
+        assert!((first[col(b.id)] - (-1.5 + 0.5)).abs() < 1e-5);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,121 @@
+// foil_detect.rs
+// Group loose FoilMetal particles into foils
+
+use super::simulation::Simulation;
+use crate::body::foil::Foil;
+use crate::body::Species;
+use std::collections::{HashSet, VecDeque};
+use ultraviolet::Vec2;
+
+impl Simulation {
+    /// Cluster FoilMetal bodies that are not yet part of a foil into new foils.
+    ///
+    /// Two bodies are connected when their centers are closer than
+    /// `connection_radius`; each connected cluster becomes one current-mode
+    /// foil with zero current and `body_to_foil` entries for its members.
+    /// Bodies already owned by a foil are neither reassigned nor used to
+    /// bridge clusters.
+    ///
+    /// Returns the ids of the created foils.
+    pub fn detect_foils(&mut self, connection_radius: f32) -> Vec<u64> {
+        if connection_radius <= 0.0 {
+            return Vec::new();
+        }
+        let assigned: HashSet<u64> = self
+            .foils
+            .iter()
+            .flat_map(|f| f.body_ids.iter().copied())
+            .chain(self.body_to_foil.keys().copied())
+            .collect();
+        let candidate = |i: usize, bodies: &[crate::body::Body]| {
+            bodies[i].species == Species::FoilMetal && !assigned.contains(&bodies[i].id)
+        };
+
+        self.cell_list.rebuild(&self.bodies);
+        let mut visited = vec![false; self.bodies.len()];
+        let mut created = Vec::new();
+        for start in 0..self.bodies.len() {
+            if visited[start] || !candidate(start, &self.bodies) {
+                continue;
+            }
+            visited[start] = true;
+            let mut members = Vec::new();
+            let mut queue = VecDeque::from([start]);
+            while let Some(i) = queue.pop_front() {
+                members.push(i);
+                for j in self
+                    .cell_list
+                    .find_neighbors_within(&self.bodies, i, connection_radius)
+                {
+                    if !visited[j] && candidate(j, &self.bodies) {
+                        visited[j] = true;
+                        queue.push_back(j);
+                    }
+                }
+            }
+
+            let centroid = members
+                .iter()
+                .fold(Vec2::zero(), |acc, &i| acc + self.bodies[i].pos)
+                / members.len() as f32;
+            let body_ids: Vec<u64> = members.iter().map(|&i| self.bodies[i].id).collect();
+            let foil = Foil::new(body_ids.clone(), centroid, 1.0, 1.0, 0.0, 0.0);
+            for id in body_ids {
+                self.body_to_foil.insert(id, foil.id);
+            }
+            created.push(foil.id);
+            self.foils.push(foil);
+        }
+        created
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::Body;
+
+    fn push(sim: &mut Simulation, x: f32, y: f32, species: Species) -> u64 {
+        let body = Body::new(Vec2::new(x, y), Vec2::zero(), 1.0, 1.0, 0.0, species);
+        let id = body.id;
+        sim.bodies.push(body);
+        id
+    }
+
+    #[test]
+    fn separated_clusters_become_distinct_foils() {
+        let mut sim = Simulation::new();
+        let left: Vec<u64> = (0..3)
+            .map(|i| push(&mut sim, -50.0, 2.0 * i as f32, Species::FoilMetal))
+            .collect();
+        let right: Vec<u64> = (0..2)
+            .map(|i| push(&mut sim, 50.0 + 2.0 * i as f32, 0.0, Species::FoilMetal))
+            .collect();
+        // Not metal, and an already-owned foil body bridging nothing
+        push(&mut sim, -50.0, 6.0, Species::LithiumIon);
+        let owned = push(&mut sim, 50.0, 2.0, Species::FoilMetal);
+        let existing = Foil::new(vec![owned], Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+        sim.body_to_foil.insert(owned, existing.id);
+        sim.foils.push(existing);
+
+        let created = sim.detect_foils(2.5);
+        assert_eq!(created.len(), 2);
+        assert_eq!(sim.foils.len(), 3);
+
+        let members = |body: u64| {
+            let foil_id = sim.body_to_foil[&body];
+            let mut ids = sim.foils.iter().find(|f| f.id == foil_id).unwrap().body_ids.clone();
+            ids.sort_unstable();
+            (foil_id, ids)
+        };
+        let (left_foil, left_members) = members(left[0]);
+        let (right_foil, right_members) = members(right[0]);
+        assert_ne!(left_foil, right_foil);
+        assert_eq!(left_members, left);
+        assert_eq!(right_members, right);
+        assert!(created.contains(&left_foil) && created.contains(&right_foil));
+
+        // Running again finds nothing new
+        assert!(sim.detect_foils(2.5).is_empty());
+    }
+}
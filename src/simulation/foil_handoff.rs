@@ -0,0 +1,158 @@
+// simulation/foil_handoff.rs
+//
+// Current-to-overpotential handoff. A foil in current mode with a
+// `HandoffRule` is checked at the start of foil processing; once its
+// attached Li metal count or electron ratio crosses the threshold it is put
+// into overpotential mode at the rule's target and the rule is consumed, so
+// the hold persists even if the condition later un-crosses. A linked partner
+// is handed off with it, like `SimCommand::EnableOverpotentialMode` does, so
+// it doesn't keep driving constant current against the hold.
+
+use crate::body::foil::{ChargingMode, HandoffCondition, LinkMode};
+
+/// Frames between handoff checks. Both conditions walk the foil's connected
+/// cluster, which is too costly to repeat for every armed foil every step.
+pub(crate) const HANDOFF_CHECK_INTERVAL: usize = 10;
+
+impl super::Simulation {
+    /// Hand off every current-mode foil whose rule condition is met, along
+    /// with its linked partner. Returns the ids of the foils that switched.
+    pub(crate) fn apply_foil_handoffs(&mut self) -> Vec<u64> {
+        let mut switched = Vec::new();
+        for i in 0..self.foils.len() {
+            let foil = &self.foils[i];
+            let Some(rule) = foil.handoff else {
+                continue;
+            };
+            if foil.charging_mode != ChargingMode::Current {
+                continue;
+            }
+            let met = match rule.condition {
+                HandoffCondition::MetalCountAtLeast(n) => self.li_metal_count_for_foil(foil) >= n,
+                HandoffCondition::RatioAtLeast(r) => self.calculate_foil_electron_ratio(foil) >= r,
+                HandoffCondition::RatioAtMost(r) => self.calculate_foil_electron_ratio(foil) <= r,
+            };
+            if !met {
+                continue;
+            }
+            let foil = &mut self.foils[i];
+            foil.handoff = None;
+            foil.enable_overpotential_mode(rule.target_ratio);
+            let link = foil.link_id.map(|link_id| (link_id, foil.mode));
+            crate::sim_log!(
+                Charging,
+                Info,
+                "Foil {} handed off to overpotential hold at {:.3} ({:?} met at {:.1} fs)",
+                foil.id,
+                rule.target_ratio,
+                rule.condition,
+                self.time
+            );
+            switched.push(foil.id);
+
+            let Some((link_id, mode)) = link else {
+                continue;
+            };
+            let Some(partner) = self
+                .foils
+                .iter_mut()
+                .find(|f| f.id == link_id && f.charging_mode == ChargingMode::Current)
+            else {
+                continue;
+            };
+            let target = match mode {
+                LinkMode::Parallel => rule.target_ratio,
+                LinkMode::Opposite => 2.0 - rule.target_ratio,
+            };
+            partner.handoff = None;
+            partner.enable_overpotential_mode(target);
+            crate::sim_log!(
+                Charging,
+                Info,
+                "Linked foil {} handed off to overpotential hold at {:.3}",
+                partner.id,
+                target
+            );
+            switched.push(partner.id);
+        }
+        switched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::foil::{ChargingMode, Foil, HandoffCondition, HandoffRule, LinkMode};
+    use crate::body::{Body, Species};
+    use crate::simulation::Simulation;
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn current_foil_switches_to_overpotential_when_deposit_is_reached() {
+        let mut sim = Simulation::new();
+        // Foil body with one touching Li metal deposit
+        for (i, species) in [Species::FoilMetal, Species::LithiumMetal].iter().enumerate() {
+            let pos = Vec2::new(i as f32 * 2.0 * species.radius(), 0.0);
+            let (mass, radius) = (species.mass(), species.radius());
+            sim.bodies.push(Body::new(pos, Vec2::zero(), mass, radius, 0.0, *species));
+        }
+        let mut foil = Foil::new(vec![sim.bodies[0].id], Vec2::zero(), 1.0, 1.0, 0.5, 0.0);
+        foil.handoff = Some(HandoffRule {
+            condition: HandoffCondition::MetalCountAtLeast(2),
+            target_ratio: 1.05,
+        });
+        sim.foils.push(foil);
+        sim.quadtree.build(&mut sim.bodies);
+
+        // One attached body is short of the threshold
+        assert!(sim.apply_foil_handoffs().is_empty());
+        assert_eq!(sim.foils[0].charging_mode, ChargingMode::Current);
+
+        let pos = Vec2::new(4.0 * Species::LithiumMetal.radius(), 0.0);
+        let li = Species::LithiumMetal;
+        sim.bodies.push(Body::new(pos, Vec2::zero(), li.mass(), li.radius(), 0.0, li));
+        sim.quadtree.build(&mut sim.bodies);
+        assert_eq!(sim.apply_foil_handoffs(), vec![sim.foils[0].id]);
+
+        // The hold persists: the rule is consumed and the target is kept
+        let mut recipients = vec![false; sim.bodies.len()];
+        sim.process_foils_with_charge_conservation(0.0, &mut recipients);
+        let foil = &sim.foils[0];
+        assert_eq!(foil.charging_mode, ChargingMode::Overpotential);
+        assert_eq!(foil.handoff, None);
+        let target = foil.overpotential_controller.as_ref().unwrap().target_ratio;
+        assert_eq!(target, 1.05);
+    }
+
+    #[test]
+    fn linked_partner_is_handed_off_with_mirrored_target() {
+        let mut sim = Simulation::new();
+        let metal = Species::FoilMetal;
+        let mut foils: Vec<Foil> = [-10.0, 10.0]
+            .into_iter()
+            .map(|x| {
+                let (mass, radius) = (metal.mass(), metal.radius());
+                let body = Body::new(Vec2::new(x, 0.0), Vec2::zero(), mass, radius, 0.0, metal);
+                let foil = Foil::new(vec![body.id], Vec2::zero(), 1.0, 1.0, 0.5, 0.0);
+                sim.bodies.push(body);
+                foil
+            })
+            .collect();
+        let (a_id, b_id) = (foils[0].id, foils[1].id);
+        foils[0].link_id = Some(b_id);
+        foils[0].mode = LinkMode::Opposite;
+        foils[1].link_id = Some(a_id);
+        foils[1].mode = LinkMode::Opposite;
+        foils[0].handoff = Some(HandoffRule {
+            condition: HandoffCondition::MetalCountAtLeast(0),
+            target_ratio: 1.1,
+        });
+        sim.foils = foils;
+        sim.quadtree.build(&mut sim.bodies);
+
+        assert_eq!(sim.apply_foil_handoffs(), vec![a_id, b_id]);
+        let target = |foil: &Foil| foil.overpotential_controller.as_ref().unwrap().target_ratio;
+        assert_eq!(sim.foils[1].charging_mode, ChargingMode::Overpotential);
+        assert_eq!(target(&sim.foils[0]), 1.1);
+        assert!((target(&sim.foils[1]) - 0.9).abs() < 1e-6);
+    }
+}
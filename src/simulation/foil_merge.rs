@@ -0,0 +1,214 @@
+// foil_merge.rs
+// Combine several foils into a single foil
+
+use super::simulation::Simulation;
+use std::collections::HashSet;
+
+impl Simulation {
+    /// Merge the foils in `ids` into the lowest-id foil among them.
+    ///
+    /// Body membership and `body_to_foil` move to the surviving foil. Its
+    /// charging mode and controller are kept, while DC/AC currents and the
+    /// fractional-electron accumulators are summed so the merged electrode
+    /// drives the same total current. Links, A/B groups, switch-charging
+    /// roles and EIS groups that pointed at a merged-away foil are repointed
+    /// to the survivor (or dropped where that would create a self-link).
+    ///
+    /// Returns the surviving foil id, or `None` if fewer than two of the
+    /// given ids exist.
+    pub fn merge_foils(&mut self, ids: &[u64]) -> Option<u64> {
+        let existing: HashSet<u64> = ids
+            .iter()
+            .copied()
+            .filter(|id| self.foils.iter().any(|f| f.id == *id))
+            .collect();
+        if existing.len() < 2 {
+            return None;
+        }
+        let target = *existing.iter().min()?;
+        let merged: HashSet<u64> = existing.iter().copied().filter(|&id| id != target).collect();
+
+        // Pull the merged-away foils out, remembering the first external link
+        let mut moved_bodies = Vec::new();
+        let mut dc_current = 0.0;
+        let mut ac_current = 0.0;
+        let mut accum = 0.0;
+        let mut electron_delta = 0;
+        let mut inherited_link = None;
+        self.foils.retain(|f| {
+            if !merged.contains(&f.id) {
+                return true;
+            }
+            moved_bodies.extend(f.body_ids.iter().copied());
+            dc_current += f.dc_current;
+            ac_current += f.ac_current;
+            accum += f.accum;
+            electron_delta += f.electron_delta_since_measure;
+            if inherited_link.is_none() {
+                inherited_link = f
+                    .link_id
+                    .filter(|l| !existing.contains(l))
+                    .map(|l| (l, f.mode));
+            }
+            false
+        });
+
+        for body_id in &moved_bodies {
+            self.body_to_foil.insert(*body_id, target);
+        }
+
+        let foil = self.foils.iter_mut().find(|f| f.id == target)?;
+        for body_id in moved_bodies {
+            if !foil.body_ids.contains(&body_id) {
+                foil.body_ids.push(body_id);
+            }
+        }
+        foil.dc_current += dc_current;
+        foil.ac_current += ac_current;
+        foil.accum += accum;
+        foil.electron_delta_since_measure += electron_delta;
+        if foil.link_id.is_some_and(|l| merged.contains(&l)) {
+            foil.link_id = None;
+        }
+        if foil.link_id.is_none() {
+            if let Some((link, mode)) = inherited_link {
+                foil.link_id = Some(link);
+                foil.mode = mode;
+            }
+        }
+        let target_link = foil.link_id;
+
+        // Repoint links held by other foils
+        for f in self.foils.iter_mut().filter(|f| f.id != target) {
+            if let Some(l) = f.link_id {
+                if merged.contains(&l) {
+                    f.link_id = Some(target);
+                }
+                // Keep links symmetric: a foil may only point at us if we point back
+                if f.link_id == Some(target) && target_link != Some(f.id) {
+                    f.link_id = None;
+                }
+            }
+        }
+
+        // Group membership: the survivor inherits A/B if it had none
+        let in_a = self.group_a.iter().any(|id| existing.contains(id));
+        let in_b = self.group_b.iter().any(|id| existing.contains(id));
+        self.group_a.retain(|id| !merged.contains(id));
+        self.group_b.retain(|id| !merged.contains(id));
+        if !self.group_a.contains(&target) && !self.group_b.contains(&target) {
+            if in_a {
+                self.group_a.insert(target);
+            } else if in_b {
+                self.group_b.insert(target);
+            }
+        }
+
+        // Switch-charging role assignments
+        for foils in self.switch_config.role_to_foil.values_mut() {
+            for id in foils.iter_mut() {
+                if merged.contains(id) {
+                    *id = target;
+                }
+            }
+            let mut seen = HashSet::new();
+            foils.retain(|id| seen.insert(*id));
+        }
+        self.switch_saved_states.retain(|id, _| !merged.contains(id));
+
+        // Running EIS sweep groups
+        if let Some(eis) = self.eis_state.as_mut() {
+            for group in [&mut eis.group_a_ids, &mut eis.group_b_ids] {
+                for id in group.iter_mut() {
+                    if merged.contains(id) {
+                        *id = target;
+                    }
+                }
+                let mut seen = HashSet::new();
+                group.retain(|id| seen.insert(*id));
+            }
+        }
+
+        Some(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::foil::{Foil, LinkMode};
+    use crate::body::{Body, Species};
+    use ultraviolet::Vec2;
+
+    fn add_foil(sim: &mut Simulation, x: f32, current: f32) -> u64 {
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let b = Body::new(
+                Vec2::new(x, 2.0 * i as f32),
+                Vec2::zero(),
+                1.0,
+                1.0,
+                0.0,
+                Species::FoilMetal,
+            );
+            ids.push(b.id);
+            sim.bodies.push(b);
+        }
+        let foil = Foil::new(ids.clone(), Vec2::zero(), 1.0, 1.0, current, 0.0);
+        for id in ids {
+            sim.body_to_foil.insert(id, foil.id);
+        }
+        let id = foil.id;
+        sim.foils.push(foil);
+        id
+    }
+
+    #[test]
+    fn merge_combines_membership_and_repoints_references() {
+        let mut sim = Simulation::new();
+        let a = add_foil(&mut sim, 0.0, 1.0);
+        let b = add_foil(&mut sim, 2.0, 2.0);
+        let c = add_foil(&mut sim, 4.0, 3.0);
+        let counter = add_foil(&mut sim, 40.0, -1.0);
+        let other = add_foil(&mut sim, 60.0, 0.0);
+
+        // c is linked to the counter electrode; a and b are linked to each other
+        for (x, y) in [(c, counter), (counter, c), (a, b), (b, a)] {
+            let f = sim.foils.iter_mut().find(|f| f.id == x).unwrap();
+            f.link_id = Some(y);
+            f.mode = LinkMode::Opposite;
+        }
+        sim.group_a.insert(b);
+        sim.group_b.insert(counter);
+
+        assert_eq!(sim.merge_foils(&[c, b, a]), Some(a));
+
+        assert_eq!(sim.foils.len(), 3);
+        let merged = sim.foils.iter().find(|f| f.id == a).unwrap();
+        assert_eq!(merged.body_ids.len(), 9);
+        assert!((merged.dc_current - 6.0).abs() < 1e-6);
+        assert!(merged
+            .body_ids
+            .iter()
+            .all(|id| sim.body_to_foil.get(id) == Some(&a)));
+
+        // Internal link dropped, external link inherited and repointed
+        assert_eq!(merged.link_id, Some(counter));
+        let counter_foil = sim.foils.iter().find(|f| f.id == counter).unwrap();
+        assert_eq!(counter_foil.link_id, Some(a));
+        assert_eq!(sim.foils.iter().find(|f| f.id == other).unwrap().link_id, None);
+
+        // Group membership moved to the survivor
+        assert!(sim.group_a.contains(&a));
+        assert!(!sim.group_a.contains(&b));
+        assert!(sim.group_b.contains(&counter));
+    }
+
+    #[test]
+    fn merge_needs_two_existing_foils() {
+        let mut sim = Simulation::new();
+        let a = add_foil(&mut sim, 0.0, 1.0);
+        assert_eq!(sim.merge_foils(&[a, 9999]), None);
+        assert_eq!(sim.foils.len(), 1);
+    }
+}
@@ -3,9 +3,11 @@
 //! Provides routines for computing electric (Coulomb) forces and Lennard-Jones (LJ) forces between bodies.
 //! Used by the main simulation loop to update accelerations and fields.
 
+use crate::body::Body;
 use crate::config;
 use crate::profile_scope;
 use crate::simulation::Simulation;
+use ultraviolet::Vec2;
 
 /// Build all spatial structures needed by the force phase once per step.
 ///
@@ -24,6 +26,13 @@ pub fn prepare_spatial_structures(sim: &mut Simulation) {
     }
 }
 
+/// Pairwise force cap for the soft-start window, or `None` once it has elapsed.
+#[inline]
+fn soft_start_cap(sim: &Simulation) -> Option<f32> {
+    let cap = sim.config.soft_start_force_cap;
+    (sim.frame < sim.config.soft_start_frames && cap > 0.0).then_some(cap)
+}
+
 /// Compute electric field and force on all bodies using the quadtree.
 ///
 /// - Computes the electric field at each body due to all others.
@@ -49,6 +58,11 @@ pub fn attract(sim: &mut Simulation) {
 /// relative to the molecular center. The nucleus experiences the field at the
 /// body position while the electron feels the field at its displaced position.
 /// The force difference creates an effective dipole interaction.
+///
+/// With `config.solvent_dipole_strength > 0`, nearby Li+ and anions also exert
+/// an orientational torque on the bound electron (the negative end of the
+/// dipole), turning it toward cations and away from anions. The torque scales
+/// with the species' `polar_charge` and the ion's Coulomb field.
 pub fn apply_polar_forces(sim: &mut Simulation) {
     use crate::body::Species;
     profile_scope!("forces_polar");
@@ -72,6 +86,9 @@ pub fn apply_polar_forces(sim: &mut Simulation) {
         }
 
         let e_pos = sim.bodies[i].pos + sim.bodies[i].electrons[0].rel_pos;
+        let rel = sim.bodies[i].electrons[0].rel_pos;
+        let dipole_dir = (rel.mag_sq() > 1e-12).then(|| rel.normalized());
+        let mut orient_acc = ultraviolet::Vec2::zero();
         let cutoff = 3.0 * sim.bodies[i].radius;
         let neighbors = if use_cell {
             sim.cell_list.find_neighbors_within(&sim.bodies, i, cutoff)
@@ -119,6 +136,25 @@ pub fn apply_polar_forces(sim: &mut Simulation) {
                 0.0
             };
 
+            // Orientational torque from ions: rotate the electron toward
+            // cations / away from anions without changing its offset length
+            if sim.config.solvent_dipole_strength > 0.0
+                && matches!(sim.bodies[j].species, Species::LithiumIon | Species::ElectrolyteAnion)
+            {
+                if let Some(dir) = dipole_dir {
+                    let to_ion = j_pos - i_nuc_pos;
+                    let r = to_ion.mag().max(i_nuc_rad + j_rad);
+                    let target = to_ion.normalized() * j_q.signum();
+                    let tangential = target - dir * target.dot(dir);
+                    orient_acc += tangential
+                        * (sim.config.solvent_dipole_strength
+                            * sim.bodies[i].species.polar_charge()
+                            * k_e
+                            * j_q.abs()
+                            / (r * r));
+                }
+            }
+
             // Field at nucleus/electron depends on selected dipole model
             let (field_nucleus, field_electron) = match sim.config.dipole_model {
                 crate::config::DipoleModel::SingleOffset => {
@@ -171,6 +207,11 @@ pub fn apply_polar_forces(sim: &mut Simulation) {
                 b.acc -= force / b.mass;
             }
         }
+
+        if orient_acc != ultraviolet::Vec2::zero() {
+            let dt = sim.dt;
+            sim.bodies[i].electrons[0].vel += orient_acc * dt;
+        }
     }
 }
 
@@ -184,6 +225,7 @@ pub fn apply_lj_forces(sim: &mut Simulation) {
     let max_cutoff = crate::species::max_lj_cutoff();
     // Spatial structures are already built by prepare_spatial_structures().
     let use_cell = sim.use_cell_list();
+    let soft_cap = soft_start_cap(sim);
 
     for i in 0..sim.bodies.len() {
         if !sim.bodies[i].species.lj_enabled() {
@@ -208,28 +250,36 @@ pub fn apply_lj_forces(sim: &mut Simulation) {
                 let (left, right) = sim.bodies.split_at_mut(j);
                 (&mut left[i], &mut right[0])
             };
-            // Use per-species LJ parameters only
-            let sigma = (a.species.lj_sigma() + b.species.lj_sigma()) * 0.5;
-            let epsilon = (a.species.lj_epsilon() * b.species.lj_epsilon()).sqrt();
-            let cutoff = 0.5
-                * (a.species.lj_cutoff() * a.species.lj_sigma()
-                    + b.species.lj_cutoff() * b.species.lj_sigma());
-            let r_vec = b.pos - a.pos;
-            let r = r_vec.mag();
-            if r < cutoff && r > 1e-6 {
-                let sr6 = (sigma / r).powi(6);
-                let max_lj_force = config::COLLISION_PASSES as f32 * config::LJ_FORCE_MAX;
-                let unclamped_force_mag = 24.0 * epsilon * (2.0 * sr6 * sr6 - sr6) / r;
-                let force_mag = unclamped_force_mag.clamp(-max_lj_force, max_lj_force);
-                let force = force_mag * r_vec.normalized();
-
-                a.acc -= force / a.mass;
-                b.acc += force / b.mass;
+            let mut max_lj_force = config::COLLISION_PASSES as f32 * config::LJ_FORCE_MAX;
+            if let Some(cap) = soft_cap {
+                max_lj_force = max_lj_force.min(cap);
             }
+            let force = lj_pair_force(a, b, max_lj_force);
+            a.acc -= force / a.mass;
+            b.acc += force / b.mass;
         }
     }
 }
 
+/// LJ force on `b` due to `a` using per-species parameters, clamped to
+/// `max_force` in magnitude. Zero outside the pair cutoff.
+pub fn lj_pair_force(a: &Body, b: &Body, max_force: f32) -> Vec2 {
+    let sigma = (a.species.lj_sigma() + b.species.lj_sigma()) * 0.5;
+    let epsilon = (a.species.lj_epsilon() * b.species.lj_epsilon()).sqrt();
+    let cutoff = 0.5
+        * (a.species.lj_cutoff() * a.species.lj_sigma()
+            + b.species.lj_cutoff() * b.species.lj_sigma());
+    let r_vec = b.pos - a.pos;
+    let r = r_vec.mag();
+    if r >= cutoff || r <= 1e-6 {
+        return Vec2::zero();
+    }
+    let sr6 = (sigma / r).powi(6);
+    let unclamped_force_mag = 24.0 * epsilon * (2.0 * sr6 * sr6 - sr6) / r;
+    let force_mag = unclamped_force_mag.clamp(-max_force, max_force);
+    force_mag * r_vec.normalized()
+}
+
 /// Compute soft-core repulsive force between two bodies.
 pub fn compute_repulsive_force(
     p1: &crate::body::Body,
@@ -255,6 +305,7 @@ pub fn apply_repulsive_forces(sim: &mut Simulation) {
     }
     // Spatial structures are already built by prepare_spatial_structures().
     let use_cell = sim.use_cell_list();
+    let soft_cap = soft_start_cap(sim);
 
     for i in 0..sim.bodies.len() {
         if !sim.bodies[i].species.repulsion_enabled() {
@@ -275,7 +326,13 @@ pub fn apply_repulsive_forces(sim: &mut Simulation) {
             }
             let r_vec = sim.bodies[j].pos - sim.bodies[i].pos;
             let r = r_vec.mag();
-            let f = compute_repulsive_force(&sim.bodies[i], &sim.bodies[j], r_vec, r);
+            let mut f = compute_repulsive_force(&sim.bodies[i], &sim.bodies[j], r_vec, r);
+            if let Some(cap) = soft_cap {
+                let mag = f.mag();
+                if mag > cap {
+                    f *= cap / mag;
+                }
+            }
             if f != ultraviolet::Vec2::zero() {
                 let (a, b) = {
                     let (left, right) = sim.bodies.split_at_mut(j);
@@ -319,3 +376,186 @@ pub fn apply_stack_pressure(sim: &mut Simulation) {
         }
     }
 }
+
+/// Apply the uniform body force (gravity-like) from `config.body_force`.
+/// The force on each particle is its species mass times the configured
+/// acceleration, or the mass excess over the mean particle mass when
+/// `body_force_buoyant` is set.
+pub fn apply_body_force(sim: &mut Simulation) {
+    profile_scope!("forces_body_force");
+
+    let g = sim.config.body_force;
+    let gz = if sim.config.enable_out_of_plane {
+        sim.config.body_force_z
+    } else {
+        0.0
+    };
+    if g == Vec2::zero() && gz == 0.0 {
+        return;
+    }
+
+    let reference_mass = if sim.config.body_force_buoyant && !sim.bodies.is_empty() {
+        sim.bodies.iter().map(|b| b.species.mass()).sum::<f32>() / sim.bodies.len() as f32
+    } else {
+        0.0
+    };
+
+    for body in &mut sim.bodies {
+        let effective_mass = body.species.mass() - reference_mass;
+        body.acc += g * (effective_mass / body.mass);
+        body.az += gz * (effective_mass / body.mass);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::{Body, Species};
+    use ultraviolet::Vec2;
+
+    fn overlapping_metal_pair() -> Simulation {
+        let mut sim = Simulation::new();
+        let r = Species::LithiumMetal.radius();
+        for x in [-0.2 * r, 0.2 * r] {
+            sim.bodies.push(Body::new(
+                Vec2::new(x, 0.0),
+                Vec2::zero(),
+                Species::LithiumMetal.mass(),
+                r,
+                0.0,
+                Species::LithiumMetal,
+            ));
+        }
+        sim
+    }
+
+    #[test]
+    fn soft_start_caps_lj_acceleration_in_early_frames() {
+        let cap = 5.0;
+        let mut sim = overlapping_metal_pair();
+        sim.config.soft_start_frames = 10;
+        sim.config.soft_start_force_cap = cap;
+        prepare_spatial_structures(&mut sim);
+        apply_lj_forces(&mut sim);
+        for body in &sim.bodies {
+            assert!(body.acc.mag() > 0.0);
+            assert!(body.acc.mag() * body.mass <= cap * 1.0001);
+        }
+
+        // Once the window has passed the regular clamp applies again
+        let mut sim = overlapping_metal_pair();
+        sim.config.soft_start_frames = 10;
+        sim.config.soft_start_force_cap = cap;
+        sim.frame = 10;
+        prepare_spatial_structures(&mut sim);
+        apply_lj_forces(&mut sim);
+        assert!(sim.bodies[0].acc.mag() * sim.bodies[0].mass > cap);
+    }
+
+    #[test]
+    fn raised_lj_cutoff_widens_cell_list_neighbor_range() {
+        use crate::species::{get_species_props, update_species_props, SPECIES_PROPS_TEST_LOCK};
+
+        let _lock = SPECIES_PROPS_TEST_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let species = Species::EC;
+        let original = get_species_props(species);
+        struct Restore(Species, crate::species::SpeciesProps);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                update_species_props(self.0, self.1);
+            }
+        }
+        let _restore = Restore(species, original);
+
+        let body_at = |x: f32| {
+            Body::new(Vec2::new(x, 0.0), Vec2::zero(), species.mass(), species.radius(), 0.0, species)
+        };
+        let mut sim = Simulation::new();
+        sim.config.cell_list_density_threshold = 0.0;
+        sim.bodies.push(body_at(0.0));
+        prepare_spatial_structures(&mut sim);
+        let before = sim.cell_list.cell_size;
+        sim.bodies.push(body_at(before + 1.0));
+        let (near_id, far_id) = (sim.bodies[0].id, sim.bodies[1].id);
+        let partners = |sim: &Simulation| {
+            let i = sim.bodies.iter().position(|b| b.id == near_id).unwrap();
+            sim.cell_list
+                .find_neighbors_within(&sim.bodies, i, sim.cell_list.cell_size)
+                .into_iter()
+                .map(|j| sim.bodies[j].id)
+                .collect::<Vec<_>>()
+        };
+        prepare_spatial_structures(&mut sim);
+        assert!(sim.use_cell_list());
+        assert!(partners(&sim).is_empty());
+
+        // Raise the LJ range past the current cell size; the next prepare picks it up
+        let mut props = original;
+        props.lj_enabled = true;
+        props.lj_cutoff = (before + 2.0) / props.lj_sigma;
+        update_species_props(species, props);
+        prepare_spatial_structures(&mut sim);
+        assert!(sim.cell_list.cell_size >= before + 2.0);
+        assert_eq!(partners(&sim), vec![far_id]);
+    }
+
+    #[test]
+    fn downward_body_force_produces_downward_drift() {
+        let mut sim = Simulation::new();
+        let species = Species::LithiumIon;
+        sim.bodies.push(Body::new(
+            Vec2::zero(),
+            Vec2::zero(),
+            species.mass(),
+            species.radius(),
+            0.0,
+            species,
+        ));
+        sim.config.body_force = Vec2::new(0.0, -1.0e-3);
+        for _ in 0..20 {
+            sim.step();
+        }
+        let body = &sim.bodies[0];
+        assert!(body.vel.y < 0.0, "velocity {:?}", body.vel);
+        assert!(body.pos.y < 0.0, "position {:?}", body.pos);
+        assert!(body.pos.x.abs() < 1e-4);
+    }
+
+    #[test]
+    fn solvent_dipole_turns_toward_nearby_cation() {
+        use crate::body::Electron;
+
+        let electron_vel_after = |strength: f32| {
+            let mut sim = Simulation::new();
+            sim.config.solvent_dipole_strength = strength;
+            let r = Species::EC.radius();
+            let mut ec = Body::new(Vec2::zero(), Vec2::zero(), Species::EC.mass(), r, 0.0, Species::EC);
+            // Dipole initially perpendicular to the ion direction
+            ec.electrons.push(Electron {
+                rel_pos: Vec2::new(0.0, 0.3 * r),
+                vel: Vec2::zero(),
+            });
+            sim.bodies.push(ec);
+            let li_r = Species::LithiumIon.radius();
+            sim.bodies.push(Body::new(
+                Vec2::new(2.0 * r, 0.0),
+                Vec2::zero(),
+                Species::LithiumIon.mass(),
+                li_r,
+                1.0,
+                Species::LithiumIon,
+            ));
+            prepare_spatial_structures(&mut sim);
+            apply_polar_forces(&mut sim);
+            sim.bodies[0].electrons[0].vel
+        };
+
+        assert_eq!(electron_vel_after(0.0), Vec2::zero());
+        let vel = electron_vel_after(1.0);
+        // Torque rotates the negative end toward the cation (+x), not radially
+        assert!(vel.x > 0.0, "electron should turn toward the cation: {vel:?}");
+        assert!(vel.y.abs() < 1e-6 * vel.x.abs().max(1.0));
+    }
+}
@@ -382,6 +382,8 @@ impl Simulation {
             sim_time,
             frame,
             dt,
+            lightweight: self.config.history_mode == crate::config::HistoryMode::Lightweight,
+            lightweight_len: self.lightweight_history.len(),
         };
     }
 }
@@ -0,0 +1,83 @@
+// simulation/id_repair.rs
+//
+// Body IDs key foil membership, selection and most lookups, so two bodies
+// sharing one make `iter().find(|b| b.id == ..)` silently hit the wrong body.
+// Imports and clones can let duplicates in; `dedupe_ids` gives every repeat
+// a fresh ID and keeps foil membership consistent.
+
+use crate::body::{Body, Species};
+use std::collections::HashSet;
+
+impl super::Simulation {
+    /// Reassign fresh IDs to bodies whose ID already appeared earlier in
+    /// `bodies`; the first holder keeps it. A foil-metal duplicate of a foil
+    /// member joins that foil under its new ID, any other duplicate owns no
+    /// foil. Returns the number of bodies renumbered.
+    pub fn dedupe_ids(&mut self) -> usize {
+        let max_id = self.bodies.iter().map(|b| b.id).max().unwrap_or(0);
+        let mut seen = HashSet::with_capacity(self.bodies.len());
+        let mut renumbered = 0;
+        for body in &mut self.bodies {
+            if seen.insert(body.id) {
+                continue;
+            }
+            let old_id = body.id;
+            body.id = Body::fresh_id_above(max_id);
+            renumbered += 1;
+            if body.species != Species::FoilMetal {
+                continue;
+            }
+            if let Some(&foil_id) = self.body_to_foil.get(&old_id) {
+                self.body_to_foil.insert(body.id, foil_id);
+                if let Some(foil) = self.foils.iter_mut().find(|f| f.id == foil_id) {
+                    foil.body_ids.push(body.id);
+                }
+            }
+        }
+        if renumbered > 0 {
+            crate::sim_log!(Physics, Warn, "Reassigned {} duplicate body IDs", renumbered);
+        }
+        renumbered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Species};
+    use crate::simulation::Simulation;
+    use std::collections::HashSet;
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn duplicate_ids_are_renumbered_with_consistent_foils() {
+        let mut sim = Simulation::new();
+        let metal = Body::new(Vec2::new(-10.0, 0.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::FoilMetal);
+        let foil = Foil::new(vec![metal.id], Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+        sim.body_to_foil.insert(metal.id, foil.id);
+        let ion = Body::new(Vec2::new(10.0, 0.0), Vec2::zero(), 1.0, 1.0, 1.0, Species::LithiumIon);
+        let mut metal_copy = metal.clone();
+        metal_copy.pos.y = 5.0;
+        let mut ion_copy = ion.clone();
+        ion_copy.pos.y = 5.0;
+        sim.bodies = vec![metal.clone(), ion.clone(), metal_copy, ion_copy];
+        sim.foils.push(foil);
+
+        assert_eq!(sim.dedupe_ids(), 2);
+        let ids: HashSet<u64> = sim.bodies.iter().map(|b| b.id).collect();
+        assert_eq!(ids.len(), 4, "all IDs unique");
+        assert_eq!(sim.bodies[0].id, metal.id, "first holder keeps its ID");
+        assert_eq!(sim.bodies[1].id, ion.id);
+
+        let copy_id = sim.bodies[2].id;
+        assert_eq!(sim.body_to_foil.get(&copy_id), Some(&sim.foils[0].id));
+        assert_eq!(sim.foils[0].body_ids, vec![metal.id, copy_id]);
+        assert!(!sim.body_to_foil.contains_key(&sim.bodies[3].id));
+        for id in &sim.foils[0].body_ids {
+            assert!(sim.bodies.iter().filter(|b| b.id == *id).count() == 1);
+        }
+
+        // Already unique: nothing to do
+        assert_eq!(sim.dedupe_ids(), 0);
+    }
+}
@@ -146,7 +146,7 @@ impl Simulation {
         for idx in to_remove {
             if idx < self.bodies.len() {
                 let removed = self.bodies.remove(idx);
-                println!("⚡ Li+ intercalated into anode (was body {})", removed.id);
+                crate::sim_log!(Charging, Debug, "⚡ Li+ intercalated into anode (was body {})", removed.id);
             }
         }
     }
@@ -249,7 +249,7 @@ impl Simulation {
                     );
                     
                     spawned_bodies.push(li_ion);
-                    println!("⚡ Li+ deintercalated from cathode at ({:.1}, {:.1})", spawn_x, spawn_y);
+                    crate::sim_log!(Charging, Debug, "⚡ Li+ deintercalated from cathode at ({:.1}, {:.1})", spawn_x, spawn_y);
                 }
             }
         }
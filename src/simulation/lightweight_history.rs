@@ -0,0 +1,150 @@
+// simulation/lightweight_history.rs
+//
+// Reduced history for long charging studies. With `HistoryMode::Lightweight`
+// the step loop records foil states and a few scalars instead of cloning
+// every body, so memory stays flat regardless of particle count. The series
+// can be exported as JSON, reloaded, and handed to the GUI to play back foil
+// currents and counts over time; there is no body cloud to scrub back to.
+
+use crate::body::foil::Foil;
+use crate::body::Species;
+use crate::switch_charging::RunState;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LightweightSnapshot {
+    pub frame: usize,
+    pub sim_time: f32,
+    pub foils: Vec<Foil>,
+    /// Body count per species present at capture time
+    pub species_counts: Vec<(Species, usize)>,
+    /// Sum of all body charges
+    pub net_charge: f32,
+    /// Switch charging step at capture time; `None` unless switch charging
+    /// was running or paused
+    pub switch_step: Option<u8>,
+}
+
+impl super::Simulation {
+    pub fn lightweight_snapshot(&self) -> LightweightSnapshot {
+        let mut species_counts: Vec<(Species, usize)> = Vec::new();
+        for body in &self.bodies {
+            match species_counts.iter_mut().find(|(s, _)| *s == body.species) {
+                Some((_, n)) => *n += 1,
+                None => species_counts.push((body.species, 1)),
+            }
+        }
+        species_counts.sort_by_key(|(s, _)| *s as u8);
+        LightweightSnapshot {
+            frame: self.frame,
+            sim_time: self.time,
+            foils: self.foils.clone(),
+            species_counts,
+            net_charge: self.bodies.iter().map(|b| b.charge).sum(),
+            switch_step: (self.switch_run_state != RunState::Idle)
+                .then(|| self.switch_scheduler.current_step()),
+        }
+    }
+
+    /// Record a reduced snapshot, keeping at most `history_capacity` of them.
+    pub fn push_lightweight_snapshot(&mut self) {
+        let snapshot = self.lightweight_snapshot();
+        self.lightweight_history.push_back(snapshot);
+        while self.lightweight_history.len() > self.history_capacity {
+            self.lightweight_history.pop_front();
+        }
+        self.publish_playback_status();
+    }
+
+    /// Write the reduced history as a JSON array, returning the snapshot count.
+    pub fn export_lightweight_history(&self, path: &Path) -> Result<usize, String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let text = serde_json::to_string_pretty(&self.lightweight_history).map_err(|e| e.to_string())?;
+        std::fs::write(path, text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(self.lightweight_history.len())
+    }
+
+    /// Replace the reduced history with one written by
+    /// `export_lightweight_history`, returning the snapshot count.
+    pub fn load_lightweight_history(&mut self, path: &Path) -> Result<usize, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let snapshots: VecDeque<LightweightSnapshot> = serde_json::from_str(&text)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+        self.history_capacity = self.history_capacity.max(snapshots.len());
+        self.lightweight_history = snapshots;
+        self.publish_playback_status();
+        Ok(self.lightweight_history.len())
+    }
+
+    /// Copy the reduced history to the renderer for playback.
+    pub fn publish_lightweight_playback(&self) {
+        *crate::renderer::state::LIGHTWEIGHT_PLAYBACK.lock() =
+            self.lightweight_history.iter().cloned().collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Species};
+    use crate::config::HistoryMode;
+    use crate::simulation::Simulation;
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn lightweight_snapshots_omit_bodies_but_keep_foils() {
+        let mut sim = Simulation::new();
+        let metal = Body::new(Vec2::new(-20.0, 0.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::FoilMetal);
+        let foil = Foil::new(vec![metal.id], Vec2::zero(), 1.0, 1.0, 0.75, 0.0);
+        sim.bodies.push(metal);
+        sim.foils.push(foil);
+        for i in 0..3 {
+            sim.bodies.push(Body::new(
+                Vec2::new(10.0 * i as f32, 10.0),
+                Vec2::zero(),
+                1.0,
+                1.0,
+                1.0,
+                Species::LithiumIon,
+            ));
+        }
+        sim.config.history_mode = HistoryMode::Lightweight;
+        sim.config.lightweight_history_stride = 1;
+        let full_before = sim.simple_history.len();
+
+        for _ in 0..10 {
+            sim.step();
+        }
+
+        assert_eq!(sim.simple_history.len(), full_before, "no full-body snapshots taken");
+        assert_eq!(sim.lightweight_history.len(), 10);
+        let last = sim.lightweight_history.back().unwrap();
+        assert_eq!(last.foils.len(), 1);
+        assert_eq!(last.foils[0].id, sim.foils[0].id);
+        assert_eq!(last.foils[0].dc_current, 0.75);
+        assert!(last.species_counts.contains(&(Species::LithiumIon, 3)));
+        assert!(last.species_counts.contains(&(Species::FoilMetal, 1)));
+        assert!((last.net_charge - sim.bodies.iter().map(|b| b.charge).sum::<f32>()).abs() < 1e-4);
+
+        // Serialized snapshots carry no body list
+        let json = serde_json::to_string(last).unwrap();
+        assert!(!json.contains("\"bodies\""));
+        // Switch charging never ran, so no step is recorded
+        assert_eq!(last.switch_step, None);
+
+        // An exported history loads back for playback
+        let path = std::env::temp_dir()
+            .join(format!("particlesim_lightweight_{}.json", std::process::id()));
+        assert_eq!(sim.export_lightweight_history(&path), Ok(10));
+        let mut fresh = Simulation::new();
+        assert_eq!(fresh.load_lightweight_history(&path), Ok(10));
+        assert_eq!(fresh.lightweight_history.back().unwrap().frame, last.frame);
+        assert_eq!(fresh.lightweight_history.back().unwrap().foils[0].dc_current, 0.75);
+        let _ = std::fs::remove_file(&path);
+    }
+}
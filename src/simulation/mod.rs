@@ -2,18 +2,46 @@
 // Re-exports and module declarations for simulation submodules
 
 //pub mod core;
+pub mod capacitive;
+pub mod charge_audit;
 pub mod collision;
+pub mod compressed_history;
+pub mod convergence;
+pub mod csv_stream;
+pub mod current_ledger;
+pub mod domain_growth;
+pub mod current_residual;
 pub mod eis;
+pub mod field_log;
+pub mod electron_events;
 pub mod electron_hopping;
+pub mod foil_config_csv;
+pub mod foil_current_log;
+pub mod foil_auto_link;
+pub mod foil_detect;
+pub mod foil_handoff;
+pub mod foil_merge;
 pub mod forces;
 pub mod intercalation;
+pub mod lightweight_history;
 pub mod morphology;
 pub mod morphology_log;
+pub mod neighbor_check;
+pub mod pid_state_log;
+pub mod quadtree_tuning;
+pub mod ratio_convergence;
+pub mod reaction_rules;
+pub mod recenter;
+pub mod replay;
+pub mod reversibility;
 pub mod sei;
+pub mod species_balance;
 pub mod simulation;
+pub mod species_count_log;
 pub mod thermal;
 pub use simulation::*;
 pub mod history;
+pub mod id_repair;
 pub use history::PlaybackProgress;
 pub mod utils;
 pub use utils::compute_temperature;
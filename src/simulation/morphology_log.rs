@@ -73,7 +73,7 @@ impl MorphologyLogger {
             metrics.dead_li_fraction,
             metrics.accessible_surface_atoms,
         ) {
-            eprintln!("morphology_log: write failed: {e}");
+            crate::sim_log!(Io, Error, "morphology_log: write failed: {e}");
             return None;
         }
         let _ = self.file.flush();
@@ -0,0 +1,135 @@
+// simulation/neighbor_check.rs
+//
+// Debug self-check for the two neighbor backends. The cell list and the
+// quadtree are picked by density, so a bug in only one of them shows up only
+// in some runs. With `neighbor_self_check` on, every
+// `neighbor_self_check_interval` frames both are rebuilt and their neighbor
+// sets compared for a random sample of bodies; any difference is logged.
+
+use crate::body::Body;
+use crate::cell_list::CellList;
+use crate::quadtree::Quadtree;
+use rand::seq::IteratorRandom;
+
+/// Neighbors of one body that only one backend reported.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NeighborMismatch {
+    pub index: usize,
+    pub only_cell_list: Vec<usize>,
+    pub only_quadtree: Vec<usize>,
+}
+
+/// Compare both backends' neighbor sets within `cutoff` for each body in
+/// `sample`. Both structures must already be built over `bodies`.
+pub fn compare_neighbor_sets(
+    cell_list: &CellList,
+    quadtree: &Quadtree,
+    bodies: &[Body],
+    sample: &[usize],
+    cutoff: f32,
+) -> Vec<NeighborMismatch> {
+    let mut mismatches = Vec::new();
+    for &i in sample.iter().filter(|&&i| i < bodies.len()) {
+        let mut from_cells = cell_list.find_neighbors_within(bodies, i, cutoff);
+        let mut from_tree = quadtree.find_neighbors_within(bodies, i, cutoff);
+        from_cells.sort_unstable();
+        from_tree.sort_unstable();
+        if from_cells == from_tree {
+            continue;
+        }
+        mismatches.push(NeighborMismatch {
+            index: i,
+            only_cell_list: from_cells
+                .iter()
+                .copied()
+                .filter(|j| from_tree.binary_search(j).is_err())
+                .collect(),
+            only_quadtree: from_tree
+                .iter()
+                .copied()
+                .filter(|j| from_cells.binary_search(j).is_err())
+                .collect(),
+        });
+    }
+    mismatches
+}
+
+impl super::Simulation {
+    /// Run the backend comparison when the self-check is enabled and due.
+    pub(crate) fn neighbor_self_check_if_due(&mut self) {
+        let interval = self.config.neighbor_self_check_interval;
+        if !self.config.neighbor_self_check
+            || interval == 0
+            || self.frame % interval != 0
+            || self.bodies.is_empty()
+        {
+            return;
+        }
+        // The quadtree build partitions `bodies` in place, so index the cell
+        // list afterwards
+        self.quadtree
+            .build_with_domain(&mut self.bodies, self.domain_width, self.domain_height);
+        self.cell_list.rebuild(&self.bodies);
+
+        let count = self.config.neighbor_self_check_samples.min(self.bodies.len());
+        let sample = (0..self.bodies.len()).choose_multiple(&mut rand::rng(), count);
+        let cutoff = self.cell_list.cell_size;
+        let mismatches =
+            compare_neighbor_sets(&self.cell_list, &self.quadtree, &self.bodies, &sample, cutoff);
+        for m in &mismatches {
+            crate::sim_log!(
+                Diagnostics,
+                Warn,
+                "⚠ Neighbor backends disagree for body {} (id {}) at frame {}: \
+                 cell list only {:?}, quadtree only {:?}",
+                m.index,
+                self.bodies[m.index].id,
+                self.frame,
+                m.only_cell_list,
+                m.only_quadtree
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::Species;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn backends_agree_on_random_configuration() {
+        let (half_w, half_h) = (60.0, 40.0);
+        let mut rng = StdRng::seed_from_u64(982);
+        let mut bodies: Vec<Body> = (0..400)
+            .map(|_| {
+                let pos = Vec2::new(
+                    rng.random_range(-half_w..half_w),
+                    rng.random_range(-half_h..half_h),
+                );
+                Body::new(pos, Vec2::zero(), 1.0, 1.0, 0.0, Species::EC)
+            })
+            .collect();
+
+        let cutoff = 7.5;
+        let mut quadtree = Quadtree::new(
+            crate::config::QUADTREE_THETA,
+            crate::config::QUADTREE_EPSILON,
+            crate::config::QUADTREE_LEAF_CAPACITY,
+            crate::config::QUADTREE_THREAD_CAPACITY,
+        );
+        quadtree.build_with_domain(&mut bodies, half_w, half_h);
+        let mut cell_list = CellList::new(half_w, half_h, cutoff);
+        cell_list.rebuild(&bodies);
+
+        let all: Vec<usize> = (0..bodies.len()).collect();
+        let mismatches = compare_neighbor_sets(&cell_list, &quadtree, &bodies, &all, cutoff);
+        assert!(mismatches.is_empty(), "backends disagree: {:?}", mismatches);
+        assert!(
+            all.iter().any(|&i| !cell_list.find_neighbors_within(&bodies, i, cutoff).is_empty()),
+            "the configuration should contain neighbor pairs"
+        );
+    }
+}
@@ -81,20 +81,21 @@ pub fn apply_out_of_plane(sim: &mut Simulation) {
 
     // Safety checks to prevent crashes
     if !stiffness.is_finite() || !damping.is_finite() || !max_z.is_finite() {
-        eprintln!("[ERROR] Invalid out-of-plane parameters detected! Disabling for safety.");
+        crate::sim_log!(Physics, Error, "[ERROR] Invalid out-of-plane parameters detected! Disabling for safety.");
         return;
     }
 
     if max_z <= 0.0 {
-        eprintln!("[ERROR] max_z must be positive! Got: {}", max_z);
+        crate::sim_log!(Physics, Error, "[ERROR] max_z must be positive! Got: {}", max_z);
         return;
     }
 
     // debug log removed
     // First pass: apply basic z-forces to all particles
     sim.bodies.par_iter_mut().for_each(|body| {
-        // Only non-metal particles can move in z-direction
-        if !matches!(body.species, Species::LithiumMetal | Species::FoilMetal) {
+        // Only z-mobile species (all but metals by default) move in z
+        let mobility = body.species.z_mobility();
+        if mobility.mobile {
             // Safety check for NaN/infinite values
             if !body.z.is_finite() || !body.vz.is_finite() {
                 body.reset_z(); // Emergency reset
@@ -106,14 +107,14 @@ pub fn apply_out_of_plane(sim: &mut Simulation) {
             let z_force = calculate_local_z_force(body, max_z);
 
             // Simple z-axis force without frustration
-            body.az += z_force - damping * body.vz;
+            body.az += z_force - damping * mobility.damping_scale * body.vz;
 
             // Additional safety check after force calculation
             if !body.az.is_finite() {
                 body.az = 0.0;
             }
         } else {
-            // Metal/foil particles stay fixed at z=0
+            // Immobile species (metal/foil by default) stay fixed at z=0
             body.z = 0.0;
             body.vz = 0.0;
             body.az = 0.0;
@@ -140,7 +141,7 @@ pub fn apply_out_of_plane(sim: &mut Simulation) {
 fn enforce_metal_z_boundaries(sim: &mut Simulation, max_z: f32) {
     // Safety check
     if !max_z.is_finite() || max_z <= 0.0 {
-        eprintln!("[ERROR] Invalid max_z in boundary enforcement: {}", max_z);
+        crate::sim_log!(Physics, Error, "[ERROR] Invalid max_z in boundary enforcement: {}", max_z);
         return;
     }
     // Early out if no metals present
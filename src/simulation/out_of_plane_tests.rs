@@ -143,4 +143,37 @@ mod out_of_plane_tests {
         // (either reset or left in a safe state)
         assert!(sim.bodies.len() == 2); // Both particles should still exist
     }
+
+    #[test]
+    fn test_immobile_species_stays_planar_under_z_force() {
+        let mut sim = create_test_simulation();
+        sim.dt = 0.1; // keep the unit z-spring well inside the stable step range
+        sim.bodies[0].z = 0.0;
+        sim.bodies.push(Body::new(
+            Vec2::new(20.0, 0.0),
+            Vec2::new(0.0, 0.0),
+            1.0, // mass
+            1.0, // radius
+            0.0, // charge
+            Species::LithiumMetal,
+        ));
+        assert!(Species::LithiumIon.z_mobility().mobile);
+        assert!(!Species::LithiumMetal.z_mobility().mobile);
+
+        for _ in 0..50 {
+            for body in &mut sim.bodies {
+                body.az = 0.0;
+            }
+            apply_out_of_plane(&mut sim);
+            // External push along +z on both bodies
+            for body in &mut sim.bodies {
+                body.az += 0.5;
+            }
+            sim.iterate();
+        }
+
+        assert!(sim.bodies[0].z > 0.01, "ion z = {}", sim.bodies[0].z);
+        assert_eq!(sim.bodies[1].z, 0.0);
+        assert_eq!(sim.bodies[1].vz, 0.0);
+    }
 }
@@ -0,0 +1,119 @@
+// simulation/pid_state_log.rs
+//
+// Per-foil overpotential PID internals, one row per recording interval, for
+// spotting integral windup and oscillation that the aggregate ratio hides.
+// Only master foils (overpotential mode with their own controller) get
+// columns; the set is fixed when the file is opened.
+//
+// CSV schema (six columns per foil, grouped by foil ID ascending):
+//   time_fs,error_f<ID>,integral_f<ID>,derivative_f<ID>,output_f<ID>,target_f<ID>,actual_f<ID>,...
+// error = target - actual from the controller's last update.
+
+use super::csv_stream::CsvStream;
+use crate::body::foil::{ChargingMode, Foil};
+use std::path::Path;
+
+const PID_FIELDS: [&str; 6] = ["error", "integral", "derivative", "output", "target", "actual"];
+
+/// Per-run PID state CSV writer.
+pub struct PidStateLogger {
+    stream: CsvStream,
+    /// Master foil IDs captured when the file was opened; fixes the column order.
+    foil_ids: Vec<u64>,
+}
+
+/// IDs of foils driven by their own overpotential controller, ascending.
+pub fn pid_master_ids(foils: &[Foil]) -> Vec<u64> {
+    let mut ids: Vec<u64> = foils
+        .iter()
+        .filter(|f| {
+            f.charging_mode == ChargingMode::Overpotential && f.overpotential_controller.is_some()
+        })
+        .map(|f| f.id)
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+pub fn pid_state_header(foil_ids: &[u64]) -> String {
+    let mut header = String::from("time_fs");
+    for id in foil_ids {
+        for field in PID_FIELDS {
+            header.push_str(&format!(",{}_f{}", field, id));
+        }
+    }
+    header
+}
+
+/// Data line for `time_fs`. Foils that are gone or no longer have a
+/// controller leave their six cells blank.
+pub fn pid_state_row(time_fs: f32, foil_ids: &[u64], foils: &[Foil]) -> String {
+    let mut line = format!("{:.3}", time_fs);
+    for id in foil_ids {
+        let controller = foils
+            .iter()
+            .find(|f| f.id == *id)
+            .and_then(|f| f.overpotential_controller.as_ref());
+        match controller {
+            Some(c) => line.push_str(&format!(
+                ",{:.6},{:.6},{:.6},{:.6},{:.6},{:.6}",
+                c.previous_error,
+                c.integral_error,
+                c.last_derivative_error,
+                c.last_output_current,
+                c.target_ratio,
+                c.last_actual_ratio
+            )),
+            None => line.push_str(&",".repeat(PID_FIELDS.len())),
+        }
+    }
+    line
+}
+
+impl PidStateLogger {
+    /// Open the log at `path` with columns for every master foil in `foils`.
+    pub fn open(path: &Path, foils: &[Foil]) -> std::io::Result<Self> {
+        let foil_ids = pid_master_ids(foils);
+        let stream = CsvStream::create(path, &pid_state_header(&foil_ids), "pid_state_log")?;
+        Ok(Self { stream, foil_ids })
+    }
+
+    pub fn write_row(&mut self, time_fs: f32, foils: &[Foil]) {
+        self.stream.append(&pid_state_row(time_fs, &self.foil_ids, foils));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn logged_error_is_target_minus_actual() {
+        let mut master = Foil::new(Vec::new(), Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+        master.enable_overpotential_mode(1.2);
+        master.compute_overpotential_current(0.9, 0.5);
+        // Current-mode foils have no controller columns
+        let plain = Foil::new(Vec::new(), Vec2::zero(), 1.0, 1.0, 1.0, 0.0);
+        let foils = vec![plain, master.clone()];
+
+        let ids = pid_master_ids(&foils);
+        assert_eq!(ids, vec![master.id]);
+        let header = pid_state_header(&ids);
+        assert_eq!(header.split(',').count(), 1 + PID_FIELDS.len());
+
+        let row = pid_state_row(10.0, &ids, &foils);
+        let values: Vec<f32> = row.split(',').map(|v| v.parse().unwrap()).collect();
+        let (error, integral, derivative, target, actual) =
+            (values[1], values[2], values[3], values[5], values[6]);
+        assert!((target - 1.2).abs() < 1e-6);
+        assert!((actual - 0.9).abs() < 1e-6);
+        assert!((error - (target - actual)).abs() < 1e-5);
+        assert!((integral - error * 0.5).abs() < 1e-5);
+        assert!((derivative - error / 0.5).abs() < 1e-4);
+
+        // A foil that disappears keeps the row width
+        let row = pid_state_row(20.0, &ids, &[]);
+        assert_eq!(row.split(',').count(), 1 + PID_FIELDS.len());
+    }
+}
@@ -0,0 +1,73 @@
+// simulation/quadtree_tuning.rs
+//
+// Live tuning of the Barnes-Hut tree granularity. `quadtree_leaf_capacity`
+// and `quadtree_thread_capacity` are copied onto the tree whenever the config
+// changes; the tree is rebuilt every step, so the next build uses them. A
+// periodic visit count over a few probe bodies is published for the GUI to
+// gauge the effect.
+
+/// Traversal cost of the most recent tree, for the GUI readout.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct QuadtreeVisitStats {
+    /// Mean nodes visited per probe body in a field evaluation
+    pub mean_visits: f32,
+    pub max_visits: usize,
+    /// Nodes in use (root plus four per subdivision)
+    pub nodes: usize,
+}
+
+/// Frames between visit-count samples.
+const VISIT_STATS_INTERVAL: usize = 10;
+/// Probe bodies per sample, evenly strided through the body list.
+const VISIT_STATS_PROBES: usize = 32;
+
+impl super::Simulation {
+    /// Copy the configured leaf/thread capacities onto the quadtree. Returns
+    /// true when either changed.
+    pub fn sync_quadtree_capacities(&mut self) -> bool {
+        let leaf = self.config.quadtree_leaf_capacity.max(1);
+        let thread = self.config.quadtree_thread_capacity.max(1);
+        if leaf == self.quadtree.leaf_capacity && thread == self.quadtree.thread_capacity {
+            return false;
+        }
+        crate::sim_log!(
+            Physics,
+            Info,
+            "Quadtree capacities leaf {} -> {}, thread {} -> {} (applied at next rebuild)",
+            self.quadtree.leaf_capacity,
+            leaf,
+            self.quadtree.thread_capacity,
+            thread
+        );
+        self.quadtree.leaf_capacity = leaf;
+        self.quadtree.thread_capacity = thread;
+        true
+    }
+
+    /// Sample how many nodes a field evaluation visits for a few bodies.
+    pub fn quadtree_visit_stats(&self) -> Option<QuadtreeVisitStats> {
+        if self.bodies.is_empty() || self.quadtree.nodes.is_empty() {
+            return None;
+        }
+        let stride = (self.bodies.len() / VISIT_STATS_PROBES).max(1);
+        let (mut total, mut max, mut probes) = (0usize, 0usize, 0usize);
+        for body in self.bodies.iter().step_by(stride) {
+            let visits = self.quadtree.visit_count(body.pos, body.radius);
+            total += visits;
+            max = max.max(visits);
+            probes += 1;
+        }
+        let subdivisions = self.quadtree.atomic_len.load(std::sync::atomic::Ordering::Relaxed);
+        Some(QuadtreeVisitStats {
+            mean_visits: total as f32 / probes as f32,
+            max_visits: max,
+            nodes: subdivisions * 4 + 1,
+        })
+    }
+
+    pub(crate) fn publish_quadtree_visit_stats(&self) {
+        if self.frame % VISIT_STATS_INTERVAL == 0 {
+            *crate::renderer::state::QUADTREE_VISIT_STATS.lock() = self.quadtree_visit_stats();
+        }
+    }
+}
@@ -0,0 +1,175 @@
+// simulation/ratio_convergence.rs
+//
+// Electron-ratio approach curves for overpotential sweeps. For each target
+// ratio the foil's electron ratio is sampled every `sample_stride` steps for
+// up to `dwell_steps`, stopping early once it has settled (same sliding-window
+// spread test as the convergence monitor). All curves are written to one CSV
+// with a column per target so their convergence behaviour can be compared.
+
+use super::convergence::ConvergenceMonitor;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// How long to dwell at each target and when to call it steady.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RatioSweepConfig {
+    pub dwell_steps: usize,
+    pub sample_stride: usize,
+    pub steady_window_fs: f32,
+    /// Max spread of the ratio over the window; 0 disables early termination
+    pub steady_tolerance: f32,
+}
+
+impl Default for RatioSweepConfig {
+    fn default() -> Self {
+        Self {
+            dwell_steps: 30_000,
+            sample_stride: 20,
+            steady_window_fs: 20_000.0,
+            steady_tolerance: 0.0,
+        }
+    }
+}
+
+/// Electron ratio vs. time since the target was applied.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RatioCurve {
+    pub target: f32,
+    /// `(time_fs, ratio)`, time relative to the start of the dwell
+    pub samples: Vec<(f32, f32)>,
+    /// Relative time at which the ratio was judged steady, if it was
+    pub steady_at_fs: Option<f32>,
+}
+
+/// Collects one [`RatioCurve`]; feed it every step.
+#[derive(Clone, Debug)]
+pub struct RatioCurveRecorder {
+    config: RatioSweepConfig,
+    curve: RatioCurve,
+    monitor: ConvergenceMonitor,
+    steps: usize,
+    t0: Option<f32>,
+}
+
+impl RatioCurveRecorder {
+    pub fn new(target: f32, config: RatioSweepConfig) -> Self {
+        Self {
+            config,
+            curve: RatioCurve {
+                target,
+                ..Default::default()
+            },
+            monitor: ConvergenceMonitor::default(),
+            steps: 0,
+            t0: None,
+        }
+    }
+
+    /// Record the state after one step. Returns `true` once the dwell is over
+    /// or the ratio has reached steady state.
+    pub fn push(&mut self, time_fs: f32, ratio: f32) -> bool {
+        if self.is_done() {
+            return true;
+        }
+        self.steps += 1;
+        let t0 = *self.t0.get_or_insert(time_fs);
+        let t = time_fs - t0;
+        if self.steps % self.config.sample_stride.max(1) == 0 {
+            self.curve.samples.push((t, ratio));
+            if self.config.steady_tolerance > 0.0
+                && self
+                    .monitor
+                    .push(t, ratio, self.config.steady_window_fs, self.config.steady_tolerance)
+                    .is_some()
+            {
+                self.curve.steady_at_fs = Some(t);
+            }
+        }
+        self.is_done()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.steps >= self.config.dwell_steps || self.curve.steady_at_fs.is_some()
+    }
+
+    pub fn finish(self) -> RatioCurve {
+        self.curve
+    }
+}
+
+/// Header for [`write_ratio_curves_csv`]: `time_fs` then one column per target.
+pub fn ratio_curves_header(curves: &[RatioCurve]) -> String {
+    let mut header = String::from("time_fs");
+    for curve in curves {
+        header.push_str(&format!(",ratio_target_{:.4}", curve.target));
+    }
+    header
+}
+
+/// Write all curves side by side. Curves that stopped early leave their
+/// remaining cells empty; the time column comes from the longest curve.
+pub fn write_ratio_curves_csv<P: AsRef<Path>>(
+    curves: &[RatioCurve],
+    path: P,
+) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    writeln!(file, "{}", ratio_curves_header(curves))?;
+    let Some(longest) = curves.iter().max_by_key(|c| c.samples.len()) else {
+        return file.flush();
+    };
+    for (row, &(t, _)) in longest.samples.iter().enumerate() {
+        let mut line = format!("{:.1}", t);
+        for curve in curves {
+            line.push(',');
+            if let Some(&(_, ratio)) = curve.samples.get(row) {
+                line.push_str(&format!("{:.6}", ratio));
+            }
+        }
+        writeln!(file, "{}", line)?;
+    }
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curve_length_matches_dwell_for_single_target() {
+        let config = RatioSweepConfig {
+            dwell_steps: 1000,
+            sample_stride: 20,
+            ..Default::default()
+        };
+        let mut recorder = RatioCurveRecorder::new(1.02, config);
+        let mut steps = 0;
+        // A slowly approaching ratio that never stops moving
+        loop {
+            let ratio = 1.02 - 0.02 * (-(steps as f32) / 400.0).exp();
+            if recorder.push(steps as f32 * 5.0, ratio) {
+                break;
+            }
+            steps += 1;
+        }
+        let curve = recorder.finish();
+        assert_eq!(steps + 1, 1000);
+        assert_eq!(curve.samples.len(), 1000 / 20);
+        assert_eq!(curve.steady_at_fs, None);
+
+        // With a tolerance, a flat ratio stops well before the dwell
+        let config = RatioSweepConfig {
+            steady_window_fs: 500.0,
+            steady_tolerance: 1e-4,
+            ..config
+        };
+        let mut recorder = RatioCurveRecorder::new(1.0, config);
+        let mut steps = 0;
+        while !recorder.push(steps as f32 * 5.0, 1.0) {
+            steps += 1;
+        }
+        let curve = recorder.finish();
+        assert!(curve.steady_at_fs.is_some());
+        assert!(curve.samples.len() < 1000 / 20);
+    }
+}
@@ -0,0 +1,218 @@
+// simulation/reaction_rules.rs
+//
+// Generic pair chemistry driven by `config.reactions`. Runs once per step
+// after collisions, using the quadtree for the neighbour search. Each body
+// takes part in at most one reaction per step; foil bodies never react.
+
+use crate::body::{Body, Species};
+use crate::config::ReactionRule;
+use crate::profile_scope;
+
+/// Turn `body` into `species`, taking its mass and radius from the species.
+fn convert(body: &mut Body, species: Species) {
+    body.species = species;
+    body.mass = species.mass();
+    body.radius = species.radius();
+}
+
+impl super::Simulation {
+    /// Apply every configured reaction rule once, returning how many pairs
+    /// reacted.
+    pub fn apply_reaction_rules(&mut self) -> usize {
+        if self.config.reactions.is_empty() {
+            return 0;
+        }
+        profile_scope!("reaction_rules");
+
+        let rules = self.config.reactions.clone();
+        let n = self.bodies.len();
+        let mut consumed = vec![false; n];
+        let mut removed: Vec<usize> = Vec::new();
+        let mut fired = 0;
+
+        for rule in &rules {
+            if rule.probability <= 0.0 || rule.cutoff <= 0.0 {
+                continue;
+            }
+            for i in 0..n {
+                let body = &self.bodies[i];
+                if consumed[i] || body.species != rule.a || self.body_to_foil.contains_key(&body.id) {
+                    continue;
+                }
+                let partner = self
+                    .quadtree
+                    .find_neighbors_within(&self.bodies, i, rule.cutoff)
+                    .into_iter()
+                    .filter(|&j| {
+                        j < n
+                            && j != i
+                            && !consumed[j]
+                            && self.bodies[j].species == rule.b
+                            && !self.body_to_foil.contains_key(&self.bodies[j].id)
+                    })
+                    .map(|j| (j, (self.bodies[j].pos - self.bodies[i].pos).mag()))
+                    .filter(|&(_, d)| d <= rule.cutoff)
+                    .min_by(|x, y| x.1.total_cmp(&y.1))
+                    .map(|(j, _)| j);
+                let Some(j) = partner else {
+                    continue;
+                };
+                if fastrand::f32() >= rule.probability {
+                    continue;
+                }
+                if self.react_pair(i, j, rule) {
+                    consumed[i] = true;
+                    consumed[j] = true;
+                    if rule.product_b.is_none() {
+                        removed.push(j);
+                    }
+                    fired += 1;
+                }
+            }
+        }
+
+        if !removed.is_empty() {
+            removed.sort_unstable_by(|a, b| b.cmp(a));
+            for &j in &removed {
+                self.bodies.remove(j);
+            }
+            self.rewound_flags.resize(self.bodies.len(), false);
+            self.quadtree.build(&mut self.bodies);
+            self.cell_list.rebuild(&self.bodies);
+        }
+        if fired > 0 {
+            crate::sim_log!(
+                Physics,
+                Debug,
+                "[reactions] frame {}: {} pair(s) reacted",
+                self.frame,
+                fired
+            );
+        }
+        fired
+    }
+
+    /// Convert bodies `i` and `j` according to `rule`, pooling their
+    /// electrons. Leaves both untouched and returns false when the products
+    /// cannot carry the reactants' net charge.
+    fn react_pair(&mut self, i: usize, j: usize, rule: &ReactionRule) -> bool {
+        let (original_a, original_b) = (self.bodies[i].clone(), self.bodies[j].clone());
+        let charge_before = original_a.charge + original_b.charge;
+        let mut pool = original_a.electrons.clone();
+        pool.extend(original_b.electrons.iter().cloned());
+
+        let mut charge_after = 0.0;
+        if let Some(product_b) = rule.product_b {
+            let b = &mut self.bodies[j];
+            convert(b, product_b);
+            let keep = b.neutral_electron_count().min(pool.len());
+            let split = pool.len() - keep;
+            b.electrons = pool.drain(split..).collect();
+            b.update_charge_from_electrons();
+            charge_after += b.charge;
+        }
+        let a = &mut self.bodies[i];
+        convert(a, rule.product_a);
+        a.electrons = pool;
+        a.update_charge_from_electrons();
+        if rule.product_b.is_none() {
+            // The product carries the pair's momentum
+            let total_mass = original_a.mass + original_b.mass;
+            if total_mass > 0.0 {
+                a.vel = (original_a.vel * original_a.mass + original_b.vel * original_b.mass)
+                    / total_mass;
+            }
+        }
+        charge_after += a.charge;
+
+        if (charge_after - charge_before).abs() > 1e-3 {
+            self.bodies[i] = original_a;
+            self.bodies[j] = original_b;
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::{Body, Electron, Species};
+    use crate::config::ReactionRule;
+    use crate::simulation::Simulation;
+    use ultraviolet::Vec2;
+
+    fn neutral(pos: Vec2, species: Species) -> Body {
+        let mut body = Body::new(pos, Vec2::zero(), 1.0, 1.0, 0.0, species);
+        for _ in 0..body.neutral_electron_count() {
+            body.electrons.push(Electron {
+                rel_pos: Vec2::zero(),
+                vel: Vec2::zero(),
+            });
+        }
+        body.update_charge_from_electrons();
+        body
+    }
+
+    /// `pairs` neutral EC/VC pairs 2 Å apart, pairs 12 Å from each other.
+    fn paired_sim(pairs: usize) -> Simulation {
+        let mut sim = Simulation::new();
+        sim.bodies.clear();
+        let per_row = (pairs as f32).sqrt().ceil() as usize;
+        for k in 0..pairs {
+            let origin = Vec2::new((k % per_row) as f32 * 12.0, (k / per_row) as f32 * 12.0);
+            sim.bodies.push(neutral(origin, Species::EC));
+            sim.bodies.push(neutral(origin + Vec2::new(2.0, 0.0), Species::VC));
+        }
+        sim.rewound_flags.resize(sim.bodies.len(), false);
+        sim.quadtree.build(&mut sim.bodies);
+        sim
+    }
+
+    fn rule(probability: f32) -> ReactionRule {
+        ReactionRule {
+            a: Species::EC,
+            b: Species::VC,
+            product_a: Species::SEI,
+            product_b: None,
+            cutoff: 3.0,
+            probability,
+        }
+    }
+
+    #[test]
+    fn pair_rule_converts_expected_fraction() {
+        // Certain reaction: every pair merges into one SEI body
+        let mut sim = paired_sim(10);
+        sim.config.reactions = vec![rule(1.0)];
+        assert_eq!(sim.apply_reaction_rules(), 10);
+        assert_eq!(sim.bodies.len(), 10);
+        assert!(sim.bodies.iter().all(|b| b.species == Species::SEI));
+        // Nothing left to react
+        assert_eq!(sim.apply_reaction_rules(), 0);
+
+        // Half probability over 400 pairs lands near 200
+        let mut sim = paired_sim(400);
+        sim.config.reactions = vec![rule(0.5)];
+        let fired = sim.apply_reaction_rules();
+        assert!((150..=250).contains(&fired), "fired {}", fired);
+        let sei = sim.bodies.iter().filter(|b| b.species == Species::SEI).count();
+        assert_eq!(sei, fired);
+        assert_eq!(sim.bodies.len(), 800 - fired);
+
+        // A rule that would destroy charge is rejected
+        let mut sim = paired_sim(1);
+        // The quadtree build may reorder bodies
+        let vc = sim.bodies.iter().position(|b| b.species == Species::VC).unwrap();
+        sim.bodies[vc].species = Species::LithiumIon;
+        sim.bodies[vc].electrons.clear();
+        sim.bodies[vc].update_charge_from_electrons();
+        sim.config.reactions = vec![ReactionRule {
+            b: Species::LithiumIon,
+            ..rule(1.0)
+        }];
+        assert_eq!(sim.apply_reaction_rules(), 0);
+        assert_eq!(sim.bodies.len(), 2);
+        assert!(sim.bodies.iter().any(|b| b.species == Species::EC));
+        assert_eq!(sim.bodies[vc].species, Species::LithiumIon);
+    }
+}
@@ -0,0 +1,115 @@
+// simulation/recenter.rs
+//
+// Periodic re-centering for long runs where asymmetric forces let the cloud
+// drift. Every `recenter_interval_frames` frames the mass-weighted center of
+// mass is shifted back to the origin and the net momentum removed. Every
+// moved body gets the same shift and velocity correction, so the internal
+// structure is untouched. Frozen species are either moved with everyone else
+// or left out of both the average and the shift, per `recenter_include_frozen`.
+
+use ultraviolet::Vec2;
+
+impl super::Simulation {
+    /// Shift the center of mass of the participating bodies to the origin
+    /// and zero their net momentum. Returns the applied displacement.
+    pub fn recenter_on_center_of_mass(&mut self) -> Option<Vec2> {
+        let include_frozen = self.config.recenter_include_frozen;
+        let frozen = &self.frozen_species;
+        let moves = |species| include_frozen || !frozen.contains(&species);
+
+        let (mut mass, mut moment) = (0.0f32, Vec2::zero());
+        // Momentum only over bodies whose velocity gets corrected, so frozen
+        // mass carried along by the shift doesn't dilute the drift
+        let (mut free_mass, mut momentum) = (0.0f32, Vec2::zero());
+        for body in self.bodies.iter().filter(|b| moves(b.species)) {
+            mass += body.mass;
+            moment += body.pos * body.mass;
+            if !frozen.contains(&body.species) {
+                free_mass += body.mass;
+                momentum += body.vel * body.mass;
+            }
+        }
+        if mass <= 0.0 {
+            return None;
+        }
+        let shift = -moment / mass;
+        let drift = if free_mass > 0.0 { momentum / free_mass } else { Vec2::zero() };
+        for body in self.bodies.iter_mut() {
+            if !moves(body.species) {
+                continue;
+            }
+            body.pos += shift;
+            // Frozen bodies stay at rest even when they are carried along
+            if !frozen.contains(&body.species) {
+                body.vel -= drift;
+            }
+        }
+        Some(shift)
+    }
+
+    /// Re-center when enabled and `recenter_interval_frames` frames have passed.
+    pub(crate) fn recenter_if_due(&mut self) {
+        let interval = self.config.recenter_interval_frames;
+        if interval == 0 || self.frame == 0 || self.frame % interval != 0 {
+            return;
+        }
+        if let Some(shift) = self.recenter_on_center_of_mass() {
+            crate::sim_log!(
+                Physics,
+                Debug,
+                "Re-centered by ({:.3}, {:.3}) Å at frame {}",
+                shift.x,
+                shift.y,
+                self.frame
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::{Body, Species};
+    use crate::simulation::Simulation;
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn recentering_zeroes_center_of_mass_and_keeps_structure() {
+        let mut sim = Simulation::new();
+        sim.bodies.clear();
+        let cloud = [(10.0, 4.0, 1.0, 0.5), (14.0, 4.0, 3.0, 0.1), (12.0, 8.0, 2.0, 0.2)];
+        for (x, y, mass, vx) in cloud {
+            let body = Body::new(Vec2::new(x, y), Vec2::new(vx, 0.0), mass, 1.0, 0.0, Species::EC);
+            sim.bodies.push(body);
+        }
+        let wall =
+            Body::new(Vec2::new(-30.0, 0.0), Vec2::zero(), 5.0, 1.0, 0.0, Species::FoilMetal);
+        sim.bodies.push(wall);
+        sim.frozen_species.insert(Species::FoilMetal);
+        let before: Vec<Vec2> = sim.bodies.iter().map(|b| b.pos).collect();
+
+        sim.recenter_on_center_of_mass().unwrap();
+
+        let (mass, moment, momentum) = sim.bodies[..3].iter().fold(
+            (0.0, Vec2::zero(), Vec2::zero()),
+            |(m, r, p), b| (m + b.mass, r + b.pos * b.mass, p + b.vel * b.mass),
+        );
+        assert!((moment / mass).mag() < 1e-5, "center of mass at origin");
+        assert!(momentum.mag() < 1e-5, "net momentum removed");
+        let gap = |p: &[Vec2]| (p[1] - p[0], p[2] - p[0]);
+        let after: Vec<Vec2> = sim.bodies.iter().map(|b| b.pos).collect();
+        let ((a0, a1), (b0, b1)) = (gap(&before), gap(&after));
+        assert!((a0 - b0).mag() < 1e-5 && (a1 - b1).mag() < 1e-5, "relative positions kept");
+        // Frozen bodies are excluded by default
+        assert_eq!(after[3], before[3]);
+
+        sim.config.recenter_include_frozen = true;
+        for body in &mut sim.bodies[..3] {
+            body.vel += Vec2::new(0.3, -0.2);
+        }
+        let shift = sim.recenter_on_center_of_mass().unwrap();
+        assert!((sim.bodies[3].pos - (before[3] + shift)).mag() < 1e-5);
+        assert_eq!(sim.bodies[3].vel, Vec2::zero());
+        let momentum = sim.bodies.iter().fold(Vec2::zero(), |p, b| p + b.vel * b.mass);
+        assert!(momentum.mag() < 1e-5, "net momentum removed with frozen mass included");
+    }
+}
@@ -0,0 +1,111 @@
+// replay.rs
+// "Hold at frame" debugging: re-run the same step from a pre-step snapshot
+
+use super::simulation::Simulation;
+use crate::body::{foil::Foil, Body};
+use std::collections::HashMap;
+use ultraviolet::Vec2;
+
+/// Dynamic state captured at the top of `step()`.
+///
+/// Config is deliberately not captured, so parameter edits made between
+/// replays take effect on the re-run step.
+#[derive(Clone)]
+pub struct StepSnapshot {
+    pub bodies: Vec<Body>,
+    pub foils: Vec<Foil>,
+    pub body_to_foil: HashMap<u64, u64>,
+    pub frame: usize,
+    pub time: f32,
+    pub last_thermostat_time: f32,
+    pub prev_induced_e_field: Vec2,
+}
+
+#[derive(Default)]
+pub struct StepReplay {
+    /// Snapshot at the top of every step while set.
+    pub armed: bool,
+    pub snapshot: Option<StepSnapshot>,
+}
+
+impl Simulation {
+    /// Arm or disarm pre-step snapshots; disarming drops the stored snapshot.
+    pub fn set_step_replay(&mut self, armed: bool) {
+        self.step_replay.armed = armed;
+        if !armed {
+            self.step_replay.snapshot = None;
+        }
+    }
+
+    pub(crate) fn capture_step_snapshot(&mut self) {
+        self.step_replay.snapshot = Some(StepSnapshot {
+            bodies: self.bodies.clone(),
+            foils: self.foils.clone(),
+            body_to_foil: self.body_to_foil.clone(),
+            frame: self.frame,
+            time: self.time,
+            last_thermostat_time: self.last_thermostat_time,
+            prev_induced_e_field: self.prev_induced_e_field,
+        });
+    }
+
+    /// Restore the last pre-step snapshot and execute the step again, so time
+    /// ends where the original step left it. Returns `false` when nothing has
+    /// been captured yet.
+    pub fn replay_step(&mut self) -> bool {
+        let Some(snapshot) = self.step_replay.snapshot.clone() else {
+            return false;
+        };
+        self.bodies = snapshot.bodies;
+        self.foils = snapshot.foils;
+        self.body_to_foil = snapshot.body_to_foil;
+        self.frame = snapshot.frame;
+        self.time = snapshot.time;
+        self.last_thermostat_time = snapshot.last_thermostat_time;
+        self.prev_induced_e_field = snapshot.prev_induced_e_field;
+        self.rewound_flags.resize(self.bodies.len(), false);
+        self.quadtree.build(&mut self.bodies);
+        self.cell_list.rebuild(&self.bodies);
+        self.step();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::Species;
+
+    #[test]
+    fn repeated_replays_are_identical() {
+        let mut sim = Simulation::new();
+        for (i, (species, charge)) in [
+            (Species::LithiumIon, 1.0),
+            (Species::ElectrolyteAnion, -1.0),
+            (Species::EC, 0.0),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let pos = Vec2::new(i as f32 * 10.0, 2.0 * i as f32);
+            sim.bodies
+                .push(Body::new_from_species(pos, Vec2::new(0.01, -0.02), charge, species));
+        }
+        assert!(!sim.replay_step(), "nothing captured before arming");
+
+        sim.set_step_replay(true);
+        sim.step();
+        let (frame, time) = (sim.frame, sim.time);
+
+        assert!(sim.replay_step());
+        let first: Vec<(Vec2, Vec2)> = sim.bodies.iter().map(|b| (b.pos, b.vel)).collect();
+        assert!(sim.replay_step());
+        let second: Vec<(Vec2, Vec2)> = sim.bodies.iter().map(|b| (b.pos, b.vel)).collect();
+
+        assert_eq!(first, second);
+        assert_eq!((sim.frame, sim.time), (frame, time), "replay does not advance time");
+
+        sim.set_step_replay(false);
+        assert!(!sim.replay_step());
+    }
+}
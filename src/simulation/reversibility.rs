@@ -0,0 +1,115 @@
+// simulation/reversibility.rs
+//
+// Time-reversal sanity check for the integrator. The harness runs N force +
+// integration steps, reverses every velocity, runs N more and measures how far
+// bodies end up from where they started. Only the dynamics are exercised:
+// thermostat, collisions, chemistry and foils are skipped, and the state is
+// restored afterwards. With damping off the return error is roundoff-level;
+// with damping it quantifies the dissipation.
+
+use crate::simulation::forces;
+use std::collections::HashMap;
+use ultraviolet::Vec2;
+
+impl super::Simulation {
+    /// Recompute accelerations at the current positions.
+    fn compute_dynamics_forces(&mut self) {
+        for body in &mut self.bodies {
+            body.acc = Vec2::zero();
+            body.az = 0.0;
+        }
+        forces::prepare_spatial_structures(self);
+        forces::attract(self);
+        forces::apply_polar_forces(self);
+        forces::apply_lj_forces(self);
+        forces::apply_repulsive_forces(self);
+        forces::apply_stack_pressure(self);
+        forces::apply_body_force(self);
+        if self.config.enable_out_of_plane {
+            super::out_of_plane::apply_out_of_plane(self);
+        }
+    }
+
+    /// Run `steps` steps forward, reverse all velocities, run `steps` back and
+    /// return the RMS distance (Å) between start and end positions. The
+    /// simulation is left exactly as it was.
+    pub fn run_reversibility_test(&mut self, steps: usize) -> f32 {
+        let saved_bodies = self.bodies.clone();
+        let start: HashMap<u64, Vec2> = self.bodies.iter().map(|b| (b.id, b.pos)).collect();
+        let dt = self.dt;
+
+        for _ in 0..steps {
+            self.compute_dynamics_forces();
+            self.iterate();
+        }
+        // Semi-implicit Euler is reversible once the final velocity gets the
+        // kick from the end positions before it is negated.
+        self.compute_dynamics_forces();
+        for body in &mut self.bodies {
+            body.vel = -(body.vel + body.acc * dt);
+            body.vz = -(body.vz + body.az * dt);
+        }
+        for _ in 0..steps {
+            self.compute_dynamics_forces();
+            self.iterate();
+        }
+
+        let (sum_sq, n) = self
+            .bodies
+            .iter()
+            .filter_map(|b| start.get(&b.id).map(|p| (b.pos - *p).mag_sq()))
+            .fold((0.0f32, 0usize), |(s, n), d| (s + d, n + 1));
+        self.bodies = saved_bodies;
+        forces::prepare_spatial_structures(self);
+        if n == 0 {
+            0.0
+        } else {
+            (sum_sq / n as f32).sqrt()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::{Body, Species};
+    use crate::simulation::Simulation;
+    use ultraviolet::Vec2;
+
+    fn two_ion_sim(damping_base: f32) -> Simulation {
+        let mut sim = Simulation::new();
+        sim.bodies.clear();
+        sim.foils.clear();
+        sim.config.damping_base = damping_base;
+        sim.bodies.push(Body::new(
+            Vec2::new(-5.0, 0.0),
+            Vec2::new(0.0, 0.05),
+            1.0,
+            1.0,
+            1.0,
+            Species::LithiumIon,
+        ));
+        sim.bodies.push(Body::new(
+            Vec2::new(5.0, 0.0),
+            Vec2::new(0.0, -0.05),
+            1.0,
+            1.0,
+            1.0,
+            Species::LithiumIon,
+        ));
+        sim
+    }
+
+    #[test]
+    fn conservative_pair_returns_to_start() {
+        let mut sim = two_ion_sim(1.0);
+        let before: Vec<Vec2> = sim.bodies.iter().map(|b| b.pos).collect();
+        let undamped = sim.run_reversibility_test(200);
+        assert!(undamped < 1e-3, "undamped return error {undamped}");
+        let after: Vec<Vec2> = sim.bodies.iter().map(|b| b.pos).collect();
+        assert_eq!(before, after, "state is restored");
+
+        let mut damped_sim = two_ion_sim(0.9);
+        let damped = damped_sim.run_reversibility_test(200);
+        assert!(damped > 10.0 * undamped.max(1e-4), "damped return error {damped}");
+    }
+}
@@ -18,6 +18,7 @@ use crate::{
     quadtree::Quadtree,
     switch_charging::{
         self, FoilStateSnapshot, RunState, StatusSender, SwitchControl, SwitchScheduler,
+        SwitchTimeline,
         SwitchStatus,
     },
 };
@@ -42,15 +43,21 @@ pub struct Simulation {
     pub rewound_flags: Vec<bool>,
     pub background_e_field: Vec2,
     pub prev_induced_e_field: Vec2,
+    /// Manual (GUI) part of `background_e_field` from the last step
+    pub manual_e_field: Vec2,
     pub foils: Vec<crate::body::foil::Foil>,
     pub body_to_foil: HashMap<u64, u64>,
     pub config: config::SimConfig,
     /// Track when thermostat was last applied (in simulation time)
     pub last_thermostat_time: f32,
+    /// Simulation time of the last species count balancing pass
+    pub last_species_balance_time: f32,
     pub simple_history: std::collections::VecDeque<crate::io::SimulationState>,
     pub history_cursor: usize,
     pub history_dirty: bool,
     pub history_capacity: usize,
+    /// Reduced snapshots recorded in `HistoryMode::Lightweight`
+    pub lightweight_history: std::collections::VecDeque<super::lightweight_history::LightweightSnapshot>,
     pub playback: PlaybackController,
     pub switch_config: switch_charging::SwitchChargingConfig,
     pub switch_scheduler: SwitchScheduler,
@@ -58,10 +65,28 @@ pub struct Simulation {
     pub switch_saved_states: HashMap<u64, FoilStateSnapshot>,
     pub switch_active_pair: Option<(u64, u64)>,
     pub switch_status_tx: Option<StatusSender>,
+    /// Active step and setpoint over time, recorded when `config.switch_timeline` is on
+    pub switch_timeline: SwitchTimeline,
     pub thermostat_bootstrapped: bool,
     // Foil group linking (parallel within group, opposite between groups)
     pub group_a: std::collections::HashSet<u64>,
     pub group_b: std::collections::HashSet<u64>,
+    // Species whose bodies are held fixed (skipped by integration and thermostat)
+    pub frozen_species: HashSet<crate::body::Species>,
+    // Per-foil intended vs. achieved electron transfers (filled when foil_charge_audit is on)
+    pub foil_charge_audit: Vec<super::charge_audit::FoilChargeAudit>,
+    // Per-foil commanded vs. measured current series (filled when current_residual_tracking is on)
+    pub current_residuals: Vec<super::current_residual::FoilCurrentResidual>,
+    // Most recent non-finite body detected after integration (None = all finite)
+    pub non_finite_report: Option<super::utils::NonFiniteReport>,
+    // Foil electron add/remove events (filled when electron_event_log is on)
+    pub electron_events: super::electron_events::ElectronEventLog,
+    // Pre-step snapshot for re-running a single step while debugging
+    pub step_replay: super::replay::StepReplay,
+    // Embedder callback run at the end of every step (logging, custom forces, stop conditions)
+    pub step_hook: Option<Box<dyn FnMut(&mut Simulation) + Send>>,
+    /// Observable history for the "measure until converged" mode
+    pub convergence: super::convergence::ConvergenceMonitor,
     // Pre-allocated temporary set for switch-charging inactive foil tracking
     temp_inactive_set: std::collections::HashSet<u64>,
     // Manual measurement recorder for auto-recording measurements to CSV
@@ -69,6 +94,14 @@ pub struct Simulation {
     // Foil metrics CSV writer state (written when manual measurements occur)
     foil_metrics_csv: Option<File>,
     foil_metrics_current_base: Option<String>,
+    // Narrow per-foil commanded-current CSV (same cadence, separate toggle)
+    foil_current_logger: Option<super::foil_current_log::FoilCurrentLogger>,
+    // Per-foil PID internals CSV (same cadence, separate toggle)
+    pid_state_logger: Option<super::pid_state_log::PidStateLogger>,
+    // Applied external field CSV (same cadence, separate toggle)
+    field_logger: Option<super::field_log::FieldLogger>,
+    /// Per-step foil current ledger (open while `config.current_ledger` is on)
+    pub(crate) current_ledger: Option<super::current_ledger::CurrentLedger>,
     // Active material regions for intercalation electrodes
     pub active_regions: Vec<ActiveMaterialRegion>,
     // EIS state machine (None when not running)
@@ -107,14 +140,17 @@ impl Simulation {
             rewound_flags,
             background_e_field: Vec2::zero(),
             prev_induced_e_field: Vec2::zero(),
+            manual_e_field: Vec2::zero(),
             foils: Vec::new(),
             body_to_foil: HashMap::new(),
             config: config::SimConfig::default(),
             last_thermostat_time: 0.0,
+            last_species_balance_time: 0.0,
             simple_history: std::collections::VecDeque::new(),
             history_cursor: 0,
             history_dirty: false,
             history_capacity,
+            lightweight_history: std::collections::VecDeque::new(),
             playback: PlaybackController::new(),
             switch_config: switch_charging::SwitchChargingConfig::default(),
             switch_scheduler: SwitchScheduler::default(),
@@ -122,13 +158,26 @@ impl Simulation {
             switch_saved_states: HashMap::new(),
             switch_active_pair: None,
             switch_status_tx: None,
+            switch_timeline: SwitchTimeline::default(),
             thermostat_bootstrapped: false,
             group_a: std::collections::HashSet::new(),
             group_b: std::collections::HashSet::new(),
+            frozen_species: HashSet::new(),
+            foil_charge_audit: Vec::new(),
+            current_residuals: Vec::new(),
+            non_finite_report: None,
+            electron_events: Default::default(),
+            step_replay: Default::default(),
+            step_hook: None,
+            convergence: Default::default(),
             temp_inactive_set: std::collections::HashSet::new(),
             manual_measurement_recorder: None,
             foil_metrics_csv: None,
             foil_metrics_current_base: None,
+            foil_current_logger: None,
+            pid_state_logger: None,
+            field_logger: None,
+            current_ledger: None,
             active_regions: Vec::new(),
             eis_state: None,
             morphology_logger: None,
@@ -154,6 +203,10 @@ impl Simulation {
     fn start_switch_charging(&mut self) {
         match self.switch_config.validate() {
             Ok(_) => {
+                if self.switch_run_state == RunState::Idle {
+                    self.switch_timeline.clear();
+                    crate::renderer::state::SWITCH_TIMELINE.lock().clear();
+                }
                 self.refresh_switch_snapshots();
                 self.switch_scheduler.start(&self.switch_config);
                 self.switch_run_state = RunState::Running;
@@ -190,6 +243,7 @@ impl Simulation {
         cfg.ensure_all_steps();
         self.switch_config = cfg;
         self.switch_config.sim_dt_s = (self.dt as f64) * 1e-15;
+        self.switch_config.apply_dwell_seconds();
         self.switch_active_pair = None;
         match self.switch_config.validate() {
             Ok(_) => {
@@ -243,15 +297,29 @@ impl Simulation {
         let simulation_time_fs = self.time;
         match recorder.start_recording(simulation_time_fs) {
             Ok(_) => {
-                println!("✓ Started manual measurement recording");
+                crate::sim_log!(Diagnostics, Info, "✓ Started manual measurement recording");
                 self.manual_measurement_recorder = Some(recorder);
             }
             Err(e) => {
-                eprintln!("✗ Failed to start manual measurement recording: {}", e);
+                crate::sim_log!(Diagnostics, Error, "✗ Failed to start manual measurement recording: {}", e);
             }
         }
     }
 
+    /// Lift a measurement auto-pause when `auto_resume_after_fs` is configured.
+    /// Called by the simulation loop while paused, so the paused frame is
+    /// published once before the run continues. Returns true if it resumed.
+    pub fn poll_measurement_auto_resume(&mut self) -> bool {
+        let resume = self
+            .manual_measurement_recorder
+            .as_mut()
+            .is_some_and(|r| r.take_auto_resume());
+        if resume {
+            crate::renderer::state::PAUSED.store(false, std::sync::atomic::Ordering::Relaxed);
+        }
+        resume
+    }
+
     pub fn stop_manual_measurement(&mut self) {
         if let Some(recorder) = &mut self.manual_measurement_recorder {
             recorder.stop_recording();
@@ -305,7 +373,9 @@ impl Simulation {
             match self.switch_config.step_active_inactive.get(&current_step) {
                 Some(sai) => (&sai.active, &sai.inactive),
                 None => {
-                    eprintln!(
+                    crate::sim_log!(
+                        Charging,
+                        Warn,
                         "Warning: No active/inactive setpoints found for step {}",
                         current_step
                     );
@@ -576,13 +646,23 @@ impl Simulation {
     }
 
     fn tick_switch_charging(&mut self) {
-        self.switch_config.sim_dt_s = (self.dt as f64) * 1e-15;
+        let dt_s = (self.dt as f64) * 1e-15;
+        if dt_s != self.switch_config.sim_dt_s {
+            self.switch_config.sim_dt_s = dt_s;
+            if self.switch_config.apply_dwell_seconds() {
+                self.switch_scheduler.sync_with_config(&self.switch_config);
+            }
+        }
+        if self.switch_run_state == RunState::Paused {
+            self.record_switch_timeline(None);
+        }
         if self.switch_run_state != RunState::Running {
             return;
         }
         if let Some(((pos_ids, neg_ids), setpoint)) =
             self.switch_scheduler.on_tick(&self.switch_config)
         {
+            self.record_switch_timeline(Some(self.switch_scheduler.current_step()));
             // When using step-based active/inactive setpoints, ignore the legacy step setpoint completely
             if self.switch_config.use_active_inactive_setpoints {
                 self.apply_switch_step_active_inactive((pos_ids, neg_ids));
@@ -599,6 +679,26 @@ impl Simulation {
         }
     }
 
+    /// Extend the switch timeline over this step (`None` while paused) and
+    /// mirror the change to the renderer.
+    fn record_switch_timeline(&mut self, step: Option<u8>) {
+        if !self.config.switch_timeline {
+            return;
+        }
+        let setpoint = step.and_then(|s| self.switch_config.resolved_active_setpoint(s));
+        let started = self
+            .switch_timeline
+            .record(self.time, self.time + self.dt, step, setpoint);
+        let Some(last) = self.switch_timeline.segments.last() else {
+            return;
+        };
+        let mut shared = crate::renderer::state::SWITCH_TIMELINE.lock();
+        match shared.last_mut() {
+            Some(mirrored) if !started => mirrored.end_fs = last.end_fs,
+            _ => shared.push(last.clone()),
+        }
+    }
+
     fn send_switch_status(&self, status: SwitchStatus) {
         if let Some(tx) = &self.switch_status_tx {
             let _ = tx.send(status);
@@ -611,17 +711,26 @@ impl Simulation {
         let global_config = crate::config::LJ_CONFIG.lock();
         if global_config.config_version != self.config.config_version {
             self.config = global_config.clone();
+            crate::logging::apply(self.config.log_level, &self.config.log_categories);
+            self.sync_quadtree_capacities();
         } else {
             drop(global_config); // Release lock early
         }
 
+        if self.step_replay.armed {
+            self.capture_step_snapshot();
+        }
+
         let mag = *FIELD_MAGNITUDE.lock();
         let theta = (*FIELD_DIRECTION.lock()).to_radians();
         let manual_field = Vec2::new(theta.cos(), theta.sin()) * mag;
 
         // Compute induced external field from foil charging (current or overpotential)
         let mut induced_field = Vec2::zero();
-        if self.foils.len() >= 2 && self.config.induced_field_gain != 0.0 {
+        if self.config.capacitive_coupling {
+            // Parallel-plate model driven by the actual charge imbalance between groups
+            induced_field = self.capacitive_coupling_field();
+        } else if self.foils.len() >= 2 && self.config.induced_field_gain != 0.0 {
             // Determine active positive/negative foil groups for direction
             // Fallback: use first two foils as pos/neg by net current sign
             let mut pos_centroid = Vec2::zero();
@@ -737,6 +846,7 @@ impl Simulation {
         self.prev_induced_e_field = smoothed_induced;
 
         // Compose total external: manual + smoothed induced
+        self.manual_e_field = manual_field;
         self.background_e_field = manual_field + smoothed_induced;
         self.rewound_flags
             .par_iter_mut()
@@ -997,6 +1107,15 @@ impl Simulation {
             }
         }
 
+        // Per-foil activation schedules override the DC current of current-mode foils
+        for foil in &mut self.foils {
+            if foil.charging_mode == crate::body::foil::ChargingMode::Current {
+                if let Some(current) = foil.scheduled_current(time) {
+                    foil.dc_current = current;
+                }
+            }
+        }
+
         self.bodies.par_iter_mut().for_each(|body| {
             body.acc = Vec2::zero();
             body.az = 0.0; // Reset z-acceleration as well
@@ -1008,6 +1127,7 @@ impl Simulation {
         forces::apply_lj_forces(self);
         forces::apply_repulsive_forces(self);
         forces::apply_stack_pressure(self);
+        forces::apply_body_force(self);
 
         // Apply out-of-plane forces if enabled
         if self.config.enable_out_of_plane {
@@ -1021,11 +1141,19 @@ impl Simulation {
         // Removed: frustration system replaced with simple Li+ collision softness
 
         self.iterate();
+        self.publish_quadtree_visit_stats();
+        self.grow_domain_if_needed();
+        self.recenter_if_due();
+        self.neighbor_self_check_if_due();
 
         let num_passes = *COLLISION_PASSES.lock();
         for _ in 1..num_passes {
             collision::collide(self);
         }
+        if self.config.reactions_enabled {
+            self.apply_reaction_rules();
+        }
+        self.check_non_finite_bodies();
         self.update_surrounded_flags();
 
         // Track which bodies receive electrons from foil current this step
@@ -1095,7 +1223,9 @@ impl Simulation {
             None
         };
         // Apply foil current sources/sinks with charge conservation
-        self.process_foils_with_charge_conservation(time, &mut foil_current_recipients);
+        if self.config.reactions_enabled || !self.config.reactions_off_skips_foils {
+            self.process_foils_with_charge_conservation(time, &mut foil_current_recipients);
+        }
         // EIS: record voltage (pre-foil) and current (post-foil) for lock-in detection.
         if let Some(cell_voltage) = eis_pre_voltage {
             // I is read after foil processing so the PID has computed its output.
@@ -1185,23 +1315,31 @@ impl Simulation {
         let bodies_slice = unsafe { std::slice::from_raw_parts(bodies_ptr, len) };
         for i in 0..len {
             let body = &mut self.bodies[i];
+            let stiffness = self.config.electron_stiffness_for(body.species);
             body.update_electrons(
                 bodies_slice,
                 quadtree,
                 self.background_e_field,
                 self.dt,
                 self.config.coulomb_constant,
+                stiffness,
+                self.config.max_electron_displacement,
             );
             body.update_charge_from_electrons();
         }
 
-        self.perform_electron_hopping_with_exclusions(&foil_current_recipients);
+        // Chemistry (hopping drives redox); skipped for pure transport studies
+        if self.config.reactions_enabled {
+            self.perform_electron_hopping_with_exclusions(&foil_current_recipients);
+        }
         self.scratch_foil_current_recipients = foil_current_recipients;
-        self.perform_sei_formation();
-        
-        // Perform intercalation/deintercalation for active material electrodes
-        self.perform_intercalation();
-        self.perform_deintercalation();
+        if self.config.reactions_enabled {
+            self.perform_sei_formation();
+
+            // Perform intercalation/deintercalation for active material electrodes
+            self.perform_intercalation();
+            self.perform_deintercalation();
+        }
         
         // Sync active region data to renderer for SOC-based coloring
         self.sync_active_region_render_data();
@@ -1223,9 +1361,12 @@ impl Simulation {
                     )
                 });
                 if has_liquid {
+                    let species = &self.config.thermostat_species;
+                    let frozen = &self.frozen_species;
                     crate::simulation::utils::initialize_liquid_velocities_to_temperature(
                         &mut self.bodies,
                         self.config.temperature,
+                        |s| super::thermal::thermostatted(s, species, frozen),
                     );
                     #[cfg(feature = "thermostat_debug")]
                     {
@@ -1256,6 +1397,13 @@ impl Simulation {
             self.last_thermostat_time = time;
         }
 
+        if !self.config.species_targets.is_empty()
+            && time - self.last_species_balance_time >= self.config.species_balance_interval_fs
+        {
+            self.balance_species();
+            self.last_species_balance_time = time;
+        }
+
         // Debug: track bodies count
         #[cfg(feature = "thermostat_debug")]
         {
@@ -1275,6 +1423,8 @@ impl Simulation {
         let mut wrote_measurements = false;
         let simulation_time_fs = self.time;
         if let Some(recorder) = &mut self.manual_measurement_recorder {
+            // Check for auto-pause at target time before measuring so the paused frame is recorded
+            let auto_pause = recorder.check_auto_pause(simulation_time_fs);
             let results = recorder.update(
                 &self.bodies,
                 &self.foils,
@@ -1288,21 +1438,24 @@ impl Simulation {
                 wrote_measurements = true;
             }
 
-            // Check for auto-pause at target time
-            if let Some(target_time_fs) = recorder.config().auto_pause_time_fs {
-                if simulation_time_fs >= target_time_fs {
-                    println!(
-                        "✓ Auto-pause triggered at {:.0} fs (target: {:.0} fs)",
-                        simulation_time_fs, target_time_fs
-                    );
-                    crate::renderer::state::PAUSED
-                        .store(true, std::sync::atomic::Ordering::Relaxed);
-                }
+            if auto_pause {
+                crate::sim_log!(
+                    Diagnostics,
+                    Info,
+                    "✓ Auto-pause triggered at {:.0} fs (next: {:?})",
+                    simulation_time_fs,
+                    recorder.next_auto_pause_fs()
+                );
+                crate::renderer::state::PAUSED
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
             }
         }
         // Also write foil metrics at the same cadence, after recorder borrow ends
         if wrote_measurements {
             self.write_foil_metrics_if_due(self.frame, simulation_time_fs);
+            self.write_foil_current_if_due(simulation_time_fs);
+            self.write_pid_state_if_due(simulation_time_fs);
+            self.write_field_if_due(simulation_time_fs);
         }
 
         // Phase 4.2: morphology metrics log + live snapshot.
@@ -1314,20 +1467,85 @@ impl Simulation {
 
         // Capture history with lightweight ring buffer approach
         // Only capture every 10 frames and keep limited history for good performance
-        if self.frame % 10 == 0 {
-            self.push_history_snapshot();
+        match self.config.history_mode {
+            crate::config::HistoryMode::Full => {
+                if self.frame % 10 == 0 {
+                    self.push_history_snapshot();
+                }
+            }
+            crate::config::HistoryMode::Lightweight => {
+                if self.frame % self.config.lightweight_history_stride.max(1) == 0 {
+                    self.push_lightweight_snapshot();
+                }
+            }
         }
 
         #[cfg(test)]
         // After all updates, print debug info for anions
         for (i, body) in self.bodies.iter().enumerate() {
             if body.species == crate::body::Species::ElectrolyteAnion {
-                println!(
+                crate::sim_log!(
+                    Physics,
+                    Debug,
                     "[DEBUG] Step {}: Anion {} charge = {}, pos = {:?}, vel = {:?}",
                     self.frame, i, body.charge, body.pos, body.vel
                 );
             }
         }
+
+        self.check_convergence();
+
+        // Taken out for the call so the hook can borrow the simulation mutably;
+        // a hook that installs a replacement keeps the replacement.
+        if let Some(mut hook) = self.step_hook.take() {
+            hook(self);
+            if self.step_hook.is_none() {
+                self.step_hook = Some(hook);
+            }
+        }
+    }
+
+    /// Post-integration sanity pass: log any body with a non-finite
+    /// position/velocity/charge (or invalid radius/mass) and, with
+    /// `config.pause_on_nan`, pause before the corruption spreads.
+    pub fn check_non_finite_bodies(&mut self) {
+        let invalid: Vec<(usize, &'static str)> = self
+            .bodies
+            .iter()
+            .enumerate()
+            .filter_map(|(i, b)| super::utils::invalid_body_field(b).map(|f| (i, f)))
+            .collect();
+        let Some(&(index, field)) = invalid.first() else {
+            self.non_finite_report = None;
+            return;
+        };
+        crate::sim_log!(
+            Physics,
+            Error,
+            "[NaN] frame {}: {} invalid bodies",
+            self.frame,
+            invalid.len()
+        );
+        for &(idx, what) in invalid.iter().take(8) {
+            let b = &self.bodies[idx];
+            crate::sim_log!(
+                Physics,
+                Error,
+                "[NaN]   id={} idx={} {:?} bad={} pos=({:.3},{:.3}) z={:.3} vel=({:.3},{:.3}) vz={:.3} q={:.3} r={:.3} m={:.3}",
+                b.id, idx, b.species, what, b.pos.x, b.pos.y, b.z, b.vel.x, b.vel.y, b.vz, b.charge, b.radius, b.mass
+            );
+        }
+        self.non_finite_report = Some(super::utils::NonFiniteReport {
+            frame: self.frame,
+            body_id: self.bodies[index].id,
+            index,
+            field,
+            count: invalid.len(),
+        });
+        if self.config.pause_on_nan {
+            crate::sim_log!(Physics, Warn, "[NaN] Pausing simulation (pause_on_nan)");
+            crate::renderer::state::PAUSED.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
     }
 
     /// Simple history capture using VecDeque ring buffer (like the original working system)
@@ -1443,14 +1661,23 @@ impl Simulation {
         let domain_height = self.domain_height;
         let domain_depth = self.domain_depth;
         let enable_out_of_plane = self.config.enable_out_of_plane;
+        let frozen_species = &self.frozen_species;
         self.bodies.par_iter_mut().for_each(|body| {
+            if frozen_species.contains(&body.species) {
+                body.vel = Vec2::zero();
+                body.vz = 0.0;
+                return;
+            }
             body.vel += body.acc * dt;
             let damping = base_damping * body.species.damping();
             body.vel *= damping;
             body.pos += body.vel * dt;
 
             // Z-coordinate integration (if out-of-plane is enabled)
-            if enable_out_of_plane {
+            if enable_out_of_plane && !body.species.z_mobility().mobile {
+                body.z = 0.0;
+                body.vz = 0.0;
+            } else if enable_out_of_plane {
                 body.vz += body.az * dt;
                 body.vz *= damping; // Apply same damping to z-velocity
                 body.z += body.vz * dt;
@@ -1486,7 +1713,7 @@ impl Simulation {
     }
 
     /// Build default foil metrics base filename using unified scheme reflecting current settings
-    fn foil_metrics_filename_base(&self) -> String {
+    pub(crate) fn foil_metrics_filename_base(&self) -> String {
         // If GUI provided an override, use it as-is
         if let Some(name) = crate::renderer::state::FOIL_METRICS_FILENAME_OVERRIDE
             .lock()
@@ -1595,16 +1822,17 @@ impl Simulation {
                 let _ = writeln!(f, "{}", header);
                 let _ = f.flush();
                 self.foil_metrics_csv = Some(f);
-                println!("✓ Started foil metrics recording to: {}", path.display());
+                crate::sim_log!(Io, Info, "✓ Started foil metrics recording to: {}", path.display());
             }
             Err(e) => {
-                eprintln!("✗ Failed to open foil metrics CSV: {}", e);
+                crate::sim_log!(Io, Error, "✗ Failed to open foil metrics CSV: {}", e);
             }
         }
     }
 
-    /// Compute Li metal count attached to a foil via connectivity (shares logic with ratio calc)
-    fn li_metal_count_for_foil(&self, foil: &crate::body::foil::Foil) -> usize {
+    /// Compute Li metal count attached to a foil via connectivity (shares logic with ratio calc).
+    /// `config.connectivity_species_filter` selects which metals the BFS may hop through.
+    pub(crate) fn li_metal_count_for_foil(&self, foil: &crate::body::foil::Foil) -> usize {
         // Build id->index map
         let id_to_index: std::collections::HashMap<u64, usize> = self
             .bodies
@@ -1627,6 +1855,7 @@ impl Simulation {
         }
 
         let use_cell = self.use_cell_list();
+        let traversable = self.config.connectivity_species_filter.traversable();
         let mut li_metal_count = 0usize;
         while let Some(body_index) = queue.pop_front() {
             if body_index >= self.bodies.len() {
@@ -1651,10 +1880,7 @@ impl Simulation {
                     continue;
                 }
                 let other_body = &self.bodies[other_idx];
-                if !matches!(
-                    other_body.species,
-                    crate::body::Species::LithiumMetal | crate::body::Species::FoilMetal
-                ) {
+                if !traversable.contains(&other_body.species) {
                     continue;
                 }
                 let threshold = (body.radius + other_body.radius) * 1.1;
@@ -1795,12 +2021,91 @@ impl Simulation {
         }
     }
 
+    /// Path of a cadence CSV next to the foil-metrics CSV: the metrics base
+    /// name with "Foil-based" swapped for `tag`, or `prefix_` prepended when
+    /// the base uses another scheme.
+    fn cadence_csv_path(&self, tag: &str, prefix: &str) -> std::path::PathBuf {
+        let base = self
+            .foil_metrics_current_base
+            .clone()
+            .unwrap_or_else(|| self.foil_metrics_filename_base());
+        let filename = if base.starts_with("Foil-based") {
+            base.replacen("Foil-based", tag, 1)
+        } else {
+            format!("{}_{}", prefix, base)
+        };
+        std::path::Path::new("doe_results").join(filename)
+    }
+
+    /// Append commanded foil currents to the narrow per-foil current CSV.
+    /// Runs at the manual-measurement cadence but is toggled independently
+    /// of the wide foil-metrics CSV; disabling it closes the file.
+    fn write_foil_current_if_due(&mut self, time_fs: f32) {
+        let path = self.cadence_csv_path("Foil-current", "current");
+        let foils = &self.foils;
+        if let Some(logger) = super::csv_stream::stream_if_enabled(
+            &mut self.foil_current_logger,
+            &crate::renderer::state::FOIL_CURRENT_CSV_ENABLED,
+            &path,
+            "foil current",
+            |path| super::foil_current_log::FoilCurrentLogger::open(path, foils),
+        ) {
+            logger.write_row(time_fs, foils);
+        }
+    }
+
+    /// Append master-foil PID internals to the PID state CSV. Same cadence
+    /// as the foil current CSV; disabling it closes the file.
+    fn write_pid_state_if_due(&mut self, time_fs: f32) {
+        let path = self.cadence_csv_path("Foil-pid", "pid");
+        let foils = &self.foils;
+        if let Some(logger) = super::csv_stream::stream_if_enabled(
+            &mut self.pid_state_logger,
+            &crate::renderer::state::PID_STATE_CSV_ENABLED,
+            &path,
+            "foil PID state",
+            |path| super::pid_state_log::PidStateLogger::open(path, foils),
+        ) {
+            logger.write_row(time_fs, foils);
+        }
+    }
+
+    /// Append the manual, smoothed induced and total external field to the
+    /// field CSV. Same cadence as the foil current CSV; disabling it closes
+    /// the file.
+    fn write_field_if_due(&mut self, time_fs: f32) {
+        let path = self.cadence_csv_path("Field", "field");
+        if let Some(logger) = super::csv_stream::stream_if_enabled(
+            &mut self.field_logger,
+            &crate::renderer::state::FIELD_CSV_ENABLED,
+            &path,
+            "applied field",
+            super::field_log::FieldLogger::open,
+        ) {
+            logger.write_row(
+                time_fs,
+                self.manual_e_field,
+                self.prev_induced_e_field,
+                self.background_e_field,
+            );
+        }
+    }
+
     pub fn use_cell_list(&self) -> bool {
         let area = (2.0 * self.domain_width) * (2.0 * self.domain_height);
         let density = self.bodies.len() as f32 / area;
         density > self.config.cell_list_density_threshold
     }
 
+    /// Per-body electron capacity of the foil owning `body_id`;
+    /// `FOIL_MAX_ELECTRONS` when the body belongs to no foil.
+    pub fn foil_max_electrons(&self, body_id: u64) -> usize {
+        self.body_to_foil
+            .get(&body_id)
+            .and_then(|foil_id| self.foils.iter().find(|f| f.id == *foil_id))
+            .map_or(crate::config::FOIL_MAX_ELECTRONS, |f| f.max_electrons())
+    }
+
     /// Calculate the proper foil electron ratio (same as diagnostic)
     /// This is the ratio of actual electrons to neutral electron count in the foil network
     /// OPTIMIZED: Uses spatial data structures and index-based BFS to avoid O(N) id scans
@@ -1927,59 +2232,42 @@ impl Simulation {
         match foil.charging_mode {
             crate::body::foil::ChargingMode::Current => {
                 // Traditional current control mode
-                let mut current = foil.dc_current;
-                if foil.switch_hz > 0.0 {
-                    let ac_component = if (time * foil.switch_hz) % 1.0 < 0.5 {
-                        foil.ac_current
-                    } else {
-                        -foil.ac_current
-                    };
-                    current += ac_component;
-                }
-                current
+                foil.commanded_current(time)
             }
             crate::body::foil::ChargingMode::Overpotential => {
                 // Check if this is a master foil (has PID controller) or slave foil (no controller)
-                if let Some(_controller) = &foil.overpotential_controller {
+                if foil.overpotential_controller.is_some() {
                     // Master foil - use PID controller
                     if let Some(ratio) = actual_ratio {
                         let pid_current = foil.compute_overpotential_current(ratio, dt);
 
                         // Still support AC component on top of PID-controlled DC current
-                        let mut current = pid_current;
-                        if foil.switch_hz > 0.0 {
-                            let ac_component = if (time * foil.switch_hz) % 1.0 < 0.5 {
-                                foil.ac_current
-                            } else {
-                                -foil.ac_current
-                            };
-                            current += ac_component;
-                        }
-                        current
+                        pid_current + foil.ac_component(time)
                     } else {
                         // Fallback to DC current if no ratio available
                         foil.dc_current
                     }
                 } else {
                     // Slave foil - use stored slave current (set by master)
-                    let mut current = foil.slave_overpotential_current;
-                    if foil.switch_hz > 0.0 {
-                        let ac_component = if (time * foil.switch_hz) % 1.0 < 0.5 {
-                            foil.ac_current
-                        } else {
-                            -foil.ac_current
-                        };
-                        current += ac_component;
-                    }
-                    current
+                    foil.commanded_current(time)
                 }
             }
         }
     }
 
     /// Process foils with charge conservation - electrons can only be added if another foil removes one
-    fn process_foils_with_charge_conservation(&mut self, time: f32, recipients: &mut [bool]) {
+    pub(crate) fn process_foils_with_charge_conservation(
+        &mut self,
+        time: f32,
+        recipients: &mut [bool],
+    ) {
         let dt = self.dt;
+        self.close_current_ledger_if_disabled();
+        let audit_start = self.charge_audit_begin();
+        let residual_start = self.current_residual_begin();
+        if self.frame % super::foil_handoff::HANDOFF_CHECK_INTERVAL == 0 {
+            self.apply_foil_handoffs();
+        }
         let mut rng = rand::rng();
 
         // Calculate proper foil electron ratios for overpotential charging foils
@@ -2087,6 +2375,9 @@ impl Simulation {
                 }
             }
         }
+
+        self.charge_audit_finish(audit_start);
+        self.current_residual_finish(residual_start, time);
     }
 
     /// Process linked pair with charge conservation (similar to existing but renamed for clarity)
@@ -2154,18 +2445,17 @@ impl Simulation {
         }
     }
 
-    fn foil_can_add(&self, idx: usize) -> bool {
+    pub(crate) fn foil_can_add(&self, idx: usize) -> bool {
         let foil = &self.foils[idx];
+        let max_electrons = foil.max_electrons();
         foil.body_ids.iter().any(|&id| {
             self.bodies.iter().any(|b| {
-                b.id == id
-                    && b.species == Species::FoilMetal
-                    && b.electrons.len() < crate::config::FOIL_MAX_ELECTRONS
+                b.id == id && b.species == Species::FoilMetal && b.electrons.len() < max_electrons
             })
         })
     }
 
-    fn foil_can_remove(&self, idx: usize) -> bool {
+    pub(crate) fn foil_can_remove(&self, idx: usize) -> bool {
         let foil = &self.foils[idx];
         foil.body_ids.iter().any(|&id| {
             self.bodies
@@ -2180,27 +2470,10 @@ impl Simulation {
         rng: &mut rand::rngs::ThreadRng,
         recipients: &mut [bool],
     ) -> bool {
-        let foil = &mut self.foils[idx];
-        if let Some(&id) = foil.body_ids.as_slice().choose(rng) {
-            if let Some((body_idx, body)) = self
-                .bodies
-                .iter_mut()
-                .enumerate()
-                .find(|(_, b)| b.id == id && b.species == Species::FoilMetal)
-            {
-                if body.electrons.len() < crate::config::FOIL_MAX_ELECTRONS {
-                    body.electrons.push(Electron {
-                        rel_pos: Vec2::zero(),
-                        vel: Vec2::zero(),
-                    });
-                    recipients[body_idx] = true;
-                    // Track signed electron change since last measurement
-                    foil.electron_delta_since_measure += 1;
-                    return true;
-                }
-            }
+        match self.select_foil_host(idx, true, rng, None) {
+            Some(body_idx) => self.push_foil_electron(idx, body_idx, recipients),
+            None => false,
         }
-        false
     }
 
     fn try_remove_electron(
@@ -2209,24 +2482,104 @@ impl Simulation {
         rng: &mut rand::rngs::ThreadRng,
         recipients: &mut [bool],
     ) -> bool {
-        let foil = &mut self.foils[idx];
-        if let Some(&id) = foil.body_ids.as_slice().choose(rng) {
-            if let Some((body_idx, body)) = self
-                .bodies
-                .iter_mut()
-                .enumerate()
-                .find(|(_, b)| b.id == id && b.species == Species::FoilMetal)
-            {
-                if !body.electrons.is_empty() {
-                    body.electrons.pop();
-                    recipients[body_idx] = true;
-                    // Track signed electron change since last measurement
-                    foil.electron_delta_since_measure -= 1;
-                    return true;
+        match self.select_foil_host(idx, false, rng, None) {
+            Some(body_idx) => self.pop_foil_electron(idx, body_idx, recipients),
+            None => false,
+        }
+    }
+
+    /// Pick the foil body that should gain (`adding`) or lose an electron.
+    ///
+    /// By default a random member is drawn and rejected if it is full/empty.
+    /// With `deterministic_foil_electrons` the eligible member holding the
+    /// fewest electrons (adding) or the most (removing) is chosen, ties going
+    /// to the earliest member of the foil.
+    fn select_foil_host(
+        &self,
+        idx: usize,
+        adding: bool,
+        rng: &mut rand::rngs::ThreadRng,
+        id_to_index: Option<&std::collections::HashMap<u64, usize>>,
+    ) -> Option<usize> {
+        let lookup = |id: u64| -> Option<usize> {
+            match id_to_index {
+                Some(map) => map.get(&id).copied().filter(|&i| i < self.bodies.len()),
+                None => self.bodies.iter().position(|b| b.id == id),
+            }
+        };
+        let foil = &self.foils[idx];
+        let max_electrons = foil.max_electrons();
+        let eligible = |i: usize| -> bool {
+            let b = &self.bodies[i];
+            b.species == Species::FoilMetal
+                && if adding {
+                    b.electrons.len() < max_electrons
+                } else {
+                    !b.electrons.is_empty()
                 }
+        };
+        if self.config.deterministic_foil_electrons {
+            let candidates = foil
+                .body_ids
+                .iter()
+                .filter_map(|&id| lookup(id))
+                .filter(|&i| eligible(i));
+            if adding {
+                candidates.min_by_key(|&i| self.bodies[i].electrons.len())
+            } else {
+                candidates.min_by_key(|&i| std::cmp::Reverse(self.bodies[i].electrons.len()))
             }
+        } else {
+            let &id = foil.body_ids.as_slice().choose(rng)?;
+            lookup(id).filter(|&i| eligible(i))
         }
-        false
+    }
+
+    fn push_foil_electron(&mut self, idx: usize, body_idx: usize, recipients: &mut [bool]) -> bool {
+        let body = &mut self.bodies[body_idx];
+        // Deterministic mode drops the new electron at the polarized cloud centroid
+        let rel_pos = if self.config.deterministic_foil_electrons && !body.electrons.is_empty() {
+            body.electrons.iter().fold(Vec2::zero(), |acc, e| acc + e.rel_pos)
+                / body.electrons.len() as f32
+        } else {
+            Vec2::zero()
+        };
+        body.electrons.push(Electron {
+            rel_pos,
+            vel: Vec2::zero(),
+        });
+        recipients[body_idx] = true;
+        // Track signed electron change since last measurement
+        self.foils[idx].electron_delta_since_measure += 1;
+        self.record_electron_event(idx, body_idx, 1);
+        true
+    }
+
+    fn pop_foil_electron(&mut self, idx: usize, body_idx: usize, recipients: &mut [bool]) -> bool {
+        self.bodies[body_idx].electrons.pop();
+        recipients[body_idx] = true;
+        // Track signed electron change since last measurement
+        self.foils[idx].electron_delta_since_measure -= 1;
+        self.record_electron_event(idx, body_idx, -1);
+        true
+    }
+
+    fn record_electron_event(&mut self, idx: usize, body_idx: usize, delta: i8) {
+        if !self.config.electron_event_log {
+            return;
+        }
+        let event = super::electron_events::ElectronEvent {
+            frame: self.frame,
+            foil_id: self.foils[idx].id,
+            body_id: self.bodies[body_idx].id,
+            delta,
+        };
+        let flush_path = self
+            .config
+            .electron_event_flush
+            .then(|| std::path::Path::new("doe_results").join("electron_events_stream.csv"));
+        self.electron_events
+            .record(event, self.config.electron_event_buffer, flush_path.as_deref());
     }
 
     // Fast index-based helpers to avoid O(N) id scans. Only used on hot overpotential path.
@@ -2237,13 +2590,12 @@ impl Simulation {
         id_to_index: &std::collections::HashMap<u64, usize>,
     ) -> bool {
         let foil = &self.foils[idx];
+        let max_electrons = foil.max_electrons();
         for &id in &foil.body_ids {
             if let Some(&bi) = id_to_index.get(&id) {
                 if bi < self.bodies.len() {
                     let b = &self.bodies[bi];
-                    if b.species == Species::FoilMetal
-                        && b.electrons.len() < crate::config::FOIL_MAX_ELECTRONS
-                    {
+                    if b.species == Species::FoilMetal && b.electrons.len() < max_electrons {
                         return true;
                     }
                 }
@@ -2280,27 +2632,10 @@ impl Simulation {
         recipients: &mut [bool],
         id_to_index: &std::collections::HashMap<u64, usize>,
     ) -> bool {
-        let foil = &mut self.foils[idx];
-        if let Some(&id) = foil.body_ids.as_slice().choose(rng) {
-            if let Some(&body_idx) = id_to_index.get(&id) {
-                if body_idx < self.bodies.len() {
-                    let body = &mut self.bodies[body_idx];
-                    if body.species == Species::FoilMetal
-                        && body.electrons.len() < crate::config::FOIL_MAX_ELECTRONS
-                    {
-                        body.electrons.push(Electron {
-                            rel_pos: Vec2::zero(),
-                            vel: Vec2::zero(),
-                        });
-                        recipients[body_idx] = true;
-                        // Track signed electron change since last measurement
-                        foil.electron_delta_since_measure += 1;
-                        return true;
-                    }
-                }
-            }
+        match self.select_foil_host(idx, true, rng, Some(id_to_index)) {
+            Some(body_idx) => self.push_foil_electron(idx, body_idx, recipients),
+            None => false,
         }
-        false
     }
 
     #[inline]
@@ -2311,22 +2646,10 @@ impl Simulation {
         recipients: &mut [bool],
         id_to_index: &std::collections::HashMap<u64, usize>,
     ) -> bool {
-        let foil = &mut self.foils[idx];
-        if let Some(&id) = foil.body_ids.as_slice().choose(rng) {
-            if let Some(&body_idx) = id_to_index.get(&id) {
-                if body_idx < self.bodies.len() {
-                    let body = &mut self.bodies[body_idx];
-                    if body.species == Species::FoilMetal && !body.electrons.is_empty() {
-                        body.electrons.pop();
-                        recipients[body_idx] = true;
-                        // Track signed electron change since last measurement
-                        foil.electron_delta_since_measure -= 1;
-                        return true;
-                    }
-                }
-            }
+        match self.select_foil_host(idx, false, rng, Some(id_to_index)) {
+            Some(body_idx) => self.pop_foil_electron(idx, body_idx, recipients),
+            None => false,
         }
-        false
     }
 
     /// Direct electron manipulation for overpotential mode - bypasses current-based accumulator system
@@ -2364,10 +2687,13 @@ impl Simulation {
                     // Slave foil - use assigned current
                     self.foils[i].slave_overpotential_current
                 };
+            self.record_current_ledger(i, self.time, effective_current);
 
             // Interpret controller output as electrons-per-step for responsiveness
-            // Positive value = add electrons, negative = remove electrons
-            let electron_transfer_rate = effective_current;
+            // Positive value = add electrons, negative = remove electrons.
+            // An integer cap leaves no fractional part, so at most `cap` operations.
+            let cap = self.config.max_electrons_per_step_per_foil as f32;
+            let electron_transfer_rate = effective_current.clamp(-cap, cap);
 
             // Direct electron manipulation - no accumulator, no charge conservation constraints
             if electron_transfer_rate > 0.0 {
@@ -2492,6 +2818,7 @@ impl Simulation {
                 dt,
                 self.frame as u64,
             );
+            self.record_current_ledger(i, time, current);
             self.foils[i].accum += current * dt;
         }
 
@@ -2880,3 +3207,580 @@ mod charge_conservation_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod auto_resume_tests {
+    use super::*;
+    use crate::manual_measurement::{ManualMeasurementConfig, ManualMeasurementRecorder};
+    use crate::renderer::state::{PAUSED, PAUSED_TEST_LOCK};
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn auto_pause_resumes_and_pauses_again_at_offset() {
+        let _paused = PAUSED_TEST_LOCK.lock();
+        let mut sim = Simulation::new();
+        sim.step();
+        let dt = sim.dt;
+        let config = ManualMeasurementConfig {
+            auto_pause_time_fs: Some(sim.time + 3.0 * dt),
+            auto_resume_after_fs: Some(5.0 * dt),
+            ..Default::default()
+        };
+        // Not recording, so no CSV is written
+        sim.manual_measurement_recorder = Some(ManualMeasurementRecorder::new(config));
+        PAUSED.store(false, Ordering::Relaxed);
+
+        let mut first_pause = None;
+        for _ in 0..10 {
+            sim.step();
+            if PAUSED.load(Ordering::Relaxed) {
+                first_pause = Some(sim.time);
+                break;
+            }
+        }
+        let first_pause = first_pause.expect("sim should auto-pause");
+
+        assert!(sim.poll_measurement_auto_resume(), "auto-resume should be queued");
+        assert!(!PAUSED.load(Ordering::Relaxed));
+        assert!(!sim.poll_measurement_auto_resume(), "resume is consumed once");
+
+        let mut second_pause = None;
+        for _ in 0..20 {
+            sim.step();
+            if PAUSED.load(Ordering::Relaxed) {
+                second_pause = Some(sim.time);
+                break;
+            }
+        }
+        let second_pause = second_pause.expect("sim should pause again after the offset");
+        assert!(second_pause - first_pause >= 5.0 * dt - 1e-3);
+        assert!(second_pause - first_pause < 6.0 * dt + 1e-3);
+        PAUSED.store(false, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn nan_velocity_pauses_and_identifies_body() {
+        use crate::body::{Body, Species};
+
+        let _paused = PAUSED_TEST_LOCK.lock();
+        let mut sim = Simulation::new();
+        assert!(sim.config.pause_on_nan);
+        for x in [-5.0, 5.0] {
+            sim.bodies.push(Body::new(
+                Vec2::new(x, 0.0),
+                Vec2::zero(),
+                1.0,
+                1.0,
+                1.0,
+                Species::LithiumIon,
+            ));
+        }
+        sim.check_non_finite_bodies();
+        assert_eq!(sim.non_finite_report, None);
+
+        sim.bodies[1].vel.x = f32::NAN;
+        let bad_id = sim.bodies[1].id;
+        PAUSED.store(false, Ordering::Relaxed);
+        sim.check_non_finite_bodies();
+        let paused = PAUSED.swap(false, Ordering::Relaxed);
+
+        let report = sim.non_finite_report.clone().expect("NaN should be reported");
+        assert!(paused, "pause_on_nan should pause the simulation");
+        assert_eq!(report.body_id, bad_id);
+        assert_eq!(report.index, 1);
+        assert_eq!(report.field, "vel");
+        assert_eq!(report.count, 1);
+    }
+}
+
+#[cfg(test)]
+mod frozen_species_tests {
+    use super::*;
+    use crate::body::{Body, Species};
+
+    #[test]
+    fn frozen_species_do_not_move() {
+        let mut sim = Simulation::new();
+        for (x, species) in [(-10.0, Species::FoilMetal), (10.0, Species::LithiumIon)] {
+            sim.bodies.push(Body::new(
+                Vec2::new(x, 0.0),
+                Vec2::zero(),
+                species.mass(),
+                species.radius(),
+                0.0,
+                species,
+            ));
+        }
+        sim.config.body_force = Vec2::new(0.0, -1.0e-3);
+        sim.frozen_species.insert(Species::FoilMetal);
+        let start: Vec<Vec2> = sim.bodies.iter().map(|b| b.pos).collect();
+
+        for _ in 0..20 {
+            sim.step();
+        }
+
+        let foil = sim.bodies.iter().find(|b| b.species == Species::FoilMetal).unwrap();
+        let ion = sim.bodies.iter().find(|b| b.species == Species::LithiumIon).unwrap();
+        assert_eq!(foil.pos, start[0]);
+        assert_eq!(foil.vel, Vec2::zero());
+        assert!((ion.pos - start[1]).mag() > 1e-4, "free species should move");
+    }
+
+    #[test]
+    fn collisions_push_only_the_free_partner_of_a_frozen_body() {
+        let mut sim = Simulation::new();
+        let (metal, ion) = (Species::FoilMetal, Species::LithiumIon);
+        let contact = metal.radius() + ion.radius();
+        // Overlapping at rest (positional branch) and closing in (velocity branch)
+        for vx in [0.0, -0.5] {
+            sim.bodies.clear();
+            sim.bodies.push(Body::new(
+                Vec2::zero(),
+                Vec2::zero(),
+                metal.mass(),
+                metal.radius(),
+                0.0,
+                metal,
+            ));
+            sim.bodies.push(Body::new(
+                Vec2::new(0.5 * contact, 0.0),
+                Vec2::new(vx, 0.0),
+                ion.mass(),
+                ion.radius(),
+                1.0,
+                ion,
+            ));
+            sim.frozen_species.insert(metal);
+
+            collision::collide(&mut sim);
+
+            assert_eq!(sim.bodies[0].pos, Vec2::zero(), "frozen body displaced (vx {vx})");
+            assert_eq!(sim.bodies[0].vel, Vec2::zero());
+            assert!(sim.bodies[1].pos.x > 0.5 * contact, "free partner should be pushed out");
+        }
+    }
+}
+
+#[cfg(test)]
+mod deterministic_foil_electron_tests {
+    use super::*;
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Species};
+
+    fn foil_with_counts(counts: &[usize]) -> Simulation {
+        let mut sim = Simulation::new();
+        let mut ids = Vec::new();
+        for (i, &n) in counts.iter().enumerate() {
+            let mut b = Body::new(
+                Vec2::new(2.0 * i as f32, 0.0),
+                Vec2::zero(),
+                1.0,
+                1.0,
+                0.0,
+                Species::FoilMetal,
+            );
+            for _ in 0..n {
+                b.electrons.push(Electron {
+                    rel_pos: Vec2::new(0.3, 0.0),
+                    vel: Vec2::zero(),
+                });
+            }
+            ids.push(b.id);
+            sim.bodies.push(b);
+        }
+        sim.foils.push(Foil::new(ids, Vec2::zero(), 1.0, 1.0, 0.0, 0.0));
+        sim.config.deterministic_foil_electrons = true;
+        sim
+    }
+
+    #[test]
+    fn deterministic_mode_targets_least_and_most_charged_hosts() {
+        let mut rng = rand::rng();
+        let mut sim = foil_with_counts(&[2, 0, 1]);
+        let mut recipients = vec![false; sim.bodies.len()];
+        assert!(sim.try_add_electron(0, &mut rng, &mut recipients));
+        assert_eq!(recipients, vec![false, true, false]);
+        assert_eq!(sim.bodies[1].electrons.len(), 1);
+        assert_eq!(sim.bodies[1].electrons[0].rel_pos, Vec2::zero());
+
+        // Ties go to the earliest host; the new electron joins its cloud centroid
+        let mut sim = foil_with_counts(&[2, 1, 1]);
+        let mut recipients = vec![false; sim.bodies.len()];
+        assert!(sim.try_add_electron(0, &mut rng, &mut recipients));
+        assert_eq!(recipients, vec![false, true, false]);
+        assert_eq!(sim.bodies[1].electrons[1].rel_pos, Vec2::new(0.3, 0.0));
+
+        let mut sim = foil_with_counts(&[1, 2, 2]);
+        let mut recipients = vec![false; sim.bodies.len()];
+        assert!(sim.try_remove_electron(0, &mut rng, &mut recipients));
+        assert_eq!(recipients, vec![false, true, false]);
+        assert_eq!(sim.bodies[1].electrons.len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod foil_capacity_tests {
+    use super::*;
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Species};
+
+    #[test]
+    fn capacity_override_caps_only_its_foil() {
+        let mut sim = Simulation::new();
+        for i in 0..2 {
+            let b = Body::new(Vec2::new(4.0 * i as f32, 0.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::FoilMetal);
+            sim.foils.push(Foil::new(vec![b.id], Vec2::zero(), 1.0, 1.0, 0.0, 0.0));
+            sim.bodies.push(b);
+        }
+        sim.foils[0].max_electrons_override = Some(5);
+        sim.foils[1].max_electrons_override = None;
+        let mut rng = rand::rng();
+        let mut recipients = vec![false; sim.bodies.len()];
+
+        for _ in 0..5 {
+            assert!(sim.try_add_electron(0, &mut rng, &mut recipients));
+        }
+        assert!(!sim.foil_can_add(0));
+        assert!(!sim.try_add_electron(0, &mut rng, &mut recipients));
+        assert_eq!(sim.bodies[0].electrons.len(), 5);
+
+        // The default foil fills to the global limit independently
+        for _ in 0..crate::config::FOIL_MAX_ELECTRONS {
+            assert!(sim.foil_can_add(1));
+            assert!(sim.try_add_electron(1, &mut rng, &mut recipients));
+        }
+        assert!(!sim.foil_can_add(1));
+        assert_eq!(sim.bodies[1].electrons.len(), crate::config::FOIL_MAX_ELECTRONS);
+
+        let id_to_index: std::collections::HashMap<u64, usize> =
+            sim.bodies.iter().enumerate().map(|(i, b)| (b.id, i)).collect();
+        sim.bodies[0].electrons.pop();
+        assert!(sim.foil_can_add_idxmap(0, &id_to_index));
+        assert!(sim.try_add_electron_idxmap(0, &mut rng, &mut recipients, &id_to_index));
+        assert!(!sim.foil_can_add_idxmap(0, &id_to_index));
+    }
+}
+
+#[cfg(test)]
+mod reactions_disabled_tests {
+    use super::*;
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Species};
+
+    #[test]
+    fn reactions_off_keeps_species_and_electrons_constant() {
+        let mut sim = Simulation::new();
+        // A driven foil next to Li+ ions and a bit of metal: normally this plates/strips
+        let mut foil_ids = Vec::new();
+        for i in 0..5 {
+            let mut b = Body::new(
+                Vec2::new(-10.0, -4.0 + 2.0 * i as f32),
+                Vec2::zero(),
+                Species::FoilMetal.mass(),
+                Species::FoilMetal.radius(),
+                0.0,
+                Species::FoilMetal,
+            );
+            b.electrons.push(Electron {
+                rel_pos: Vec2::zero(),
+                vel: Vec2::zero(),
+            });
+            foil_ids.push(b.id);
+            sim.bodies.push(b);
+        }
+        sim.foils.push(Foil::new(foil_ids, Vec2::zero(), 1.0, 1.0, 5.0, 0.0));
+        for i in 0..5 {
+            sim.bodies.push(Body::new(
+                Vec2::new(-7.0, -4.0 + 2.0 * i as f32),
+                Vec2::zero(),
+                Species::LithiumIon.mass(),
+                Species::LithiumIon.radius(),
+                1.0,
+                Species::LithiumIon,
+            ));
+        }
+        sim.config.reactions_enabled = false;
+
+        let species_before: Vec<Species> = sim.bodies.iter().map(|b| b.species).collect();
+        let electrons_before: Vec<usize> = sim.bodies.iter().map(|b| b.electrons.len()).collect();
+        let ion_pos_before: Vec<Vec2> = sim.bodies[5..].iter().map(|b| b.pos).collect();
+
+        for _ in 0..50 {
+            sim.step();
+        }
+
+        let species_after: Vec<Species> = sim.bodies.iter().map(|b| b.species).collect();
+        let electrons_after: Vec<usize> = sim.bodies.iter().map(|b| b.electrons.len()).collect();
+        assert_eq!(species_before, species_after);
+        assert_eq!(electrons_before, electrons_after);
+        let moved = sim.bodies[5..]
+            .iter()
+            .zip(&ion_pos_before)
+            .any(|(b, p)| (b.pos - *p).mag() > 1e-4);
+        assert!(moved, "ions should still migrate");
+    }
+}
+
+#[cfg(test)]
+mod step_hook_tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn step_hook_runs_once_per_step() {
+        let mut sim = Simulation::new();
+        // No hook installed: stepping is unaffected
+        sim.step();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        sim.step_hook = Some(Box::new(move |sim: &mut Simulation| {
+            let n = counter.fetch_add(1, Ordering::Relaxed) + 1;
+            sim.config.spawn_wall_margin = n as f32;
+        }));
+
+        for expected in 1..=3 {
+            sim.step();
+            assert_eq!(calls.load(Ordering::Relaxed), expected);
+            assert_eq!(sim.config.spawn_wall_margin, expected as f32);
+        }
+        assert!(sim.step_hook.is_some(), "hook stays installed between steps");
+    }
+}
+
+#[cfg(test)]
+mod connectivity_filter_tests {
+    use super::*;
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Species};
+    use crate::config::ConnectivityFilter;
+    use crate::diagnostics::{connected_indices_through, connected_metal_indices};
+
+    #[test]
+    fn lithium_only_filter_counts_deposit_not_bridged_metal() {
+        let mut sim = Simulation::new();
+        // Foil body, two Li, a stray foil-metal body, two more Li, all touching in a row
+        let layout = [
+            Species::FoilMetal,
+            Species::LithiumMetal,
+            Species::LithiumMetal,
+            Species::FoilMetal,
+            Species::LithiumMetal,
+            Species::LithiumMetal,
+        ];
+        for (i, species) in layout.iter().enumerate() {
+            sim.bodies.push(Body::new(
+                Vec2::new(i as f32 * 2.0 * species.radius(), 0.0),
+                Vec2::zero(),
+                species.mass(),
+                species.radius(),
+                0.0,
+                *species,
+            ));
+        }
+        let foil = Foil::new(vec![sim.bodies[0].id], Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+        sim.quadtree.build(&mut sim.bodies);
+        assert!(!sim.use_cell_list());
+
+        assert_eq!(sim.li_metal_count_for_foil(&foil), 4);
+        sim.config.connectivity_species_filter = ConnectivityFilter::LithiumOnly;
+        assert_eq!(sim.li_metal_count_for_foil(&foil), 2);
+
+        let id_to_index: HashMap<u64, usize> =
+            sim.bodies.iter().enumerate().map(|(i, b)| (b.id, i)).collect();
+        let total = connected_metal_indices(&sim.bodies, &foil, &sim.quadtree, &id_to_index);
+        assert_eq!(total.len(), 6, "total connected metal includes foil bodies");
+        let deposit = connected_indices_through(
+            &sim.bodies,
+            &foil,
+            &sim.quadtree,
+            &id_to_index,
+            ConnectivityFilter::LithiumOnly.traversable(),
+        );
+        assert_eq!(deposit.len(), 3, "foil seed plus the contiguous Li deposit");
+    }
+}
+
+#[cfg(test)]
+mod foil_schedule_tests {
+    use super::*;
+    use crate::body::foil::{Foil, ScheduleSegment};
+    use crate::body::{Body, Species};
+
+    #[test]
+    fn foil_follows_two_segment_schedule() {
+        let mut sim = Simulation::new();
+        let b = Body::new(Vec2::zero(), Vec2::zero(), 1.0, 1.0, 0.0, Species::FoilMetal);
+        let mut foil = Foil::new(vec![b.id], Vec2::zero(), 1.0, 1.0, 3.0, 0.0);
+        foil.schedule = vec![
+            ScheduleSegment { start_fs: 20.0, end_fs: 40.0, setpoint: 2.0 },
+            ScheduleSegment { start_fs: 60.0, end_fs: 80.0, setpoint: -1.0 },
+        ];
+        sim.bodies.push(b);
+        sim.foils.push(foil);
+
+        // Sample points sit well inside each window so the step size does not matter
+        let expected = [(10.0, 0.0), (25.0, 2.0), (50.0, 0.0), (65.0, -1.0), (90.0, 0.0)];
+        for (t, current) in expected {
+            while sim.time < t {
+                sim.step();
+            }
+            assert_eq!(sim.foils[0].dc_current, current, "current at {} fs", sim.time);
+        }
+    }
+}
+
+#[cfg(test)]
+mod induced_field_tests {
+    use super::*;
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Species};
+
+    fn induced_magnitude(gain: f32) -> f32 {
+        let mut sim = Simulation::new();
+        for (x, current) in [(-20.0, -1.0), (20.0, 1.0)] {
+            let b = Body::new(Vec2::new(x, 0.0), Vec2::zero(), 1.0, 1.0, 0.0, Species::FoilMetal);
+            sim.foils.push(Foil::new(vec![b.id], Vec2::zero(), 1.0, 1.0, current, 0.0));
+            sim.bodies.push(b);
+        }
+        sim.config.capacitive_coupling = false;
+        sim.config.induced_field_smoothing = 0.0;
+        sim.config.induced_field_gain = gain;
+        sim.step();
+        sim.prev_induced_e_field.mag()
+    }
+
+    #[test]
+    fn induced_field_scales_with_gain() {
+        assert_eq!(induced_magnitude(0.0), 0.0);
+        let low = induced_magnitude(2.0);
+        let high = induced_magnitude(6.0);
+        // Average drive of the ±1 pair is 1, so |E| equals the gain
+        assert!((low - 2.0).abs() < 1e-4, "{low}");
+        assert!((high - 3.0 * low).abs() < 1e-4, "{high}");
+    }
+}
+
+#[cfg(test)]
+mod switch_timeline_tests {
+    use super::*;
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Species};
+    use crate::switch_charging::{Role, StepSetpoint, SwitchChargingConfig, SwitchTimeline};
+
+    #[test]
+    fn timeline_matches_configured_dwell_and_marks_pauses() {
+        let mut sim = Simulation::new();
+        sim.config.switch_timeline = true;
+        let mut cfg = SwitchChargingConfig::default();
+        for (i, role) in Role::ALL.iter().enumerate() {
+            let pos = Vec2::new(i as f32 * 10.0, 0.0);
+            let b = Body::new(pos, Vec2::zero(), 1.0, 1.0, 0.0, Species::FoilMetal);
+            let foil = Foil::new(vec![b.id], Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+            cfg.role_to_foil.insert(*role, vec![foil.id]);
+            sim.bodies.push(b);
+            sim.foils.push(foil);
+        }
+        for step in 0..4u8 {
+            let setpoint = StepSetpoint { value: step as f64 + 1.0, ..Default::default() };
+            cfg.step_setpoints.insert(step, setpoint);
+        }
+        cfg.use_active_inactive_setpoints = false;
+        cfg.dwell_seconds = Some(3.0 * sim.dt as f64 * 1e-15);
+        sim.handle_switch_control(SwitchControl::UpdateConfig(cfg));
+        assert_eq!(sim.switch_config.delta_steps, 3);
+
+        let tick = |sim: &mut Simulation, n: usize| {
+            for _ in 0..n {
+                sim.tick_switch_charging();
+                sim.time += sim.dt;
+            }
+        };
+        sim.handle_switch_control(SwitchControl::Start);
+        tick(&mut sim, 14);
+        sim.handle_switch_control(SwitchControl::Pause);
+        tick(&mut sim, 2);
+        sim.handle_switch_control(SwitchControl::Start);
+        tick(&mut sim, 1);
+
+        let dt = sim.dt;
+        let summary: Vec<(Option<u8>, f32, Option<f64>)> = sim
+            .switch_timeline
+            .segments
+            .iter()
+            .map(|s| (s.step, ((s.end_fs - s.start_fs) / dt).round(), s.setpoint))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some(0), 3.0, Some(1.0)),
+                (Some(1), 3.0, Some(2.0)),
+                (Some(2), 3.0, Some(3.0)),
+                (Some(3), 3.0, Some(4.0)),
+                (Some(0), 2.0, Some(1.0)),
+                (None, 2.0, None),
+                (Some(0), 1.0, Some(1.0)),
+            ]
+        );
+        let points = SwitchTimeline::step_function(&sim.switch_timeline.segments);
+        assert_eq!(points.iter().filter(|p| p.1.is_nan()).count(), 2, "pause breaks the line");
+    }
+}
+
+#[cfg(test)]
+mod electron_rate_cap_tests {
+    use super::*;
+    use crate::body::foil::Foil;
+    use crate::body::{Body, Species};
+
+    #[test]
+    fn overpotential_foil_transfers_at_most_the_cap_per_step() {
+        let mut sim = Simulation::new();
+        sim.bodies.clear();
+        sim.foils.clear();
+        let ids: Vec<u64> = (0..4)
+            .map(|i| {
+                let body = Body::new(
+                    Vec2::new(2.0 * i as f32, 0.0),
+                    Vec2::zero(),
+                    1.0,
+                    1.0,
+                    0.0,
+                    Species::FoilMetal,
+                );
+                let id = body.id;
+                sim.bodies.push(body);
+                id
+            })
+            .collect();
+        let mut foil = Foil::new(ids, Vec2::zero(), 1.0, 1.0, 0.0, 0.0);
+        foil.max_electrons_override = Some(100);
+        foil.enable_overpotential_mode(1.0);
+        foil.overpotential_controller.as_mut().unwrap().last_output_current = 50.0;
+        sim.foils.push(foil);
+        sim.config.max_electrons_per_step_per_foil = 3;
+
+        let electrons =
+            |sim: &Simulation| sim.bodies.iter().map(|b| b.electrons.len()).sum::<usize>();
+        let mut rng = rand::rng();
+        let mut recipients = vec![false; sim.bodies.len()];
+        for step in 1..=4 {
+            sim.process_overpotential_direct_electron_control(
+                &std::collections::HashMap::new(),
+                &mut rng,
+                &mut recipients,
+            );
+            assert_eq!(electrons(&sim), 3 * step);
+        }
+
+        // Removal is capped the same way
+        sim.foils[0].overpotential_controller.as_mut().unwrap().last_output_current = -50.0;
+        sim.process_overpotential_direct_electron_control(
+            &std::collections::HashMap::new(),
+            &mut rng,
+            &mut recipients,
+        );
+        assert_eq!(electrons(&sim), 9);
+    }
+}
@@ -0,0 +1,167 @@
+// species_balance.rs
+// Hold mobile species at target counts by spawning/culling in the bulk
+
+use super::simulation::Simulation;
+use crate::app::spawn::{
+    clear_of_walls, is_near_metal, is_position_valid, sample_velocity, seed_electrons, spawn_electron_count,
+};
+use crate::body::{Body, Species};
+use ultraviolet::Vec2;
+
+/// Distance from metal (Å) inside which bodies are neither spawned nor culled.
+const BULK_METAL_EXCLUSION: f32 = 10.0;
+/// Placement attempts per spawned body.
+const SPAWN_ATTEMPTS: usize = 200;
+
+/// Ion that compensates the charge of `species`, if it is charged.
+fn counter_ion(species: Species) -> Option<Species> {
+    match species {
+        Species::LithiumIon => Some(Species::ElectrolyteAnion),
+        Species::ElectrolyteAnion => Some(Species::LithiumIon),
+        _ => None,
+    }
+}
+
+impl Simulation {
+    /// Spawn or cull bulk bodies so every species in `config.species_targets`
+    /// reaches its target count.
+    ///
+    /// Each charged ion added or removed is paired with its counter-ion
+    /// (Li+ ↔ PF6-) so the net charge is unchanged, unless the counter-ion has
+    /// its own target, in which case that target wins. Bodies owned by foils
+    /// or within `BULK_METAL_EXCLUSION` of metal are never touched. Returns
+    /// the number of bodies added and removed.
+    pub fn balance_species(&mut self) -> (usize, usize) {
+        let mut targets: Vec<(Species, usize)> =
+            self.config.species_targets.iter().map(|(s, n)| (*s, *n)).collect();
+        targets.sort_by_key(|(s, _)| *s as u8);
+
+        let (mut added, mut removed) = (0, 0);
+        for (species, target) in targets {
+            let count = self.bodies.iter().filter(|b| b.species == species).count();
+            let counter = counter_ion(species)
+                .filter(|c| !self.config.species_targets.contains_key(c));
+            if count > target {
+                let culled = self.cull_bulk(species, count - target);
+                removed += culled;
+                if let Some(counter) = counter {
+                    removed += self.cull_bulk(counter, culled);
+                }
+            } else if count < target {
+                let spawned = self.spawn_bulk(species, target - count);
+                added += spawned;
+                if let Some(counter) = counter {
+                    added += self.spawn_bulk(counter, spawned);
+                }
+            }
+        }
+        if added + removed > 0 {
+            self.rewound_flags.resize(self.bodies.len(), false);
+            self.quadtree.build(&mut self.bodies);
+            self.cell_list.rebuild(&self.bodies);
+            crate::sim_log!(
+                Physics,
+                Debug,
+                "[species balance] frame {}: +{} / -{} bodies",
+                self.frame,
+                added,
+                removed
+            );
+        }
+        (added, removed)
+    }
+
+    /// Remove up to `n` random bulk bodies of `species`.
+    fn cull_bulk(&mut self, species: Species, n: usize) -> usize {
+        let mut candidates: Vec<usize> = (0..self.bodies.len())
+            .filter(|&i| {
+                let b = &self.bodies[i];
+                b.species == species
+                    && !self.body_to_foil.contains_key(&b.id)
+                    && !is_near_metal(&self.bodies, b.pos, BULK_METAL_EXCLUSION)
+            })
+            .collect();
+        fastrand::shuffle(&mut candidates);
+        candidates.truncate(n);
+        // Remove from the back so earlier indices stay valid
+        candidates.sort_unstable_by(|a, b| b.cmp(a));
+        for &i in &candidates {
+            self.bodies.remove(i);
+        }
+        candidates.len()
+    }
+
+    /// Place up to `n` bodies of `species` at free bulk positions.
+    fn spawn_bulk(&mut self, species: Species, n: usize) -> usize {
+        let (half_w, half_h) = (self.domain_width, self.domain_height);
+        let radius = species.radius();
+        let mut placed = 0;
+        for _ in 0..n {
+            for _ in 0..SPAWN_ATTEMPTS {
+                let pos = Vec2::new(
+                    (fastrand::f32() * 2.0 - 1.0) * (half_w - radius),
+                    (fastrand::f32() * 2.0 - 1.0) * (half_h - radius),
+                );
+                if !clear_of_walls(self, pos, radius)
+                    || !is_position_valid(pos, radius, &self.bodies, self.config.spawn_min_separation)
+                    || is_near_metal(&self.bodies, pos, BULK_METAL_EXCLUSION)
+                {
+                    continue;
+                }
+                let mut body = Body::new(pos, Vec2::zero(), species.mass(), radius, 0.0, species);
+                body.vel = sample_velocity(body.mass, self.config.temperature);
+                seed_electrons(&mut body, spawn_electron_count(species, None));
+                body.update_charge_from_electrons();
+                self.bodies.push(body);
+                placed += 1;
+                break;
+            }
+        }
+        placed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balancer_culls_and_replenishes_keeping_neutrality() {
+        let mut sim = Simulation::new();
+        sim.domain_width = 100.0;
+        sim.domain_height = 100.0;
+        let mut add = |species: Species, x: f32, y: f32| {
+            let mut body = Body::new(Vec2::new(x, y), Vec2::zero(), species.mass(), species.radius(), 0.0, species);
+            seed_electrons(&mut body, spawn_electron_count(species, None));
+            body.update_charge_from_electrons();
+            sim.bodies.push(body);
+        };
+        // 10 ion pairs and 2 EC on a coarse grid
+        for i in 0..10 {
+            let x = -80.0 + 16.0 * i as f32;
+            add(Species::LithiumIon, x, -40.0);
+            add(Species::ElectrolyteAnion, x, 40.0);
+        }
+        add(Species::EC, 0.0, 0.0);
+        add(Species::EC, 20.0, 0.0);
+        let net_charge = |sim: &Simulation| sim.bodies.iter().map(|b| b.charge).sum::<f32>();
+        let count = |sim: &Simulation, s: Species| sim.bodies.iter().filter(|b| b.species == s).count();
+        let initial_charge = net_charge(&sim);
+
+        sim.config.species_targets.insert(Species::LithiumIon, 6);
+        sim.config.species_targets.insert(Species::EC, 5);
+        sim.balance_species();
+
+        assert_eq!(count(&sim, Species::LithiumIon), 6);
+        assert_eq!(count(&sim, Species::ElectrolyteAnion), 6, "counter-ions culled with Li+");
+        assert_eq!(count(&sim, Species::EC), 5);
+        assert!((net_charge(&sim) - initial_charge).abs() < 1e-3);
+
+        // Below target: Li+ replenished together with PF6-
+        sim.config.species_targets.insert(Species::LithiumIon, 9);
+        sim.balance_species();
+        assert_eq!(count(&sim, Species::LithiumIon), 9);
+        assert_eq!(count(&sim, Species::ElectrolyteAnion), 9);
+        assert!((net_charge(&sim) - initial_charge).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,104 @@
+// simulation/species_count_log.rs
+//
+// Species count matrix over the playback history as a wide CSV. Columns are
+// discovered from the history rather than a fixed list, so species that
+// appear partway through (new solvents, SEI, deposited metal) get a column.
+//
+// CSV schema:
+//   time_fs,<Species>,<Species>,...
+// Columns follow first appearance in the history; species first seen in the
+// same frame keep their enum declaration order. Frames without a species
+// write 0 in its column.
+
+use crate::body::{Body, Species};
+use std::path::Path;
+
+/// Species present anywhere in `frames`, in column order.
+pub fn species_count_columns(frames: &[(f32, &[Body])]) -> Vec<Species> {
+    let mut columns: Vec<Species> = Vec::new();
+    for (_, bodies) in frames {
+        let mut new_here: Vec<Species> = Vec::new();
+        for body in bodies.iter() {
+            if !columns.contains(&body.species) && !new_here.contains(&body.species) {
+                new_here.push(body.species);
+            }
+        }
+        new_here.sort_by_key(|s| *s as u8);
+        columns.extend(new_here);
+    }
+    columns
+}
+
+/// Full CSV text for `frames` (`(time_fs, bodies)` per row).
+pub fn species_count_csv(frames: &[(f32, &[Body])]) -> String {
+    let columns = species_count_columns(frames);
+    let mut out = String::from("time_fs");
+    for species in &columns {
+        out.push_str(&format!(",{:?}", species));
+    }
+    out.push('\n');
+    for (time_fs, bodies) in frames {
+        out.push_str(&format!("{:.3}", time_fs));
+        for species in &columns {
+            let n = bodies.iter().filter(|b| b.species == *species).count();
+            out.push_str(&format!(",{}", n));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+impl super::Simulation {
+    /// Write the species count matrix for every frame in the playback history.
+    pub fn export_species_counts(&self, path: &Path) -> std::io::Result<usize> {
+        let frames: Vec<(f32, &[Body])> = self
+            .simple_history
+            .iter()
+            .map(|state| (state.sim_time, state.bodies.as_slice()))
+            .collect();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, species_count_csv(&frames))?;
+        Ok(frames.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ultraviolet::Vec2;
+
+    fn body(species: Species) -> Body {
+        Body::new(Vec2::zero(), Vec2::zero(), 1.0, 1.0, 0.0, species)
+    }
+
+    #[test]
+    fn late_species_gets_zero_filled_column() {
+        let early = vec![body(Species::EC), body(Species::LithiumIon), body(Species::LithiumIon)];
+        let late = vec![
+            body(Species::LithiumIon),
+            body(Species::EC),
+            body(Species::SEI),
+            body(Species::SEI),
+        ];
+        let frames: Vec<(f32, &[Body])> = vec![
+            (0.0, early.as_slice()),
+            (10.0, early.as_slice()),
+            (20.0, late.as_slice()),
+        ];
+
+        // Always-present species keep enum order; the newcomer is appended
+        assert_eq!(
+            species_count_columns(&frames),
+            vec![Species::LithiumIon, Species::EC, Species::SEI]
+        );
+
+        let csv = species_count_csv(&frames);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "time_fs,LithiumIon,EC,SEI");
+        assert_eq!(lines[1], "0.000,2,1,0");
+        assert_eq!(lines[2], "10.000,2,1,0");
+        assert_eq!(lines[3], "20.000,1,1,2");
+    }
+}
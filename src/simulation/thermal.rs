@@ -5,7 +5,7 @@ use crate::body::Species;
 use crate::units::BOLTZMANN_CONSTANT;
 
 #[cfg(feature = "thermostat_debug")]
-macro_rules! tdbg { ($($arg:tt)*) => { eprintln!($($arg)*); } }
+macro_rules! tdbg { ($($arg:tt)*) => { crate::sim_log!(Physics, Debug, $($arg)*); } }
 #[cfg(not(feature = "thermostat_debug"))]
 macro_rules! tdbg {
     ($($arg:tt)*) => {};
@@ -14,7 +14,9 @@ use super::Simulation;
 
 impl Simulation {
     /// Apply Maxwell-Boltzmann thermostat to maintain target temperature
-    /// Applies only to liquid particles (Li+, anion, EC, DMC); excludes metals
+    /// Applies only to species in `config.thermostat_species` (the liquid
+    /// solvent/anion set by default); excludes any species frozen via
+    /// `frozen_species`
     pub fn apply_thermostat(&mut self) {
         let target_temp = self.config.temperature;
         if self.bodies.is_empty() {
@@ -41,22 +43,18 @@ impl Simulation {
         let mut _li_count = 0;
         let mut _anion_count = 0;
         for body in &self.bodies {
-            match body.species {
-                Species::ElectrolyteAnion | Species::EC | Species::DMC
-                | Species::VC | Species::FEC | Species::EMC => {
-                    liquid_ke += 0.5 * body.mass * body.vel.mag_sq();
-                    liquid_count += 1;
-                    if body.species == Species::EC {
-                        _ec_count += 1;
-                    }
-                    if body.species == Species::DMC {
-                        _dmc_count += 1;
-                    }
-                    if body.species == Species::ElectrolyteAnion {
-                        _anion_count += 1;
-                    }
+            if self.is_thermostatted(body.species) {
+                liquid_ke += 0.5 * body.mass * body.vel.mag_sq();
+                liquid_count += 1;
+                if body.species == Species::EC {
+                    _ec_count += 1;
+                }
+                if body.species == Species::DMC {
+                    _dmc_count += 1;
+                }
+                if body.species == Species::ElectrolyteAnion {
+                    _anion_count += 1;
                 }
-                _ => {}
             }
         }
         tdbg!(
@@ -126,10 +124,13 @@ impl Simulation {
         );
         if current_temp <= 1e-3 {
             // effectively zero Kelvin
-            // Bootstrap: assign random velocities to all liquid particles (Li+, anion, EC, DMC)
+            // Bootstrap: assign random velocities to the thermostatted species only
+            let species = &self.config.thermostat_species;
+            let frozen = &self.frozen_species;
             crate::simulation::utils::initialize_liquid_velocities_to_temperature(
                 &mut self.bodies,
                 target_temp,
+                |s| thermostatted(s, species, frozen),
             );
             tdbg!(
                 "[thermostat-bootstrap] frame={} bootstrapped velocities",
@@ -143,15 +144,13 @@ impl Simulation {
         tdbg!("[thermostat-scale] frame={} current_temp={:.6} target_temp={:.2} scale={:.4} safe_scale={:.4}", 
             self.frame, current_temp, target_temp, scale, safe_scale);
         *crate::renderer::state::LAST_THERMOSTAT_SCALE.lock() = safe_scale;
-        // Scale velocities for solvent/anion particles only; Li⁺ excluded
-        // because their velocity is Coulomb-driven, not thermal.
+        // Scale velocities for thermostatted species only; Li⁺ is left out
+        // by default because its velocity is Coulomb-driven, not thermal.
+        let species = &self.config.thermostat_species;
+        let frozen = &self.frozen_species;
         for body in &mut self.bodies {
-            match body.species {
-                Species::ElectrolyteAnion | Species::EC | Species::DMC
-                | Species::VC | Species::FEC | Species::EMC => {
-                    body.vel *= safe_scale;
-                }
-                _ => {}
+            if thermostatted(body.species, species, frozen) {
+                body.vel *= safe_scale;
             }
         }
         // Recompute liquid temperature after scaling (debug builds only)
@@ -159,12 +158,8 @@ impl Simulation {
         {
             let mut new_liquid_ke = 0.0f32;
             for body in &self.bodies {
-                match body.species {
-                    Species::ElectrolyteAnion | Species::EC | Species::DMC
-                    | Species::VC | Species::FEC | Species::EMC => {
-                        new_liquid_ke += 0.5 * body.mass * body.vel.mag_sq();
-                    }
-                    _ => {}
+                if self.is_thermostatted(body.species) {
+                    new_liquid_ke += 0.5 * body.mass * body.vel.mag_sq();
                 }
             }
             let new_ke_per_particle = new_liquid_ke / liquid_count as f32;
@@ -175,4 +170,65 @@ impl Simulation {
         }
         // Store last scale factor in a debug field? Could add instrumentation later.
     }
+
+    /// Whether the thermostat measures and rescales `species`.
+    fn is_thermostatted(&self, species: Species) -> bool {
+        thermostatted(species, &self.config.thermostat_species, &self.frozen_species)
+    }
+}
+
+/// Thermostat selection: listed in `thermostat_species` and not frozen. Free
+/// so callers holding `&mut bodies` can use it alongside the config borrow.
+pub(crate) fn thermostatted(
+    species: Species,
+    thermostat_species: &[Species],
+    frozen: &std::collections::HashSet<Species>,
+) -> bool {
+    thermostat_species.contains(&species) && !frozen.contains(&species)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::Body;
+    use ultraviolet::Vec2;
+
+    #[test]
+    fn excluded_species_keep_their_velocity() {
+        let mut sim = Simulation::new();
+        sim.bodies.clear();
+        sim.config.temperature = 300.0;
+        sim.config.thermostat_species = vec![Species::EC];
+        let vel = Vec2::new(3.0, -4.0);
+        for (x, species) in [(0.0, Species::EC), (10.0, Species::LithiumMetal)] {
+            let body = Body::new(Vec2::new(x, 0.0), vel, 1.0, 1.0, 0.0, species);
+            sim.bodies.push(body);
+        }
+
+        sim.apply_thermostat();
+
+        // EC alone sets the temperature: KE = 12.5 per particle
+        let expected = (300.0 * BOLTZMANN_CONSTANT / 12.5).sqrt().clamp(0.1, 10.0);
+        assert!((sim.bodies[0].vel - vel * expected).mag() < 1e-4 * vel.mag());
+        assert_eq!(sim.bodies[1].vel, vel, "metal is not thermostatted");
+    }
+
+    #[test]
+    fn bootstrap_only_seeds_thermostatted_species() {
+        let mut sim = Simulation::new();
+        sim.bodies.clear();
+        sim.config.temperature = 300.0;
+        sim.config.thermostat_species = vec![Species::EC];
+        for (x, species) in [(0.0, Species::EC), (10.0, Species::DMC), (20.0, Species::EMC)] {
+            sim.bodies.push(Body::new(Vec2::new(x, 0.0), Vec2::zero(), 1.0, 1.0, 0.0, species));
+        }
+        sim.frozen_species.insert(Species::EMC);
+
+        // At rest the thermostat bootstraps instead of rescaling
+        sim.apply_thermostat();
+
+        assert_ne!(sim.bodies[0].vel, Vec2::zero(), "EC is seeded");
+        assert_eq!(sim.bodies[1].vel, Vec2::zero(), "DMC is not in thermostat_species");
+        assert_eq!(sim.bodies[2].vel, Vec2::zero(), "frozen species are not seeded");
+    }
 }
@@ -2,11 +2,44 @@ use crate::body::Body;
 use crate::body::Species;
 use crate::units::BOLTZMANN_CONSTANT;
 
-/// Returns true if an electron should be allowed to hop from src to dst
-pub fn can_transfer_electron(src: &Body, dst: &Body) -> bool {
+/// First invalid body found by the post-integration NaN check.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NonFiniteReport {
+    pub frame: usize,
+    pub body_id: u64,
+    pub index: usize,
+    pub field: &'static str,
+    /// Total number of invalid bodies in that frame
+    pub count: usize,
+}
+
+/// Name of the first non-finite or invalid field on `b`, if any.
+pub fn invalid_body_field(b: &Body) -> Option<&'static str> {
+    if !b.pos.x.is_finite() || !b.pos.y.is_finite() {
+        Some("pos")
+    } else if !b.vel.x.is_finite() || !b.vel.y.is_finite() {
+        Some("vel")
+    } else if !b.z.is_finite() {
+        Some("z")
+    } else if !b.vz.is_finite() {
+        Some("vz")
+    } else if !b.charge.is_finite() {
+        Some("charge")
+    } else if !b.radius.is_finite() || b.radius <= 0.0 {
+        Some("radius")
+    } else if !b.mass.is_finite() || b.mass <= 0.0 {
+        Some("mass")
+    } else {
+        None
+    }
+}
+
+/// Returns true if an electron should be allowed to hop from src to dst.
+/// `foil_max_electrons` is the capacity of dst's foil when dst is a foil body.
+pub fn can_transfer_electron(src: &Body, dst: &Body, foil_max_electrons: usize) -> bool {
     // Check if destination would exceed maximum electron limit
     let dst_max_electrons = match dst.species {
-        Species::FoilMetal => crate::config::FOIL_MAX_ELECTRONS,
+        Species::FoilMetal => foil_max_electrons,
         Species::LithiumMetal => crate::config::LITHIUM_METAL_MAX_ELECTRONS,
         // Intercalation electrode materials can hold multiple electrons
         // Use a reasonable limit based on the material's capacity for charge transfer
@@ -105,31 +138,30 @@ pub fn compute_liquid_temperature(bodies: &[Body]) -> f32 {
     ke_per_particle / BOLTZMANN_CONSTANT
 }
 
-/// Initialize (or reinitialize) velocities for liquid species (Li+, anion, EC, DMC) to match target temperature.
-/// This seeds a Maxwell-Boltzmann distribution across all liquid components when bootstrapping.
-pub fn initialize_liquid_velocities_to_temperature(bodies: &mut [Body], target_temp: f32) {
+/// Initialize (or reinitialize) velocities for the thermostatted species to match target temperature.
+/// This seeds a Maxwell-Boltzmann distribution across every body for which `thermostatted` holds
+/// (the same selection the thermostat rescales) when bootstrapping.
+pub fn initialize_liquid_velocities_to_temperature(
+    bodies: &mut [Body],
+    target_temp: f32,
+    thermostatted: impl Fn(Species) -> bool,
+) {
     if target_temp <= 0.0 {
         return;
     }
     use rand::{rng, Rng};
     let mut rng = rng();
     // In simulation units: (1/2) m (vx^2 + vy^2) = T => each component variance = T / m
-    for b in bodies.iter_mut() {
-        match b.species {
-            Species::ElectrolyteAnion | Species::EC | Species::DMC
-            | Species::VC | Species::FEC | Species::EMC => {
-                let sigma = (target_temp / b.mass).sqrt();
-                // Box-Muller
-                let r1: f32 = rng.random::<f32>().max(1e-12);
-                let r2: f32 = rng.random::<f32>();
-                let mag: f32 = (-2.0_f32 * r1.ln()).sqrt();
-                let z0 = mag * (2.0 * std::f32::consts::PI * r2).cos();
-                let z1 = mag * (2.0 * std::f32::consts::PI * r2).sin();
-                b.vel.x = z0 * sigma;
-                b.vel.y = z1 * sigma;
-            }
-            _ => {} // Skip metals
-        }
+    for b in bodies.iter_mut().filter(|b| thermostatted(b.species)) {
+        let sigma = (target_temp / b.mass).sqrt();
+        // Box-Muller
+        let r1: f32 = rng.random::<f32>().max(1e-12);
+        let r2: f32 = rng.random::<f32>();
+        let mag: f32 = (-2.0_f32 * r1.ln()).sqrt();
+        let z0 = mag * (2.0 * std::f32::consts::PI * r2).cos();
+        let z1 = mag * (2.0 * std::f32::consts::PI * r2).sin();
+        b.vel.x = z0 * sigma;
+        b.vel.y = z1 * sigma;
     }
 }
 
@@ -169,7 +201,11 @@ mod tests {
         // This one has no electrons (deficient)
 
         // Try to transfer electron from max to deficient - should work
-        assert!(can_transfer_electron(&lithium_metal, &deficient_lithium));
+        assert!(can_transfer_electron(
+            &lithium_metal,
+            &deficient_lithium,
+            crate::config::FOIL_MAX_ELECTRONS
+        ));
 
         // Try to transfer electron to a lithium metal that's already at max - should fail
         let mut another_max_lithium = Body::new(
@@ -187,7 +223,11 @@ mod tests {
             });
         }
 
-        assert!(!can_transfer_electron(&lithium_metal, &another_max_lithium));
+        assert!(!can_transfer_electron(
+            &lithium_metal,
+            &another_max_lithium,
+            crate::config::FOIL_MAX_ELECTRONS
+        ));
     }
 
     #[test]
@@ -218,6 +258,17 @@ mod tests {
         }
 
         // Should not allow transfer when destination is at max
-        assert!(!can_transfer_electron(&src, &dst));
+        assert!(!can_transfer_electron(&src, &dst, crate::config::FOIL_MAX_ELECTRONS));
+
+        // A richer donor may fill a foil whose own capacity is larger
+        let mut src = src;
+        for _ in 0..=crate::config::FOIL_MAX_ELECTRONS {
+            src.electrons.push(Electron {
+                rel_pos: Vec2::zero(),
+                vel: Vec2::zero(),
+            });
+        }
+        assert!(!can_transfer_electron(&src, &dst, crate::config::FOIL_MAX_ELECTRONS));
+        assert!(can_transfer_electron(&src, &dst, crate::config::FOIL_MAX_ELECTRONS + 1));
     }
 }
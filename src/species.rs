@@ -1,10 +1,11 @@
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
 
 use crate::body::Species;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct SpeciesProps {
     /// Mass in atomic mass units (amu)
     pub mass: f32,
@@ -21,6 +22,31 @@ pub struct SpeciesProps {
     pub enable_repulsion: bool,
     pub repulsion_strength: f32,
     pub repulsion_cutoff: f32,
+    /// Contact radius in Å used by collision resolution; `None` uses `radius`.
+    /// Rendering always uses `radius`.
+    pub collision_radius: Option<f32>,
+    /// Out-of-plane participation; `None` uses `ZMobility::default_for`.
+    #[serde(default)]
+    pub z_mobility: Option<ZMobility>,
+}
+
+/// How a species takes part in out-of-plane (z) motion.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ZMobility {
+    /// Whether z-forces move the species at all; immobile species stay at z = 0
+    pub mobile: bool,
+    /// Multiplier on `config.z_damping` for this species
+    pub damping_scale: f32,
+}
+
+impl ZMobility {
+    /// Metals stay planar, everything else moves in z with the shared damping.
+    pub fn default_for(species: Species) -> Self {
+        Self {
+            mobile: !matches!(species, Species::LithiumMetal | Species::FoilMetal),
+            damping_scale: 1.0,
+        }
+    }
 }
 
 pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(|| {
@@ -42,6 +68,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -60,6 +88,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -78,6 +108,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -96,6 +128,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -114,6 +148,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0, // Reduced from 100.0 - represents osmotic pressure
             repulsion_cutoff: 5.0,   // Reduced from 11.0 - shorter range interaction
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -132,6 +168,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0, // Reduced from 100.0 - represents osmotic pressure
             repulsion_cutoff: 5.0,   // Reduced from 11.0 - shorter range interaction
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -150,6 +188,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 5.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -168,6 +208,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 6.0,
             repulsion_cutoff: 5.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -186,6 +228,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 4.5,
             repulsion_cutoff: 5.5,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -204,6 +248,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -222,6 +268,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -240,6 +288,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -258,6 +308,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     // Intercalation electrode materials
@@ -277,6 +329,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -295,6 +349,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -313,6 +369,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -331,6 +389,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -349,6 +409,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -367,6 +429,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -385,6 +449,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m.insert(
@@ -403,6 +469,8 @@ pub static SPECIES_PROPERTIES: Lazy<HashMap<Species, SpeciesProps>> = Lazy::new(
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         },
     );
     m
@@ -507,10 +575,17 @@ pub fn get_species_props(species: Species) -> SpeciesProps {
             enable_repulsion: false,
             repulsion_strength: 5.0,
             repulsion_cutoff: 2.0,
+            collision_radius: None,
+            z_mobility: None,
         }
     })
 }
 
+/// Serializes tests that override species properties, since the overrides
+/// are process-wide.
+#[cfg(test)]
+pub(crate) static SPECIES_PROPS_TEST_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
 /// Update species properties (used by GUI)
 pub fn update_species_props(species: Species, props: SpeciesProps) {
     if let Ok(mut overrides) = SPECIES_PROPERTY_OVERRIDES.lock() {
@@ -626,3 +701,77 @@ pub fn calculate_solvent_particle_counts(
 
     results
 }
+
+/// Particle counts for a LiPF6 in EC:DMC electrolyte.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ElectrolyteCounts {
+    pub lithium: usize,
+    pub anion: usize,
+    pub ec: usize,
+    pub dmc: usize,
+}
+
+/// Split `total` particles into Li+/PF6- pairs and EC/DMC solvent.
+///
+/// Salt uses the same heuristic as the electrolyte panel (1.0 M ≈ one salt
+/// pair per 15 solvent molecules, scaled linearly with `molarity`); Li+ and
+/// anion counts are always equal so the mixture is neutral. The remaining
+/// particles are split by EC:DMC volume parts via
+/// [`calculate_solvent_particle_counts`]. A zero part drops that solvent.
+pub fn electrolyte_counts(
+    total: usize,
+    molarity: f32,
+    ec_parts: f32,
+    dmc_parts: f32,
+) -> ElectrolyteCounts {
+    let solvent_to_salt_ratio = 15.0;
+    let salt_fraction = 1.0 / (1.0 + solvent_to_salt_ratio);
+    let pairs = (total as f32 * salt_fraction * molarity.max(0.0)).round() as usize;
+    let pairs = pairs.min(total / 2);
+    let remaining = total - 2 * pairs;
+
+    let solvent_parts: Vec<(Species, f32)> = [(Species::EC, ec_parts), (Species::DMC, dmc_parts)]
+        .into_iter()
+        .filter(|(_, parts)| *parts > 0.0)
+        .collect();
+    let mut counts = ElectrolyteCounts {
+        lithium: pairs,
+        anion: pairs,
+        ..Default::default()
+    };
+    for (species, count) in calculate_solvent_particle_counts(&solvent_parts, remaining) {
+        match species {
+            Species::EC => counts.ec = count,
+            Species::DMC => counts.dmc = count,
+            _ => {}
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod electrolyte_counts_tests {
+    use super::*;
+
+    #[test]
+    fn counts_follow_requested_molarity_and_solvent_ratio() {
+        let vm_ec = get_solvent_physical_props(Species::EC).molar_volume();
+        let vm_dmc = get_solvent_physical_props(Species::DMC).molar_volume();
+        for &(total, molarity, ec_parts, dmc_parts) in &[
+            (5471usize, 1.0f32, 1.0f32, 1.0f32),
+            (4000, 0.5, 3.0, 7.0),
+            (2000, 2.0, 1.0, 0.0),
+        ] {
+            let c = electrolyte_counts(total, molarity, ec_parts, dmc_parts);
+            assert_eq!(c.lithium, c.anion, "salt must be neutral");
+            assert_eq!(c.lithium + c.anion + c.ec + c.dmc, total);
+            let expected_pairs = (total as f32 * molarity / 16.0).round() as usize;
+            assert_eq!(c.lithium, expected_pairs);
+
+            // Particle ratio follows volume parts divided by molar volume
+            let solvent = (c.ec + c.dmc) as f32;
+            let ec_share = (ec_parts / vm_ec) / (ec_parts / vm_ec + dmc_parts / vm_dmc);
+            assert!((c.ec as f32 - solvent * ec_share).abs() <= 1.0);
+        }
+    }
+}
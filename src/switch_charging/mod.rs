@@ -116,6 +116,9 @@ pub struct SwitchChargingConfig {
     pub global_active: StepSetpoint,
     /// Global Inactive setpoint applied to the non-active foils regardless of step when `use_global_active_inactive` is true
     pub global_inactive: StepSetpoint,
+    /// Optional dwell per half-cycle in seconds. When set, `delta_steps` is
+    /// re-derived from it with the current dt; `delta_steps` stays authoritative.
+    pub dwell_seconds: Option<f64>,
 }
 
 impl Default for SwitchChargingConfig {
@@ -169,6 +172,7 @@ impl Default for SwitchChargingConfig {
                 mode: Mode::Overpotential,
                 value: 1.0,
             },
+            dwell_seconds: None,
         };
         cfg.ensure_all_steps();
         cfg.ensure_all_step_active_inactive();
@@ -254,6 +258,34 @@ impl SwitchChargingConfig {
         4.0 * self.delta_steps as f64 * self.sim_dt_s
     }
 
+    /// Duration of `steps` simulation steps in seconds at the current dt.
+    pub fn steps_to_seconds(&self, steps: u32) -> f64 {
+        steps as f64 * self.sim_dt_s
+    }
+
+    /// Nearest whole number of steps (at least one) covering `seconds`.
+    pub fn seconds_to_steps(&self, seconds: f64) -> u32 {
+        if !seconds.is_finite() || self.sim_dt_s <= 0.0 {
+            return self.delta_steps.max(1);
+        }
+        (seconds / self.sim_dt_s).round().clamp(1.0, u32::MAX as f64) as u32
+    }
+
+    /// Re-derive `delta_steps` from `dwell_seconds` (if set) at the current dt.
+    /// Returns true when the step count changed.
+    pub fn apply_dwell_seconds(&mut self) -> bool {
+        let Some(seconds) = self.dwell_seconds else {
+            return false;
+        };
+        let steps = self.seconds_to_steps(seconds);
+        if steps == self.delta_steps {
+            return false;
+        }
+        self.delta_steps = steps;
+        self.recompute_from_steps();
+        true
+    }
+
     pub fn foils_for_role(&self, role: Role) -> &[FoilId] {
         self.role_to_foil
             .get(&role)
@@ -517,6 +549,8 @@ impl SwitchUiState {
             self.config.sim_dt_s = dt_s;
             // Don't automatically recompute user-set values during simulation
             // self.config.recompute_from_steps();
+            // A dwell given in seconds is the exception: keep its duration fixed
+            self.config.apply_dwell_seconds();
             self.update_validation();
         }
     }
@@ -777,11 +811,12 @@ pub fn ui_switch_charging(ui: &mut egui::Ui, state: &mut SwitchUiState) {
             let (pos, neg) = roles_for_step(step);
             ui.separator();
             ui.label(format!(
-                "Step {} ({} → {}) | dwell remaining: {}",
+                "Step {} ({} → {}) | dwell remaining: {} steps ({:.3e} s)",
                 step + 1,
                 pos.display(),
                 neg.display(),
-                dwell
+                dwell,
+                state.config.steps_to_seconds(dwell)
             ));
         }
 
@@ -915,14 +950,51 @@ pub fn ui_switch_charging(ui: &mut egui::Ui, state: &mut SwitchUiState) {
         ui.label("Switching Rate");
 
         // Steps per half-cycle is the primary control; frequency is derived
-        ui.horizontal(|ui| {
-            ui.label("Steps per half-cycle:");
-            let mut steps = state.config.delta_steps;
-            ui.add(egui::DragValue::new(&mut steps).speed(1.0));
-            if steps != state.config.delta_steps {
-                state.set_delta_steps(steps);
-            }
-        });
+        let mut dwell_in_seconds = state.config.dwell_seconds.is_some();
+        if ui
+            .checkbox(&mut dwell_in_seconds, "Specify dwell in seconds")
+            .on_hover_text("Keep the dwell duration fixed and convert it to steps with the current dt")
+            .changed()
+        {
+            state.config.dwell_seconds =
+                dwell_in_seconds.then(|| state.config.steps_to_seconds(state.config.delta_steps));
+            state.config_dirty = true;
+            state.send_update();
+        }
+        if let Some(mut seconds) = state.config.dwell_seconds {
+            ui.horizontal(|ui| {
+                ui.label("Dwell per half-cycle:");
+                let response = ui.add(
+                    egui::DragValue::new(&mut seconds)
+                        .speed(state.config.sim_dt_s)
+                        .clamp_range(state.config.sim_dt_s..=f64::MAX)
+                        .custom_formatter(|v, _| format!("{:.4e}", v)),
+                );
+                ui.weak("s");
+                if response.changed() {
+                    state.config.dwell_seconds = Some(seconds);
+                    let steps = state.config.seconds_to_steps(seconds);
+                    state.set_delta_steps(steps);
+                    // The step count may be unchanged; still persist the new seconds
+                    state.config_dirty = true;
+                    state.send_update();
+                }
+            });
+            ui.label(format!("Steps per half-cycle: {}", state.config.delta_steps));
+        } else {
+            ui.horizontal(|ui| {
+                ui.label("Steps per half-cycle:");
+                let mut steps = state.config.delta_steps;
+                ui.add(egui::DragValue::new(&mut steps).speed(1.0));
+                if steps != state.config.delta_steps {
+                    state.set_delta_steps(steps);
+                }
+            });
+            ui.label(format!(
+                "Dwell per half-cycle: {:.4e} s",
+                state.config.steps_to_seconds(state.config.delta_steps)
+            ));
+        }
 
         // Frequency is now display-only, derived from steps and dt
         ui.horizontal(|ui| {
@@ -1306,6 +1378,83 @@ pub fn ui_switch_charging(ui: &mut egui::Ui, state: &mut SwitchUiState) {
     });
 }
 
+/// Stretch of sim time during which one step stayed active at one resolved
+/// setpoint. Pauses are recorded as their own segments with no step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimelineSegment {
+    pub start_fs: f32,
+    pub end_fs: f32,
+    pub step: Option<u8>,
+    pub setpoint: Option<f64>,
+}
+
+/// Run-length record of the scheduler for the switch timeline plot.
+#[derive(Clone, Debug, Default)]
+pub struct SwitchTimeline {
+    pub segments: Vec<TimelineSegment>,
+}
+
+impl SwitchTimeline {
+    /// Record the interval `start_fs..end_fs`, extending the last segment
+    /// when it ends at `start_fs` with the same step and setpoint. Returns
+    /// true when a new segment was started.
+    pub fn record(
+        &mut self,
+        start_fs: f32,
+        end_fs: f32,
+        step: Option<u8>,
+        setpoint: Option<f64>,
+    ) -> bool {
+        if let Some(last) = self.segments.last_mut() {
+            let tolerance = 1e-3 * (end_fs - start_fs).abs().max(1e-6);
+            let contiguous = (last.end_fs - start_fs).abs() <= tolerance;
+            if contiguous && last.step == step && last.setpoint == setpoint {
+                last.end_fs = end_fs;
+                return false;
+            }
+        }
+        self.segments.push(TimelineSegment { start_fs, end_fs, step, setpoint });
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.segments.clear();
+    }
+
+    /// Step-function vertices `(time_fs, step, setpoint)`, two per segment.
+    /// Paused segments and holes between segments (e.g. after a stop) carry
+    /// NaN so plots break the line there instead of bridging the gap.
+    pub fn step_function(segments: &[TimelineSegment]) -> Vec<(f64, f64, f64)> {
+        let mut points = Vec::with_capacity(segments.len() * 2);
+        let mut last_end: Option<f32> = None;
+        for seg in segments {
+            if last_end.is_some_and(|end| seg.start_fs - end > 1e-3) {
+                points.push((last_end.unwrap_or_default() as f64, f64::NAN, f64::NAN));
+            }
+            let step = seg.step.map_or(f64::NAN, |s| s as f64);
+            let setpoint = seg.setpoint.unwrap_or(f64::NAN);
+            points.push((seg.start_fs as f64, step, setpoint));
+            points.push((seg.end_fs as f64, step, setpoint));
+            last_end = Some(seg.end_fs);
+        }
+        points
+    }
+}
+
+impl SwitchChargingConfig {
+    /// Setpoint value the active foils of `step` are driven with, honouring
+    /// the global and per-step active/inactive modes.
+    pub fn resolved_active_setpoint(&self, step: u8) -> Option<f64> {
+        if !self.use_active_inactive_setpoints {
+            self.step_setpoints.get(&step).map(|sp| sp.value)
+        } else if self.use_global_active_inactive {
+            Some(self.global_active.value)
+        } else {
+            self.step_active_inactive.get(&step).map(|sai| sai.active.value)
+        }
+    }
+}
+
 pub fn default_sim_dt_s() -> f64 {
     (crate::config::DEFAULT_DT_FS as f64) * 1e-15
 }
@@ -1419,4 +1568,25 @@ mod tests {
         cfg.delta_steps = 0;
         assert!(cfg.validate().is_err());
     }
+
+    #[test]
+    fn dwell_seconds_round_trip_steps() {
+        let mut cfg = make_valid_config();
+        cfg.sim_dt_s = 5e-15;
+        for steps in [1u32, 7, 1000, 123_456] {
+            let seconds = cfg.steps_to_seconds(steps);
+            assert_eq!(cfg.seconds_to_steps(seconds), steps);
+        }
+        // Sub-step durations still dwell for one step
+        assert_eq!(cfg.seconds_to_steps(1e-18), 1);
+
+        // A dwell in seconds follows dt changes; steps stay the source of truth
+        cfg.dwell_seconds = Some(5e-12);
+        assert!(cfg.apply_dwell_seconds());
+        assert_eq!(cfg.delta_steps, 1000);
+        cfg.sim_dt_s = 1e-14;
+        assert!(cfg.apply_dwell_seconds());
+        assert_eq!(cfg.delta_steps, 500);
+        assert!(!cfg.apply_dwell_seconds());
+    }
 }